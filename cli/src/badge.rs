@@ -0,0 +1,143 @@
+//! Export of benchmark results as badges.
+//!
+//! Badges are written in the endpoint format of shields.io, so they can be embedded in READMEs and
+//! dashboards. They are generated from the result database: one badge is written for every
+//! benchmark, identified by its label, its name and the dataset it was performed on. The message
+//! of a badge contains the mean number of cycles of the latest result of the benchmark and,
+//! if an earlier run also contains the benchmark, the change relative to it.
+
+use std::{collections::HashMap, fs, io, path::Path};
+
+use benchmark_common::Aggregate;
+use serde::Serialize;
+
+use crate::database::StoredResult;
+
+/// Badge in the endpoint format of shields.io
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Badge {
+    schema_version: u32,
+    label: String,
+    message: String,
+    color: String,
+}
+
+/// Identifies the benchmark a badge is written for: its label, name and dataset
+type BadgeKey<'a> = (Option<&'a str>, &'a str, Option<i64>);
+
+/// Results of one request of a benchmark, identified by its run and line
+type Request<'a> = (i64, i64, Vec<&'a StoredResult>);
+
+/// Returns the key of the benchmark a stored result belongs to
+fn key_of(stored: &StoredResult) -> BadgeKey<'_> {
+    (
+        stored.label.as_deref(),
+        stored.result.name(),
+        stored.dataset,
+    )
+}
+
+/// Returns the mean number of cycles of the results of one benchmark request
+///
+/// # Arguments
+///
+/// * `results` - the stored results of the request, of the same benchmark
+fn mean(results: &[&StoredResult]) -> Option<f64> {
+    match results.first()?.aggregate {
+        // The sum of the mean of every phase is the mean of the totals
+        Aggregate::Summary => results
+            .iter()
+            .find(|stored| stored.aggregate.statistic(stored.index) == Some("mean"))
+            .map(|stored| stored.result.total() as f64),
+        _ => {
+            let total: u64 = results.iter().map(|stored| stored.result.total()).sum();
+            Some(total as f64 / results.len() as f64)
+        }
+    }
+}
+
+/// Writes a badge for every benchmark of the results read from the result database into the
+/// provided directory, replacing the badges of earlier runs
+///
+/// # Arguments
+///
+/// * `dir` - the directory containing the badges, created if it does not exist
+/// * `stored` - the results stored in the database, the latest result first
+pub fn write_badges(dir: &Path, stored: &[StoredResult]) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+
+    // The latest request of every benchmark and the latest request of it in an earlier run
+    let mut requests: HashMap<BadgeKey, Vec<Request>> = HashMap::new();
+    let mut order = Vec::new();
+    for result in stored {
+        let key = key_of(result);
+        let history = requests.entry(key).or_insert_with(|| {
+            order.push(key);
+            Vec::new()
+        });
+        match history.last_mut() {
+            Some((run, line, results)) if *run == result.run => {
+                // Earlier requests of the same run are skipped
+                if *line == result.line {
+                    results.push(result);
+                }
+            }
+            _ => {
+                if history.len() < 2 {
+                    history.push((result.run, result.line, vec![result]));
+                }
+            }
+        }
+    }
+
+    for key in order {
+        let history = &requests[&key];
+        let latest = match mean(&history[0].2) {
+            Some(latest) => latest,
+            None => continue,
+        };
+        let previous = history.get(1).and_then(|(_, _, results)| mean(results));
+
+        let (message, color) = match previous {
+            Some(previous) if previous > 0.0 => {
+                let delta = (latest - previous) / previous * 100.0;
+                let color = if delta > 0.0 { "orange" } else { "brightgreen" };
+                (format!("{latest:.0} cycles ({delta:+.1}%)"), color)
+            }
+            _ => (format!("{latest:.0} cycles"), "blue"),
+        };
+
+        let (label, name, dataset) = key;
+        let mut title = label.unwrap_or(name).to_string();
+        let mut file_name = match label {
+            Some(label) => format!("{label}_{name}"),
+            None => name.to_string(),
+        };
+        if let Some(dataset) = dataset {
+            title.push_str(&format!(" (dataset {dataset})"));
+            file_name.push_str(&format!("_dataset{dataset}"));
+        }
+        // Labels are chosen freely, so they may not be valid in file names
+        let file_name: String = file_name
+            .chars()
+            .map(|c| match c {
+                'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' => c,
+                _ => '_',
+            })
+            .collect();
+
+        let badge = Badge {
+            schema_version: 1,
+            label: title,
+            message,
+            color: color.to_string(),
+        };
+        fs::write(
+            dir.join(file_name).with_extension("json"),
+            serde_json::to_string(&badge).expect("Can not serialize struct"),
+        )?;
+    }
+
+    Ok(())
+}
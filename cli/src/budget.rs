@@ -0,0 +1,90 @@
+//! Cycle budgets of benchmarks.
+//!
+//! A budget limits the number of cycles a single phase of a benchmark, or the whole benchmark,
+//! may take. Budgets are written as `max_<phase> = <cycles>`, where `total` refers to the sum
+//! of all phases, ex. `max_computation = 60000`.
+
+use std::fmt::Display;
+
+use benchmark_common::BenchmarkResult;
+
+/// Name used in budgets to refer to the sum of all phases of a benchmark
+const TOTAL: &str = "total";
+
+/// Maximum number of cycles a phase of a benchmark may take
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Budget {
+    /// Name of the phase, or `total` for the whole benchmark
+    pub phase: String,
+    /// Maximum number of cycles
+    pub max: u64,
+}
+
+impl Budget {
+    /// Checks the results of all repetitions of a benchmark against this budget,
+    /// returns a description of the outcome, that is an error if the budget was exceeded
+    /// or the phase is not part of the benchmark
+    ///
+    /// # Arguments
+    ///
+    /// * `results` - the results of the benchmark
+    pub fn check(&self, results: &[BenchmarkResult]) -> Result<String, String> {
+        let mut worst = None;
+
+        for result in results {
+            let cycles = if self.phase == TOTAL {
+                result.total()
+            } else {
+                result
+                    .phases()
+                    .into_iter()
+                    .find(|(phase, _)| *phase == self.phase)
+                    .map(|(_, cycles)| cycles)
+                    .ok_or_else(|| format!("{self}: {} has no such phase", result.name()))?
+            };
+            worst = worst.max(Some(cycles));
+        }
+
+        match worst {
+            Some(worst) if worst > self.max => Err(format!("{self}: FAIL (max {worst})")),
+            Some(worst) => Ok(format!("{self}: PASS (max {worst})")),
+            None => Err(format!("{self}: no results")),
+        }
+    }
+}
+
+impl Display for Budget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "max_{} = {}", self.phase, self.max)
+    }
+}
+
+/// Parses a comma separated list of budgets, ex. `max_computation = 60000, max_total = 90000`
+///
+/// # Arguments
+///
+/// * `value` - the String that should be parsed
+pub fn parse_budgets(value: &str) -> Result<Vec<Budget>, String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|budget| !budget.is_empty())
+        .map(|budget| {
+            let (key, max) = budget.split_once('=').ok_or_else(|| {
+                format!("Invalid budget '{budget}', expected max_<phase> = <cycles>")
+            })?;
+            let phase = key.trim().strip_prefix("max_").ok_or_else(|| {
+                format!("Invalid budget '{budget}', expected max_<phase> = <cycles>")
+            })?;
+            let max = max
+                .trim()
+                .parse()
+                .map_err(|_| format!("Invalid number of cycles in budget '{budget}'"))?;
+
+            Ok(Budget {
+                phase: phase.to_string(),
+                max,
+            })
+        })
+        .collect()
+}
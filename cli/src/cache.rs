@@ -0,0 +1,96 @@
+//! Caching of benchmark results.
+//!
+//! Results are stored in a directory, one file per benchmark request, keyed by a hash of the
+//! request and the platform descriptor of the suite. Rerunning an unchanged request against an
+//! unchanged build of the suite can therefore reuse the results of an earlier run.
+
+use std::{fs, path::PathBuf};
+
+use benchmark_common::{BenchmarkConfig, IncomingMessage, OutgoingMessage, PlatformDescriptor};
+use sha2::{Digest, Sha256};
+
+/// Cache of benchmark results, produced by a single build of the suite
+pub struct ResultCache {
+    dir: PathBuf,
+    build: String,
+}
+
+impl ResultCache {
+    /// Opens the cache located in the provided directory, for results produced by the described suite.
+    ///
+    /// Returns None if the build of the suite can not be identified,
+    /// since results of different builds could not be told apart.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - the directory containing the cached results, created if it does not exist
+    /// * `platform` - the descriptor reported by the suite
+    pub fn open(dir: PathBuf, platform: &PlatformDescriptor) -> Option<ResultCache> {
+        platform.git_hash.as_ref()?;
+
+        fs::create_dir_all(&dir).expect("Failed to create cache directory");
+
+        Some(ResultCache {
+            dir,
+            build: serde_json::to_string(platform).expect("Can not serialize struct"),
+        })
+    }
+
+    /// Returns the cached response to the provided request, if present
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - the message that would be sent to the suite
+    pub fn load(&self, request: &OutgoingMessage) -> Option<IncomingMessage> {
+        let content = fs::read_to_string(self.path(request)?).ok()?;
+
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Stores the response to the provided request, only responses to benchmarks are cached
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - the message that was sent to the suite
+    /// * `response` - the response of the suite
+    pub fn store(&self, request: &OutgoingMessage, response: &IncomingMessage) {
+        if let (Some(path), IncomingMessage::BenchmarkResults(_)) = (self.path(request), response) {
+            let content = serde_json::to_string(response).expect("Can not serialize struct");
+            fs::write(path, content).expect("Failed to write cache file");
+        }
+    }
+
+    /// Returns the path of the file caching the response to the provided request,
+    /// or None if responses to the request are not cached
+    ///
+    /// The request id is not part of the key, as it only identifies the request within a file.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - the message sent to the suite
+    fn path(&self, request: &OutgoingMessage) -> Option<PathBuf> {
+        let request = match request {
+            OutgoingMessage::Benchmark(info, config) => OutgoingMessage::Benchmark(
+                info.clone(),
+                BenchmarkConfig {
+                    request_id: None,
+                    ..config.clone()
+                },
+            ),
+            _ => return None,
+        };
+
+        let mut hasher = Sha256::new();
+        hasher.update(self.build.as_bytes());
+        hasher.update(b"\n");
+        hasher.update(serde_json::to_string(&request).expect("Can not serialize struct"));
+
+        let key: String = hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+
+        Some(self.dir.join(key).with_extension("json"))
+    }
+}
@@ -0,0 +1,149 @@
+//! Calibration of the overhead of measurements.
+//!
+//! Every phase measured by the suite contains the cycles needed to sample the cycle counter at
+//! its start and end, including the calls involved. To calibrate, `BenchmarkInfo::MicroBenchmarks`
+//! is performed before the benchmarks of a file, the overhead is the minimum of its `sample`
+//! phase, an empty phase, over all repetitions. Adjusted results subtract the overhead from every
+//! phase, phases that took fewer cycles than the overhead are adjusted to 0.
+//!
+//! Benchmarks requested as `BenchmarkInfo::Calibrated` carry their own micro benchmarks, measured
+//! immediately before and after them. Their results are adjusted for the overhead measured by
+//! those, instead of the one calibrated before the file, and the micro benchmarks are left out.
+
+use benchmark_common::{
+    BenchmarkConfig, BenchmarkInfo, BenchmarkResult, IncomingMessage, OutgoingMessage,
+    ResultEnvelope,
+};
+
+use serde::Serialize;
+
+use crate::{tty::SuiteConnection, ExitCode};
+
+/// Number of repetitions of the micro benchmarks used to calibrate the overhead
+pub const CALIBRATION_REPETITIONS: u32 = 16;
+
+/// Phases of the micro benchmarks, in order
+const MICRO_BENCHMARK_PHASES: [&str; 3] = ["get_cycle", "sample", "call"];
+
+/// Phase of the micro benchmarks measuring an empty phase
+const OVERHEAD_PHASE: &str = "sample";
+
+/// Returns true if the result was reported by the micro benchmarks
+///
+/// # Arguments
+///
+/// * `result` - a result of a benchmark
+fn is_micro_benchmark(result: &BenchmarkResult) -> bool {
+    match result {
+        BenchmarkResult::Phases(phases) => phases
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .eq(MICRO_BENCHMARK_PHASES),
+        _ => false,
+    }
+}
+
+/// Returns the minimum cycles of a phase of the micro benchmarks contained in the results,
+/// None if the results do not contain micro benchmarks
+///
+/// # Arguments
+///
+/// * `results` - the results of a benchmark
+/// * `phase` - the name of the phase
+fn minimum(results: &[BenchmarkResult], phase: &str) -> Option<u64> {
+    results
+        .iter()
+        .filter(|result| is_micro_benchmark(result))
+        .flat_map(BenchmarkResult::phases)
+        .filter(|(name, _)| *name == phase)
+        .map(|(_, cycles)| cycles)
+        .min()
+}
+
+/// Performs the micro benchmarks on the suite and returns the overhead of every phase in cycles,
+/// the minimum of every micro benchmark is printed.
+///
+/// Returns the exit code of the failure if the overhead could not be measured.
+///
+/// # Arguments
+///
+/// * `suite` - the connection to the suite
+pub fn calibrate(suite: &mut SuiteConnection) -> Result<u64, ExitCode> {
+    let envelope = match suite.request(&OutgoingMessage::Benchmark(
+        BenchmarkInfo::MicroBenchmarks,
+        BenchmarkConfig::samples(CALIBRATION_REPETITIONS),
+    )) {
+        Ok(IncomingMessage::BenchmarkResults(envelope)) => envelope,
+        Ok(msg) => {
+            eprintln!("Suite could not be calibrated, received: {msg:?}");
+            return Err(ExitCode::of_message(&msg).or(ExitCode::ProtocolError));
+        }
+        Err(err) => {
+            eprintln!("Connection lost: {err}");
+            return Err(ExitCode::ConnectionFailure);
+        }
+    };
+
+    let overhead = match minimum(&envelope.results, OVERHEAD_PHASE) {
+        Some(overhead) => overhead,
+        None => {
+            eprintln!("Suite could not be calibrated, the results do not contain micro benchmarks");
+            return Err(ExitCode::ProtocolError);
+        }
+    };
+
+    let benchmarks: Vec<String> = MICRO_BENCHMARK_PHASES
+        .iter()
+        .filter_map(|name| Some(format!("{name} {}", minimum(&envelope.results, name)?)))
+        .collect();
+    eprintln!(
+        "Calibrated an overhead of {overhead} cycles per phase ({})",
+        benchmarks.join(", ")
+    );
+
+    Ok(overhead)
+}
+
+/// Results of a benchmark adjusted for the overhead of measurements
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Adjusted {
+    /// The overhead of every phase in cycles that was subtracted
+    pub overhead: u64,
+    /// The adjusted results, without the results of micro benchmarks
+    pub results: Vec<BenchmarkResult>,
+}
+
+/// Returns the results of a benchmark adjusted for the overhead of measurements,
+/// or None if the benchmark does not carry micro benchmarks and no overhead was calibrated
+///
+/// # Arguments
+///
+/// * `envelope` - the results of a benchmark
+/// * `calibrated` - the overhead of every phase calibrated before the file, if any
+pub fn adjust(envelope: &ResultEnvelope, calibrated: Option<u64>) -> Option<Adjusted> {
+    let (overhead, results): (u64, Vec<&BenchmarkResult>) = match &envelope.benchmark {
+        Some(BenchmarkInfo::Calibrated(_)) => (
+            minimum(&envelope.results, OVERHEAD_PHASE)?,
+            envelope
+                .results
+                .iter()
+                .filter(|result| !is_micro_benchmark(result))
+                .collect(),
+        ),
+        _ => (calibrated?, envelope.results.iter().collect()),
+    };
+
+    let results = results
+        .into_iter()
+        .map(|result| {
+            let phases: Vec<u64> = result
+                .phases()
+                .iter()
+                .map(|(_, cycles)| cycles.saturating_sub(overhead))
+                .collect();
+            result.with_phases(&phases)
+        })
+        .collect();
+    Some(Adjusted { overhead, results })
+}
@@ -0,0 +1,221 @@
+//! Checkpoints of long benchmark runs.
+//!
+//! While the messages of a file are sent, the progress is recorded in a .checkpoint file next to
+//! it: after every message the suite responded to, the responses and the output belonging to it
+//! are appended as a line of JSON. If the run dies, e.g. as the board browned out or the serial
+//! connection was lost, it is continued with `--resume`: the messages that were already answered
+//! are not sent again and the output of the new run is appended to the restored output, so the
+//! result files are written as if the run never stopped.
+//!
+//! The first line of a checkpoint contains the hash of the file and the number of messages sent
+//! for it, a checkpoint of a file that changed since, or whose messages changed due to different
+//! options, is not resumed. A line that was cut off while it was written is ignored, the restored
+//! progress is written anew when resuming. The checkpoint is removed once the suite responded to
+//! all messages of the file, a run that is not resumed starts a new checkpoint.
+
+use std::{
+    fs::{self, File},
+    io::{self, LineWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use benchmark_common::IncomingMessage;
+use serde::{Deserialize, Serialize};
+
+use crate::{manifest::Artifact, ExitCode};
+
+/// First line of a checkpoint, identifying the run it belongs to
+#[derive(Serialize, Deserialize, PartialEq, Eq)]
+struct Header {
+    /// SHA256 hash of the file, hex encoded
+    sha256: String,
+    /// Number of messages sent for the file
+    messages: usize,
+}
+
+/// Progress recorded after a message was answered
+#[derive(Serialize, Deserialize)]
+struct Step {
+    /// Number of messages answered so far
+    answered: usize,
+    /// Responses received since the previous step, with the line number of their entry
+    responses: Vec<(usize, IncomingMessage)>,
+    /// Output written since the previous step
+    output: String,
+    /// Exit code corresponding to the first failure that occurred so far
+    exit_code: ExitCode,
+}
+
+/// Progress of a run restored from its checkpoint
+pub struct Progress {
+    /// Number of messages that were answered
+    pub answered: usize,
+    /// The responses received, with the line number of their entry, in order
+    pub responses: Vec<(usize, IncomingMessage)>,
+    /// Human readable record of all responses and anomalies
+    pub output: String,
+    /// Exit code corresponding to the first failure that occurred
+    pub exit_code: ExitCode,
+}
+
+/// Records the progress of a run
+pub struct Checkpoint {
+    path: PathBuf,
+    file: LineWriter<File>,
+    /// Number of responses already recorded
+    responses: usize,
+    /// Length of the output already recorded
+    output: usize,
+}
+
+impl Checkpoint {
+    /// Starts recording the progress of a file, or continues the recorded progress when resuming.
+    /// Returns the checkpoint, None if it could not be created, and the restored progress, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_file` - the file whose messages are sent
+    /// * `messages` - the number of messages sent for the file
+    /// * `resume` - whether the recorded progress is continued
+    pub fn open(
+        input_file: &Path,
+        messages: usize,
+        resume: bool,
+    ) -> (Option<Checkpoint>, Option<Progress>) {
+        let path = input_file.with_extension("checkpoint");
+        let header = match Artifact::hash(input_file) {
+            Ok(artifact) => Header {
+                sha256: artifact.sha256,
+                messages,
+            },
+            Err(err) => {
+                eprintln!("{err}, the run can not be resumed");
+                return (None, None);
+            }
+        };
+
+        let progress = if resume {
+            let progress = restore(&path, &header);
+            match &progress {
+                Some(progress) => eprintln!(
+                    "Resuming {} after {} of {messages} messages",
+                    input_file.display(),
+                    progress.answered
+                ),
+                None => eprintln!(
+                    "No checkpoint of this version of {}, starting from the beginning",
+                    input_file.display()
+                ),
+            }
+            progress
+        } else {
+            None
+        };
+
+        let file = File::create(&path).and_then(|file| {
+            let mut file = LineWriter::new(file);
+            let header = serde_json::to_string(&header).expect("Can not serialize struct");
+            writeln!(file, "{header}").map(|()| file)
+        });
+        let mut checkpoint = match file {
+            Ok(file) => Checkpoint {
+                path,
+                file,
+                responses: 0,
+                output: 0,
+            },
+            Err(err) => {
+                eprintln!(
+                    "Failed to create {}: {err}, the run can not be resumed",
+                    path.display()
+                );
+                return (None, progress);
+            }
+        };
+        if let Some(progress) = &progress {
+            checkpoint.record(
+                progress.answered,
+                &progress.responses,
+                &progress.output,
+                progress.exit_code,
+            );
+        }
+
+        (Some(checkpoint), progress)
+    }
+
+    /// Records the progress after a message was answered. A checkpoint that can not be written is
+    /// reported, it does not abort the run.
+    ///
+    /// # Arguments
+    ///
+    /// * `answered` - the number of messages answered so far
+    /// * `responses` - all responses received so far
+    /// * `output` - the whole output so far
+    /// * `exit_code` - the exit code corresponding to the first failure so far
+    pub fn record(
+        &mut self,
+        answered: usize,
+        responses: &[(usize, IncomingMessage)],
+        output: &str,
+        exit_code: ExitCode,
+    ) {
+        let step = Step {
+            answered,
+            responses: responses[self.responses..].to_vec(),
+            output: output[self.output..].to_string(),
+            exit_code,
+        };
+        let line = serde_json::to_string(&step).expect("Can not serialize struct");
+
+        match writeln!(self.file, "{line}") {
+            Ok(()) => {
+                self.responses = responses.len();
+                self.output = output.len();
+            }
+            Err(err) => eprintln!("Failed to write {}: {err}", self.path.display()),
+        }
+    }
+
+    /// Removes the checkpoint after all messages were answered
+    pub fn remove(self) -> io::Result<()> {
+        let Checkpoint { path, file, .. } = self;
+        drop(file);
+        fs::remove_file(path)
+    }
+
+    /// Returns the path of the checkpoint
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// Restores the progress recorded in a checkpoint, None if there is no checkpoint of the run
+///
+/// # Arguments
+///
+/// * `path` - the path of the checkpoint
+/// * `header` - identifies the run that is resumed
+fn restore(path: &Path, header: &Header) -> Option<Progress> {
+    let content = fs::read_to_string(path).ok()?;
+    let mut lines = content.lines();
+    if serde_json::from_str::<Header>(lines.next()?).ok()? != *header {
+        return None;
+    }
+
+    let mut progress = Progress {
+        answered: 0,
+        responses: Vec::new(),
+        output: String::new(),
+        exit_code: ExitCode::Success,
+    };
+    // Only the last line can be cut off, the run died while it was written
+    for mut step in lines.map_while(|line| serde_json::from_str::<Step>(line).ok()) {
+        progress.answered = step.answered;
+        progress.responses.append(&mut step.responses);
+        progress.output.push_str(&step.output);
+        progress.exit_code = step.exit_code;
+    }
+
+    Some(progress)
+}
@@ -0,0 +1,181 @@
+//! Destination of the lines printed while the messages of a file are sent to the suite.
+//!
+//! Lines are printed to stdout and stderr, or shown in the live dashboard, check the dashboard
+//! module. The dashboard is only available if the CLI is built with the `dashboard` feature.
+
+#[cfg(not(feature = "dashboard"))]
+use std::{io, time::Duration};
+
+use benchmark_common::{IncomingMessage, OutgoingMessage};
+
+#[cfg(feature = "dashboard")]
+use crate::dashboard::Dashboard;
+use crate::tty::SuiteConnection;
+
+/// Stands in for the dashboard if the CLI is built without the `dashboard` feature,
+/// it can not be opened, so the console never shows one
+#[cfg(not(feature = "dashboard"))]
+enum Dashboard {}
+
+#[cfg(not(feature = "dashboard"))]
+impl Dashboard {
+    fn open(_title: String, _total: usize, _latency: Duration) -> io::Result<Dashboard> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "the CLI was built without the dashboard feature",
+        ))
+    }
+
+    fn log(&mut self, _line: String) {
+        match *self {}
+    }
+
+    fn take_logs(&mut self, _suite: &mut SuiteConnection) {
+        match *self {}
+    }
+
+    fn performing(&mut self, _line: usize, _msg: &OutgoingMessage) -> bool {
+        match *self {}
+    }
+
+    fn received(&mut self, _suite: &mut SuiteConnection, _msg: &IncomingMessage) {
+        match *self {}
+    }
+
+    fn responded(&mut self) {
+        match *self {}
+    }
+
+    fn draw(&mut self) -> io::Result<()> {
+        match *self {}
+    }
+}
+
+/// Destination of the lines printed while the messages of a file are sent to the suite
+pub struct Console {
+    dashboard: Option<Dashboard>,
+    /// Whether stdout is reserved for the responses streamed to another process
+    piped: bool,
+}
+
+impl Console {
+    /// Returns a console printing lines to stdout and stderr
+    pub fn plain() -> Console {
+        Console {
+            dashboard: None,
+            piped: false,
+        }
+    }
+
+    /// Returns a console printing all lines to stderr, as stdout is reserved for the responses
+    /// that are streamed to another process
+    pub fn piped() -> Console {
+        Console {
+            dashboard: None,
+            piped: true,
+        }
+    }
+
+    /// Returns a console showing a dashboard, or printing lines if the dashboard can not be shown.
+    /// The log messages of the suite are collected while the dashboard is shown.
+    ///
+    /// # Arguments
+    ///
+    /// * `title` - the title of the dashboard, e.g. the name of the file
+    /// * `total` - the number of responses expected from the suite
+    /// * `suite` - the connection to the suite
+    pub fn dashboard(title: String, total: usize, suite: &mut SuiteConnection) -> Console {
+        match Dashboard::open(title, total, suite.latency()) {
+            Ok(dashboard) => {
+                suite.collect_logs(true);
+                let mut console = Console {
+                    dashboard: Some(dashboard),
+                    piped: false,
+                };
+                console.redraw();
+                console
+            }
+            Err(err) => {
+                eprintln!("Failed to show dashboard: {err}");
+                Console::plain()
+            }
+        }
+    }
+
+    /// Prints a line to stdout, or stderr if stdout is piped, or adds it to the log of the dashboard
+    pub fn println(&mut self, line: String) {
+        match &mut self.dashboard {
+            Some(dashboard) => dashboard.log(line),
+            None if self.piped => eprintln!("{line}"),
+            None => println!("{line}"),
+        }
+        self.redraw();
+    }
+
+    /// Prints a line to stderr, or adds it to the log of the dashboard
+    pub fn eprintln(&mut self, line: String) {
+        match &mut self.dashboard {
+            Some(dashboard) => dashboard.log(line),
+            None => eprintln!("{line}"),
+        }
+        self.redraw();
+    }
+
+    /// Shows the message the suite is expected to respond to next
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - the line number of the message
+    /// * `msg` - the message
+    pub fn performing(&mut self, line: usize, msg: &OutgoingMessage) {
+        if let Some(dashboard) = &mut self.dashboard {
+            if dashboard.performing(line, msg) {
+                self.redraw();
+            }
+        }
+    }
+
+    /// Shows a message received from the suite and the log messages received before it
+    ///
+    /// # Arguments
+    ///
+    /// * `suite` - the connection the message was received from
+    /// * `msg` - the message
+    pub fn received(&mut self, suite: &mut SuiteConnection, msg: &IncomingMessage) {
+        if let Some(dashboard) = &mut self.dashboard {
+            dashboard.received(suite, msg);
+        }
+        self.redraw();
+    }
+
+    /// Advances the progress, after the response to a message was received or reused
+    pub fn responded(&mut self) {
+        if let Some(dashboard) = &mut self.dashboard {
+            dashboard.responded();
+        }
+        self.redraw();
+    }
+
+    /// Closes the dashboard, if shown, and prints its log.
+    /// The log messages of the suite are printed again afterwards
+    ///
+    /// # Arguments
+    ///
+    /// * `suite` - the connection to the suite
+    pub fn close(mut self, suite: &mut SuiteConnection) {
+        if let Some(dashboard) = &mut self.dashboard {
+            dashboard.take_logs(suite);
+        }
+        suite.collect_logs(false);
+    }
+
+    /// Redraws the dashboard, it is closed if it can not be drawn
+    fn redraw(&mut self) {
+        if let Some(dashboard) = &mut self.dashboard {
+            if let Err(err) = dashboard.draw() {
+                self.dashboard = None;
+                eprintln!("Failed to draw dashboard: {err}");
+            }
+        }
+    }
+}
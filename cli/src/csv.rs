@@ -0,0 +1,126 @@
+//! Export of benchmark results as CSV files.
+//!
+//! Every benchmark is written to its own file, so the results can be loaded directly into
+//! spreadsheets or data frames. A file contains one row per result and one column per phase.
+//! Phases that occur multiple times in a result, like the squeezes of every block of an XOF,
+//! get one column per occurrence, numbered starting at 0, e.g. `squeeze_0,squeeze_1`.
+
+use std::{collections::BTreeMap, fmt::Write, fs, io, path::Path};
+
+use benchmark_common::{Aggregate, BenchmarkResult, IncomingMessage};
+
+/// Writes a CSV file for every benchmark whose results are contained in the responses
+///
+/// The files are named after the line number and the benchmark, e.g. `3_AES.csv`, check `lines`
+/// for lines that requested multiple benchmarks. Responses that do not contain results are ignored.
+/// Fails if the directory could not be created or a file could not be written.
+///
+/// # Arguments
+///
+/// * `dir` - the directory the files are written to, created if it does not exist
+/// * `responses` - the responses, with the line number of the message they belong to
+pub fn write_csv(dir: &Path, responses: &[(usize, IncomingMessage)]) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+
+    for ((_, response), line) in responses.iter().zip(lines(responses)) {
+        let envelope = match response {
+            IncomingMessage::BenchmarkResults(envelope) => envelope,
+            _ => continue,
+        };
+        let name = match envelope.results.first() {
+            Some(result) => result.name(),
+            None => continue,
+        };
+
+        // Summaries contain the minimum, mean and maximum, in this order
+        let (first_column, rows): (&str, Vec<String>) = match envelope.aggregate {
+            Aggregate::Summary => (
+                "statistic",
+                ["min", "mean", "max"]
+                    .iter()
+                    .map(|row| row.to_string())
+                    .collect(),
+            ),
+            _ => (
+                "repetition",
+                (0..envelope.results.len()).map(|i| i.to_string()).collect(),
+            ),
+        };
+
+        let mut csv = String::new();
+        writeln!(
+            csv,
+            "{first_column},{}",
+            columns(&envelope.results[0]).join(",")
+        )
+        .unwrap();
+        for (row, result) in rows.iter().zip(&envelope.results) {
+            let cycles: Vec<String> = result
+                .phases()
+                .iter()
+                .map(|(_, cycles)| cycles.to_string())
+                .collect();
+            writeln!(csv, "{row},{}", cycles.join(",")).unwrap();
+        }
+
+        fs::write(dir.join(format!("{line}_{name}.csv")), csv)?;
+    }
+
+    Ok(())
+}
+
+/// Returns the line number every response is named after in exported files. Lines that were
+/// expanded into multiple benchmarks by directives are followed by the number of the results,
+/// starting at 0, e.g. `3-0` and `3-1`, so they do not overwrite each other.
+///
+/// # Arguments
+///
+/// * `responses` - the responses, with the line number of the message they belong to
+pub fn lines(responses: &[(usize, IncomingMessage)]) -> Vec<String> {
+    let mut results: BTreeMap<usize, usize> = BTreeMap::new();
+    for (line, response) in responses {
+        if let IncomingMessage::BenchmarkResults(_) = response {
+            *results.entry(*line).or_default() += 1;
+        }
+    }
+
+    let mut numbered: BTreeMap<usize, usize> = BTreeMap::new();
+    responses
+        .iter()
+        .map(|(line, response)| match (response, results.get(line)) {
+            (IncomingMessage::BenchmarkResults(_), Some(count)) if *count > 1 => {
+                let number = numbered.entry(*line).or_default();
+                *number += 1;
+                format!("{line}-{}", *number - 1)
+            }
+            _ => line.to_string(),
+        })
+        .collect()
+}
+
+/// Returns the column names of the phases of a result
+///
+/// # Arguments
+///
+/// * `result` - the result whose phases are named
+pub fn columns(result: &BenchmarkResult) -> Vec<String> {
+    let phases = result.phases();
+    let mut occurrences: BTreeMap<&str, usize> = BTreeMap::new();
+    for (name, _) in &phases {
+        *occurrences.entry(name).or_default() += 1;
+    }
+
+    let mut seen: BTreeMap<&str, usize> = BTreeMap::new();
+    phases
+        .iter()
+        .map(|(name, _)| {
+            if occurrences[name] > 1 {
+                let index = seen.entry(name).or_default();
+                *index += 1;
+                format!("{name}_{}", *index - 1)
+            } else {
+                name.to_string()
+            }
+        })
+        .collect()
+}
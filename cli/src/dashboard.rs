@@ -0,0 +1,248 @@
+//! Live dashboard shown while the messages of a file are sent to the suite.
+//!
+//! The dashboard is a terminal UI showing the benchmark currently performed, the progress through
+//! the file, a summary of the last results and the health of the serial link. Lines that would be
+//! printed during the run, including the log messages of the suite, are shown in its log instead
+//! and printed once it is closed, so nothing is lost. The dashboard is redrawn whenever a message
+//! is received, a benchmark that runs for hours therefore shows the time of its last message.
+//! It is only built with the `dashboard` feature.
+
+use std::{
+    io::{self, Stdout},
+    time::{Duration, Instant},
+};
+
+use benchmark_common::{IncomingMessage, OutgoingMessage};
+use crossterm::{cursor, execute, terminal};
+use tui::{
+    backend::CrosstermBackend,
+    layout::{Constraint, Direction, Layout},
+    widgets::{Block, Borders, Gauge, Paragraph},
+    Terminal,
+};
+
+use crate::{csv::columns, statistics::summarize, tty::SuiteConnection};
+
+/// Height of the panes showing the last results and the health of the link, including borders
+const SUMMARY_HEIGHT: u16 = 10;
+
+/// Terminal UI showing the state of a run, check the module documentation
+pub(crate) struct Dashboard {
+    terminal: Terminal<CrosstermBackend<Stdout>>,
+    title: String,
+    started: Instant,
+    /// Number of responses expected from the suite
+    total: usize,
+    /// Number of responses received or reused
+    done: usize,
+    /// Description of the message the suite is expected to respond to next
+    current: Option<String>,
+    /// Lines summarizing the last results received
+    last_result: Vec<String>,
+    /// Round-trip latency measured before the run
+    latency: Duration,
+    /// Number of frames received from the suite
+    frames: usize,
+    /// Number of invalid messages received from the suite
+    invalid: usize,
+    /// Time since the start of the run at which the last message was received
+    last_message: Option<Duration>,
+    /// Lines printed during the run, printed once the dashboard is closed
+    log: Vec<String>,
+}
+
+impl Dashboard {
+    /// Switches the terminal to the alternate screen, which the dashboard is drawn on
+    ///
+    /// # Arguments
+    ///
+    /// * `title` - the title of the dashboard
+    /// * `total` - the number of responses expected from the suite
+    /// * `latency` - the round-trip latency measured before the run
+    pub(crate) fn open(title: String, total: usize, latency: Duration) -> io::Result<Dashboard> {
+        let mut stdout = io::stdout();
+        execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+
+        Ok(Dashboard {
+            terminal: Terminal::new(CrosstermBackend::new(stdout))?,
+            title,
+            started: Instant::now(),
+            total,
+            done: 0,
+            current: None,
+            last_result: Vec::new(),
+            latency,
+            frames: 0,
+            invalid: 0,
+            last_message: None,
+            log: Vec::new(),
+        })
+    }
+
+    /// Adds a line to the log
+    pub(crate) fn log(&mut self, line: String) {
+        self.log.push(line);
+    }
+
+    /// Adds the log messages of the suite received so far to the log
+    ///
+    /// # Arguments
+    ///
+    /// * `suite` - the connection to the suite
+    pub(crate) fn take_logs(&mut self, suite: &mut SuiteConnection) {
+        self.log.append(&mut suite.take_logs());
+    }
+
+    /// Shows the message the suite is expected to respond to next,
+    /// returns whether the dashboard changed
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - the line number of the message
+    /// * `msg` - the message
+    pub(crate) fn performing(&mut self, line: usize, msg: &OutgoingMessage) -> bool {
+        let current = match msg {
+            OutgoingMessage::Benchmark(info, config) => {
+                format!("Line {line}: {info:?} ({} repetitions)", config.repetitions)
+            }
+            msg => format!("Line {line}: {msg:?}"),
+        };
+        if self.current.as_ref() == Some(&current) {
+            return false;
+        }
+        self.current = Some(current);
+        true
+    }
+
+    /// Shows a message received from the suite and the log messages received before it
+    ///
+    /// # Arguments
+    ///
+    /// * `suite` - the connection the message was received from
+    /// * `msg` - the message
+    pub(crate) fn received(&mut self, suite: &mut SuiteConnection, msg: &IncomingMessage) {
+        self.take_logs(suite);
+        self.frames = suite.frames_received();
+        self.last_message = Some(self.started.elapsed());
+
+        match msg {
+            IncomingMessage::BenchmarkResults(envelope)
+            | IncomingMessage::Window {
+                results: envelope, ..
+            } => {
+                let mut summary = vec![format!(
+                    "{}: {} results",
+                    envelope.results.first().map_or("-", |result| result.name()),
+                    envelope.results.len()
+                )];
+                match summarize(envelope).first() {
+                    Some(statistics) => summary.extend(statistics.phases.iter().map(|phase| {
+                        format!(
+                            "{}: min {}, mean {:.1}, max {}",
+                            phase.phase,
+                            phase.statistics.min,
+                            phase.statistics.mean,
+                            phase.statistics.max
+                        )
+                    })),
+                    None => {
+                        if let Some(result) = envelope.results.first() {
+                            summary.extend(
+                                columns(result)
+                                    .iter()
+                                    .zip(result.phases())
+                                    .map(|(phase, (_, cycles))| format!("{phase}: {cycles}")),
+                            )
+                        }
+                    }
+                }
+                self.last_result = summary;
+            }
+            IncomingMessage::Skipped { .. } | IncomingMessage::Error(_) => {
+                self.last_result = vec![format!("{msg:?}")];
+            }
+            IncomingMessage::Invalid(_) => self.invalid += 1,
+            _ => {}
+        }
+    }
+
+    /// Advances the progress, after the response to a message was received or reused
+    pub(crate) fn responded(&mut self) {
+        self.done += 1;
+    }
+
+    /// Draws the current state of the run
+    pub(crate) fn draw(&mut self) -> io::Result<()> {
+        let current = format!(
+            "{}\nElapsed: {}s",
+            self.current.as_deref().unwrap_or("Waiting for the suite"),
+            self.started.elapsed().as_secs()
+        );
+        let ratio = if self.total == 0 {
+            1.0
+        } else {
+            (self.done as f64 / self.total as f64).min(1.0)
+        };
+        let progress = format!("{}/{} responses", self.done, self.total);
+        let link =
+            format!(
+            "Round-trip latency: {:?}\nFrames received: {}\nInvalid messages: {}\nLast message: {}",
+            self.latency,
+            self.frames,
+            self.invalid,
+            self.last_message
+                .map_or(String::from("-"), |time| format!("after {}s", time.as_secs()))
+        );
+        let last_result = self.last_result.join("\n");
+        let log = &self.log;
+        let title = self.title.as_str();
+
+        self.terminal.draw(|frame| {
+            let rows = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Length(4),
+                    Constraint::Length(3),
+                    Constraint::Length(SUMMARY_HEIGHT),
+                    Constraint::Min(3),
+                ])
+                .split(frame.size());
+            let panes = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+                .split(rows[2]);
+
+            let block = |title| Block::default().title(title).borders(Borders::ALL);
+            // Only the most recent lines of the log fit into its pane
+            let visible = rows[3].height.saturating_sub(2) as usize;
+            let recent = log[log.len().saturating_sub(visible)..].join("\n");
+
+            frame.render_widget(Paragraph::new(current).block(block(title)), rows[0]);
+            frame.render_widget(
+                Gauge::default()
+                    .block(block("Progress"))
+                    .ratio(ratio)
+                    .label(progress),
+                rows[1],
+            );
+            frame.render_widget(
+                Paragraph::new(last_result).block(block("Last result")),
+                panes[0],
+            );
+            frame.render_widget(Paragraph::new(link).block(block("Serial link")), panes[1]);
+            frame.render_widget(Paragraph::new(recent).block(block("Log")), rows[3]);
+        })?;
+
+        Ok(())
+    }
+}
+
+impl Drop for Dashboard {
+    fn drop(&mut self) {
+        // The terminal is restored even if the CLI panics while the dashboard is shown
+        let _ = execute!(io::stdout(), terminal::LeaveAlternateScreen, cursor::Show);
+        for line in &self.log {
+            println!("{line}");
+        }
+    }
+}
@@ -0,0 +1,266 @@
+//! Storage of benchmark results in a SQLite database.
+//!
+//! Every processed file is stored as a run, together with the time it was processed, the platform
+//! and build of the suite and the arguments of the CLI. Every result of a run is stored as a row
+//! of `results`, the cycles of its phases as rows of `phases`, named like the columns of CSV files.
+//! Collecting many runs in one database allows to query how results develop over time:
+//!
+//! ```sql
+//! SELECT runs.started, runs.git_hash, AVG(phases.cycles) FROM phases
+//!     JOIN results ON results.id = phases.result JOIN runs ON runs.id = results.run
+//!     WHERE results.name = 'AES' AND results.dataset = 0 AND phases.phase = 'computation'
+//!     GROUP BY runs.id ORDER BY runs.started;
+//! ```
+
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use benchmark_common::{
+    Aggregate, BenchmarkResult, IncomingMessage, OutgoingMessage, PlatformDescriptor,
+    SCHEMA_VERSION,
+};
+use rusqlite::{params, Connection, OpenFlags};
+
+use crate::{
+    csv::columns,
+    manifest::Artifact,
+    raw::{dataset_of, RawEntry},
+};
+
+/// Tables of the database, created if they do not exist
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS runs (
+    id INTEGER PRIMARY KEY,
+    started INTEGER NOT NULL,
+    file TEXT NOT NULL,
+    arguments TEXT NOT NULL,
+    platform TEXT,
+    git_hash TEXT,
+    elf_sha256 TEXT,
+    schema_version INTEGER NOT NULL,
+    board TEXT
+);
+CREATE TABLE IF NOT EXISTS results (
+    id INTEGER PRIMARY KEY,
+    run INTEGER NOT NULL REFERENCES runs(id),
+    line INTEGER NOT NULL,
+    benchmark TEXT,
+    dataset INTEGER,
+    aggregate TEXT NOT NULL,
+    result_index INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    result TEXT NOT NULL,
+    label TEXT
+);
+CREATE TABLE IF NOT EXISTS phases (
+    result INTEGER NOT NULL REFERENCES results(id),
+    phase TEXT NOT NULL,
+    cycles INTEGER NOT NULL
+);
+";
+
+/// Describes the database results are stored in and the invocation of the CLI producing them
+#[derive(Debug, Clone)]
+pub struct DatabaseOptions {
+    /// Path of the database, created if it does not exist
+    pub path: PathBuf,
+    /// The arguments the CLI was invoked with, stored with every run
+    pub arguments: Vec<String>,
+    /// The ELF of the suite that is measured, its SHA256 hash is stored with every run if provided
+    pub elf: Option<PathBuf>,
+}
+
+/// Time to wait for the database while another board stores its results
+const BUSY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Stores the results contained in the responses to the entries of a file as a new run
+///
+/// Only responses containing the results of a benchmark are stored,
+/// the results of all repetitions of a benchmark are stored as separate rows.
+/// The benchmark and dataset of results replayed by the suite are unknown.
+///
+/// # Arguments
+///
+/// * `options` - the database and the invocation of the CLI
+/// * `file` - the processed file
+/// * `board` - the board the file was processed on, if benchmarking on multiple boards
+/// * `platform` - the platform the suite reported, if any
+/// * `entries` - the entries of the file that were sent
+/// * `responses` - the responses, with the line number of the entry they belong to
+pub fn store_run(
+    options: &DatabaseOptions,
+    file: &Path,
+    board: Option<&str>,
+    platform: Option<&PlatformDescriptor>,
+    entries: &[RawEntry],
+    responses: &[(usize, IncomingMessage)],
+) -> Result<(), String> {
+    let elf = options.elf.as_deref().map(Artifact::hash).transpose()?;
+    let started = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|time| time.as_secs())
+        .unwrap_or_default();
+
+    let mut connection = Connection::open(&options.path)
+        .map_err(|err| format!("Failed to open {}: {err}", options.path.display()))?;
+    connection
+        .busy_timeout(BUSY_TIMEOUT)
+        .map_err(|err| err.to_string())?;
+    let transaction = connection.transaction().map_err(|err| err.to_string())?;
+    transaction
+        .execute_batch(SCHEMA)
+        .map_err(|err| err.to_string())?;
+    // Databases created before boards were recorded lack the column
+    if transaction.prepare("SELECT board FROM runs").is_err() {
+        transaction
+            .execute_batch("ALTER TABLE runs ADD COLUMN board TEXT")
+            .map_err(|err| err.to_string())?;
+    }
+    // Databases created before labels were recorded lack the column
+    if transaction.prepare("SELECT label FROM results").is_err() {
+        transaction
+            .execute_batch("ALTER TABLE results ADD COLUMN label TEXT")
+            .map_err(|err| err.to_string())?;
+    }
+
+    transaction
+        .execute(
+            "INSERT INTO runs (started, file, arguments, platform, git_hash, elf_sha256, schema_version, board)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                started as i64,
+                file.display().to_string(),
+                serde_json::to_string(&options.arguments).expect("Can not serialize struct"),
+                platform.map(|platform| platform.name.as_str()),
+                platform.and_then(|platform| platform.git_hash.as_deref()),
+                elf.map(|elf| elf.sha256),
+                SCHEMA_VERSION,
+                board,
+            ],
+        )
+        .map_err(|err| err.to_string())?;
+    let run = transaction.last_insert_rowid();
+
+    for (line, response) in responses {
+        let envelope = match response {
+            IncomingMessage::BenchmarkResults(envelope) => envelope,
+            _ => continue,
+        };
+        // Suites predating echoing the request only report it in the entry
+        let info = envelope.benchmark.as_ref().or_else(|| {
+            entries
+                .iter()
+                .find(|entry| entry.line_num == *line)
+                .and_then(|entry| match &entry.msg {
+                    OutgoingMessage::Benchmark(info, _) => Some(info),
+                    _ => None,
+                })
+        });
+
+        for (index, result) in envelope.results.iter().enumerate() {
+            transaction
+                .execute(
+                    "INSERT INTO results (run, line, benchmark, dataset, aggregate, result_index, name, result, label)
+                        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                    params![
+                        run,
+                        *line as i64,
+                        info.map(|info| {
+                            serde_json::to_string(info).expect("Can not serialize struct")
+                        }),
+                        info.and_then(dataset_of).map(|id| id as i64),
+                        format!("{:?}", envelope.aggregate),
+                        index as i64,
+                        result.name(),
+                        serde_json::to_string(result).expect("Can not serialize struct"),
+                        envelope.label,
+                    ],
+                )
+                .map_err(|err| err.to_string())?;
+            let id = transaction.last_insert_rowid();
+
+            for (phase, (_, cycles)) in columns(result).iter().zip(result.phases()) {
+                transaction
+                    .execute(
+                        "INSERT INTO phases (result, phase, cycles) VALUES (?1, ?2, ?3)",
+                        params![id, phase, cycles as i64],
+                    )
+                    .map_err(|err| err.to_string())?;
+            }
+        }
+    }
+
+    transaction.commit().map_err(|err| err.to_string())
+}
+
+/// Result stored in the database
+pub struct StoredResult {
+    /// Id of the run the result belongs to
+    pub run: i64,
+    /// Line of the entry that requested the result
+    pub line: i64,
+    /// Label of the benchmark, if any
+    pub label: Option<String>,
+    /// Id of the dataset the benchmark was performed on, if it uses one
+    pub dataset: Option<i64>,
+    /// How the results of the repetitions of the benchmark were reported
+    pub aggregate: Aggregate,
+    /// Index of the result in its envelope
+    pub index: usize,
+    /// The result
+    pub result: BenchmarkResult,
+}
+
+/// Reads all results stored in the database, the latest result first
+///
+/// # Arguments
+///
+/// * `path` - path of the database
+pub fn stored_results(path: &Path) -> Result<Vec<StoredResult>, String> {
+    let connection = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|err| format!("Failed to open {}: {err}", path.display()))?;
+    connection
+        .busy_timeout(BUSY_TIMEOUT)
+        .map_err(|err| err.to_string())?;
+
+    let mut statement = connection
+        .prepare(
+            "SELECT run, line, label, dataset, aggregate, result_index, result FROM results
+                ORDER BY id DESC",
+        )
+        .map_err(|err| err.to_string())?;
+    let rows = statement
+        .query_map([], |row| {
+            Ok((
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                row.get(3)?,
+                row.get::<_, String>(4)?,
+                row.get::<_, i64>(5)?,
+                row.get::<_, String>(6)?,
+            ))
+        })
+        .map_err(|err| err.to_string())?;
+
+    let mut results = Vec::new();
+    for row in rows {
+        let (run, line, label, dataset, aggregate, index, result) =
+            row.map_err(|err| err.to_string())?;
+        results.push(StoredResult {
+            run,
+            line,
+            label,
+            dataset,
+            // The aggregate is stored by the name of its variant
+            aggregate: serde_json::from_value(serde_json::Value::String(aggregate))
+                .map_err(|err| err.to_string())?,
+            index: index as usize,
+            result: serde_json::from_str(&result).map_err(|err| err.to_string())?,
+        });
+    }
+
+    Ok(results)
+}
@@ -0,0 +1,108 @@
+//! Flashing of the suite onto a board before it is benchmarked.
+//!
+//! The image is loaded by an external flasher command, opentitantool by default. The serial port
+//! of the suite is opened before flashing, so the banner the suite sends once it started, the resync
+//! token followed by the Ready status, is not missed. Benchmarks are only sent after the banner.
+
+use std::{path::Path, process::Command, str::FromStr, time::Duration};
+
+use crate::tty::{ConnectionOptions, SerialConnection};
+
+/// Placeholder of the flasher command that is replaced by the path of the image
+pub const IMAGE_PLACEHOLDER: &str = "{image}";
+
+/// Flasher used if none is configured
+pub const DEFAULT_FLASHER: &str = "opentitantool bootstrap {image}";
+
+/// Time the suite has to send its banner after it was flashed
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Command loading an image onto the board, e.g. `opentitantool bootstrap {image}`
+///
+/// The command is split at whitespace, every `{image}` is replaced by the path of the image.
+/// If the command does not contain the placeholder, the path is appended as last argument.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Flasher {
+    program: String,
+    arguments: Vec<String>,
+}
+
+impl Flasher {
+    /// Returns the command flashing the image
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - the path of the image
+    fn command(&self, image: &Path) -> Command {
+        let mut command = Command::new(&self.program);
+        let mut placed = false;
+        for argument in &self.arguments {
+            if argument.contains(IMAGE_PLACEHOLDER) {
+                placed = true;
+                let image = image.to_string_lossy();
+                command.arg(argument.replace(IMAGE_PLACEHOLDER, &image));
+            } else {
+                command.arg(argument);
+            }
+        }
+        if !placed {
+            command.arg(image);
+        }
+        command
+    }
+}
+
+impl FromStr for Flasher {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut words = s.split_whitespace().map(String::from);
+        match words.next() {
+            Some(program) => Ok(Flasher {
+                program,
+                arguments: words.collect(),
+            }),
+            None => Err(String::from("The flasher command is empty")),
+        }
+    }
+}
+
+/// Flashes the image onto the board and waits until the suite reports that it is ready.
+///
+/// Returns a description of the failure if the flasher failed
+/// or the suite did not start within `STARTUP_TIMEOUT`.
+///
+/// # Arguments
+///
+/// * `connection` - describes how to connect to the suite
+/// * `flasher` - the command loading the image
+/// * `image` - the image of the suite
+pub fn flash(
+    connection: &ConnectionOptions,
+    flasher: &Flasher,
+    image: &Path,
+) -> Result<(), String> {
+    let mut serial = SerialConnection::new(&connection.endpoint)
+        .map_err(|err| format!("Failed to connect to {}: {err}", connection.endpoint))?;
+    serial.set_transcript(connection.transcript.clone(), &connection.endpoint);
+    serial
+        .set_timeout(STARTUP_TIMEOUT)
+        .map_err(|err| err.to_string())?;
+    serial.discard_input().map_err(|err| err.to_string())?;
+
+    eprintln!("Flashing {}", image.display());
+    let status = flasher
+        .command(image)
+        .status()
+        .map_err(|err| format!("Failed to start {}: {err}", flasher.program))?;
+    if !status.success() {
+        return Err(format!("{} failed with {status}", flasher.program));
+    }
+
+    serial
+        .wait_for_startup()
+        .map_err(|err| format!("Suite did not start after flashing: {err}"))?;
+    eprintln!("Suite started after flashing");
+
+    Ok(())
+}
@@ -0,0 +1,300 @@
+//! Export of aggregated benchmark results as LaTeX tables.
+//!
+//! All results of a file are written as a single `tabular` using the rules of the `booktabs`
+//! package, ready to be included in a `table` environment of a paper. Every configuration of a
+//! benchmark, like every combination of an AES sweep, is a group of rows with one row per phase,
+//! named like the columns of CSV files. The statistics shown as columns and the unit of the values
+//! are configurable. Times are computed from the clock frequency reported by the suite.
+
+use std::{fmt::Write, fs, io, path::Path, str::FromStr};
+
+use benchmark_common::{Aggregate, IncomingMessage};
+
+use crate::{
+    csv::columns,
+    statistics::{configurations, Statistics},
+};
+
+/// Statistic shown as a column of the table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LatexColumn {
+    Min,
+    Max,
+    Mean,
+    Median,
+    Stddev,
+    P95,
+    P99,
+}
+
+impl LatexColumn {
+    /// Returns the header of the column
+    fn header(&self) -> &'static str {
+        match self {
+            LatexColumn::Min => "Min",
+            LatexColumn::Max => "Max",
+            LatexColumn::Mean => "Mean",
+            LatexColumn::Median => "Median",
+            LatexColumn::Stddev => "Std.\\ dev.",
+            LatexColumn::P95 => "P95",
+            LatexColumn::P99 => "P99",
+        }
+    }
+
+    /// True if the values of the column are always whole cycles
+    fn integral(&self) -> bool {
+        matches!(
+            self,
+            LatexColumn::Min | LatexColumn::Max | LatexColumn::P95 | LatexColumn::P99
+        )
+    }
+
+    /// Returns the value of the column in cycles, None if the statistic is not known
+    ///
+    /// # Arguments
+    ///
+    /// * `statistics` - the statistics of the phase, only minimum, mean and maximum are known for
+    ///   summaries
+    /// * `summary` - whether the statistics were computed from a summary
+    fn value(&self, statistics: &Statistics, summary: bool) -> Option<f64> {
+        match self {
+            LatexColumn::Min => Some(statistics.min as f64),
+            LatexColumn::Max => Some(statistics.max as f64),
+            LatexColumn::Mean => Some(statistics.mean),
+            _ if summary => None,
+            LatexColumn::Median => Some(statistics.median),
+            LatexColumn::Stddev => Some(statistics.stddev),
+            LatexColumn::P95 => Some(statistics.p95 as f64),
+            LatexColumn::P99 => Some(statistics.p99 as f64),
+        }
+    }
+}
+
+impl FromStr for LatexColumn {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "min" => Ok(LatexColumn::Min),
+            "max" => Ok(LatexColumn::Max),
+            "mean" => Ok(LatexColumn::Mean),
+            "median" => Ok(LatexColumn::Median),
+            "stddev" => Ok(LatexColumn::Stddev),
+            "p95" => Ok(LatexColumn::P95),
+            "p99" => Ok(LatexColumn::P99),
+            _ => Err(format!(
+                "Unknown column '{s}', use min, max, mean, median, stddev, p95 or p99"
+            )),
+        }
+    }
+}
+
+/// Unit of the values in the table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LatexUnit {
+    Cycles,
+    Nanoseconds,
+    Microseconds,
+    Milliseconds,
+}
+
+impl LatexUnit {
+    /// Returns the unit as written in the header of the table
+    fn label(&self) -> &'static str {
+        match self {
+            LatexUnit::Cycles => "cycles",
+            LatexUnit::Nanoseconds => "ns",
+            LatexUnit::Microseconds => "\\textmu s",
+            LatexUnit::Milliseconds => "ms",
+        }
+    }
+
+    /// Returns the number of seconds per unit, None for cycles
+    fn seconds(&self) -> Option<f64> {
+        match self {
+            LatexUnit::Cycles => None,
+            LatexUnit::Nanoseconds => Some(1e-9),
+            LatexUnit::Microseconds => Some(1e-6),
+            LatexUnit::Milliseconds => Some(1e-3),
+        }
+    }
+}
+
+impl FromStr for LatexUnit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "cycles" => Ok(LatexUnit::Cycles),
+            "ns" => Ok(LatexUnit::Nanoseconds),
+            "us" => Ok(LatexUnit::Microseconds),
+            "ms" => Ok(LatexUnit::Milliseconds),
+            _ => Err(format!("Unknown unit '{s}', use cycles, ns, us or ms")),
+        }
+    }
+}
+
+/// Describes the tables written for every file
+#[derive(Debug, Clone)]
+pub struct LatexOptions {
+    /// The statistics shown as columns, in this order
+    pub columns: Vec<LatexColumn>,
+    /// The unit of the values
+    pub unit: LatexUnit,
+    /// Names of the benchmarks whose results are written, like `AES`, all if empty
+    pub benchmarks: Vec<String>,
+}
+
+/// Writes a table of all results contained in the responses
+///
+/// Responses that do not contain results, or contain results of benchmarks that are not selected,
+/// are ignored. If times are requested but the clock frequency is not known, cycles are written.
+///
+/// # Arguments
+///
+/// * `path` - the path of the .tex file
+/// * `options` - the columns, unit and benchmarks of the table
+/// * `clock_frequency` - the frequency of the suite in Hz, if known
+/// * `responses` - the responses, with the line number of the message they belong to
+pub fn write_latex(
+    path: &Path,
+    options: &LatexOptions,
+    clock_frequency: Option<u64>,
+    responses: &[(usize, IncomingMessage)],
+) -> io::Result<()> {
+    let unit = match (options.unit.seconds(), clock_frequency) {
+        (Some(_), None) => {
+            eprintln!("The clock frequency of the suite is not known, writing cycles to LaTeX");
+            LatexUnit::Cycles
+        }
+        _ => options.unit,
+    };
+    // Number of units per cycle
+    let scale = match (unit.seconds(), clock_frequency) {
+        (Some(seconds), Some(frequency)) => 1.0 / (frequency as f64 * seconds),
+        _ => 1.0,
+    };
+
+    let mut rows = String::new();
+    for (line, response) in responses {
+        let envelope = match response {
+            IncomingMessage::BenchmarkResults(envelope) => envelope,
+            _ => continue,
+        };
+
+        // Summaries contain the minimum, mean and maximum, in this order
+        let groups = match envelope.aggregate {
+            Aggregate::Samples => configurations(envelope),
+            Aggregate::Summary if envelope.results.len() == 3 => {
+                let key = &envelope.results[0];
+                vec![(key.with_phases(&vec![0; key.phases().len()]), vec![0, 1, 2])]
+            }
+            _ => continue,
+        };
+        let summary = envelope.aggregate == Aggregate::Summary;
+
+        for (key, indices) in groups {
+            if !options.benchmarks.is_empty()
+                && !options.benchmarks.iter().any(|name| name == key.name())
+            {
+                continue;
+            }
+
+            let label = if indices[0] == 0 {
+                format!("{} (line {line})", key.name())
+            } else {
+                format!("{} (line {line}, from result {})", key.name(), indices[0])
+            };
+            if !rows.is_empty() {
+                rows.push_str("\\midrule\n");
+            }
+
+            for (phase, name) in columns(&key).into_iter().enumerate() {
+                let values: Vec<u64> = indices
+                    .iter()
+                    .map(|index| envelope.results[*index].phases()[phase].1)
+                    .collect();
+                let statistics = match Statistics::of(&values) {
+                    Some(statistics) if summary => Statistics {
+                        min: values[0],
+                        mean: values[1] as f64,
+                        max: values[2],
+                        ..statistics
+                    },
+                    Some(statistics) => statistics,
+                    None => continue,
+                };
+
+                let label = if phase == 0 {
+                    escape(&label)
+                } else {
+                    String::new()
+                };
+                let cells: Vec<String> = options
+                    .columns
+                    .iter()
+                    .map(|column| match column.value(&statistics, summary) {
+                        Some(value) if unit != LatexUnit::Cycles => {
+                            format!("{:.3}", value * scale)
+                        }
+                        Some(value) if column.integral() => format!("{value:.0}"),
+                        Some(value) => format!("{value:.1}"),
+                        None => String::from("--"),
+                    })
+                    .collect();
+                writeln!(
+                    rows,
+                    "{label} & {} & {} \\\\",
+                    escape(&name),
+                    cells.join(" & ")
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    let headers: Vec<String> = options
+        .columns
+        .iter()
+        .map(|column| format!("{} [{}]", column.header(), unit.label()))
+        .collect();
+
+    let mut tex = String::new();
+    writeln!(tex, "% Requires \\usepackage{{booktabs}}").unwrap();
+    writeln!(
+        tex,
+        "\\begin{{tabular}}{{ll{}}}",
+        "r".repeat(options.columns.len())
+    )
+    .unwrap();
+    writeln!(tex, "\\toprule").unwrap();
+    writeln!(tex, "Benchmark & Phase & {} \\\\", headers.join(" & ")).unwrap();
+    writeln!(tex, "\\midrule").unwrap();
+    tex.push_str(&rows);
+    writeln!(tex, "\\bottomrule").unwrap();
+    writeln!(tex, "\\end{{tabular}}").unwrap();
+
+    fs::write(path, tex)
+}
+
+/// Escapes the characters that have a special meaning in LaTeX
+///
+/// # Arguments
+///
+/// * `text` - the text to escape
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
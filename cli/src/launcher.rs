@@ -0,0 +1,279 @@
+//! Launching of the suite in an emulator or simulator.
+//!
+//! Instead of starting the emulator by hand and looking up the tty it connected the UART of the
+//! suite to, the CLI can start the emulator itself. The emulator is started with the ELF of the
+//! suite and its serial port redirected to a pty, whose path is read from the output of the
+//! emulator. The emulator is stopped once the `Emulator` is dropped, after all files were processed,
+//! or once its wall-clock timeout expired, which the CLI then notices as a lost connection.
+
+use std::{
+    env,
+    ffi::{OsStr, OsString},
+    fs,
+    io::{BufRead, BufReader, Read},
+    path::{Path, PathBuf},
+    process::{Child, Command, Stdio},
+    str::FromStr,
+    sync::{
+        mpsc::{self, RecvTimeoutError},
+        Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+/// Binary of QEMU emulating 32 bit RISC-V machines
+const QEMU_BINARY: &str = "qemu-system-riscv32";
+
+/// Output of QEMU preceding the path of the pty the serial port is redirected to
+const QEMU_PTY_PREFIX: &str = "char device redirected to ";
+
+/// Output of the Verilator simulation preceding the path of the pty of the UART
+const VERILATOR_PTY_PREFIX: &str = "UART: Created ";
+
+/// Time the emulator has to report the pty, before starting it is considered failed
+const LAUNCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Emulators the CLI can launch the suite in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Launcher {
+    /// The virt machine of QEMU, requires a suite built with the `platform_qemu_virt` feature
+    Qemu,
+    /// The Verilator simulation of the earlgrey chip, requires a suite built with the
+    /// `platform_verilator_earlgrey` feature. Like the runner of the suite, it is configured by the
+    /// environment variables `VERILATOR_SIM`, `VERILATOR_ROM` and `VERILATOR_OTP`
+    Verilator,
+}
+
+impl Launcher {
+    /// Returns the name of the emulator, as used in messages
+    fn name(&self) -> &'static str {
+        match self {
+            Launcher::Qemu => QEMU_BINARY,
+            Launcher::Verilator => "Verilator simulation",
+        }
+    }
+
+    /// Returns the command starting the emulator,
+    /// or a description of the failure if the emulator is not configured
+    ///
+    /// # Arguments
+    ///
+    /// * `elf` - the ELF of the suite
+    fn command(&self, elf: &Path) -> Result<Command, String> {
+        match self {
+            Launcher::Qemu => {
+                let mut command = Command::new(QEMU_BINARY);
+                command
+                    .args(["-M", "virt", "-cpu", "rv32", "-smp", "1", "-m", "32M"])
+                    .args(["-display", "none", "-bios", "none", "-serial", "pty"])
+                    .arg("-kernel")
+                    .arg(elf);
+                Ok(command)
+            }
+            Launcher::Verilator => {
+                let variable = |name: &str| {
+                    env::var_os(name)
+                        .ok_or_else(|| format!("Set {name} to launch the Verilator simulation"))
+                };
+                let (sim, rom, otp) = (
+                    variable("VERILATOR_SIM")?,
+                    variable("VERILATOR_ROM")?,
+                    variable("VERILATOR_OTP")?,
+                );
+
+                let mut command = Command::new(sim);
+                command
+                    .arg(meminit("rom", rom))
+                    .arg(meminit("flash", elf_image(elf)?.into_os_string()))
+                    .arg(meminit("otp", otp));
+                Ok(command)
+            }
+        }
+    }
+
+    /// Returns the path of the pty if the line of output reports it, e.g.
+    /// `char device redirected to /dev/pts/3 (label serial0)` for QEMU or
+    /// `UART: Created /dev/pts/3 for uart0. Connect to it with any terminal program` for Verilator
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - a line of output of the emulator
+    fn pty_of<'a>(&self, line: &'a str) -> Option<&'a str> {
+        let prefix = match self {
+            Launcher::Qemu => QEMU_PTY_PREFIX,
+            Launcher::Verilator => VERILATOR_PTY_PREFIX,
+        };
+        let start = line.find(prefix)? + prefix.len();
+        line[start..].split_whitespace().next()
+    }
+}
+
+impl FromStr for Launcher {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "qemu" => Ok(Launcher::Qemu),
+            "verilator" => Ok(Launcher::Verilator),
+            _ => Err(format!("Unknown launcher '{s}', use qemu or verilator")),
+        }
+    }
+}
+
+/// An emulator running the suite, stopped when dropped
+pub struct Emulator {
+    child: Arc<Mutex<Child>>,
+    /// Path to the pty the serial port of the suite is redirected to
+    tty: OsString,
+    /// Stops the thread enforcing the wall-clock timeout once dropped, if a timeout is set
+    _watchdog: Option<mpsc::Sender<()>>,
+}
+
+impl Emulator {
+    /// Starts the suite in an emulator and waits until the emulator reports the pty of the
+    /// serial port. Returns a description of the failure if it could not be started.
+    ///
+    /// # Arguments
+    ///
+    /// * `launcher` - the emulator to use
+    /// * `elf` - the ELF of the suite
+    /// * `timeout` - the wall-clock time after which the emulator is stopped, if any
+    pub fn launch(
+        launcher: Launcher,
+        elf: &Path,
+        timeout: Option<Duration>,
+    ) -> Result<Emulator, String> {
+        let name = launcher.name();
+        let mut child = launcher
+            .command(elf)?
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|err| format!("Failed to start {name}: {err}"))?;
+
+        // Depending on its version, QEMU reports the pty on stdout or stderr
+        let (sender, receiver) = mpsc::channel();
+        forward_lines(child.stdout.take(), sender.clone());
+        forward_lines(child.stderr.take(), sender);
+
+        let mut output = Vec::new();
+        let tty = loop {
+            match receiver.recv_timeout(LAUNCH_TIMEOUT) {
+                Ok(line) => match launcher.pty_of(&line) {
+                    Some(tty) => break OsString::from(tty),
+                    None => output.push(line),
+                },
+                Err(err) => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    let reason = match err {
+                        RecvTimeoutError::Timeout => "did not report a pty in time",
+                        RecvTimeoutError::Disconnected => "exited without reporting a pty",
+                    };
+                    return Err(format!(
+                        "{name} {reason}, its output was:\n{}",
+                        output.join("\n")
+                    ));
+                }
+            }
+        };
+        eprintln!(
+            "Launched {name}, the suite is connected to {}",
+            tty.to_string_lossy()
+        );
+
+        let child = Arc::new(Mutex::new(child));
+        let watchdog = timeout.map(|timeout| {
+            let (stop, stopped) = mpsc::channel::<()>();
+            let child = child.clone();
+            thread::spawn(move || {
+                if let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(timeout) {
+                    eprintln!(
+                        "{name} exceeded its timeout of {}s, stopping it",
+                        timeout.as_secs()
+                    );
+                    let mut child = child.lock().expect("Emulator lock poisoned");
+                    let _ = child.kill();
+                    let _ = child.wait();
+                }
+            });
+            stop
+        });
+
+        Ok(Emulator {
+            child,
+            tty,
+            _watchdog: watchdog,
+        })
+    }
+
+    /// Returns the path to the pty the serial port of the suite is redirected to
+    pub fn tty(&self) -> &OsString {
+        &self.tty
+    }
+}
+
+impl Drop for Emulator {
+    fn drop(&mut self) {
+        // The suite keeps waiting for requests after a file was processed, so it is stopped
+        if let Ok(mut child) = self.child.lock() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+/// Sends every line read from the output of the emulator to the receiver, on a separate thread.
+/// The output is read until the emulator exits, even once nobody receives the lines anymore,
+/// so the emulator never blocks writing to a full pipe.
+///
+/// # Arguments
+///
+/// * `output` - stdout or stderr of the emulator
+/// * `sender` - the channel the lines are sent to
+fn forward_lines(output: Option<impl Read + Send + 'static>, sender: mpsc::Sender<String>) {
+    if let Some(output) = output {
+        thread::spawn(move || {
+            for line in BufReader::new(output).lines() {
+                match line {
+                    Ok(line) => {
+                        let _ = sender.send(line);
+                    }
+                    Err(_) => break,
+                }
+            }
+        });
+    }
+}
+
+/// Returns a `--meminit` argument of the Verilator simulation
+///
+/// # Arguments
+///
+/// * `memory` - the memory that is initialized, e.g. `rom`
+/// * `image` - the path to the image the memory is initialized with
+fn meminit(memory: &str, image: OsString) -> OsString {
+    let mut argument = OsString::from(format!("--meminit={memory},"));
+    argument.push(image);
+    argument
+}
+
+/// Returns the path of the ELF with an `.elf` extension, which the Verilator simulation requires
+/// to detect the format of the image. Like the runner of the suite, the ELF is copied otherwise.
+///
+/// # Arguments
+///
+/// * `elf` - the ELF of the suite
+fn elf_image(elf: &Path) -> Result<PathBuf, String> {
+    if elf.extension() == Some(OsStr::new("elf")) {
+        return Ok(elf.to_path_buf());
+    }
+
+    let mut image = elf.as_os_str().to_os_string();
+    image.push(".elf");
+    let image = PathBuf::from(image);
+    fs::copy(elf, &image).map_err(|err| format!("Failed to copy {}: {err}", elf.display()))?;
+    Ok(image)
+}
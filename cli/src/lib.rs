@@ -1,8 +1,511 @@
+//! The benchmarking CLI, usable as a library by other tools.
+//!
+//! The binary processes files, but the same building blocks are available to tools that drive
+//! the suite themselves, e.g. a harness capturing side-channel traces while a benchmark runs:
+//!
+//! ```no_run
+//! use benchmark_common::BenchmarkInfo;
+//! use cli::{
+//!     connect,
+//!     transport::Endpoint,
+//!     tty::{ConnectionOptions, Handshake},
+//! };
+//!
+//! let connection = ConnectionOptions {
+//!     endpoint: Endpoint::Tcp(String::from("localhost:5555")),
+//!     handshake: Handshake::Resync,
+//!     format: None,
+//!     transcript: None,
+//!     board: None,
+//! };
+//! let mut suite = connect(&connection).unwrap();
+//! let result = suite.run_benchmark(BenchmarkInfo::ExampleSHA256).unwrap();
+//! println!("{:?}", result.phases());
+//! ```
+//!
+//! `connect` establishes a `SuiteConnection`, whose `run_benchmark` performs a single benchmark and
+//! returns its typed result, `run_benchmark_with` performs it as configured. Plans, built with
+//! `plan::parse_plan` or directly, are performed with `perform_plan`, which returns the responses
+//! of every section instead of writing result files. `benchmark_file` and `benchmark_raw_file`
+//! process files like the binary does.
+
+#[cfg(feature = "database")]
+pub mod badge;
+pub mod budget;
+pub mod cache;
+pub mod calibration;
+pub mod checkpoint;
+pub mod console;
+pub mod csv;
+#[cfg(feature = "dashboard")]
+pub mod dashboard;
+#[cfg(feature = "database")]
+pub mod database;
+pub mod flash;
+pub mod latex;
+pub mod launcher;
+pub mod manifest;
+pub mod metadata;
+pub mod metrics;
+pub mod plan;
+pub mod plot;
+pub mod raw;
+pub mod shares;
+pub mod statistics;
+pub mod timing;
+pub mod transcript;
+pub mod transport;
 pub mod tty;
+pub mod watch;
+pub mod watchdog;
+
+use benchmark_common::{
+    corpus, Aggregate, BenchmarkConfig, BenchmarkInfo, DatasetDescriptor, HashBenchmarkType,
+    IncomingMessage, OutgoingMessage, PlatformDescriptor, ResultEnvelope, SkipReason, SuiteConfig,
+    SuiteError, SuiteStatus, SCHEMA_VERSION,
+};
+use cache::ResultCache;
+use calibration::Adjusted;
+use checkpoint::Checkpoint;
+use console::Console;
+#[cfg(feature = "database")]
+use database::DatabaseOptions;
+use latex::LatexOptions;
+use metadata::Metadata;
+use metrics::Pushgateway;
+use plan::{parse_plan, Plan};
+use raw::{check_datasets, check_raw_file, parse_raw_file, LineError, RawEntry, ResponseKind};
+use serde::{Deserialize, Serialize};
+use shares::ResultShares;
+use statistics::ResultStatistics;
+use std::{
+    collections::VecDeque,
+    fmt::Display,
+    fs,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{Arc, Condvar, Mutex},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use timing::{Clock, ResultTime};
+use tty::{ConnectionOptions, SerialConnection, SuiteConnection};
+use watchdog::Watchdog;
+
+/// Exit codes of the CLI, used to signal the class of a failure to automation
+///
+/// If multiple failures occur, the first one determines the exit code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExitCode {
+    Success = 0,
+    /// The connection to the suite could not be established or was lost
+    ConnectionFailure = 2,
+    /// The suite sent an invalid or unexpected message
+    ProtocolError = 3,
+    /// The suite panicked
+    SuitePanic = 4,
+    /// The suite reported that the output of a benchmark did not match the expected output
+    VerificationFailure = 5,
+    /// A benchmark exceeded its allowed number of cycles
+    RegressionThresholdExceeded = 6,
+    /// The suite could not be flashed or did not start afterwards
+    FlashFailure = 7,
+    /// A file could not be read, contains invalid lines or is not a valid plan
+    InvalidInput = 8,
+    /// The results of a file could not be written, stored in the database or pushed
+    OutputFailure = 9,
+    /// A warning occurred in strict mode, check `RunOptions::strict`
+    Warning = 10,
+    /// The suite did not respond to a message within its deadline, check `RunOptions::watchdog`
+    Hang = 11,
+}
+
+impl ExitCode {
+    /// Returns this exit code if it already signals a failure, otherwise the provided exit code
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - the exit code of a later operation
+    pub fn or(self, other: ExitCode) -> ExitCode {
+        if self == ExitCode::Success {
+            other
+        } else {
+            self
+        }
+    }
+
+    /// Returns the exit code signaled by a message of the suite,
+    /// or Success if the message does not indicate a failure
+    ///
+    /// # Arguments
+    ///
+    /// * `msg` - the message received from the suite
+    pub fn of_message(msg: &IncomingMessage) -> ExitCode {
+        match msg {
+            IncomingMessage::Error(SuiteError::VerificationFailed) => ExitCode::VerificationFailure,
+            IncomingMessage::BenchmarkResults(envelope)
+            | IncomingMessage::Window {
+                results: envelope, ..
+            } if envelope.verification_failure.is_some() => ExitCode::VerificationFailure,
+            // The panic handler of the suite prefixes its output with an exclamation mark
+            IncomingMessage::Invalid(line) if line.starts_with("! ") => ExitCode::SuitePanic,
+            IncomingMessage::Invalid(_) => ExitCode::ProtocolError,
+            _ => ExitCode::Success,
+        }
+    }
+}
+
+/// Failure that prevented a file from being benchmarked or its results from being written
+#[derive(Debug)]
+pub enum FileError {
+    /// The file could not be read
+    Read(io::Error),
+    /// The raw file contains invalid lines, or benchmarks of the plan request unknown datasets,
+    /// every one of them is described
+    InvalidLines(Vec<LineError>),
+    /// The file is not a valid plan
+    InvalidPlan(String),
+    /// The benchmarks could not be performed, the failure was already reported
+    Session(ExitCode),
+    /// The results could not be written
+    Write(io::Error),
+    /// The results could not be stored in or read from the database
+    Database(String),
+    /// The metrics of the results could not be pushed
+    Push(String),
+}
+
+impl FileError {
+    /// Returns the exit code signaling this failure
+    pub fn exit_code(&self) -> ExitCode {
+        match self {
+            FileError::Read(_) | FileError::InvalidLines(_) | FileError::InvalidPlan(_) => {
+                ExitCode::InvalidInput
+            }
+            FileError::Session(code) => *code,
+            FileError::Write(_) | FileError::Database(_) | FileError::Push(_) => {
+                ExitCode::OutputFailure
+            }
+        }
+    }
+}
+
+impl Display for FileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileError::Read(err) => write!(f, "Failed to read file: {err}"),
+            FileError::InvalidLines(errors) => {
+                let plural = if errors.len() == 1 { "" } else { "s" };
+                write!(f, "File contains {} invalid line{plural}", errors.len())?;
+                for error in errors {
+                    write!(f, "\n  {error}")?;
+                }
+                Ok(())
+            }
+            FileError::InvalidPlan(err) => write!(f, "File contains an invalid plan: {err}"),
+            FileError::Session(_) => write!(f, "Benchmarks could not be performed"),
+            FileError::Write(err) => write!(f, "Failed to write results: {err}"),
+            FileError::Database(err) => write!(f, "Failed to access result database: {err}"),
+            FileError::Push(err) => write!(f, "Failed to push metrics: {err}"),
+        }
+    }
+}
+
+/// Number of attempts to connect to the suite, before the connection is considered failed
+const CONNECT_ATTEMPTS: u32 = 3;
+
+/// Delay before the second attempt to connect to the suite, doubled for every further attempt
+const CONNECT_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Establishes a connection to the suite
+///
+/// Failures are retried with an increasing delay, up to `CONNECT_ATTEMPTS` attempts in total,
+/// as the serial port may be busy for a moment or the suite may still be starting.
+/// Returns a description of the failure if no attempt succeeded.
+///
+/// # Arguments
+///
+/// * `connection` - describes how to connect to the suite
+pub fn connect(connection: &ConnectionOptions) -> Result<SuiteConnection, String> {
+    let mut backoff = CONNECT_BACKOFF;
+    for _ in 1..CONNECT_ATTEMPTS {
+        match try_connect(connection) {
+            Ok(suite) => return Ok(suite),
+            Err(err) => {
+                eprintln!("{err}, retrying in {}s", backoff.as_secs());
+                thread::sleep(backoff);
+                backoff *= 2;
+            }
+        }
+    }
+
+    try_connect(connection)
+}
+
+/// Performs a single attempt to establish a connection to the suite
+///
+/// # Arguments
+///
+/// * `connection` - describes how to connect to the suite
+fn try_connect(connection: &ConnectionOptions) -> Result<SuiteConnection, String> {
+    let mut serial = SerialConnection::new(&connection.endpoint)
+        .map_err(|err| format!("Failed to connect to {}: {err}", connection.endpoint))?;
+    serial.set_transcript(connection.transcript.clone(), &connection.endpoint);
+
+    SuiteConnection::new(serial, connection.handshake, connection.format)
+        .map_err(|err| format!("Failed to establish valid connection with suite: {err}"))
+}
+
+/// Selects benchmarks based on their tags and names
+///
+/// Patterns are compared to the tags of a message, the name of the requested benchmark as written
+/// in files (ex. `AESDataSet`), its label and, for plans, the name of its section. Patterns may
+/// contain `*`, matching any sequence of characters, and `?`, matching a single character,
+/// e.g. `aes-*`. Case is ignored.
+#[derive(Default, Clone)]
+pub struct BenchmarkFilter {
+    /// If not empty, only messages matching at least one of these patterns are selected
+    pub only: Vec<String>,
+    /// Messages matching any of these patterns are not selected
+    pub skip: Vec<String>,
+}
+
+impl BenchmarkFilter {
+    /// Returns true if a message with the provided tags and names is selected by this filter
+    ///
+    /// # Arguments
+    ///
+    /// * `names` - the tags and names of the message
+    pub fn matches(&self, names: &[&str]) -> bool {
+        let matching = |pattern: &String| names.iter().any(|name| glob_matches(pattern, name));
+
+        (self.only.is_empty() || self.only.iter().any(matching)) && !self.skip.iter().any(matching)
+    }
+
+    /// Returns true if the entry is selected by this filter, check `matches`
+    ///
+    /// # Arguments
+    ///
+    /// * `entry` - the entry of a raw file or a plan
+    /// * `section` - the name of the section of the plan the entry belongs to, if any
+    pub fn selects(&self, entry: &RawEntry, section: Option<&str>) -> bool {
+        let benchmark = match &entry.msg {
+            OutgoingMessage::Benchmark(info, config) => Some((benchmark_name(info), config)),
+            _ => None,
+        };
+
+        let mut names: Vec<&str> = entry.tags.iter().map(String::as_str).collect();
+        if let Some((name, config)) = &benchmark {
+            names.push(name);
+            names.extend(config.label.as_deref());
+        }
+        names.extend(section);
+
+        self.matches(&names)
+    }
+}
+
+/// Returns the name of a benchmark as written in files, e.g. `AESDataSet` for `{"AESDataSet":1}`
+///
+/// # Arguments
+///
+/// * `info` - the benchmark
+fn benchmark_name(info: &BenchmarkInfo) -> String {
+    match serde_json::to_value(info) {
+        Ok(serde_json::Value::String(name)) => name,
+        Ok(serde_json::Value::Object(variant)) => {
+            variant.keys().next().cloned().unwrap_or_default()
+        }
+        _ => String::new(),
+    }
+}
+
+/// Returns true if the name matches the pattern ignoring case, `*` matches any sequence of
+/// characters and `?` matches a single character
+///
+/// # Arguments
+///
+/// * `pattern` - the pattern, e.g. `aes-*`
+/// * `name` - the name that is matched
+fn glob_matches(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let name: Vec<char> = name.to_lowercase().chars().collect();
+
+    let (mut p, mut n) = (0, 0);
+    // Position of the last star and of the character of the name it matched up to,
+    // a mismatch later on lets the star match one more character instead
+    let mut star = None;
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, n));
+            p += 1;
+        } else if let Some((star_p, star_n)) = star {
+            star = Some((star_p, star_n + 1));
+            p = star_p + 1;
+            n = star_n + 1;
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Name of the file standing for stdin, check `benchmark_stdin`
+pub const STDIN_FILE: &str = "-";
+
+/// Title of the dashboard of a plan that was not read from a file, check `perform_plan`
+const PLAN_TITLE: &str = "plan";
+
+/// Options controlling how benchmark files are processed
+#[derive(Default, Clone)]
+pub struct RunOptions {
+    /// Selects the benchmarks that should be performed, based on their tags and names
+    pub filter: BenchmarkFilter,
+    /// Directory used to cache benchmark results, caching is disabled if None
+    pub cache: Option<PathBuf>,
+    /// Directory badges of the results stored in the database are written to, if any,
+    /// requires a database
+    pub badges: Option<PathBuf>,
+    /// Whether the suite is reset before the messages of a file are sent
+    pub reset: bool,
+    /// Format of the result files
+    pub format: OutputFormat,
+    /// Whether the overhead of measurements is calibrated before the messages of a file are sent,
+    /// so results adjusted for it are written in addition to the raw results
+    pub calibrate: bool,
+    /// Sends every benchmark repeatedly and aggregates the results, instead of sending it once
+    pub repeat: Option<Repeat>,
+    /// Whether the results are additionally plotted as SVG files into a .plots directory,
+    /// check the plot module for the plots
+    pub plot: bool,
+    /// Describes the LaTeX table of the results written to a .tex file, no table is written if None
+    pub latex: Option<LatexOptions>,
+    /// Frequency of the clock of the suite in Hz, overriding the frequency reported by the suite,
+    /// the cycles of the results are converted into time with it, check the timing module
+    pub clock_frequency: Option<u64>,
+    /// Database the results of every file are additionally stored in, if any,
+    /// requires the `database` feature
+    #[cfg(feature = "database")]
+    pub database: Option<DatabaseOptions>,
+    /// Whether the cycles of every result are additionally written as OpenMetrics to a .prom file,
+    /// check the metrics module
+    pub openmetrics: bool,
+    /// Pushgateway the cycles of every result are pushed to as metrics, if any
+    pub pushgateway: Option<Pushgateway>,
+    /// Seed of the pseudo random choices of the suite, the fixed values of the suite are used if None
+    pub seed: Option<u64>,
+    /// Whether the suite resets its modules and initializes its heap again before every benchmark,
+    /// check `SuiteConfig::isolation`
+    pub isolation: bool,
+    /// Whether a live dashboard is shown while the messages of a file are sent,
+    /// check the dashboard module, which requires the `dashboard` feature
+    pub dashboard: bool,
+    /// The arguments the CLI was invoked with, recorded in the metadata of the result files
+    pub arguments: Vec<String>,
+    /// Whether files are continued from their checkpoints, check the checkpoint module
+    pub resume: bool,
+    /// Whether warnings fail the run: skipped benchmarks, retried repetitions, windows of
+    /// duty-cycled benchmarks that started late, responses of a newer schema and log messages of
+    /// the suite with level Warn or Error
+    pub strict: bool,
+    /// Detects messages the suite does not respond to and recovers from them, instead of stopping
+    /// the run, check the watchdog module. Disabled if None
+    pub watchdog: Option<Watchdog>,
+    /// Whether every response is written to stdout as a line of JSON as soon as it is received,
+    /// as done when the messages are read from stdin, check `benchmark_stdin`. Lines otherwise
+    /// printed to stdout are printed to stderr instead and no checkpoint is recorded
+    pub stream: bool,
+}
+
+impl RunOptions {
+    /// Returns the exit code of a warning, which only signals a failure in strict mode
+    fn warning(&self) -> ExitCode {
+        if self.strict {
+            ExitCode::Warning
+        } else {
+            ExitCode::Success
+        }
+    }
+}
 
-use benchmark_common::{parse_raw, IncomingMessage, OutgoingMessage, SuiteStatus};
-use std::{ffi::OsString, fs, path::PathBuf};
-use tty::{SerialConnection, SuiteConnection};
+/// How often every benchmark is sent to the suite, when benchmarks are repeated
+#[derive(Debug, Clone, Copy)]
+pub struct Repeat {
+    /// Number of times the benchmark is sent, whose results are aggregated
+    pub count: u32,
+    /// Number of times the benchmark is sent beforehand, whose results are discarded
+    pub warmup: u32,
+}
+
+/// Role of a message sent to the suite, when benchmarks are repeated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Repetition {
+    /// The message is sent once
+    Single,
+    /// The benchmark is sent to warm up, its results are discarded
+    Warmup,
+    /// The benchmark is sent repeatedly, its results are aggregated
+    Measured,
+}
+
+impl Repetition {
+    /// Returns the copies of an entry that are sent to the suite, with their roles, in order
+    ///
+    /// # Arguments
+    ///
+    /// * `entry` - the entry that should be sent
+    /// * `repeat` - how often benchmarks are sent, if they are repeated
+    fn copies_of(entry: &RawEntry, repeat: Option<Repeat>) -> Vec<(&RawEntry, Repetition)> {
+        match (&entry.msg, repeat) {
+            (OutgoingMessage::Benchmark(..), Some(repeat)) => {
+                let mut copies = vec![(entry, Repetition::Warmup); repeat.warmup as usize];
+                copies.extend(vec![(entry, Repetition::Measured); repeat.count as usize]);
+                copies
+            }
+            _ => vec![(entry, Repetition::Single)],
+        }
+    }
+}
+
+/// Format of the result files written by the CLI
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human readable dumps of the responses, together with the anomalies and outcomes of checks,
+    /// written to a .result file
+    Text,
+    /// The responses serialized as JSON, written to a .result.json file
+    Json,
+    /// The .result file of the text format, additionally the results of every benchmark as CSV file
+    /// in a .csv directory, check the csv module for the layout of the files
+    Csv,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for OutputFormat {
+    fn default() -> Self {
+        OutputFormat::Text
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "csv" => Ok(OutputFormat::Csv),
+            _ => Err(format!(
+                "Unknown output format '{s}', use text, json or csv"
+            )),
+        }
+    }
+}
 
 /// Benchmark the suite using the file provided, interpreted in raw mode.
 ///
@@ -10,73 +513,2031 @@ use tty::{SerialConnection, SuiteConnection};
 /// message that the CLI sends directly to the Suite.
 /// Implicitly sends OutgoingMessage::Done at the end.
 ///
-/// For information about supported messages an their (de)serialization check the common crate.
+/// Responses are checked against the annotations of the line they belong to,
+/// unexpected responses are reported and marked in the output file.
+///
+/// Before any message of the file is sent, the round-trip latency is measured using a Ping,
+/// it is added to all timeouts. If requested, the suite is reset afterwards.
+/// If a seed is provided, the suite is configured with it before any message of the file is sent,
+/// the seed is also added to every config of the file that does not set one, and recorded at the
+/// top of the output file. Isolation is configured and recorded the same way, every config of the
+/// file enables it if requested.
+/// If calibration is requested, the overhead of measurements is calibrated, check the calibration
+/// module, and the results of every benchmark are additionally written adjusted for it.
+/// Benchmarks without an annotated timeout use a timeout derived from the cycles the suite expects
+/// them to take, if the suite reports its clock frequency.
+/// If caching is enabled, benchmarks whose results are cached for the build of the suite are not
+/// sent, instead the cached results are reused and marked in the output file.
+/// The windows of duty-cycled benchmarks are printed as they arrive and written like responses.
+/// If benchmarks are repeated, every benchmark is sent repeatedly after its warm-up copies, and the
+/// results of the repetitions are merged into a single response, check `merge_repetitions`.
+/// If requested, a live dashboard is shown while the messages are sent, check the dashboard module.
+/// If a database is provided, the results are stored in it as a run of the file.
+/// If a badge directory is also provided, a badge is then written for every benchmark stored in it.
+/// If requested, the cycles of the results are exported as metrics, check the metrics module.
+/// The result files start with the metadata of the host, check the metadata module.
+/// The progress is recorded in a checkpoint after every response, if resuming is requested the
+/// messages answered before are not sent again, check the checkpoint module.
+/// Responses that do not belong to any message, a suite that finished before answering every
+/// message and a lost connection are failures, warnings only fail the run in strict mode.
+///
+/// For information about supported messages an their (de)serialization check the common crate,
+/// for information about supported annotations check the raw module.
+///
+/// Returns the exit code corresponding to the first failure that occurred while benchmarking,
+/// or the failure that prevented the file from being benchmarked or its results from being written.
+/// A file containing invalid lines is not benchmarked at all, every invalid line is reported.
 ///
 /// # Arguments
 ///
-/// * `tty` - path to the tty used to communicate with the suite
+/// * `connection` - describes how to connect to the suite
 /// * `input_file` - path to the file containing the messages that should be sent
-pub fn benchmark_raw_file(tty: &OsString, input_file: PathBuf) {
-    let mut suite =
-        SuiteConnection::new(SerialConnection::new(tty).expect("Failed to connect to serial"))
-            .expect("Failed to establish valid connection with suite");
+/// * `options` - controls which messages are sent and how results are cached and exported
+pub fn benchmark_raw_file(
+    connection: &ConnectionOptions,
+    input_file: PathBuf,
+    options: &RunOptions,
+) -> Result<ExitCode, FileError> {
+    let input_msg = fs::read_to_string(&input_file).map_err(FileError::Read)?;
+    let mut entries = parse_raw_file(&input_msg).map_err(FileError::InvalidLines)?;
+    entries.retain(|entry| options.filter.selects(entry, None));
+    let metadata = Metadata::collect(&input_file, &input_msg, &options.arguments);
 
-    let input_msg = fs::read_to_string(&input_file).expect("Failed to read input file");
-    for (line_num, line) in input_msg.lines().enumerate() {
-        let line = line.trim();
+    let mut session = perform_entries(connection, Some(&input_file), &entries, options)
+        .map_err(FileError::Session)?;
+    session.output.insert_str(0, &metadata.header());
 
-        if line.starts_with('#') || line.is_empty() {
-            continue;
+    if options.plot {
+        plot::write_plots(&input_file.with_extension("plots"), &session.responses)
+            .map_err(FileError::Write)?;
+    }
+    write_latex(options, &input_file, &session)?;
+    #[cfg(feature = "database")]
+    {
+        store_in_database(options, &input_file, &entries, &session)?;
+        write_badges(options)?;
+    }
+    export_metrics(options, &input_file, &entries, &session)?;
+    match options.format {
+        OutputFormat::Text => fs::write(input_file.with_extension("result"), &session.output),
+        OutputFormat::Csv => csv::write_csv(&input_file.with_extension("csv"), &session.responses)
+            .and_then(|()| fs::write(input_file.with_extension("result"), &session.output)),
+        OutputFormat::Json => {
+            let responses: Vec<RawResponse> = session
+                .responses
+                .iter()
+                .map(|(line, response)| RawResponse {
+                    line: *line,
+                    board: session.board.as_deref(),
+                    response,
+                    statistics: statistics_of(response),
+                    shares: shares_of(response),
+                    adjusted: adjusted_of(response, session.overhead),
+                    times: times_of(response, &session.clock),
+                })
+                .collect();
+            let output = RawOutput {
+                metadata: &metadata,
+                responses,
+            };
+            let json = serde_json::to_string_pretty(&output).expect("Can not serialize struct");
+            fs::write(input_file.with_extension("result.json"), json)
         }
+    }
+    .map_err(FileError::Write)?;
 
-        let msg = parse_raw(line);
+    Ok(session.exit_code)
+}
 
-        if matches!(
-            msg,
-            OutgoingMessage::Invalid(_) | OutgoingMessage::Done | OutgoingMessage::GetStatus
-        ) {
-            panic!(
-                "Input file contains invalid messages on line {}",
-                line_num + 1
-            )
+/// Benchmarks the suite using the messages read from stdin, as if they were read from a raw file,
+/// so the CLI can be scripted from pipelines and other processes without temporary files.
+///
+/// Stdin is read until it is closed, then the messages are sent. Every response is written to
+/// stdout as a line of JSON as soon as it is received, in the format of the responses of
+/// .result.json files. No result files are written, other output is printed to stderr.
+///
+/// Returns the exit code corresponding to the first failure that occurred.
+///
+/// # Arguments
+///
+/// * `connection` - describes how to connect to the suite
+/// * `options` - controls which messages are sent, responses are streamed regardless of
+///   `RunOptions::stream`
+pub fn benchmark_stdin(connection: &ConnectionOptions, options: &RunOptions) -> ExitCode {
+    let mut input_msg = String::new();
+    if let Err(err) = io::stdin().read_to_string(&mut input_msg) {
+        eprintln!("{STDIN_FILE}: {}", FileError::Read(err));
+        return ExitCode::InvalidInput;
+    }
+    let mut entries = match parse_raw_file(&input_msg) {
+        Ok(entries) => entries,
+        Err(errors) => {
+            eprintln!("{STDIN_FILE}: {}", FileError::InvalidLines(errors));
+            return ExitCode::InvalidInput;
         }
+    };
+    entries.retain(|entry| options.filter.selects(entry, None));
 
-        suite.send_message(&msg);
+    let options = RunOptions {
+        stream: true,
+        ..options.clone()
+    };
+    match perform_entries(connection, Some(Path::new(STDIN_FILE)), &entries, &options) {
+        Ok(session) => session.exit_code,
+        Err(code) => code,
     }
-    suite.send_message(&OutgoingMessage::Done);
+}
 
-    let mut output_msg = String::new();
-    loop {
-        match suite.read_message() {
-            Ok(msg) => {
-                output_msg.push_str(&format!("{msg:#?}\n"));
+/// Checks a file without connecting to the suite, e.g. before a long session.
+///
+/// The lines of a raw file are parsed, a plan is translated into its benchmarks, and the datasets
+/// requested by benchmarks are validated against the datasets the suite provides, check
+/// `DatasetKind::count`. Returns the number of messages of the file, or the failure describing
+/// every invalid line.
+///
+/// # Arguments
+///
+/// * `input_file` - path to the file that should be checked
+/// * `raw` - whether the file is a raw file, otherwise it is a plan
+pub fn check_file(input_file: &Path, raw: bool) -> Result<usize, FileError> {
+    let content = fs::read_to_string(input_file).map_err(FileError::Read)?;
 
-                if matches!(msg, IncomingMessage::Status(SuiteStatus::Done)) {
-                    break;
+    let entries = if raw {
+        check_raw_file(&content).map_err(FileError::InvalidLines)?
+    } else {
+        let plan = parse_plan(&content).map_err(FileError::InvalidPlan)?;
+        let entries: Vec<RawEntry> = plan
+            .entries()
+            .map_err(FileError::InvalidPlan)?
+            .into_iter()
+            .map(|(_, entry)| entry)
+            .collect();
+        let errors = check_datasets(&entries);
+        if !errors.is_empty() {
+            return Err(FileError::InvalidLines(errors));
+        }
+        entries
+    };
+
+    Ok(entries.len())
+}
+
+/// Benchmarks the suite using the files provided one after the other, as raw files or plans,
+/// check `benchmark_raw_file` and `benchmark_file`. A file that can not be processed is reported,
+/// the remaining files are processed nevertheless.
+///
+/// Returns the exit code corresponding to the first failure that occurred.
+///
+/// # Arguments
+///
+/// * `connection` - describes how to connect to the suite
+/// * `files` - the files that should be processed
+/// * `raw` - whether the files are raw files, otherwise they are plans
+/// * `options` - controls which benchmarks are performed and how results are cached and exported
+pub fn benchmark_files(
+    connection: &ConnectionOptions,
+    files: impl Iterator<Item = PathBuf>,
+    raw: bool,
+    options: &RunOptions,
+) -> ExitCode {
+    let mut exit_code = ExitCode::Success;
+
+    for file in files {
+        let result = process_file(connection, &file, raw, options);
+        exit_code = exit_code.or(report_file(&file, result));
+    }
+
+    exit_code
+}
+
+/// Benchmarks the suite using a single file, as raw file or plan,
+/// check `benchmark_raw_file` and `benchmark_file`
+///
+/// # Arguments
+///
+/// * `connection` - describes how to connect to the suite
+/// * `file` - the file that should be processed
+/// * `raw` - whether the file is a raw file, otherwise it is a plan
+/// * `options` - controls which benchmarks are performed and how results are cached and exported
+fn process_file(
+    connection: &ConnectionOptions,
+    file: &Path,
+    raw: bool,
+    options: &RunOptions,
+) -> Result<ExitCode, FileError> {
+    if let Some(board) = &connection.board {
+        println!("{board}: processing {}", file.display());
+    }
+    if raw {
+        benchmark_raw_file(connection, file.to_path_buf(), options)
+    } else {
+        benchmark_file(connection, file.to_path_buf(), options)
+    }
+}
+
+/// Reports the failure that prevented a file from being processed, if any,
+/// and returns the exit code of the outcome
+///
+/// # Arguments
+///
+/// * `file` - the processed file
+/// * `result` - the outcome of processing the file
+fn report_file(file: &Path, result: Result<ExitCode, FileError>) -> ExitCode {
+    match result {
+        Ok(code) => code,
+        Err(err) => {
+            eprintln!("{}: {err}", file.display());
+            err.exit_code()
+        }
+    }
+}
+
+/// Benchmarks multiple boards in parallel, each running the suite, using the files provided.
+///
+/// Every board is served by its own thread, which takes the next file that was not processed yet
+/// whenever it finished its last one, until all files were processed. The results of every file
+/// are written like with a single board, marked with the board they were produced on, check
+/// `ConnectionOptions::board`. A board that can not be connected to returns its file to the queue
+/// and processes no further files, so they are left to the other boards. Files that remain
+/// unprocessed because no board could be connected to are reported.
+///
+/// Returns the exit code corresponding to the first failure of the first board that failed.
+///
+/// # Arguments
+///
+/// * `boards` - describes how to connect to the suite on every board
+/// * `files` - the files that should be processed
+/// * `raw` - whether the files are raw files, otherwise they are plans
+/// * `options` - controls which benchmarks are performed and how results are cached and exported,
+///   the dashboard can not be shown for multiple boards
+pub fn benchmark_boards(
+    boards: Vec<ConnectionOptions>,
+    files: Vec<PathBuf>,
+    raw: bool,
+    options: RunOptions,
+) -> ExitCode {
+    let options = Arc::new(options);
+    let queue = Arc::new(FileQueue::new(files));
+
+    let threads: Vec<_> = boards
+        .into_iter()
+        .map(|connection| {
+            let options = options.clone();
+            let queue = queue.clone();
+            thread::spawn(move || {
+                let mut exit_code = ExitCode::Success;
+
+                while let Some(file) = queue.take() {
+                    match process_file(&connection, &file, raw, &options) {
+                        // Connecting failed after all attempts, so the other boards take over
+                        Err(FileError::Session(ExitCode::ConnectionFailure)) => {
+                            eprintln!(
+                                "{}: failed to connect, processing no further files",
+                                connection.board.as_deref().unwrap_or_default()
+                            );
+                            queue.finish(Some(file));
+                            return exit_code.or(ExitCode::ConnectionFailure);
+                        }
+                        result => {
+                            exit_code = exit_code.or(report_file(&file, result));
+                            queue.finish(None);
+                        }
+                    }
                 }
+
+                exit_code
+            })
+        })
+        .collect();
+
+    let exit_code = threads
+        .into_iter()
+        .fold(ExitCode::Success, |exit_code, thread| {
+            let code = thread
+                .join()
+                .unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+            exit_code.or(code)
+        });
+
+    for file in queue.remaining() {
+        eprintln!(
+            "{}: not processed, no board could be connected to",
+            file.display()
+        );
+    }
+
+    exit_code
+}
+
+/// Files shared by the threads of boards benchmarked in parallel
+struct FileQueue {
+    /// The files no board took yet and the number of files boards are processing
+    state: Mutex<(VecDeque<PathBuf>, usize)>,
+    /// Notified whenever a board finished processing a file
+    finished: Condvar,
+}
+
+impl FileQueue {
+    /// Creates a new queue of the provided files
+    fn new(files: Vec<PathBuf>) -> FileQueue {
+        FileQueue {
+            state: Mutex::new((VecDeque::from(files), 0)),
+            finished: Condvar::new(),
+        }
+    }
+
+    /// Takes the next file, waiting while the queue is empty but other boards are processing
+    /// files, which they may return. Returns None once all files were processed.
+    fn take(&self) -> Option<PathBuf> {
+        let mut state = self.state.lock().expect("File queue poisoned");
+
+        loop {
+            if let Some(file) = state.0.pop_front() {
+                state.1 += 1;
+                return Some(file);
             }
-            Err(_) => {
-                println!("Connection closed.");
-                break;
+            if state.1 == 0 {
+                return None;
             }
+            state = self.finished.wait(state).expect("File queue poisoned");
+        }
+    }
+
+    /// Marks a file taken before as processed
+    ///
+    /// # Arguments
+    ///
+    /// * `unprocessed` - the file if it was not processed, it is returned to the queue
+    fn finish(&self, unprocessed: Option<PathBuf>) {
+        let mut state = self.state.lock().expect("File queue poisoned");
+
+        state.1 -= 1;
+        if let Some(file) = unprocessed {
+            state.0.push_front(file);
         }
+        self.finished.notify_all();
     }
 
-    fs::write(input_file.with_extension("result"), output_msg)
-        .expect("Failed to write output file");
+    /// Returns the files no board took
+    fn remaining(&self) -> Vec<PathBuf> {
+        let state = self.state.lock().expect("File queue poisoned");
+        state.0.iter().cloned().collect()
+    }
 }
 
-/// Benchmark the suite using the file provided.
+/// Returns the JSON Schema of .result.json files, as printed by `cli schema`
 ///
-/// The CLI will read the description of the benchmark from the file and
-/// determine the messages that should be sent to the suite in order to
-/// perform said benchmark.
+/// # Arguments
+///
+/// * `plan` - whether the schema describes the results of plans, otherwise of raw files
+#[cfg(feature = "schema")]
+pub fn result_schema(plan: bool) -> String {
+    let schema = if plan {
+        schemars::schema_for!(PlanOutput<'static>)
+    } else {
+        schemars::schema_for!(RawOutput<'static>)
+    };
+
+    serde_json::to_string_pretty(&schema).expect("Can not serialize struct")
+}
+
+/// Content of the JSON result file of a raw file
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+struct RawOutput<'a> {
+    /// Describes where, when and how the results were produced
+    metadata: &'a Metadata,
+    /// The responses, in the order the messages were sent
+    responses: Vec<RawResponse<'a>>,
+}
+
+/// Response to a message of a raw file, as written to JSON result files
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+struct RawResponse<'a> {
+    /// Line of the message the suite responded to
+    line: usize,
+    /// The board the message was sent to, if benchmarking on multiple boards
+    #[serde(skip_serializing_if = "Option::is_none")]
+    board: Option<&'a str>,
+    /// The response of the suite
+    response: &'a IncomingMessage,
+    /// Statistics of the results contained in the response, if any
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    statistics: Vec<ResultStatistics>,
+    /// Shares of the phases of the results contained in the response, if any
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    shares: Vec<ResultShares>,
+    /// The results contained in the response adjusted for the overhead of measurements, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    adjusted: Option<Adjusted>,
+    /// Time and throughput of the phases of the results contained in the response, if the clock
+    /// frequency is known
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    times: Vec<ResultTime>,
+}
+
+/// Outcome of sending the entries of a file to the suite
+struct Session {
+    /// Exit code corresponding to the first failure that occurred
+    exit_code: ExitCode,
+    /// Human readable record of all responses and anomalies
+    output: String,
+    /// The responses matched to the entries, with the line number of the entry, in order.
+    /// Windows of duty-cycled benchmarks are contained before the response of their entry
+    responses: Vec<(usize, IncomingMessage)>,
+    /// The calibrated overhead of every phase in cycles, if calibration was requested
+    overhead: Option<u64>,
+    /// Converts the cycles of the results into time
+    clock: Clock,
+    /// The platform the suite reported, None if it did not describe its platform
+    platform: Option<PlatformDescriptor>,
+    /// The board the entries were sent to, if benchmarking on multiple boards
+    board: Option<String>,
+}
+
+/// Writes the results of a session as LaTeX table to a .tex file, if requested
+///
+/// # Arguments
+///
+/// * `options` - describes the table
+/// * `input_file` - the file the entries were read from
+/// * `session` - the outcome of sending the entries
+fn write_latex(
+    options: &RunOptions,
+    input_file: &Path,
+    session: &Session,
+) -> Result<(), FileError> {
+    if let Some(latex) = &options.latex {
+        latex::write_latex(
+            &input_file.with_extension("tex"),
+            latex,
+            options.clock_frequency.or_else(|| {
+                session
+                    .platform
+                    .as_ref()
+                    .and_then(|platform| platform.clock_frequency)
+            }),
+            &session.responses,
+        )
+        .map_err(FileError::Write)?;
+    }
+
+    Ok(())
+}
+
+/// Stores the results of a session in the database, if one is provided
+///
+/// # Arguments
+///
+/// * `options` - contains the database the results are stored in
+/// * `input_file` - the file the entries were read from
+/// * `entries` - the entries that were sent
+/// * `session` - the outcome of sending the entries
+#[cfg(feature = "database")]
+fn store_in_database(
+    options: &RunOptions,
+    input_file: &Path,
+    entries: &[RawEntry],
+    session: &Session,
+) -> Result<(), FileError> {
+    match &options.database {
+        Some(database) => database::store_run(
+            database,
+            input_file,
+            session.board.as_deref(),
+            session.platform.as_ref(),
+            entries,
+            &session.responses,
+        )
+        .map_err(FileError::Database),
+        None => Ok(()),
+    }
+}
+
+/// Writes a badge for every benchmark stored in the database, if a badge directory is provided
+///
+/// # Arguments
+///
+/// * `options` - contains the database and the directory the badges are written to
+#[cfg(feature = "database")]
+fn write_badges(options: &RunOptions) -> Result<(), FileError> {
+    match (&options.badges, &options.database) {
+        (Some(dir), Some(database)) => {
+            let stored = database::stored_results(&database.path).map_err(FileError::Database)?;
+            badge::write_badges(dir, &stored).map_err(FileError::Write)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Exports the cycles of the results of a session as metrics, if requested: writes them to a .prom
+/// file and pushes them to the Pushgateway
+///
+/// # Arguments
+///
+/// * `options` - describes where the metrics are exported to
+/// * `input_file` - the file the entries were read from
+/// * `entries` - the entries that were sent
+/// * `session` - the outcome of sending the entries
+fn export_metrics(
+    options: &RunOptions,
+    input_file: &Path,
+    entries: &[RawEntry],
+    session: &Session,
+) -> Result<(), FileError> {
+    if !options.openmetrics && options.pushgateway.is_none() {
+        return Ok(());
+    }
+
+    let metrics = metrics::openmetrics(
+        input_file,
+        session.board.as_deref(),
+        entries,
+        &session.responses,
+    );
+    if options.openmetrics {
+        fs::write(input_file.with_extension("prom"), &metrics).map_err(FileError::Write)?;
+    }
+    match &options.pushgateway {
+        Some(pushgateway) => pushgateway
+            .push(input_file, &metrics)
+            .map_err(FileError::Push),
+        None => Ok(()),
+    }
+}
+
+/// Sends entries to the suite and checks the responses against their annotations,
+/// as described for `benchmark_raw_file`.
+///
+/// Returns the exit code of the failure if the session could not be started.
 ///
 /// # Arguments
 ///
-/// * `_tty` - path to the tty used to communicate with the suite
-/// * `_input_file` - path to the file containing a description of the benchmark that should be performed
-pub fn benchmark_file(_tty: &OsString, _input_file: PathBuf) {
-    // TODO: implement normal benchmarking function, including better output
-    todo!()
+/// * `connection` - describes how to connect to the suite
+/// * `input_file` - the file the entries were read from, shown by the dashboard, None if they
+///   were not read from a file, then no checkpoint is recorded
+/// * `entries` - the entries that should be sent
+/// * `options` - controls how results are cached and exported
+fn perform_entries(
+    connection: &ConnectionOptions,
+    input_file: Option<&Path>,
+    entries: &[RawEntry],
+    options: &RunOptions,
+) -> Result<Session, ExitCode> {
+    let mut suite = match connect(connection) {
+        Ok(suite) => suite,
+        Err(err) => {
+            eprintln!("{err}");
+            return Err(ExitCode::ConnectionFailure);
+        }
+    };
+
+    // Detect a wedged suite early, instead of waiting for the first response to time out
+    match suite.ping() {
+        Ok(latency) => eprintln!("Round-trip latency: {latency:?}"),
+        Err(err) => {
+            eprintln!("Suite did not answer ping: {err}");
+            return Err(ExitCode::ConnectionFailure);
+        }
+    };
+
+    if options.reset {
+        reset_suite(&mut suite)?;
+    }
+
+    let mut output_msg = String::new();
+    if let Some(board) = &connection.board {
+        output_msg.push_str(&format!("-- board {board} --\n"));
+    }
+    if options.seed.is_some() || options.isolation {
+        configure_suite(
+            &mut suite,
+            SuiteConfig {
+                seed: options.seed,
+                isolation: options.isolation,
+                ..SuiteConfig::DEFAULT
+            },
+        )?;
+    }
+    if let Some(seed) = options.seed {
+        output_msg.push_str(&format!("-- seed {seed} --\n"));
+    }
+    if options.isolation {
+        output_msg.push_str("-- isolated --\n");
+    }
+
+    // The platform is only required for caching, without it timeouts are not derived from hints
+    let platform = match query_platform(&mut suite) {
+        Ok(platform) => Some(platform),
+        Err(code) if options.cache.is_some() => return Err(code),
+        Err(_) => None,
+    };
+    let frequency = options.clock_frequency.or_else(|| {
+        platform
+            .as_ref()
+            .and_then(|platform| platform.clock_frequency)
+    });
+    suite.set_clock_frequency(frequency);
+    // The sizes of the datasets are only needed to derive the throughput from the time
+    let requests_datasets = entries.iter().any(|entry| {
+        matches!(&entry.msg, OutgoingMessage::Benchmark(info, _) if !info.datasets().is_empty())
+    });
+    let clock = Clock {
+        frequency: options.clock_frequency,
+        datasets: match frequency {
+            Some(_) if requests_datasets => query_datasets(&mut suite),
+            _ => Vec::new(),
+        },
+    };
+
+    let overhead = if options.calibrate {
+        Some(calibration::calibrate(&mut suite)?)
+    } else {
+        None
+    };
+
+    let cache = match (&options.cache, &platform) {
+        (Some(dir), Some(platform)) => open_cache(dir.clone(), platform),
+        _ => None,
+    };
+    let mut copies: Vec<(&RawEntry, Repetition)> = entries
+        .iter()
+        .flat_map(|entry| Repetition::copies_of(entry, options.repeat))
+        .collect();
+
+    let mut exit_code = ExitCode::Success;
+    let mut skipped = Vec::new();
+    let mut responses = Vec::new();
+    let (mut checkpoint, progress) = match input_file {
+        Some(input_file) if !options.stream => {
+            Checkpoint::open(input_file, copies.len(), options.resume)
+        }
+        _ => (None, None),
+    };
+    let mut answered = 0;
+    if let Some(progress) = progress {
+        // The Suspend messages following the last answered message are sent again
+        let resume_from = match progress.answered.checked_sub(1) {
+            Some(last) => copies
+                .iter()
+                .enumerate()
+                .filter(|(_, (entry, _))| !matches!(entry.msg, OutgoingMessage::Suspend(_)))
+                .nth(last)
+                .map_or(copies.len(), |(index, _)| index + 1),
+            None => 0,
+        };
+        copies.drain(..resume_from);
+
+        for (line, msg) in &progress.responses {
+            if let IncomingMessage::Skipped { reason, .. } = msg {
+                let entry = entries.iter().find(|entry| entry.line_num == *line);
+                if let Some(entry) = entry {
+                    if !skipped
+                        .iter()
+                        .any(|(skipped, _): &(&RawEntry, _)| skipped.line_num == entry.line_num)
+                    {
+                        skipped.push((entry, *reason));
+                    }
+                }
+            }
+        }
+        answered = progress.answered;
+        responses = progress.responses;
+        exit_code = progress.exit_code;
+        output_msg = progress.output;
+        output_msg.push_str(&format!(
+            "-- resumed after {answered} answered messages --\n"
+        ));
+    }
+    // Repeated benchmarks are always performed, as every repetition should be measured
+    let cached: Vec<Option<IncomingMessage>> = copies
+        .iter()
+        .map(|(entry, repetition)| match (&cache, repetition) {
+            (Some(cache), Repetition::Single) => cache.load(&entry.msg),
+            _ => None,
+        })
+        .collect();
+
+    let mut console = if options.dashboard {
+        let total = copies
+            .iter()
+            .filter(|(entry, _)| !matches!(entry.msg, OutgoingMessage::Suspend(_)))
+            .count();
+        let title = input_file.map_or_else(
+            || String::from(PLAN_TITLE),
+            |file| file.display().to_string(),
+        );
+        Console::dashboard(title, total, &mut suite)
+    } else if options.stream {
+        Console::piped()
+    } else {
+        Console::plain()
+    };
+
+    if let Err(err) = send_copies(&mut suite, &mut console, &copies, &cached, options) {
+        console.eprintln(format!("Connection lost: {err}"));
+        return Err(ExitCode::ConnectionFailure);
+    }
+
+    let warnings_logged = suite.warnings_logged();
+
+    // The suite replies to every message in order, except for Suspend.
+    // The index of the copy is kept, so the following copies can be sent again after a hang
+    let mut pending_entries = copies
+        .iter()
+        .zip(&cached)
+        .enumerate()
+        .filter(|(_, ((entry, _), _))| !matches!(entry.msg, OutgoingMessage::Suspend(_)))
+        .map(|(index, ((entry, repetition), cached))| (*entry, cached, *repetition, index))
+        .peekable();
+
+    loop {
+        while let Some((entry, Some(msg), _, _)) = pending_entries.peek() {
+            console.eprintln(format!("Line {}: reusing cached results", entry.line_num));
+            output_msg.push_str(&format!("-- cached --\n{msg:#?}\n"));
+            if let IncomingMessage::BenchmarkResults(envelope) = msg {
+                label_deterministic(envelope, &mut output_msg);
+                output_msg.push_str(&statistics::format_statistics(&statistics::summarize(
+                    envelope,
+                )));
+                append_shares(entry.line_num, envelope, &mut console, &mut output_msg);
+                append_adjusted(envelope, overhead, &mut output_msg);
+                output_msg.push_str(&timing::format_times(&timing::times(envelope, &clock)));
+            }
+            exit_code = exit_code.or(check_budgets(entry, msg, &mut console, &mut output_msg));
+            stream_response(options, connection, entry.line_num, msg, overhead, &clock);
+            responses.push((entry.line_num, (*msg).clone()));
+            pending_entries.next();
+            console.responded();
+            answered += 1;
+            if let Some(checkpoint) = &mut checkpoint {
+                checkpoint.record(answered, &responses, &output_msg, exit_code);
+            }
+        }
+
+        if let Some((entry, _, _, _)) = pending_entries.peek() {
+            console.performing(entry.line_num, &entry.msg);
+        }
+
+        // The timeouts only account for the time the suite takes to respond, not for the transfer
+        let timeout = pending_entries.peek().and_then(|(entry, _, _, _)| {
+            entry
+                .timeout
+                .or_else(|| options.watchdog.as_ref().map(|watchdog| watchdog.deadline))
+        });
+        let response = suite
+            .set_response_timeout(timeout)
+            .and_then(|()| suite.read_message());
+
+        match response {
+            Ok(msg) => {
+                output_msg.push_str(&format!("{msg:#?}\n"));
+                console.received(&mut suite, &msg);
+
+                if matches!(msg, IncomingMessage::Status(SuiteStatus::Done)) {
+                    let unanswered = pending_entries.clone().count();
+                    if unanswered > 0 {
+                        let anomaly =
+                            format!("The suite finished without answering {unanswered} messages");
+                        console.eprintln(anomaly.clone());
+                        output_msg.push_str(&format!("-- {anomaly} --\n"));
+                        exit_code = exit_code.or(ExitCode::ProtocolError);
+                    }
+                    break;
+                }
+
+                if let IncomingMessage::BenchmarkResults(envelope) = &msg {
+                    if envelope.schema_version > SCHEMA_VERSION {
+                        console.eprintln(format!(
+                            "Results use schema version {}, which is newer than the supported version {SCHEMA_VERSION}",
+                            envelope.schema_version
+                        ));
+                        exit_code = exit_code.or(options.warning());
+                    }
+                    label_deterministic(envelope, &mut output_msg);
+                    output_msg.push_str(&statistics::format_statistics(&statistics::summarize(
+                        envelope,
+                    )));
+                    if let Some((entry, _, _, _)) = pending_entries.peek() {
+                        append_shares(entry.line_num, envelope, &mut console, &mut output_msg);
+                    }
+                    append_adjusted(envelope, overhead, &mut output_msg);
+                    output_msg.push_str(&timing::format_times(&timing::times(envelope, &clock)));
+                }
+
+                match ResponseKind::of(&msg) {
+                    Some(response) => {
+                        if let Some((entry, _, repetition, _)) = pending_entries.next() {
+                            console.responded();
+                            if let Some(anomaly) = entry
+                                .check_response(response)
+                                .or_else(|| entry.check_request_id(&msg))
+                            {
+                                console.eprintln(anomaly.clone());
+                                output_msg.push_str(&format!("-- {anomaly} --\n"));
+
+                                exit_code = exit_code.or(match ExitCode::of_message(&msg) {
+                                    ExitCode::Success => ExitCode::ProtocolError,
+                                    code => code,
+                                });
+                            } else if let Some(cache) = &cache {
+                                // Failed verifications are not cached, so they are retried next time
+                                if ExitCode::of_message(&msg) == ExitCode::Success
+                                    && repetition == Repetition::Single
+                                {
+                                    cache.store(&entry.msg, &msg);
+                                }
+                            }
+
+                            if let IncomingMessage::Skipped { reason, .. } = &msg {
+                                console.println(format!(
+                                    "Line {}: skipped ({reason:?})",
+                                    entry.line_num
+                                ));
+                                output_msg.push_str(&format!("-- skipped: {reason:?} --\n"));
+                                exit_code = exit_code.or(options.warning());
+                                // Repeated benchmarks are only listed once in the summary
+                                if repetition != Repetition::Warmup
+                                    && !skipped.iter().any(|(skipped, _): &(&RawEntry, _)| {
+                                        skipped.line_num == entry.line_num
+                                    })
+                                {
+                                    skipped.push((entry, *reason));
+                                }
+                            }
+
+                            if let IncomingMessage::BenchmarkResults(ResultEnvelope {
+                                verification_failure: Some(failure),
+                                ..
+                            }) = &msg
+                            {
+                                let block = failure
+                                    .block_index
+                                    .map_or(String::new(), |index| format!(" at block {index}"));
+                                console.println(format!(
+                                    "Line {}: verification of {} failed{block}",
+                                    entry.line_num, failure.benchmark
+                                ));
+                                output_msg.push_str(&format!(
+                                    "-- verification failed: {}{block} --\n",
+                                    failure.benchmark
+                                ));
+                                exit_code = exit_code.or(ExitCode::VerificationFailure);
+                            }
+
+                            if let IncomingMessage::BenchmarkResults(envelope) = &msg {
+                                if envelope.retries > 0 {
+                                    console.println(format!(
+                                        "Line {}: {} repetitions were retried",
+                                        entry.line_num, envelope.retries
+                                    ));
+                                    output_msg.push_str(&format!(
+                                        "-- retried: {} --\n",
+                                        envelope.retries
+                                    ));
+                                    exit_code = exit_code.or(options.warning());
+                                }
+                            }
+
+                            if let IncomingMessage::DutyCycleDone {
+                                windows, overruns, ..
+                            } = &msg
+                            {
+                                console.println(format!(
+                                    "Line {}: {windows} windows, {overruns} started late",
+                                    entry.line_num
+                                ));
+                                if *overruns > 0 {
+                                    exit_code = exit_code.or(options.warning());
+                                }
+                            }
+
+                            if response == ResponseKind::Unsupported {
+                                console.println(format!(
+                                    "Line {}: the response is not understood by this CLI",
+                                    entry.line_num
+                                ));
+                                exit_code = exit_code.or(options.warning());
+                            }
+
+                            if repetition == Repetition::Warmup {
+                                output_msg.push_str("-- warm-up, discarded --\n");
+                            } else {
+                                exit_code = exit_code.or(check_budgets(
+                                    entry,
+                                    &msg,
+                                    &mut console,
+                                    &mut output_msg,
+                                ));
+                                stream_response(
+                                    options,
+                                    connection,
+                                    entry.line_num,
+                                    &msg,
+                                    overhead,
+                                    &clock,
+                                );
+                                responses.push((entry.line_num, msg));
+                            }
+
+                            answered += 1;
+                            if let Some(checkpoint) = &mut checkpoint {
+                                checkpoint.record(answered, &responses, &output_msg, exit_code);
+                            }
+                        } else {
+                            let anomaly = format!("Received {response} after the last message");
+                            console.eprintln(anomaly.clone());
+                            output_msg.push_str(&format!("-- {anomaly} --\n"));
+                            exit_code = exit_code.or(ExitCode::ProtocolError);
+                        }
+                    }
+                    None => {
+                        exit_code = exit_code.or(ExitCode::of_message(&msg));
+
+                        // Windows are streamed before the response, they belong to the next entry
+                        if let IncomingMessage::Window {
+                            index, timestamp, ..
+                        } = &msg
+                        {
+                            if let Some((entry, _, _, _)) = pending_entries.peek() {
+                                console.println(format!(
+                                    "Line {}: window {index} after {timestamp} cycles",
+                                    entry.line_num
+                                ));
+                                stream_response(
+                                    options,
+                                    connection,
+                                    entry.line_num,
+                                    &msg,
+                                    overhead,
+                                    &clock,
+                                );
+                                responses.push((entry.line_num, msg));
+                            }
+                        }
+                    }
+                }
+            }
+            Err(err) => {
+                let hung = match (&options.watchdog, pending_entries.peek()) {
+                    (Some(watchdog), Some((entry, _, _, index)))
+                        if err.kind() == io::ErrorKind::TimedOut =>
+                    {
+                        Some((watchdog, *entry, *index))
+                    }
+                    _ => None,
+                };
+                let (watchdog, entry, index) = match hung {
+                    Some(hung) => hung,
+                    None => {
+                        console.println(String::from("Connection closed."));
+                        output_msg.push_str("-- connection lost --\n");
+                        exit_code = exit_code.or(ExitCode::ConnectionFailure);
+                        break;
+                    }
+                };
+
+                let deadline = entry.timeout.unwrap_or(watchdog.deadline);
+                console.eprintln(format!(
+                    "Line {}: no response within {deadline:?}, the suite hangs",
+                    entry.line_num
+                ));
+                output_msg.push_str(&format!("-- hang: no response within {deadline:?} --\n"));
+                exit_code = exit_code.or(ExitCode::Hang);
+
+                // The message counts as answered, so a resumed run continues after it as well
+                pending_entries.next();
+                answered += 1;
+                if let Some(checkpoint) = &mut checkpoint {
+                    checkpoint.record(answered, &responses, &output_msg, exit_code);
+                }
+
+                let recovered = recover_suite(&mut suite, watchdog, options).and_then(|()| {
+                    send_copies(
+                        &mut suite,
+                        &mut console,
+                        &copies[index + 1..],
+                        &cached[index + 1..],
+                        options,
+                    )
+                    .map_err(|err| format!("Connection lost: {err}"))
+                });
+                match recovered {
+                    Ok(()) => {
+                        console.println(format!(
+                            "Line {}: the suite recovered, continuing with the next message",
+                            entry.line_num
+                        ));
+                        output_msg.push_str("-- recovered --\n");
+                    }
+                    Err(err) => {
+                        console.eprintln(err);
+                        output_msg.push_str("-- connection lost --\n");
+                        exit_code = exit_code.or(ExitCode::ConnectionFailure);
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    console.close(&mut suite);
+
+    let warnings_logged = suite.warnings_logged() - warnings_logged;
+    if warnings_logged > 0 {
+        output_msg.push_str(&format!(
+            "-- the suite logged {warnings_logged} warnings or errors --\n"
+        ));
+        exit_code = exit_code.or(options.warning());
+    }
+
+    if let Some(checkpoint) = checkpoint {
+        if pending_entries.peek().is_none() {
+            if let Err(err) = checkpoint.remove() {
+                eprintln!("Failed to remove the checkpoint: {err}");
+            }
+        } else {
+            eprintln!(
+                "Progress saved to {}, continue with --resume",
+                checkpoint.path().display()
+            );
+        }
+    }
+
+    if !skipped.is_empty() {
+        let summary = skipped_summary(&mut suite, &skipped);
+        if options.stream {
+            eprint!("{summary}");
+        } else {
+            print!("{summary}");
+        }
+        output_msg.push_str(&summary);
+    }
+
+    if options.repeat.is_some() {
+        responses = merge_repetitions(responses, &mut output_msg);
+    }
+
+    Ok(Session {
+        exit_code,
+        output: output_msg,
+        responses,
+        overhead,
+        clock,
+        platform,
+        board: connection.board.clone(),
+    })
+}
+
+/// Sends the copies of the entries whose responses are not cached to the suite, followed by Done
+///
+/// # Arguments
+///
+/// * `suite` - the connection to the suite
+/// * `console` - prints the messages suspending the suite
+/// * `copies` - the copies of the entries, in order
+/// * `cached` - the cached response of every copy, if any
+/// * `options` - contains the seed and isolation applied to configs
+fn send_copies(
+    suite: &mut SuiteConnection,
+    console: &mut Console,
+    copies: &[(&RawEntry, Repetition)],
+    cached: &[Option<IncomingMessage>],
+    options: &RunOptions,
+) -> Result<(), std::io::Error> {
+    for ((entry, _), cached) in copies.iter().zip(cached) {
+        if cached.is_none() {
+            match entry.msg {
+                OutgoingMessage::Suspend(code) => {
+                    console.println(format!(
+                        "Line {}: suspending the suite, {code}",
+                        entry.line_num
+                    ));
+                    suite.send_message(&entry.msg)
+                }
+                // The seed applies to the whole file, unless a config sets its own
+                OutgoingMessage::SetConfig(config) => {
+                    suite.send_message(&OutgoingMessage::SetConfig(SuiteConfig {
+                        seed: config.seed.or(options.seed),
+                        isolation: config.isolation || options.isolation,
+                        ..config
+                    }))
+                }
+                _ => suite.send_message(&entry.msg),
+            }?;
+        }
+    }
+
+    suite.send_message(&OutgoingMessage::Done)
+}
+
+/// Recovers from a message the suite did not respond to within its deadline, by running the reset
+/// hook of the watchdog, if any, and waiting until the suite is ready again. The seed and isolation
+/// of the file are configured again, as a suite that was reset forgot them.
+///
+/// Returns a description of the failure if the suite did not recover.
+///
+/// # Arguments
+///
+/// * `suite` - the connection to the suite
+/// * `watchdog` - describes how to recover
+/// * `options` - contains the seed and isolation of the file
+fn recover_suite(
+    suite: &mut SuiteConnection,
+    watchdog: &Watchdog,
+    options: &RunOptions,
+) -> Result<(), String> {
+    if let Some(reset) = &watchdog.reset {
+        reset.run()?;
+    }
+    suite
+        .recover(watchdog.deadline)
+        .map_err(|err| format!("Suite did not recover: {err}"))?;
+
+    if options.seed.is_some() || options.isolation {
+        configure_suite(
+            suite,
+            SuiteConfig {
+                seed: options.seed,
+                isolation: options.isolation,
+                ..SuiteConfig::DEFAULT
+            },
+        )
+        .map_err(|_| String::from("Suite could not be configured again"))?;
+    }
+
+    Ok(())
+}
+
+/// Writes a response to stdout as a line of JSON, if responses are streamed
+///
+/// # Arguments
+///
+/// * `options` - whether responses are streamed
+/// * `connection` - the board the message was sent to, if any
+/// * `line` - the line of the message the suite responded to
+/// * `response` - the response
+/// * `overhead` - the calibrated overhead of every phase in cycles, if calibrated
+/// * `clock` - converts the cycles of the results into time
+fn stream_response(
+    options: &RunOptions,
+    connection: &ConnectionOptions,
+    line: usize,
+    response: &IncomingMessage,
+    overhead: Option<u64>,
+    clock: &Clock,
+) {
+    if !options.stream {
+        return;
+    }
+
+    let response = RawResponse {
+        line,
+        board: connection.board.as_deref(),
+        response,
+        statistics: statistics_of(response),
+        shares: shares_of(response),
+        adjusted: adjusted_of(response, overhead),
+        times: times_of(response, clock),
+    };
+    let json = serde_json::to_string(&response).expect("Can not serialize struct");
+    // Flushed on every line, so the other process receives the response right away
+    let mut stdout = io::stdout().lock();
+    let _ = writeln!(stdout, "{json}").and_then(|()| stdout.flush());
+}
+
+/// Merges the results of consecutive responses to the same entry into a single response,
+/// so the results of the repetitions of a benchmark are aggregated.
+/// The statistics and phase shares of every merged response are appended to the output.
+///
+/// Only responses reporting samples are merged, other responses are returned unchanged.
+///
+/// # Arguments
+///
+/// * `responses` - the responses with the line number of their entry, in order
+/// * `output_msg` - the output the statistics are appended to
+fn merge_repetitions(
+    responses: Vec<(usize, IncomingMessage)>,
+    output_msg: &mut String,
+) -> Vec<(usize, IncomingMessage)> {
+    let mut merged: Vec<(usize, IncomingMessage, usize)> = Vec::new();
+
+    for (line, response) in responses {
+        if let (
+            Some((last_line, IncomingMessage::BenchmarkResults(last), count)),
+            IncomingMessage::BenchmarkResults(envelope),
+        ) = (merged.last_mut(), &response)
+        {
+            if *last_line == line
+                && last.aggregate == Aggregate::Samples
+                && envelope.aggregate == Aggregate::Samples
+            {
+                let mut envelope = envelope.clone();
+                last.results.append(&mut envelope.results);
+                last.poll_counts.append(&mut envelope.poll_counts);
+                last.virtual_time.append(&mut envelope.virtual_time);
+                last.unaccounted_cycles
+                    .append(&mut envelope.unaccounted_cycles);
+                last.verification_failure = last
+                    .verification_failure
+                    .take()
+                    .or(envelope.verification_failure);
+                last.retries += envelope.retries;
+                last.isolated &= envelope.isolated;
+                *count += 1;
+                continue;
+            }
+        }
+        merged.push((line, response, 1));
+    }
+
+    merged
+        .into_iter()
+        .map(|(line, response, count)| {
+            if let (IncomingMessage::BenchmarkResults(envelope), true) = (&response, count > 1) {
+                output_msg.push_str(&format!(
+                    "-- line {line}: {count} repetitions aggregated --\n"
+                ));
+                output_msg.push_str(&statistics::format_statistics(&statistics::summarize(
+                    envelope,
+                )));
+                output_msg.push_str(&shares::format_shares(&shares::shares(envelope)));
+            }
+            (line, response)
+        })
+        .collect()
+}
+
+/// Returns a summary of all skipped benchmarks with the reasons reported by the suite,
+/// followed by the capabilities of the suite, which explain why modules were unavailable.
+///
+/// # Arguments
+///
+/// * `suite` - the connection to the suite, used to query its capabilities
+/// * `skipped` - the entries whose benchmarks were skipped, with the reported reasons
+fn skipped_summary(suite: &mut SuiteConnection, skipped: &[(&RawEntry, SkipReason)]) -> String {
+    let mut summary = String::from("-- skipped benchmarks --\n");
+
+    for (entry, reason) in skipped {
+        let (info, module) = match &entry.msg {
+            OutgoingMessage::Benchmark(info, _) => (format!("{info:?}"), info.required_module()),
+            msg => (format!("{msg:?}"), None),
+        };
+        let module = module.map_or(String::new(), |module| format!(", requires {module:?}"));
+
+        summary.push_str(&format!(
+            "Line {}: {info}{module}, {reason:?}\n",
+            entry.line_num
+        ));
+    }
+
+    match suite.request(&OutgoingMessage::GetCapabilities) {
+        Ok(IncomingMessage::Capabilities(modules)) => {
+            summary.push_str(&format!("Capabilities of the suite: {modules:?}\n"))
+        }
+        _ => summary.push_str("Capabilities of the suite: unknown\n"),
+    }
+
+    summary
+}
+
+/// Marks results in the output that are exactly reproducible, as they were measured in a
+/// cycle accurate simulation using virtual time
+///
+/// # Arguments
+///
+/// * `envelope` - the results of a benchmark
+/// * `output_msg` - the output the label is appended to
+fn label_deterministic(envelope: &ResultEnvelope, output_msg: &mut String) {
+    if envelope.is_simulation_deterministic() {
+        output_msg.push_str("-- simulation-deterministic --\n");
+    }
+}
+
+/// Appends the shares of the phases of the results to the output, check the shares module.
+/// Benchmarks dominated by initialization are additionally reported on the console
+///
+/// # Arguments
+///
+/// * `line` - the line number of the entry the results belong to
+/// * `envelope` - the results of a benchmark
+/// * `console` - the console benchmarks dominated by initialization are reported on
+/// * `output_msg` - the output the shares are appended to
+fn append_shares(
+    line: usize,
+    envelope: &ResultEnvelope,
+    console: &mut Console,
+    output_msg: &mut String,
+) {
+    let shares = shares::shares(envelope);
+    for result in shares.iter().filter(|result| result.setup_dominates()) {
+        console.eprintln(format!(
+            "Line {line}: {:.1}% of the cycles of {} from result {} are spent in initialization, \
+             the driver rather than the hardware is the bottleneck",
+            result.setup, result.benchmark, result.first_result
+        ));
+    }
+    output_msg.push_str(&shares::format_shares(&shares));
+}
+
+/// Appends the results adjusted for the overhead of measurements to the output,
+/// nothing is appended if the results can not be adjusted, check `calibration::adjust`
+///
+/// # Arguments
+///
+/// * `envelope` - the results of a benchmark
+/// * `overhead` - the calibrated overhead of every phase in cycles, if any
+/// * `output_msg` - the output the adjusted results are appended to
+fn append_adjusted(envelope: &ResultEnvelope, overhead: Option<u64>, output_msg: &mut String) {
+    if let Some(adjusted) = calibration::adjust(envelope, overhead) {
+        output_msg.push_str(&format!(
+            "-- adjusted for an overhead of {} cycles per phase --\n{:#?}\n",
+            adjusted.overhead, adjusted.results
+        ));
+    }
+}
+
+/// Returns the results contained in a response adjusted for the overhead of measurements,
+/// None if the response does not contain results or they can not be adjusted
+///
+/// # Arguments
+///
+/// * `msg` - the response of the suite
+/// * `overhead` - the calibrated overhead of every phase in cycles, if any
+fn adjusted_of(msg: &IncomingMessage, overhead: Option<u64>) -> Option<Adjusted> {
+    match msg {
+        IncomingMessage::BenchmarkResults(envelope) => calibration::adjust(envelope, overhead),
+        _ => None,
+    }
+}
+
+/// Returns the time of the phases of the results contained in a response,
+/// empty if the response does not contain results or the clock frequency is not known
+///
+/// # Arguments
+///
+/// * `msg` - the response of the suite
+/// * `clock` - converts the cycles of the results into time
+fn times_of(msg: &IncomingMessage, clock: &Clock) -> Vec<ResultTime> {
+    match msg {
+        IncomingMessage::BenchmarkResults(envelope) => timing::times(envelope, clock),
+        _ => Vec::new(),
+    }
+}
+
+/// Returns the shares of the phases of the results contained in a response,
+/// empty if the response does not contain results
+///
+/// # Arguments
+///
+/// * `msg` - the response of the suite
+fn shares_of(msg: &IncomingMessage) -> Vec<ResultShares> {
+    match msg {
+        IncomingMessage::BenchmarkResults(envelope) => shares::shares(envelope),
+        _ => Vec::new(),
+    }
+}
+
+/// Returns the statistics of the results contained in a response,
+/// empty if the response does not contain results
+///
+/// # Arguments
+///
+/// * `msg` - the response of the suite
+fn statistics_of(msg: &IncomingMessage) -> Vec<ResultStatistics> {
+    match msg {
+        IncomingMessage::BenchmarkResults(envelope) => statistics::summarize(envelope),
+        _ => Vec::new(),
+    }
+}
+
+/// Checks the results of a benchmark against the budgets of the entry that requested it,
+/// the outcome of every budget is printed and marked in the output.
+///
+/// Returns the exit code corresponding to the first failure that occurred.
+///
+/// # Arguments
+///
+/// * `entry` - the entry that requested the benchmark
+/// * `msg` - the response of the suite to the entry
+/// * `console` - prints the outcomes
+/// * `output_msg` - the output the outcomes are appended to
+fn check_budgets(
+    entry: &RawEntry,
+    msg: &IncomingMessage,
+    console: &mut Console,
+    output_msg: &mut String,
+) -> ExitCode {
+    let results = match msg {
+        IncomingMessage::BenchmarkResults(envelope) => &envelope.results,
+        _ => return ExitCode::Success,
+    };
+
+    let mut exit_code = ExitCode::Success;
+    for budget in &entry.budgets {
+        let outcome = budget.check(results).unwrap_or_else(|outcome| {
+            exit_code = ExitCode::RegressionThresholdExceeded;
+            outcome
+        });
+
+        console.println(format!("Line {}: {outcome}", entry.line_num));
+        output_msg.push_str(&format!("-- Line {}: {outcome} --\n", entry.line_num));
+    }
+
+    exit_code
+}
+
+/// Resets the suite, so benchmarks start from a clean hardware state.
+///
+/// Returns the exit code of the failure if the suite could not be reset.
+///
+/// # Arguments
+///
+/// * `suite` - the connection to the suite
+fn reset_suite(suite: &mut SuiteConnection) -> Result<(), ExitCode> {
+    match suite.request(&OutgoingMessage::Reset) {
+        Ok(IncomingMessage::Status(SuiteStatus::Ready)) => Ok(()),
+        Ok(msg) => {
+            eprintln!("Suite could not be reset, received: {msg:?}");
+            Err(ExitCode::of_message(&msg).or(ExitCode::ProtocolError))
+        }
+        Err(err) => {
+            eprintln!("Connection lost: {err}");
+            Err(ExitCode::ConnectionFailure)
+        }
+    }
+}
+
+/// Configures the suite before any message of a file is sent, like seeding its pseudo random
+/// choices. The suite must accept the config unchanged.
+///
+/// Returns the exit code of the failure if the suite could not be configured.
+///
+/// # Arguments
+///
+/// * `suite` - the connection to the suite
+/// * `config` - the config
+fn configure_suite(suite: &mut SuiteConnection, config: SuiteConfig) -> Result<(), ExitCode> {
+    match suite.request(&OutgoingMessage::SetConfig(config)) {
+        Ok(IncomingMessage::Config(accepted)) if accepted == config => {
+            if let Some(seed) = config.seed {
+                eprintln!("Seeded the suite with {seed}");
+            }
+            if config.isolation {
+                eprintln!("Isolating every benchmark on the suite");
+            }
+            Ok(())
+        }
+        Ok(msg) => {
+            eprintln!("Suite could not be configured, received: {msg:?}");
+            Err(ExitCode::of_message(&msg).or(ExitCode::ProtocolError))
+        }
+        Err(err) => {
+            eprintln!("Connection lost: {err}");
+            Err(ExitCode::ConnectionFailure)
+        }
+    }
+}
+
+/// Opens the result cache in the provided directory for the build of the connected suite.
+///
+/// Returns None if the suite does not report which build it is, in which case caching is disabled.
+///
+/// # Arguments
+///
+/// * `dir` - the directory containing the cached results
+/// * `platform` - the platform and build of the connected suite
+fn open_cache(dir: PathBuf, platform: &PlatformDescriptor) -> Option<ResultCache> {
+    let cache = ResultCache::open(dir, platform);
+    if cache.is_none() {
+        eprintln!("Suite does not report its build, caching is disabled");
+    }
+    cache
+}
+
+/// Queries the platform and build of the connected suite,
+/// returns the exit code of the failure if the suite could not be queried.
+///
+/// # Arguments
+///
+/// * `suite` - the connection to the suite
+fn query_platform(suite: &mut SuiteConnection) -> Result<PlatformDescriptor, ExitCode> {
+    match suite.request(&OutgoingMessage::GetPlatform) {
+        Ok(IncomingMessage::Platform(platform)) => Ok(platform),
+        Ok(msg) => {
+            eprintln!("Suite did not describe its platform, received: {msg:?}");
+            Err(ExitCode::of_message(&msg).or(ExitCode::ProtocolError))
+        }
+        Err(err) => {
+            eprintln!("Connection lost: {err}");
+            Err(ExitCode::ConnectionFailure)
+        }
+    }
+}
+
+/// Queries the datasets available on the connected suite, returns an empty list if the suite did
+/// not list them, as they are only needed to derive the throughput of benchmarks
+///
+/// # Arguments
+///
+/// * `suite` - the connection to the suite
+fn query_datasets(suite: &mut SuiteConnection) -> Vec<DatasetDescriptor> {
+    match suite.request(&OutgoingMessage::ListDatasets) {
+        Ok(IncomingMessage::Datasets(datasets)) => datasets,
+        Ok(msg) => {
+            eprintln!("Suite did not list its datasets, received: {msg:?}");
+            Vec::new()
+        }
+        Err(err) => {
+            eprintln!("Suite did not list its datasets: {err}");
+            Vec::new()
+        }
+    }
+}
+
+/// Benchmarks that finish quickly and are therefore used for smoke testing the suite
+const SMOKE_TEST_BENCHMARKS: [BenchmarkInfo; 7] = [
+    BenchmarkInfo::ExampleSHA256,
+    BenchmarkInfo::ExampleAES256,
+    BenchmarkInfo::ExampleRNG,
+    BenchmarkInfo::ExampleECDSA,
+    BenchmarkInfo::AESTotal,
+    BenchmarkInfo::ECDSA(0),
+    BenchmarkInfo::Hash {
+        hash: HashBenchmarkType::SHAKE128 { output_length: 32 },
+        dataset: 2,
+    },
+];
+
+/// Smoke test the suite, verifying that all modules available on the suite are working.
+///
+/// For every module reported by the suite, a randomly chosen small benchmark using that module
+/// is performed once. The outcome of every benchmark is printed.
+/// If a seed is provided, the benchmarks are chosen based on it and the suite is seeded with it,
+/// so the smoke test can be reproduced.
+///
+/// Returns the exit code corresponding to the first failure that occurred.
+///
+/// # Arguments
+///
+/// * `connection` - describes how to connect to the suite
+/// * `seed` - seed of the pseudo random choices, the benchmarks are chosen based on the time if None
+pub fn smoke_test(connection: &ConnectionOptions, seed: Option<u64>) -> ExitCode {
+    let mut suite = match connect(connection) {
+        Ok(suite) => suite,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::ConnectionFailure;
+        }
+    };
+
+    let modules = match suite.request(&OutgoingMessage::GetCapabilities) {
+        Ok(IncomingMessage::Capabilities(modules)) => modules,
+        Ok(msg) => {
+            eprintln!("Suite did not report its capabilities, received: {msg:?}");
+            return ExitCode::of_message(&msg).or(ExitCode::ProtocolError);
+        }
+        Err(err) => {
+            eprintln!("Connection lost: {err}");
+            return ExitCode::ConnectionFailure;
+        }
+    };
+
+    if let Some(seed) = seed {
+        let config = SuiteConfig {
+            seed: Some(seed),
+            ..SuiteConfig::DEFAULT
+        };
+        if let Err(code) = configure_suite(&mut suite, config) {
+            return code;
+        }
+    }
+
+    let mut exit_code = ExitCode::Success;
+    for (index, module) in modules.into_iter().enumerate() {
+        let candidates: Vec<&BenchmarkInfo> = SMOKE_TEST_BENCHMARKS
+            .iter()
+            .filter(|info| info.required_module() == Some(module))
+            .collect();
+
+        if candidates.is_empty() {
+            println!("{module:?}: no benchmark available");
+            continue;
+        }
+
+        let seed = seed.map(|seed| seed.wrapping_add(index as u64));
+        let info = candidates[random_index(candidates.len(), seed)];
+        match suite.request(&OutgoingMessage::Benchmark(
+            info.clone(),
+            BenchmarkConfig::samples(1),
+        )) {
+            Ok(IncomingMessage::BenchmarkResults(envelope)) if !envelope.results.is_empty() => {
+                println!("{module:?}: {info:?} ok")
+            }
+            Ok(msg) => {
+                println!("{module:?}: {info:?} failed, received: {msg:?}");
+                exit_code = exit_code.or(ExitCode::of_message(&msg).or(ExitCode::ProtocolError));
+            }
+            Err(err) => {
+                println!("{module:?}: {info:?} failed, connection lost: {err}");
+                return exit_code.or(ExitCode::ConnectionFailure);
+            }
+        }
+    }
+
+    exit_code
+}
+
+/// Checks that results of every kind sent by the suite are decoded and processed correctly.
+///
+/// The suite is requested to replay canned results covering every kind of result, which are
+/// compared to the canned results known to the CLI. Every result is then processed like the
+/// results of benchmarks, i.e. split into phases, summarized and adjusted for an overhead.
+/// The outcome for every result is printed.
+///
+/// Returns the exit code corresponding to the first failure that occurred.
+///
+/// # Arguments
+///
+/// * `connection` - describes how to connect to the suite
+pub fn conformance_test(connection: &ConnectionOptions) -> ExitCode {
+    let mut suite = match connect(connection) {
+        Ok(suite) => suite,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::ConnectionFailure;
+        }
+    };
+
+    let envelope = match suite.request(&OutgoingMessage::Replay) {
+        Ok(IncomingMessage::BenchmarkResults(envelope)) => envelope,
+        Ok(msg) => {
+            eprintln!("Suite did not replay results, received: {msg:?}");
+            return ExitCode::of_message(&msg).or(ExitCode::ProtocolError);
+        }
+        Err(err) => {
+            eprintln!("Connection lost: {err}");
+            return ExitCode::ConnectionFailure;
+        }
+    };
+
+    let expected = corpus::envelope(corpus::benchmark_results());
+    let mut exit_code = ExitCode::Success;
+    if envelope.results.len() != expected.results.len() {
+        println!(
+            "Received {} results, expected {}",
+            envelope.results.len(),
+            expected.results.len()
+        );
+        exit_code = ExitCode::ProtocolError;
+    }
+
+    // Every result occurs twice, so it is summarized on its own
+    let mut doubled = envelope.clone();
+    doubled.results.extend(envelope.results.iter().cloned());
+    let statistics = statistics::summarize(&doubled);
+    let adjusted =
+        calibration::adjust(&envelope, Some(1)).map_or(Vec::new(), |adjusted| adjusted.results);
+
+    for (index, (result, expected)) in envelope.results.iter().zip(&expected.results).enumerate() {
+        let cycles: Vec<u64> = result.phases().iter().map(|(_, cycles)| *cycles).collect();
+        let failure = if result != expected {
+            Some(format!("received {result:?}"))
+        } else if csv::columns(result).len() != cycles.len()
+            || result.with_phases(&cycles) != *result
+        {
+            Some(String::from("phases can not be processed"))
+        } else if !statistics.iter().any(|statistics| {
+            statistics.first_result == index && statistics.phases.len() == cycles.len()
+        }) {
+            Some(String::from("results can not be summarized"))
+        } else if adjusted.get(index).map(|adjusted| adjusted.phases().len()) != Some(cycles.len())
+        {
+            Some(String::from("results can not be adjusted"))
+        } else {
+            None
+        };
+
+        match failure {
+            None => println!("{}: ok", expected.name()),
+            Some(failure) => {
+                println!("{}: failed, {failure}", expected.name());
+                exit_code = ExitCode::ProtocolError;
+            }
+        }
+    }
+
+    exit_code
+}
+
+/// Lists all datasets available on the suite, together with the id used to reference them
+/// in benchmark requests.
+///
+/// Returns the exit code corresponding to the failure that occurred, if any.
+///
+/// # Arguments
+///
+/// * `connection` - describes how to connect to the suite
+pub fn list_datasets(connection: &ConnectionOptions) -> ExitCode {
+    let mut suite = match connect(connection) {
+        Ok(suite) => suite,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::ConnectionFailure;
+        }
+    };
+
+    let datasets = match suite.request(&OutgoingMessage::ListDatasets) {
+        Ok(IncomingMessage::Datasets(datasets)) => datasets,
+        Ok(msg) => {
+            eprintln!("Suite did not list its datasets, received: {msg:?}");
+            return ExitCode::of_message(&msg).or(ExitCode::ProtocolError);
+        }
+        Err(err) => {
+            eprintln!("Connection lost: {err}");
+            return ExitCode::ConnectionFailure;
+        }
+    };
+
+    println!(
+        "{:<8} {:>3}  {:<24} {:>6}  {:>4}  {:<4}",
+        "Kind", "Id", "Name", "Bytes", "Key", "Mode"
+    );
+    for dataset in datasets {
+        println!(
+            "{:<8} {:>3}  {:<24} {:>6}  {:>4}  {:<4}",
+            format!("{:?}", dataset.kind),
+            dataset.id,
+            dataset.name,
+            dataset.size,
+            dataset
+                .key_length
+                .map_or(String::from("-"), |bits| bits.to_string()),
+            dataset.mode.as_deref().unwrap_or("-"),
+        );
+    }
+
+    ExitCode::Success
+}
+
+/// Returns a pseudo random index smaller than `len`, derived from the seed if provided,
+/// otherwise from the current time
+///
+/// # Arguments
+///
+/// * `len` - the length of the collection to index into, has to be greater than 0
+/// * `seed` - the seed the index is derived from
+fn random_index(len: usize, seed: Option<u64>) -> usize {
+    let value = match seed {
+        // Multiplying spreads consecutive seeds over the upper bits
+        Some(seed) => seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) >> 32,
+        None => SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.subsec_nanos()) as u64,
+    };
+
+    (value % len as u64) as usize
+}
+
+/// Benchmark the suite using the file provided.
+///
+/// The CLI will read the description of the benchmark from the file and
+/// determine the messages that should be sent to the suite in order to
+/// perform said benchmark.
+///
+/// The file is a plan, check the plan module for its format. Its benchmarks are performed like the
+/// messages of a raw file, so caching, badges and the checks of the responses work the same way.
+/// A .result.json file is written, containing the responses grouped by the sections of the plan,
+/// in text and CSV format additionally the .result file, in CSV format also the CSV files. If requested,
+/// the results are plotted, stored in a database and exported as metrics. A summary of every section is printed.
+/// The result files start with the metadata of the host, check the metadata module.
+///
+/// Returns the exit code corresponding to the first failure that occurred while benchmarking,
+/// or the failure that prevented the file from being benchmarked or its results from being written.
+///
+/// # Arguments
+///
+/// * `connection` - describes how to connect to the suite
+/// * `input_file` - path to the file containing a description of the benchmark that should be performed
+/// * `options` - controls which benchmarks are performed and how results are cached and exported
+pub fn benchmark_file(
+    connection: &ConnectionOptions,
+    input_file: PathBuf,
+    options: &RunOptions,
+) -> Result<ExitCode, FileError> {
+    let content = fs::read_to_string(&input_file).map_err(FileError::Read)?;
+    let plan = parse_plan(&content).map_err(FileError::InvalidPlan)?;
+    let (sections, entries) = plan_entries(&plan, &options.filter)?;
+    let metadata = Metadata::collect(&input_file, &content, &options.arguments);
+
+    let mut session = perform_entries(connection, Some(&input_file), &entries, options)
+        .map_err(FileError::Session)?;
+    session.output.insert_str(0, &metadata.header());
+
+    let mut output: Vec<SectionOutput> = plan
+        .sections
+        .iter()
+        .map(|section| SectionOutput {
+            name: &section.name,
+            responses: Vec::new(),
+        })
+        .collect();
+    for (number, response) in &session.responses {
+        if let Some(index) = entries.iter().position(|entry| entry.line_num == *number) {
+            if let OutgoingMessage::Benchmark(info, _) = &entries[index].msg {
+                output[sections[index]].responses.push(PlanResponse {
+                    number: *number,
+                    board: session.board.as_deref(),
+                    benchmark: info,
+                    response,
+                    statistics: statistics_of(response),
+                    shares: shares_of(response),
+                    adjusted: adjusted_of(response, session.overhead),
+                    times: times_of(response, &session.clock),
+                });
+            }
+        }
+    }
+
+    for section in &output {
+        let count = |kind: ResponseKind| {
+            section
+                .responses
+                .iter()
+                .filter(|response| ResponseKind::of(response.response) == Some(kind))
+                .count()
+        };
+        println!(
+            "Section {}: {} results, {} skipped, {} errors",
+            section.name,
+            count(ResponseKind::BenchmarkResults),
+            count(ResponseKind::Skipped),
+            count(ResponseKind::Error)
+        );
+    }
+
+    if options.format != OutputFormat::Json {
+        fs::write(input_file.with_extension("result"), &session.output)
+            .map_err(FileError::Write)?;
+    }
+    if options.format == OutputFormat::Csv {
+        csv::write_csv(&input_file.with_extension("csv"), &session.responses)
+            .map_err(FileError::Write)?;
+    }
+    if options.plot {
+        plot::write_plots(&input_file.with_extension("plots"), &session.responses)
+            .map_err(FileError::Write)?;
+    }
+    write_latex(options, &input_file, &session)?;
+    #[cfg(feature = "database")]
+    {
+        store_in_database(options, &input_file, &entries, &session)?;
+        write_badges(options)?;
+    }
+    export_metrics(options, &input_file, &entries, &session)?;
+    let output = PlanOutput {
+        metadata: &metadata,
+        sections: output,
+    };
+    let json = serde_json::to_string_pretty(&output).expect("Can not serialize struct");
+    fs::write(input_file.with_extension("result.json"), json).map_err(FileError::Write)?;
+
+    Ok(session.exit_code)
+}
+
+/// Returns the entries of a plan that are selected by the filter,
+/// and for every entry the index of the section it belongs to
+///
+/// # Arguments
+///
+/// * `plan` - the plan whose benchmarks should be performed
+/// * `filter` - selects which benchmarks are performed
+fn plan_entries(
+    plan: &Plan,
+    filter: &BenchmarkFilter,
+) -> Result<(Vec<usize>, Vec<RawEntry>), FileError> {
+    Ok(plan
+        .entries()
+        .map_err(FileError::InvalidPlan)?
+        .into_iter()
+        .filter(|(section, entry)| {
+            let section = &plan.sections[*section].name;
+            filter.selects(entry, Some(section))
+        })
+        .unzip())
+}
+
+/// Outcome of performing the benchmarks of a plan, check `perform_plan`
+#[derive(Debug)]
+pub struct PlanRun {
+    /// Exit code corresponding to the first failure that occurred while benchmarking
+    pub exit_code: ExitCode,
+    /// The responses to the benchmarks of every section, in the order of the sections
+    pub sections: Vec<SectionRun>,
+}
+
+/// Responses to the benchmarks of a section of a plan
+#[derive(Debug)]
+pub struct SectionRun {
+    /// Name of the section
+    pub name: String,
+    /// The benchmarks of the section that were performed, with the response of the suite, in
+    /// order. Windows of duty-cycled benchmarks are contained before the response of their benchmark
+    pub responses: Vec<(BenchmarkInfo, IncomingMessage)>,
+}
+
+/// Performs the benchmarks of a plan and returns the responses of the suite, without writing any
+/// files, for tools using the CLI as a library. Plans are built with `parse_plan` or directly.
+///
+/// Benchmarks are sent as done by `benchmark_file`, they are filtered, repeated, cached and
+/// watched as described by the options. The responses and anomalies are printed as done for
+/// files, but no result files are written, no checkpoint is recorded, and the results are
+/// neither stored in a database nor exported as metrics.
+///
+/// Returns the failure that prevented the plan from being performed.
+///
+/// # Arguments
+///
+/// * `connection` - describes how to connect to the suite
+/// * `plan` - the plan whose benchmarks should be performed
+/// * `options` - controls which benchmarks are performed and how results are cached
+pub fn perform_plan(
+    connection: &ConnectionOptions,
+    plan: &Plan,
+    options: &RunOptions,
+) -> Result<PlanRun, FileError> {
+    let (sections, entries) = plan_entries(plan, &options.filter)?;
+    let session =
+        perform_entries(connection, None, &entries, options).map_err(FileError::Session)?;
+
+    let mut run = PlanRun {
+        exit_code: session.exit_code,
+        sections: plan
+            .sections
+            .iter()
+            .map(|section| SectionRun {
+                name: section.name.clone(),
+                responses: Vec::new(),
+            })
+            .collect(),
+    };
+    for (number, response) in session.responses {
+        if let Some(index) = entries.iter().position(|entry| entry.line_num == number) {
+            if let OutgoingMessage::Benchmark(info, _) = &entries[index].msg {
+                run.sections[sections[index]]
+                    .responses
+                    .push((info.clone(), response));
+            }
+        }
+    }
+
+    Ok(run)
+}
+
+/// Content of the JSON result file of a plan
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+struct PlanOutput<'a> {
+    /// Describes where, when and how the results were produced
+    metadata: &'a Metadata,
+    /// The responses grouped by the sections of the plan, in the order of the plan
+    sections: Vec<SectionOutput<'a>>,
+}
+
+/// Responses to the benchmarks of a section of a plan
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+struct SectionOutput<'a> {
+    /// Name of the section
+    name: &'a str,
+    /// The responses, in the order the benchmarks were performed
+    responses: Vec<PlanResponse<'a>>,
+}
+
+/// Response to a benchmark of a plan
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+struct PlanResponse<'a> {
+    /// Number of the benchmark inside the plan
+    number: usize,
+    /// The board the benchmark was performed on, if benchmarking on multiple boards
+    #[serde(skip_serializing_if = "Option::is_none")]
+    board: Option<&'a str>,
+    /// The benchmark that was requested
+    benchmark: &'a BenchmarkInfo,
+    /// The response of the suite
+    response: &'a IncomingMessage,
+    /// Statistics of the results contained in the response, if any
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    statistics: Vec<ResultStatistics>,
+    /// Shares of the phases of the results contained in the response, if any
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    shares: Vec<ResultShares>,
+    /// The results contained in the response adjusted for the overhead of measurements, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    adjusted: Option<Adjusted>,
+    /// Time and throughput of the phases of the results contained in the response, if the clock
+    /// frequency is known
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    times: Vec<ResultTime>,
 }
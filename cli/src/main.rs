@@ -1,11 +1,63 @@
-use clap::Parser;
-use std::{ffi::OsString, path::PathBuf};
+use benchmark_common::codec::WireFormat;
+use clap::{error::ErrorKind, ArgGroup, CommandFactory, Parser, Subcommand};
+#[cfg(feature = "database")]
+use cli::database::DatabaseOptions;
+use cli::{
+    flash::{Flasher, DEFAULT_FLASHER},
+    latex::{LatexColumn, LatexOptions, LatexUnit},
+    launcher::{Emulator, Launcher},
+    manifest::SessionManifest,
+    metrics::Pushgateway,
+    raw::parse_duration,
+    transcript::Transcript,
+    transport::Endpoint,
+    tty::{detect_port, ConnectionOptions, Handshake},
+    watch::{wait_for_change, Stamp},
+    watchdog::{ResetHook, Watchdog},
+    BenchmarkFilter, ExitCode, OutputFormat, Repeat, RunOptions, STDIN_FILE,
+};
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
-#[derive(Parser)]
+#[derive(Parser, Clone)]
+#[clap(group(ArgGroup::new("suite_binary").args(&["elf", "watch"])))]
 struct Args {
     /// A valid path to the tty that should be used to communicate with the suite.
-    #[clap(short, long)]
-    tty: OsString,
+    /// If omitted, every available serial port is probed until the suite answers.
+    /// If given multiple times, the files are distributed across the boards and processed in
+    /// parallel, the results are marked with the tty of the board they were produced on.
+    #[clap(short, long, conflicts_with = "launch")]
+    tty: Vec<OsString>,
+
+    /// Connect to the suite over the given socket instead of a tty, e.g. `tcp:localhost:5555`
+    /// for an emulator exposing the UART over TCP, or `unix:/tmp/uart.sock` for a Unix domain
+    /// socket. Can be given multiple times like `--tty`, also together with it.
+    #[clap(long, conflicts_with = "launch")]
+    connect: Vec<Endpoint>,
+
+    /// Launch the suite passed with `--elf` in an emulator and connect to it, instead of connecting
+    /// to a running suite. `qemu` starts qemu-system-riscv32 with the virt machine, `verilator`
+    /// the simulation of earlgrey configured by VERILATOR_SIM, VERILATOR_ROM and VERILATOR_OTP.
+    /// The emulator is stopped once all files were processed.
+    #[clap(long, requires = "suite_binary")]
+    launch: Option<Launcher>,
+
+    /// Stop the launched emulator after the given number of seconds of wall-clock time,
+    /// e.g. to bound a simulation that stopped responding.
+    #[clap(long, requires = "launch")]
+    launch_timeout: Option<u64>,
+
+    /// Flash the given image of the suite onto the board before processing the files, and wait
+    /// until the suite reports that it is ready.
+    #[clap(long, conflicts_with = "launch")]
+    flash: Option<PathBuf>,
+
+    /// Command flashing an image onto the board, `{image}` is replaced by the path of the image.
+    #[clap(long, default_value = DEFAULT_FLASHER)]
+    flasher: Flasher,
 
     /// Enables raw mode when processing files,
     /// each input line will be parsed as a message and sent directly to the suite.
@@ -13,20 +65,523 @@ struct Args {
     #[clap(short, long)]
     raw: bool,
 
+    /// Check the files without connecting to the suite: every line is parsed and the datasets
+    /// requested by benchmarks are validated, all invalid lines are reported. Nothing is sent.
+    #[clap(long, conflicts_with_all = &["launch", "flash"])]
+    check: bool,
+
+    /// Resynchronize with a suite that is already running and may still be sending output
+    /// of an earlier session, instead of expecting a freshly started suite.
+    #[clap(long)]
+    resync: bool,
+
     /// List of files, each representing a benchmark that should be performed.
+    /// Without raw mode, files are TOML plans describing the benchmarks.
     /// A .result file will be generated for each benchmark.
+    /// With `-` in raw mode, the messages are read from stdin and every response is written to
+    /// stdout as a line of JSON instead.
     #[clap(short, long, multiple_values = true)]
     files: Vec<PathBuf>,
+
+    /// Only perform benchmarks matching one of the given patterns: a tag, the name of the
+    /// benchmark, e.g. AESDataSet, its label or the section of the plan. Patterns may contain `*`
+    /// and `?`, e.g. `aes-*`, case is ignored.
+    #[clap(long)]
+    only: Vec<String>,
+
+    /// Skip benchmarks matching any of the given patterns, like for --only.
+    #[clap(long)]
+    skip: Vec<String>,
+
+    /// Wire format of the frames exchanged with the suite, `json` or the binary codec this build
+    /// supports. If omitted and this build supports multiple formats, it is detected during the handshake.
+    #[clap(long)]
+    wire_format: Option<WireFormat>,
+
+    /// Directory used to cache benchmark results. Benchmarks whose results are cached
+    /// for the build of the suite are not performed again, the cached results are reused instead.
+    #[clap(long)]
+    cache: Option<PathBuf>,
+
+    /// Directory to write a badge (shields.io endpoint JSON) for every benchmark stored in the
+    /// database to, showing its latest result and the change relative to the run before.
+    #[clap(long, requires = "db")]
+    badges: Option<PathBuf>,
+
+    /// Reset all modules of the suite before processing each file,
+    /// so consecutive files start from a clean hardware state.
+    #[clap(long)]
+    reset_before_run: bool,
+
+    /// Measure the overhead of every phase using the micro benchmarks before processing each file,
+    /// and additionally write all results adjusted for it.
+    #[clap(long)]
+    calibrate: bool,
+
+    /// Send every benchmark the given number of times instead of once,
+    /// the results of all repetitions are aggregated into a single response.
+    #[clap(long)]
+    repeat: Option<u32>,
+
+    /// Send every benchmark the given number of times before its repetitions,
+    /// discarding the results.
+    #[clap(long, requires = "repeat", default_value = "0")]
+    warmup: u32,
+
+    /// Continue every file from its .checkpoint, which records the progress of a run that died,
+    /// instead of starting from the first line. The answered messages are not sent again.
+    #[clap(long)]
+    resume: bool,
+
+    /// Fail with exit code 10 on warnings as well: skipped benchmarks, retried repetitions,
+    /// windows that started late, responses of a newer schema and warnings or errors logged by the
+    /// suite. Errors, invalid messages, failed verifications and a lost connection always fail.
+    #[clap(long)]
+    strict: bool,
+
+    /// Give the suite the given time to respond to every message without a `# timeout`, e.g.
+    /// `30s`. A message it does not respond to in time is recorded as hanging, exit code 11, and
+    /// the run continues with the next message once the suite is ready again.
+    #[clap(long, parse(try_from_str = parse_duration))]
+    watchdog: Option<Duration>,
+
+    /// Command resetting the board when a message hangs, e.g. a command toggling its reset line.
+    #[clap(long, requires = "watchdog")]
+    reset_hook: Option<ResetHook>,
+
+    /// Plot the results of every file as SVG files into a .plots directory: a histogram of every
+    /// phase and, for phases measured per block, the cycles of every block.
+    #[clap(long)]
+    plot: bool,
+
+    /// Write the results of every file as LaTeX table to a .tex file, with one row per phase.
+    #[clap(long)]
+    latex: bool,
+
+    /// Statistics shown as columns of the LaTeX tables, out of min, max, mean, median, stddev, p95
+    /// and p99. Summaries only provide min, mean and max.
+    #[clap(
+        long,
+        requires = "latex",
+        use_value_delimiter = true,
+        default_value = "mean,min,max"
+    )]
+    latex_columns: Vec<LatexColumn>,
+
+    /// Unit of the values in the LaTeX tables: cycles, ns, us or ms. Times are computed from the
+    /// clock frequency reported by the suite.
+    #[clap(long, requires = "latex", default_value = "cycles")]
+    latex_unit: LatexUnit,
+
+    /// Only write the results of the given benchmarks to the LaTeX tables, named like in the
+    /// results, e.g. AES or HMAC.
+    #[clap(long, requires = "latex")]
+    latex_only: Vec<String>,
+
+    /// Frequency of the clock driving the cycle counter of the suite in Hz, used instead of the
+    /// frequency reported by the suite to annotate the results with time and throughput.
+    #[clap(long)]
+    clock_hz: Option<u64>,
+
+    /// Store the results of every file in the given SQLite database, created if it does not exist,
+    /// together with the time, the platform and build of the suite, the SHA256 hash of the ELF if
+    /// provided and the arguments of the CLI. Requires the CLI to be built with the `database`
+    /// feature.
+    #[clap(long)]
+    db: Option<PathBuf>,
+
+    /// Write the cycles of every result of a file as OpenMetrics to a .prom file,
+    /// labeled with the benchmark, dataset, phase and platform.
+    #[clap(long)]
+    openmetrics: bool,
+
+    /// Push the cycles of every result of a file as metrics to the given Prometheus Pushgateway,
+    /// e.g. `http://pushgateway:9091`, replacing the metrics pushed for the file before.
+    #[clap(long)]
+    pushgateway: Option<Pushgateway>,
+
+    /// Write a session manifest to the given path, containing the SHA256 hashes of the suite ELF,
+    /// the FPGA bitstream, all benchmark files and their results.
+    #[clap(long, requires = "suite_binary")]
+    manifest: Option<PathBuf>,
+
+    /// The ELF of the suite that is measured, recorded in the session manifest and the database,
+    /// and launched with `--launch`.
+    #[clap(long)]
+    elf: Option<PathBuf>,
+
+    /// Benchmark the given ELF of the suite, then wait until it changes, e.g. as it was rebuilt,
+    /// and benchmark it again, until interrupted. The ELF is launched with `--launch`, otherwise
+    /// it is flashed onto the board with the flasher. Used instead of `--elf`.
+    #[clap(long, conflicts_with_all = &["flash", "check"])]
+    watch: Option<PathBuf>,
+
+    /// The FPGA bitstream the suite is running on, recorded in the session manifest.
+    #[clap(long, requires = "manifest")]
+    bitstream: Option<PathBuf>,
+
+    /// Show a live dashboard while the messages of a file are sent to the suite, with the benchmark
+    /// currently performed, the progress, the last results and the health of the serial link.
+    /// Requires the CLI to be built with the `dashboard` feature.
+    #[clap(long)]
+    tui: bool,
+
+    /// Seed of all pseudo random choices, like the key of AES sweeps on the suite or the benchmarks
+    /// chosen by the smoke test, so a campaign can be reproduced. The seed is recorded in the results.
+    #[clap(long)]
+    seed: Option<u64>,
+
+    /// Reset the modules and initialize the heap of the suite again before every benchmark, so
+    /// every measurement starts from the same state. Takes longer, as the suite forgets the cycles
+    /// of earlier benchmarks and timeouts are no longer derived from them. Recorded in the results.
+    #[clap(long)]
+    isolate: bool,
+
+    /// Record every frame sent to and received from the suite in the given file, with the time of
+    /// the host, so the communication can be examined and replayed afterwards.
+    #[clap(long)]
+    transcript: Option<PathBuf>,
+
+    /// Format of the result files: `text` writes human readable .result files,
+    /// `json` writes the responses of the suite as JSON to .result.json files,
+    /// `csv` additionally writes the results of every benchmark as CSV file into a .csv directory.
+    #[clap(long, default_value = "text")]
+    format: OutputFormat,
+
+    #[clap(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Clone)]
+enum Command {
+    /// Performs a randomly chosen small benchmark for every module available on the suite,
+    /// to verify that the suite is working.
+    Smoke,
+    /// Lists the datasets available on the suite, with the ids used to reference them
+    /// in benchmark requests.
+    Datasets,
+    /// Requests canned results of every kind from the suite, to verify that the CLI decodes and
+    /// processes them correctly. Works on every platform, as no benchmark is performed.
+    Conformance,
+    /// Flashes the image onto the board and waits until the suite started, without benchmarking.
+    Flash {
+        /// The image of the suite
+        image: PathBuf,
+    },
+    /// Prints the JSON Schema of the .result.json files written for raw files, or for plans,
+    /// generated from the types of the results. Does not connect to the suite.
+    #[cfg(feature = "schema")]
+    Schema {
+        /// Describe the results of plans instead of raw files
+        #[clap(long)]
+        plan: bool,
+    },
 }
 
 fn main() {
-    let args = Args::parse();
+    let mut args = Args::parse();
+    // The watched build is the ELF of the session, it is flashed unless it is launched
+    if let Some(binary) = &args.watch {
+        args.elf = Some(binary.clone());
+        if args.launch.is_none() {
+            args.flash = Some(binary.clone());
+        }
+    }
+    let boards = args.tty.len() + args.connect.len();
+    if boards > 1 && (args.tui || args.flash.is_some() || args.command.is_some()) {
+        Args::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "The dashboard, flashing and commands require a single --tty",
+            )
+            .exit();
+    }
+
+    if args.files.iter().any(|file| file == Path::new(STDIN_FILE))
+        && (args.files.len() > 1
+            || !args.raw
+            || boards > 1
+            || args.tui
+            || args.manifest.is_some()
+            || args.watch.is_some())
+    {
+        Args::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "Reading messages from stdin requires --raw and a single --tty, \
+                 it can not be combined with other files, the dashboard, a manifest or --watch",
+            )
+            .exit();
+    }
+
+    if cfg!(not(feature = "database")) && args.db.is_some() {
+        Args::command()
+            .error(
+                ErrorKind::InvalidValue,
+                "--db requires the CLI to be built with the database feature, \
+                 e.g. `cargo run --features database`",
+            )
+            .exit();
+    }
+    if cfg!(not(feature = "dashboard")) && args.tui {
+        Args::command()
+            .error(
+                ErrorKind::InvalidValue,
+                "--tui requires the CLI to be built with the dashboard feature, \
+                 e.g. `cargo run --features dashboard`",
+            )
+            .exit();
+    }
+
+    // Checking files does not require the suite, so no port is opened
+    if args.check {
+        std::process::exit(check(&args) as i32);
+    }
+    #[cfg(feature = "schema")]
+    if let Some(Command::Schema { plan }) = args.command {
+        println!("{}", cli::result_schema(plan));
+        return;
+    }
+
+    match args.watch.clone() {
+        Some(binary) => watch(args, &binary),
+        None => std::process::exit(launch_and_run(args) as i32),
+    }
+}
+
+/// Benchmarks the watched build of the suite, and again whenever it changed, until the CLI is
+/// interrupted
+///
+/// # Arguments
+///
+/// * `args` - the arguments of the CLI
+/// * `binary` - the watched build
+fn watch(args: Args, binary: &Path) -> ! {
+    let mut stamp = Stamp::of(binary);
+    loop {
+        let exit_code = launch_and_run(args.clone());
+        eprintln!(
+            "Finished with exit code {}, waiting for {} to change",
+            exit_code as i32,
+            binary.display()
+        );
+        stamp = wait_for_change(binary, stamp);
+        eprintln!("{} changed, benchmarking it again", binary.display());
+    }
+}
+
+/// Launches the suite if requested and connects to it to perform the command or process the
+/// files passed to the CLI, returns the exit code
+///
+/// # Arguments
+///
+/// * `args` - the arguments of the CLI
+fn launch_and_run(args: Args) -> ExitCode {
+    let emulator = match args.launch {
+        Some(launcher) => {
+            let elf = args.elf.as_deref().expect("Launching requires an ELF");
+            let timeout = args.launch_timeout.map(Duration::from_secs);
+            match Emulator::launch(launcher, elf, timeout) {
+                Ok(emulator) => Some(emulator),
+                Err(err) => {
+                    eprintln!("Failed to launch the suite: {err}");
+                    return ExitCode::ConnectionFailure;
+                }
+            }
+        }
+        None => None,
+    };
+    let endpoints = match &emulator {
+        Some(emulator) => vec![Endpoint::from(emulator.tty().clone())],
+        None if !args.tty.is_empty() || !args.connect.is_empty() => args
+            .tty
+            .iter()
+            .cloned()
+            .map(Endpoint::from)
+            .chain(args.connect.iter().cloned())
+            .collect(),
+        None => match detect_port(args.wire_format) {
+            Ok(endpoint) => vec![endpoint],
+            Err(err) => {
+                eprintln!("Failed to detect the suite: {err}");
+                return ExitCode::ConnectionFailure;
+            }
+        },
+    };
+
+    let exit_code = run(args, endpoints);
+    // The emulator is stopped before the CLI exits, as exiting does not run destructors
+    drop(emulator);
+    exit_code
+}
+
+/// Checks the files passed to the CLI without connecting to the suite, returns the exit code
+///
+/// # Arguments
+///
+/// * `args` - the arguments of the CLI
+fn check(args: &Args) -> ExitCode {
+    let mut exit_code = ExitCode::Success;
+    for file in &args.files {
+        match cli::check_file(file, args.raw) {
+            Ok(count) => println!("{}: {count} messages are valid", file.display()),
+            Err(err) => {
+                eprintln!("{}: {err}", file.display());
+                exit_code = exit_code.or(err.exit_code());
+            }
+        }
+    }
+
+    exit_code
+}
+
+/// Performs the command or processes the files passed to the CLI, returns the exit code
+///
+/// # Arguments
+///
+/// * `args` - the arguments of the CLI
+/// * `endpoints` - the ttys or sockets of the suite, one per board
+fn run(args: Args, endpoints: Vec<Endpoint>) -> ExitCode {
+    let transcript = match args
+        .transcript
+        .as_deref()
+        .map(Transcript::create)
+        .transpose()
+    {
+        Ok(transcript) => transcript,
+        Err(err) => {
+            eprintln!("Failed to create transcript: {err}");
+            return ExitCode::OutputFailure;
+        }
+    };
+    // Boards are only identified if there are multiple, so the results of one board are unchanged
+    let multiple = endpoints.len() > 1;
+    let boards: Vec<ConnectionOptions> = endpoints
+        .into_iter()
+        .map(|endpoint| {
+            let board = if multiple {
+                Some(endpoint.to_string())
+            } else {
+                None
+            };
+            ConnectionOptions {
+                transcript: match (&transcript, &board) {
+                    (Some(transcript), Some(board)) => Some(transcript.of_board(board)),
+                    _ => transcript.clone(),
+                },
+                endpoint,
+                handshake: if args.resync {
+                    Handshake::Resync
+                } else {
+                    Handshake::Fresh
+                },
+                format: args.wire_format,
+                board,
+            }
+        })
+        .collect();
+    // Flashing and commands are limited to a single board
+    let connection = &boards[0];
 
-    for file in args.files {
-        if args.raw {
-            cli::benchmark_raw_file(&args.tty, file);
+    let image = match &args.command {
+        Some(Command::Flash { image }) => Some(image),
+        _ => args.flash.as_ref(),
+    };
+    if let Some(image) = image {
+        if let Err(err) = cli::flash::flash(connection, &args.flasher, image) {
+            eprintln!("Failed to flash the suite: {err}");
+            return ExitCode::FlashFailure;
+        }
+    }
+
+    match args.command {
+        Some(Command::Flash { .. }) => return ExitCode::Success,
+        Some(Command::Smoke) => return cli::smoke_test(connection, args.seed),
+        Some(Command::Datasets) => return cli::list_datasets(connection),
+        Some(Command::Conformance) => return cli::conformance_test(connection),
+        #[cfg(feature = "schema")]
+        Some(Command::Schema { .. }) => unreachable!("The schema is printed before connecting"),
+        None => {}
+    }
+
+    let arguments: Vec<String> = std::env::args_os()
+        .map(|arg| arg.to_string_lossy().into_owned())
+        .collect();
+    let options = RunOptions {
+        filter: BenchmarkFilter {
+            only: args.only,
+            skip: args.skip,
+        },
+        cache: args.cache,
+        badges: args.badges,
+        reset: args.reset_before_run,
+        format: args.format,
+        calibrate: args.calibrate,
+        repeat: args.repeat.map(|count| Repeat {
+            count,
+            warmup: args.warmup,
+        }),
+        plot: args.plot,
+        latex: if args.latex {
+            Some(LatexOptions {
+                columns: args.latex_columns,
+                unit: args.latex_unit,
+                benchmarks: args.latex_only,
+            })
         } else {
-            cli::benchmark_file(&args.tty, file);
+            None
+        },
+        seed: args.seed,
+        isolation: args.isolate,
+        dashboard: args.tui,
+        clock_frequency: args.clock_hz,
+        #[cfg(feature = "database")]
+        database: args.db.map(|path| DatabaseOptions {
+            path,
+            arguments: arguments.clone(),
+            elf: args.elf.clone(),
+        }),
+        openmetrics: args.openmetrics,
+        pushgateway: args.pushgateway,
+        arguments,
+        resume: args.resume,
+        strict: args.strict,
+        watchdog: args.watchdog.map(|deadline| Watchdog {
+            deadline,
+            reset: args.reset_hook,
+        }),
+        stream: false,
+    };
+
+    // The binaries are hashed before the session, so they can not change unnoticed while it runs
+    let session = match args.manifest {
+        Some(path) => {
+            let elf = args.elf.as_deref().expect("Manifest requires an ELF");
+            match SessionManifest::begin(elf, args.bitstream.as_deref()) {
+                Ok(session) => Some((path, session)),
+                Err(err) => {
+                    eprintln!("Failed to start session manifest: {err}");
+                    return ExitCode::InvalidInput;
+                }
+            }
+        }
+        None => None,
+    };
+
+    let exit_code = if args.files.iter().any(|file| file == Path::new(STDIN_FILE)) {
+        cli::benchmark_stdin(connection, &options)
+    } else if multiple {
+        cli::benchmark_boards(boards, args.files.clone(), args.raw, options)
+    } else {
+        cli::benchmark_files(connection, args.files.iter().cloned(), args.raw, &options)
+    };
+
+    if let Some((path, session)) = session {
+        if let Err(err) = session.finish(&path, &args.files) {
+            eprintln!("Failed to write session manifest: {err}");
+            return exit_code.or(ExitCode::OutputFailure);
         }
     }
+
+    exit_code
 }
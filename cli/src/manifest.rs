@@ -0,0 +1,114 @@
+//! Session manifests tying benchmark results to the binaries that were measured.
+//!
+//! A manifest records the SHA256 hashes of the suite ELF, optionally of the FPGA bitstream,
+//! and of every benchmark file together with the .result file produced for it. Publishing the
+//! manifest with the results allows to verify that they were produced by exactly these binaries.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// A file referenced by the manifest
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Artifact {
+    /// Path of the file, as it was passed to the CLI
+    pub path: PathBuf,
+    /// SHA256 hash of the content of the file, hex encoded
+    pub sha256: String,
+}
+
+impl Artifact {
+    /// Hashes the file at the provided path
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - the path of the file
+    pub fn hash(path: &Path) -> Result<Artifact, String> {
+        let content =
+            fs::read(path).map_err(|err| format!("Failed to read {}: {err}", path.display()))?;
+
+        Ok(Artifact::of(path, &content))
+    }
+
+    /// Hashes the content of the file at the provided path, which was already read
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - the path of the file
+    /// * `content` - the content of the file
+    pub fn of(path: &Path, content: &[u8]) -> Artifact {
+        Artifact {
+            path: path.to_path_buf(),
+            sha256: Sha256::digest(content)
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect(),
+        }
+    }
+}
+
+/// A benchmark file and the results produced for it
+#[derive(Serialize, Deserialize)]
+pub struct SessionRun {
+    pub input: Artifact,
+    /// None if no results were written for the file
+    pub result: Option<Artifact>,
+}
+
+/// Describes a session of the CLI, the binaries it measured and the results it produced
+#[derive(Serialize, Deserialize)]
+pub struct SessionManifest {
+    /// Time the session started, in seconds since the unix epoch
+    pub started: u64,
+    /// The ELF of the suite that was measured
+    pub elf: Artifact,
+    /// The FPGA bitstream the suite was running on, if provided
+    pub bitstream: Option<Artifact>,
+    /// The benchmark files processed in this session, in order
+    pub runs: Vec<SessionRun>,
+}
+
+impl SessionManifest {
+    /// Starts a session, hashing the binaries before any benchmark is performed
+    ///
+    /// # Arguments
+    ///
+    /// * `elf` - the ELF of the suite that is measured
+    /// * `bitstream` - the FPGA bitstream the suite is running on, if any
+    pub fn begin(elf: &Path, bitstream: Option<&Path>) -> Result<SessionManifest, String> {
+        Ok(SessionManifest {
+            started: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|time| time.as_secs())
+                .unwrap_or_default(),
+            elf: Artifact::hash(elf)?,
+            bitstream: bitstream.map(Artifact::hash).transpose()?,
+            runs: Vec::new(),
+        })
+    }
+
+    /// Finishes the session after the provided benchmark files were processed,
+    /// hashing them and their results, and writes the manifest
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - the path the manifest is written to
+    /// * `files` - the benchmark files processed in the session
+    pub fn finish(mut self, path: &Path, files: &[PathBuf]) -> Result<(), String> {
+        for file in files {
+            self.runs.push(SessionRun {
+                input: Artifact::hash(file)?,
+                result: Artifact::hash(&file.with_extension("result")).ok(),
+            });
+        }
+
+        let content = serde_json::to_string_pretty(&self).expect("Can not serialize struct");
+        fs::write(path, content).map_err(|err| format!("Failed to write {}: {err}", path.display()))
+    }
+}
@@ -0,0 +1,121 @@
+//! Metadata of the host that produced benchmark results.
+//!
+//! Every result file starts with the name of the host the CLI ran on, the time the file was
+//! processed, the version of the CLI, the SHA256 hash of the benchmark file and the arguments the CLI
+//! was invoked with, so results can still be attributed long after they were produced, e.g. when
+//! comparing results of different bitstreams. In .result files they are written as header lines of
+//! the form `-- host lab-pc --`, in .result.json files as `metadata` next to the responses.
+
+use std::{
+    env,
+    path::Path,
+    process::Command,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::Serialize;
+
+use crate::manifest::Artifact;
+
+/// Seconds of a day
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Describes where, when and how the results of a file were produced
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Metadata {
+    /// Name of the host the CLI ran on, `unknown` if it could not be determined
+    pub host: String,
+    /// Time the file was processed, in seconds since the unix epoch
+    pub started: u64,
+    /// Version of the CLI
+    pub cli_version: String,
+    /// The benchmark file and the hash of its content
+    pub input: Artifact,
+    /// The arguments the CLI was invoked with
+    pub arguments: Vec<String>,
+}
+
+impl Metadata {
+    /// Collects the metadata of a file that is about to be processed
+    ///
+    /// # Arguments
+    ///
+    /// * `input_file` - the benchmark file
+    /// * `content` - the content of the benchmark file
+    /// * `arguments` - the arguments the CLI was invoked with
+    pub fn collect(input_file: &Path, content: &str, arguments: &[String]) -> Metadata {
+        Metadata {
+            host: host_name(),
+            started: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|time| time.as_secs())
+                .unwrap_or_default(),
+            cli_version: String::from(env!("CARGO_PKG_VERSION")),
+            input: Artifact::of(input_file, content.as_bytes()),
+            arguments: arguments.to_vec(),
+        }
+    }
+
+    /// Returns the header lines of .result files
+    pub fn header(&self) -> String {
+        format!(
+            "-- host {} --\n-- started {} --\n-- cli {} --\n-- file {} sha256 {} --\n-- arguments {} --\n",
+            self.host,
+            utc_date(self.started),
+            self.cli_version,
+            self.input.path.display(),
+            self.input.sha256,
+            self.arguments.join(" ")
+        )
+    }
+}
+
+/// Returns the name of the host, as reported by the `hostname` command, which is available on
+/// Linux, macOS and Windows, or the environment. Returns `unknown` if neither knows the name.
+fn host_name() -> String {
+    let reported = Command::new("hostname")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string());
+
+    reported
+        .or_else(|| env::var("HOSTNAME").ok())
+        .or_else(|| env::var("COMPUTERNAME").ok())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| String::from("unknown"))
+}
+
+/// Formats a time as date and time in UTC, e.g. `2022-04-04 13:37:00 UTC`
+///
+/// # Arguments
+///
+/// * `secs` - the time in seconds since the unix epoch
+fn utc_date(secs: u64) -> String {
+    let (days, time) = (secs / SECONDS_PER_DAY, secs % SECONDS_PER_DAY);
+
+    // Converts days since the epoch into a date of the Gregorian calendar, check
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02} UTC",
+        time / 3600,
+        time / 60 % 60,
+        time % 60
+    )
+}
@@ -0,0 +1,235 @@
+//! Export of benchmark metrics for monitoring systems.
+//!
+//! The cycles of every phase of every result are exported as samples of the gauge
+//! `benchmark_cycles`, in the OpenMetrics text format, so the performance of the IPs can be
+//! tracked over time on existing dashboards. With `--openmetrics`, the metrics of a file are
+//! written to a .prom file next to it, e.g. to be collected by the textfile collector of the node
+//! exporter. With `--pushgateway`, they are pushed to a Prometheus Pushgateway, grouped by the
+//! file, so every file replaces the metrics of its previous run:
+//!
+//! ```text
+//! benchmark_cycles{file="aes.txt",line="3",label="",benchmark="AES",dataset="0",result="mean",phase="computation",platform="earlgrey",git_hash="1a2b3c",board=""} 4711
+//! ```
+//!
+//! Summaries are exported with their statistic as `result`, other results with the number of their
+//! repetition, starting at 0. Labels that do not apply, like the dataset of a benchmark without one,
+//! are empty. Lines that were expanded into multiple benchmarks are numbered like in CSV files.
+
+use std::{
+    fmt::Write as _,
+    io::{Read, Write},
+    net::{TcpStream, ToSocketAddrs},
+    path::Path,
+    str::FromStr,
+    time::Duration,
+};
+
+use benchmark_common::{IncomingMessage, OutgoingMessage};
+
+use crate::{
+    csv::{columns, lines},
+    raw::{dataset_of, RawEntry},
+};
+
+/// Name of the job the metrics are pushed as
+const JOB: &str = "cycle_benchmark";
+
+/// Port of the Pushgateway if the address does not contain one
+const DEFAULT_PORT: u16 = 9091;
+
+/// Time to wait for the Pushgateway to accept the connection and respond
+const PUSH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Pushgateway the metrics are pushed to, e.g. `http://pushgateway:9091`
+///
+/// Only plain HTTP is supported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pushgateway {
+    /// Host and port of the Pushgateway
+    address: String,
+}
+
+impl FromStr for Pushgateway {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let address = s
+            .strip_prefix("http://")
+            .ok_or_else(|| String::from("The Pushgateway must be an http:// URL"))?
+            .trim_end_matches('/');
+        if address.is_empty() || address.contains('/') {
+            return Err(format!(
+                "{s} is not the address of a Pushgateway, e.g. http://pushgateway:9091"
+            ));
+        }
+
+        Ok(Pushgateway {
+            address: if address.contains(':') {
+                address.to_string()
+            } else {
+                format!("{address}:{DEFAULT_PORT}")
+            },
+        })
+    }
+}
+
+impl Pushgateway {
+    /// Pushes the metrics of a file, replacing the metrics pushed for it before.
+    /// Returns a description of the failure if the Pushgateway did not accept them.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - the file the metrics belong to, identifying their group
+    /// * `metrics` - the metrics in the OpenMetrics text format
+    pub fn push(&self, file: &Path, metrics: &str) -> Result<(), String> {
+        let address = self
+            .address
+            .to_socket_addrs()
+            .ok()
+            .and_then(|mut addresses| addresses.next())
+            .ok_or_else(|| format!("Failed to resolve {}", self.address))?;
+        let mut stream = TcpStream::connect_timeout(&address, PUSH_TIMEOUT)
+            .map_err(|err| format!("Failed to connect to {}: {err}", self.address))?;
+        stream
+            .set_read_timeout(Some(PUSH_TIMEOUT))
+            .map_err(|err| err.to_string())?;
+
+        // Paths contain slashes, so the file is passed base64 encoded
+        let request = format!(
+            "PUT /metrics/job/{JOB}/file@base64/{} HTTP/1.1\r\n\
+             Host: {}\r\n\
+             Content-Type: text/plain; version=0.0.4\r\n\
+             Content-Length: {}\r\n\
+             Connection: close\r\n\r\n{metrics}",
+            base64_url(file.display().to_string().as_bytes()),
+            self.address,
+            metrics.len()
+        );
+        let mut response = String::new();
+        stream
+            .write_all(request.as_bytes())
+            .and_then(|()| stream.read_to_string(&mut response))
+            .map_err(|err| format!("Failed to push to {}: {err}", self.address))?;
+
+        let status = response.lines().next().unwrap_or_default();
+        match status.split_whitespace().nth(1) {
+            Some(code) if code.starts_with('2') => Ok(()),
+            _ => Err(format!(
+                "{} rejected the metrics: {}",
+                self.address,
+                response.trim()
+            )),
+        }
+    }
+}
+
+/// Returns the metrics of the results contained in the responses to the entries of a file, in the
+/// OpenMetrics text format. Responses that do not contain results are ignored.
+///
+/// # Arguments
+///
+/// * `file` - the processed file
+/// * `board` - the board the file was processed on, if benchmarking on multiple boards
+/// * `entries` - the entries of the file that were sent
+/// * `responses` - the responses, with the line number of the entry they belong to
+pub fn openmetrics(
+    file: &Path,
+    board: Option<&str>,
+    entries: &[RawEntry],
+    responses: &[(usize, IncomingMessage)],
+) -> String {
+    let mut metrics = String::from(
+        "# TYPE benchmark_cycles gauge\n\
+         # HELP benchmark_cycles Cycles spent in a phase of a benchmark.\n",
+    );
+    let file = file.display().to_string();
+
+    for ((line, response), numbered) in responses.iter().zip(lines(responses)) {
+        let envelope = match response {
+            IncomingMessage::BenchmarkResults(envelope) => envelope,
+            _ => continue,
+        };
+        // Suites predating echoing the request only report it in the entry
+        let info = envelope.benchmark.as_ref().or_else(|| {
+            entries
+                .iter()
+                .find(|entry| entry.line_num == *line)
+                .and_then(|entry| match &entry.msg {
+                    OutgoingMessage::Benchmark(info, _) => Some(info),
+                    _ => None,
+                })
+        });
+        let dataset = info
+            .and_then(dataset_of)
+            .map(|id| id.to_string())
+            .unwrap_or_default();
+
+        for (index, result) in envelope.results.iter().enumerate() {
+            let row = envelope
+                .aggregate
+                .statistic(index)
+                .map_or_else(|| index.to_string(), String::from);
+            for (phase, (_, cycles)) in columns(result).iter().zip(result.phases()) {
+                let labels = [
+                    ("file", file.as_str()),
+                    ("line", &numbered),
+                    ("label", envelope.label.as_deref().unwrap_or_default()),
+                    ("benchmark", result.name()),
+                    ("dataset", &dataset),
+                    ("result", &row),
+                    ("phase", phase),
+                    ("platform", &envelope.platform.name),
+                    (
+                        "git_hash",
+                        envelope.platform.git_hash.as_deref().unwrap_or_default(),
+                    ),
+                    ("board", board.unwrap_or_default()),
+                ];
+                let labels: Vec<String> = labels
+                    .iter()
+                    .map(|(name, value)| format!("{name}=\"{}\"", escape(value)))
+                    .collect();
+                writeln!(metrics, "benchmark_cycles{{{}}} {cycles}", labels.join(",")).unwrap();
+            }
+        }
+    }
+
+    metrics.push_str("# EOF\n");
+    metrics
+}
+
+/// Escapes a label value, as required by the OpenMetrics text format
+///
+/// # Arguments
+///
+/// * `value` - the value of the label
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Encodes bytes as URL safe base64, as accepted by the Pushgateway in label values of paths
+///
+/// # Arguments
+///
+/// * `bytes` - the bytes that are encoded
+fn base64_url(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+    let mut encoded = String::new();
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (i, byte)| {
+            group | u32::from(*byte) << (16 - 8 * i)
+        });
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(group >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
@@ -0,0 +1,170 @@
+//! Parsing of benchmark plans.
+//!
+//! A plan is a TOML file that describes the benchmarks that should be performed declaratively,
+//! grouped into sections. Every section lists benchmarks and dataset selections, which are all
+//! performed with the same config:
+//!
+//! ```toml
+//! [[section]]
+//! name = "aes"
+//! repetitions = 10
+//! warmup_iterations = 2
+//! aggregate = "Summary"
+//! benchmarks = ["ExampleAES256", { AESSweep = { key_lengths = [128], modes = ["ECB"], blocks = [1, 4] } }]
+//! datasets = [{ kind = "AES", ids = [0, 1] }]
+//! tags = ["aes"]
+//! timeout = "600s"
+//! expect = "BenchmarkResults"
+//! budget = { max_computation = 60000 }
+//! ```
+//!
+//! Besides `name`, `benchmarks` and `datasets`, a section accepts all fields of a `BenchmarkConfig`,
+//! only `repetitions` is required. The `label` of the config defaults to the name of the section.
+//! `tags`, `timeout`, `expect` and `budget` work like the annotations of raw benchmarking files.
+//!
+//! The benchmarks of a plan are numbered in order, starting at 1. The number is used as request id
+//! and takes the place of the line number in all output.
+
+use std::collections::BTreeMap;
+
+use benchmark_common::{BenchmarkConfig, BenchmarkInfo, DatasetKind, OutgoingMessage};
+use serde::Deserialize;
+
+use crate::{
+    budget::parse_budgets,
+    raw::{parse_duration, RawEntry},
+};
+
+/// A benchmark plan, consisting of sections that are performed in order
+#[derive(Debug, Deserialize)]
+pub struct Plan {
+    #[serde(default, rename = "section")]
+    pub sections: Vec<PlanSection>,
+}
+
+/// A section of a benchmark plan
+#[derive(Debug, Deserialize)]
+pub struct PlanSection {
+    /// Name of the section, used to group the results
+    pub name: String,
+    /// Benchmarks performed by this section
+    #[serde(default)]
+    pub benchmarks: Vec<BenchmarkInfo>,
+    /// Datasets the dataset benchmark of their kind is performed on, after the other benchmarks
+    #[serde(default)]
+    pub datasets: Vec<DatasetSelection>,
+    /// Tags assigned to all benchmarks of the section
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Timeout used while waiting for the results of every benchmark of the section
+    #[serde(default)]
+    pub timeout: Option<String>,
+    /// The response type the suite is expected to reply with to every benchmark of the section
+    #[serde(default)]
+    pub expect: Option<String>,
+    /// Cycle budgets every benchmark of the section is checked against, ex. `max_computation`
+    #[serde(default)]
+    pub budget: BTreeMap<String, u64>,
+    /// How often the benchmarks are performed and how their results are reported
+    #[serde(flatten)]
+    pub config: BenchmarkConfig,
+}
+
+/// Selects datasets of one kind
+#[derive(Debug, Deserialize)]
+pub struct DatasetSelection {
+    /// Kind of the datasets
+    pub kind: DatasetKind,
+    /// Ids of the datasets
+    pub ids: Vec<usize>,
+}
+
+impl DatasetSelection {
+    /// Returns the benchmarks performed on the selected datasets,
+    /// or an error if datasets of this kind can not be benchmarked on their own
+    fn benchmarks(&self) -> Result<Vec<BenchmarkInfo>, String> {
+        let benchmark = match self.kind {
+            DatasetKind::AES => BenchmarkInfo::AESDataSet,
+            DatasetKind::GCM => BenchmarkInfo::GCMDataSet,
+            DatasetKind::Hashing => BenchmarkInfo::HashingDataSet,
+            DatasetKind::RNG => BenchmarkInfo::RNGDataSet,
+            DatasetKind::ECDSA => BenchmarkInfo::ECDSA,
+            kind => return Err(format!("Datasets of kind {kind:?} can not be benchmarked")),
+        };
+
+        Ok(self.ids.iter().copied().map(benchmark).collect())
+    }
+}
+
+impl Plan {
+    /// Translates the plan into entries, as if they were read from a raw benchmarking file.
+    /// Returns the entries together with the index of the section each entry belongs to,
+    /// or a description of the first invalid section.
+    pub fn entries(&self) -> Result<Vec<(usize, RawEntry)>, String> {
+        let mut entries = Vec::new();
+
+        for (index, section) in self.sections.iter().enumerate() {
+            let invalid = |err: String| format!("Section '{}' is invalid: {err}", section.name);
+
+            let expect = section
+                .expect
+                .as_deref()
+                .map(str::parse)
+                .transpose()
+                .map_err(invalid)?;
+            let timeout = section
+                .timeout
+                .as_deref()
+                .map(parse_duration)
+                .transpose()
+                .map_err(invalid)?;
+            let budget: Vec<String> = section
+                .budget
+                .iter()
+                .map(|(phase, max)| format!("{phase} = {max}"))
+                .collect();
+            let budgets = parse_budgets(&budget.join(",")).map_err(invalid)?;
+
+            let mut benchmarks = section.benchmarks.clone();
+            for selection in &section.datasets {
+                benchmarks.extend(selection.benchmarks().map_err(invalid)?);
+            }
+
+            for info in benchmarks {
+                let number = entries.len() + 1;
+                let config = BenchmarkConfig {
+                    request_id: Some(number as u32),
+                    label: section
+                        .config
+                        .label
+                        .clone()
+                        .or_else(|| Some(section.name.clone())),
+                    ..section.config.clone()
+                };
+
+                entries.push((
+                    index,
+                    RawEntry {
+                        line_num: number,
+                        msg: OutgoingMessage::Benchmark(info, config),
+                        expect,
+                        timeout,
+                        tags: section.tags.clone(),
+                        budgets: budgets.clone(),
+                    },
+                ));
+            }
+        }
+
+        Ok(entries)
+    }
+}
+
+/// Parses the content of a plan
+///
+/// # Arguments
+///
+/// * `content` - the content of the plan
+pub fn parse_plan(content: &str) -> Result<Plan, String> {
+    toml::from_str(content).map_err(|err| err.to_string())
+}
@@ -0,0 +1,240 @@
+//! Plots of benchmark results as SVG files.
+//!
+//! Every configuration of a benchmark reported as samples, like every combination of an AES sweep,
+//! is plotted separately. For every phase, a histogram of its cycles over all results is written,
+//! named after the line number, the benchmark, the index of the first result of the configuration
+//! and the phase, e.g. `3_AES_0_computation.svg`. Lines are numbered like for CSV files. Phases are named like the columns of CSV files.
+//! Phases that occur multiple times in a result, like the squeezes of every block of an XOF, are
+//! additionally plotted over the blocks, with one line per result, e.g. `3_XOF_0_squeeze.svg`.
+
+use std::{collections::BTreeMap, fmt::Write, fs, io, path::Path};
+
+use benchmark_common::{Aggregate, IncomingMessage};
+
+use crate::{
+    csv::{columns, lines},
+    statistics::configurations,
+};
+
+/// Width of a plot in pixels
+const WIDTH: f64 = 640.0;
+
+/// Height of a plot in pixels
+const HEIGHT: f64 = 400.0;
+
+/// Space between the border of a plot and its plotting area, containing the axes and labels
+const MARGIN: f64 = 60.0;
+
+/// Maximum number of bins of a histogram
+const BINS: u64 = 20;
+
+/// Writes plots of every benchmark whose results are contained in the responses
+///
+/// Responses that do not contain results reported as samples are ignored.
+/// Fails if the directory could not be created or a plot could not be written.
+///
+/// # Arguments
+///
+/// * `dir` - the directory the plots are written to, created if it does not exist
+/// * `responses` - the responses, with the line number of the message they belong to
+pub fn write_plots(dir: &Path, responses: &[(usize, IncomingMessage)]) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+
+    for ((_, response), line) in responses.iter().zip(lines(responses)) {
+        let envelope = match response {
+            IncomingMessage::BenchmarkResults(envelope)
+                if envelope.aggregate == Aggregate::Samples =>
+            {
+                envelope
+            }
+            _ => continue,
+        };
+
+        for (key, indices) in configurations(envelope) {
+            let prefix = format!("{line}_{}_{}", key.name(), indices[0]);
+            let results: Vec<Vec<(&str, u64)>> = indices
+                .iter()
+                .map(|index| envelope.results[*index].phases())
+                .collect();
+
+            for (phase, name) in columns(&key).into_iter().enumerate() {
+                let values: Vec<u64> = results.iter().map(|phases| phases[phase].1).collect();
+                let svg = histogram(&format!("{} {name}", key.name()), &values);
+                fs::write(dir.join(format!("{prefix}_{name}.svg")), svg)?;
+            }
+
+            let mut occurrences: BTreeMap<&str, usize> = BTreeMap::new();
+            for (name, _) in &results[0] {
+                *occurrences.entry(name).or_default() += 1;
+            }
+            for (name, _) in occurrences.iter().filter(|(_, count)| **count > 1) {
+                let series: Vec<Vec<u64>> = results
+                    .iter()
+                    .map(|phases| {
+                        phases
+                            .iter()
+                            .filter(|(phase, _)| phase == name)
+                            .map(|(_, cycles)| *cycles)
+                            .collect()
+                    })
+                    .collect();
+                let svg = blocks(&format!("{} {name} per block", key.name()), &series);
+                fs::write(dir.join(format!("{prefix}_{name}.svg")), svg)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns a histogram of the cycles of a phase as SVG
+///
+/// The values are divided into at most `BINS` bins of equal width, starting at the minimum.
+///
+/// # Arguments
+///
+/// * `title` - the title of the plot
+/// * `values` - the cycles of the phase in every result
+fn histogram(title: &str, values: &[u64]) -> String {
+    let min = values.iter().copied().min().unwrap_or(0);
+    let max = values.iter().copied().max().unwrap_or(0);
+    let bins = (max - min + 1).min(BINS);
+    let bin_width = (max - min) / bins + 1;
+
+    let mut counts = vec![0u64; bins as usize];
+    for value in values {
+        counts[((value - min) / bin_width) as usize] += 1;
+    }
+    let highest = counts.iter().copied().max().unwrap_or(0).max(1);
+
+    let mut body = String::new();
+    let bar_width = (WIDTH - 2.0 * MARGIN) / bins as f64;
+    for (bin, count) in counts.iter().enumerate() {
+        let height = (HEIGHT - 2.0 * MARGIN) * *count as f64 / highest as f64;
+        writeln!(
+            body,
+            r#"<rect x="{:.1}" y="{:.1}" width="{bar_width:.1}" height="{height:.1}" fill="steelblue" stroke="white"/>"#,
+            MARGIN + bin as f64 * bar_width,
+            HEIGHT - MARGIN - height,
+        )
+        .unwrap();
+    }
+
+    plot(
+        title,
+        ("cycles", min, min + bins * bin_width),
+        ("results", 0, highest),
+        &body,
+    )
+}
+
+/// Returns a plot of the cycles of a phase over the blocks as SVG, with one line per result
+///
+/// # Arguments
+///
+/// * `title` - the title of the plot
+/// * `series` - the cycles of every block, for every result
+fn blocks(title: &str, series: &[Vec<u64>]) -> String {
+    let count = series.iter().map(Vec::len).max().unwrap_or(0);
+    let min = series.iter().flatten().copied().min().unwrap_or(0);
+    let max = series.iter().flatten().copied().max().unwrap_or(0);
+
+    let x =
+        |block: usize| MARGIN + (WIDTH - 2.0 * MARGIN) * block as f64 / (count.max(2) - 1) as f64;
+    let y = |cycles: u64| {
+        HEIGHT
+            - MARGIN
+            - (HEIGHT - 2.0 * MARGIN) * (cycles - min) as f64 / (max - min).max(1) as f64
+    };
+
+    let mut body = String::new();
+    for values in series {
+        let points: Vec<String> = values
+            .iter()
+            .enumerate()
+            .map(|(block, cycles)| format!("{:.1},{:.1}", x(block), y(*cycles)))
+            .collect();
+        writeln!(
+            body,
+            r#"<polyline points="{}" fill="none" stroke="steelblue" stroke-opacity="0.5"/>"#,
+            points.join(" ")
+        )
+        .unwrap();
+    }
+
+    plot(
+        title,
+        ("block", 0, count.saturating_sub(1) as u64),
+        ("cycles", min, max),
+        &body,
+    )
+}
+
+/// Returns an SVG containing the body, framed by a title and labeled axes
+///
+/// # Arguments
+///
+/// * `title` - the title of the plot
+/// * `x_axis` - the label, the lowest and the highest value of the horizontal axis
+/// * `y_axis` - the label, the lowest and the highest value of the vertical axis
+/// * `body` - the SVG elements plotted inside the axes
+fn plot(title: &str, x_axis: (&str, u64, u64), y_axis: (&str, u64, u64), body: &str) -> String {
+    let (left, right, top, bottom) = (MARGIN, WIDTH - MARGIN, MARGIN, HEIGHT - MARGIN);
+    let mut svg = String::new();
+
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{HEIGHT}" font-family="sans-serif" font-size="12">"#
+    )
+    .unwrap();
+    writeln!(svg, r#"<rect width="100%" height="100%" fill="white"/>"#).unwrap();
+    writeln!(
+        svg,
+        r#"<text x="{}" y="{}" text-anchor="middle" font-size="16">{title}</text>"#,
+        WIDTH / 2.0,
+        MARGIN / 2.0
+    )
+    .unwrap();
+    svg.push_str(body);
+    writeln!(
+        svg,
+        r#"<path d="M{left},{top} V{bottom} H{right}" fill="none" stroke="black"/>"#
+    )
+    .unwrap();
+
+    let (x_label, x_min, x_max) = x_axis;
+    let (y_label, y_min, y_max) = y_axis;
+    for (x, value) in [(left, x_min), (right, x_max)] {
+        writeln!(
+            svg,
+            r#"<text x="{x}" y="{}" text-anchor="middle">{value}</text>"#,
+            bottom + 16.0
+        )
+        .unwrap();
+    }
+    for (y, value) in [(bottom, y_min), (top, y_max)] {
+        writeln!(
+            svg,
+            r#"<text x="{}" y="{y}" text-anchor="end">{value}</text>"#,
+            left - 6.0
+        )
+        .unwrap();
+    }
+    writeln!(
+        svg,
+        r#"<text x="{}" y="{}" text-anchor="middle">{x_label}</text>"#,
+        WIDTH / 2.0,
+        HEIGHT - MARGIN / 3.0
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        r#"<text x="{0}" y="{1}" text-anchor="middle" transform="rotate(-90 {0} {1})">{y_label}</text>"#,
+        MARGIN / 4.0,
+        HEIGHT / 2.0
+    )
+    .unwrap();
+    svg.push_str("</svg>\n");
+
+    svg
+}
@@ -0,0 +1,669 @@
+//! Parsing of raw benchmarking files.
+//!
+//! Every non-empty line that does not start with `#` is parsed as a message that is sent directly to the suite.
+//! Comments of the form `# key: value` with a known key are annotations, that apply to the next message in the file.
+//!
+//! Supported annotations:
+//! * `# expect: <Response>` - the suite is expected to reply to the next message with the given response type
+//!   (`Status`, `Capabilities`, `Platform`, `Datasets`, `Error`, `BenchmarkResults`, `Pong`, `Config`, `Skipped`,
+//!   `DutyCycleDone` or `Unsupported`). Messages annotated with `# expect: Error` may also be invalid,
+//!   in which case they are forwarded to the suite so its error handling can be probed.
+//! * `# timeout: <Duration>` - overrides the serial timeout while waiting for the response to the next message,
+//!   the duration is given in milliseconds, seconds or minutes (ex. `500ms`, `600s`, `10m`).
+//!   For duty-cycled benchmarks it applies to every window instead.
+//! * `# tags: <Tag>, <Tag>, ...` - assigns tags to the next message, which can be used to filter the messages that are sent.
+//! * `# budget: max_<phase> = <cycles>, ...` - limits the number of cycles the phases of the
+//!   benchmark requested by the next message may take, check the budget module for details.
+//! * `# label: <Label>` - assigns a label to the benchmark requested by the next message,
+//!   which the suite attaches to its results.
+//!
+//! Lines starting with `@` are directives, that expand the next message into multiple messages,
+//! so sweeps do not have to be written out line by line:
+//! * `@repeat <count>` - the next message is sent `count` times.
+//! * `@foreach <name> in <values>` - the next message is sent once for every value, with every
+//!   `{name}` in the message replaced by the value. The values are a range (ex. `0..8` or `0..=7`)
+//!   or a comma separated list (ex. `16, 32, 64` or `"ECB", "CBC"`).
+//!
+//! Multiple directives preceding the same message are nested, the first one being the outermost,
+//! e.g. `@foreach id in 0..4` followed by `@repeat 10` sends every dataset ten times before the
+//! next one. The annotations of the message apply to every copy, `{name}` is also replaced in its
+//! label. All copies keep the line number of the message.
+//!
+//! Benchmark requests without a request id are assigned their line number as id, so the results
+//! can be matched to the line that requested them.
+
+use std::{fmt::Display, str::FromStr, time::Duration};
+
+use benchmark_common::{parse_raw, BenchmarkInfo, IncomingMessage, OutgoingMessage};
+
+use crate::budget::{parse_budgets, Budget};
+
+/// Maximum number of messages the directives may expand a single message into,
+/// so a mistyped count is reported instead of exhausting the memory
+const MAX_EXPANSION: usize = 100_000;
+
+/// Type of a response the suite may reply with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseKind {
+    Status,
+    Capabilities,
+    Platform,
+    Datasets,
+    Error,
+    BenchmarkResults,
+    Pong,
+    Config,
+    Skipped,
+    /// Response to a duty-cycled benchmark
+    DutyCycleDone,
+    /// A response of a newer schema, that is not understood by the CLI
+    Unsupported,
+}
+
+impl ResponseKind {
+    /// Returns the type of the provided message, or None if the message is not a valid response
+    ///
+    /// # Arguments
+    ///
+    /// * `msg` - the message received from the suite
+    pub fn of(msg: &IncomingMessage) -> Option<ResponseKind> {
+        match msg {
+            IncomingMessage::Status(_) => Some(ResponseKind::Status),
+            IncomingMessage::Capabilities(_) => Some(ResponseKind::Capabilities),
+            IncomingMessage::Platform(_) => Some(ResponseKind::Platform),
+            IncomingMessage::Datasets(_) => Some(ResponseKind::Datasets),
+            IncomingMessage::Error(_) => Some(ResponseKind::Error),
+            IncomingMessage::BenchmarkResults(_) | IncomingMessage::ResultChunk { .. } => {
+                Some(ResponseKind::BenchmarkResults)
+            }
+            IncomingMessage::Pong(_) => Some(ResponseKind::Pong),
+            IncomingMessage::Config(_) => Some(ResponseKind::Config),
+            IncomingMessage::Skipped { .. } => Some(ResponseKind::Skipped),
+            IncomingMessage::DutyCycleDone { .. } => Some(ResponseKind::DutyCycleDone),
+            IncomingMessage::Unsupported(_) => Some(ResponseKind::Unsupported),
+            IncomingMessage::Accepted { .. }
+            | IncomingMessage::Log { .. }
+            | IncomingMessage::Window { .. }
+            | IncomingMessage::Invalid(_) => None,
+        }
+    }
+}
+
+impl FromStr for ResponseKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Status" => Ok(ResponseKind::Status),
+            "Capabilities" => Ok(ResponseKind::Capabilities),
+            "Platform" => Ok(ResponseKind::Platform),
+            "Datasets" => Ok(ResponseKind::Datasets),
+            "Error" => Ok(ResponseKind::Error),
+            "BenchmarkResults" => Ok(ResponseKind::BenchmarkResults),
+            "Pong" => Ok(ResponseKind::Pong),
+            "Config" => Ok(ResponseKind::Config),
+            "Skipped" => Ok(ResponseKind::Skipped),
+            "DutyCycleDone" => Ok(ResponseKind::DutyCycleDone),
+            "Unsupported" => Ok(ResponseKind::Unsupported),
+            _ => Err(format!("Unknown response type '{s}'")),
+        }
+    }
+}
+
+impl Display for ResponseKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+/// A single message of a raw benchmarking file, together with its annotations
+pub struct RawEntry {
+    /// Line number inside the file, starting at 1
+    pub line_num: usize,
+    /// The message that should be sent to the suite
+    pub msg: OutgoingMessage,
+    /// The response type the suite is expected to reply with, if annotated
+    pub expect: Option<ResponseKind>,
+    /// The timeout used while waiting for the response, if annotated
+    pub timeout: Option<Duration>,
+    /// Tags assigned to the message
+    pub tags: Vec<String>,
+    /// Cycle budgets the results of the requested benchmark are checked against
+    pub budgets: Vec<Budget>,
+}
+
+impl RawEntry {
+    /// Checks the type of the response the suite replied with against the annotations of this entry,
+    /// returns a description of the anomaly if the response was not expected
+    ///
+    /// # Arguments
+    ///
+    /// * `response` - the type of the response to this entry
+    pub fn check_response(&self, response: ResponseKind) -> Option<String> {
+        match self.expect {
+            Some(expected) if expected != response => Some(format!(
+                "Line {}: expected {expected}, received {response}",
+                self.line_num
+            )),
+            None if response == ResponseKind::Error => {
+                Some(format!("Line {}: unexpected {response}", self.line_num))
+            }
+            _ => None,
+        }
+    }
+
+    /// Checks that results or skips received in response to this entry carry the id of its request,
+    /// returns a description of the anomaly if they belong to a different request
+    ///
+    /// # Arguments
+    ///
+    /// * `msg` - the response to this entry
+    pub fn check_request_id(&self, msg: &IncomingMessage) -> Option<String> {
+        let expected = match &self.msg {
+            OutgoingMessage::Benchmark(_, config) => config.request_id,
+            _ => None,
+        };
+
+        let received = match msg {
+            IncomingMessage::BenchmarkResults(envelope) => envelope.request_id,
+            IncomingMessage::Skipped { request_id, .. }
+            | IncomingMessage::DutyCycleDone { request_id, .. } => *request_id,
+            _ => None,
+        };
+
+        match received {
+            Some(id) if Some(id) != expected => Some(format!(
+                "Line {}: received a response to request {id}",
+                self.line_num
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// Diagnostic of an invalid line of a raw benchmarking file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineError {
+    /// Number of the line, starting at 1
+    pub line_num: usize,
+    /// Why the line is invalid
+    pub reason: String,
+}
+
+impl Display for LineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line_num, self.reason)
+    }
+}
+
+/// Parses the content of a raw benchmarking file into its entries
+///
+/// Parsing continues after an invalid line, so every invalid line of the file is reported at once.
+/// Returns a diagnostic of every invalid message or annotation if the file contains any.
+///
+/// # Arguments
+///
+/// * `content` - the content of the raw benchmarking file
+pub fn parse_raw_file(content: &str) -> Result<Vec<RawEntry>, Vec<LineError>> {
+    let (entries, errors) = parse_entries(content);
+
+    if errors.is_empty() {
+        Ok(entries)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Checks the content of a raw benchmarking file without sending it, like `parse_raw_file`, but
+/// additionally reports benchmarks requesting datasets the suite does not provide, check
+/// `check_datasets`. The diagnostics are ordered by line.
+///
+/// # Arguments
+///
+/// * `content` - the content of the raw benchmarking file
+pub fn check_raw_file(content: &str) -> Result<Vec<RawEntry>, Vec<LineError>> {
+    let (entries, mut errors) = parse_entries(content);
+    errors.extend(check_datasets(&entries));
+    errors.sort_by_key(|error| error.line_num);
+
+    if errors.is_empty() {
+        Ok(entries)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Returns a diagnostic of every benchmark requesting a dataset the suite does not provide.
+/// Entries expecting an Error are left out, as they may probe how the suite handles unknown datasets
+///
+/// # Arguments
+///
+/// * `entries` - the entries of a raw benchmarking file or a plan
+pub fn check_datasets(entries: &[RawEntry]) -> Vec<LineError> {
+    let mut errors = Vec::new();
+
+    for entry in entries {
+        let info = match &entry.msg {
+            OutgoingMessage::Benchmark(info, _) if entry.expect != Some(ResponseKind::Error) => {
+                info
+            }
+            _ => continue,
+        };
+
+        for (kind, id) in info.datasets() {
+            if id >= kind.count() {
+                errors.push(LineError {
+                    line_num: entry.line_num,
+                    reason: format!(
+                        "unknown {kind:?} dataset {id}, the suite provides {} of this kind",
+                        kind.count()
+                    ),
+                });
+            }
+        }
+    }
+
+    errors
+}
+
+/// Returns the id of the dataset a benchmark is performed on, if it uses one
+///
+/// # Arguments
+///
+/// * `info` - the requested benchmark
+pub fn dataset_of(info: &BenchmarkInfo) -> Option<usize> {
+    match info {
+        BenchmarkInfo::AESDataSet(id)
+        | BenchmarkInfo::GCMDataSet(id)
+        | BenchmarkInfo::ChaCha20Poly1305(id)
+        | BenchmarkInfo::HashingDataSet(id)
+        | BenchmarkInfo::RNGDataSet(id)
+        | BenchmarkInfo::ECDSA(id) => Some(*id),
+        BenchmarkInfo::Hash { dataset, .. }
+        | BenchmarkInfo::KMACMasking { dataset }
+        | BenchmarkInfo::SHA256Streaming { dataset, .. }
+        | BenchmarkInfo::SHA3 { dataset, .. }
+        | BenchmarkInfo::RNGReseed { dataset, .. }
+        | BenchmarkInfo::RNGGenerateLengths { dataset, .. } => Some(*dataset),
+        BenchmarkInfo::Calibrated(target) => dataset_of(target),
+        BenchmarkInfo::ExampleSHA256
+        | BenchmarkInfo::ExampleAES256
+        | BenchmarkInfo::ExampleRNG
+        | BenchmarkInfo::ExampleECDSA
+        | BenchmarkInfo::AESTotal
+        | BenchmarkInfo::AESSweep { .. }
+        | BenchmarkInfo::AESContextSwitch { .. }
+        | BenchmarkInfo::AESThroughput { .. }
+        | BenchmarkInfo::EntropyLatency
+        | BenchmarkInfo::OTBN(_)
+        | BenchmarkInfo::Teardown(_)
+        | BenchmarkInfo::PollingInterval { .. }
+        | BenchmarkInfo::MicroBenchmarks => None,
+    }
+}
+
+/// A directive expanding the next message into multiple messages
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Directive {
+    /// `@repeat <count>` - the message is sent `count` times
+    Repeat(usize),
+    /// `@foreach <name> in <values>` - the message is sent once for every value,
+    /// with `{name}` replaced by the value
+    Foreach(String, Vec<String>),
+}
+
+impl Directive {
+    /// Returns the number of copies of the message
+    fn count(&self) -> usize {
+        match self {
+            Directive::Repeat(count) => *count,
+            Directive::Foreach(_, values) => values.len(),
+        }
+    }
+}
+
+impl FromStr for Directive {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, arguments) = s.split_once(char::is_whitespace).unwrap_or((s, ""));
+        let arguments = arguments.trim();
+
+        match name {
+            "repeat" => arguments
+                .parse()
+                .map(Directive::Repeat)
+                .map_err(|_| format!("Invalid count '{arguments}'")),
+            "foreach" => {
+                let (variable, values) = arguments
+                    .split_once(" in ")
+                    .ok_or_else(|| String::from("Use '@foreach <name> in <values>'"))?;
+                let variable = variable.trim();
+                if variable.is_empty()
+                    || !variable
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '_')
+                {
+                    return Err(format!("Invalid name '{variable}'"));
+                }
+
+                let values = parse_values(values.trim())?;
+                if values.is_empty() {
+                    return Err(format!("No values for '{variable}'"));
+                }
+                Ok(Directive::Foreach(variable.to_string(), values))
+            }
+            _ => Err(format!("Unknown directive '@{name}'")),
+        }
+    }
+}
+
+/// A copy of a message expanded from directives
+struct Expansion {
+    /// The message, with the placeholders replaced
+    line: String,
+    /// The values of the variables of the `@foreach` directives
+    bindings: Vec<(String, String)>,
+}
+
+impl Expansion {
+    /// Replaces every `{name}` of a variable by its value
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - the text containing the placeholders
+    fn substitute(&self, text: &str) -> String {
+        self.bindings
+            .iter()
+            .fold(text.to_string(), |text, (name, value)| {
+                text.replace(&format!("{{{name}}}"), value)
+            })
+    }
+
+    /// Returns the values of the variables, e.g. ` for id = 3`, or an empty String
+    fn describe(&self) -> String {
+        let bindings: Vec<String> = self
+            .bindings
+            .iter()
+            .map(|(name, value)| format!("{name} = {value}"))
+            .collect();
+        if bindings.is_empty() {
+            String::new()
+        } else {
+            format!(" for {}", bindings.join(", "))
+        }
+    }
+}
+
+/// Expands a message into its copies, the first directive being the outermost.
+/// Returns a description of the failure if the directives are contradictory or expand into too
+/// many messages.
+///
+/// # Arguments
+///
+/// * `line` - the message
+/// * `directives` - the directives preceding the message, in order
+fn expand(line: &str, directives: &[Directive]) -> Result<Vec<Expansion>, String> {
+    let total = directives.iter().try_fold(1usize, |total, directive| {
+        total.checked_mul(directive.count())
+    });
+    if total.map(|total| total > MAX_EXPANSION).unwrap_or(true) {
+        return Err(format!(
+            "directives expand the message into more than {MAX_EXPANSION} messages"
+        ));
+    }
+
+    let mut expansions = vec![Expansion {
+        line: line.to_string(),
+        bindings: Vec::new(),
+    }];
+    for directive in directives {
+        let mut nested = Vec::new();
+        for expansion in &expansions {
+            match directive {
+                Directive::Repeat(count) => {
+                    for _ in 0..*count {
+                        nested.push(Expansion {
+                            line: expansion.line.clone(),
+                            bindings: expansion.bindings.clone(),
+                        });
+                    }
+                }
+                Directive::Foreach(name, values) => {
+                    if expansion.bindings.iter().any(|(bound, _)| bound == name) {
+                        return Err(format!("'{name}' is used by multiple @foreach directives"));
+                    }
+                    for value in values {
+                        let mut bindings = expansion.bindings.clone();
+                        bindings.push((name.clone(), value.clone()));
+                        nested.push(Expansion {
+                            line: expansion.line.clone(),
+                            bindings,
+                        });
+                    }
+                }
+            }
+        }
+        expansions = nested;
+    }
+
+    for expansion in &mut expansions {
+        expansion.line = expansion.substitute(&expansion.line);
+    }
+    Ok(expansions)
+}
+
+/// Parses the values of a `@foreach` directive, a range or a comma separated list
+///
+/// # Arguments
+///
+/// * `value` - the String that should be parsed
+fn parse_values(value: &str) -> Result<Vec<String>, String> {
+    let ((start, end), inclusive) = if let Some(bounds) = value.split_once("..=") {
+        (bounds, true)
+    } else if let Some(bounds) = value.split_once("..") {
+        (bounds, false)
+    } else {
+        return Ok(value
+            .split(',')
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(String::from)
+            .collect());
+    };
+
+    let bound = |bound: &str| {
+        bound
+            .trim()
+            .parse::<u64>()
+            .map_err(|_| format!("Invalid range '{value}'"))
+    };
+    let start = bound(start)?;
+    let end = if inclusive {
+        bound(end)?.saturating_add(1)
+    } else {
+        bound(end)?
+    };
+
+    if end.saturating_sub(start) > MAX_EXPANSION as u64 {
+        return Err(format!(
+            "Range '{value}' has more than {MAX_EXPANSION} values"
+        ));
+    }
+    Ok((start..end).map(|value| value.to_string()).collect())
+}
+
+/// Parses the content of a raw benchmarking file into the entries of its valid lines,
+/// and a diagnostic of every invalid line
+///
+/// # Arguments
+///
+/// * `content` - the content of the raw benchmarking file
+fn parse_entries(content: &str) -> (Vec<RawEntry>, Vec<LineError>) {
+    let mut entries = Vec::new();
+    let mut errors = Vec::new();
+    let mut expect = None;
+    let mut timeout = None;
+    let mut tags = Vec::new();
+    let mut budgets = Vec::new();
+    let mut label = None;
+    let mut directives = Vec::new();
+    // Line of the last directive that was not yet applied to a message
+    let mut directive_line = None;
+
+    for (line_num, line) in content.lines().enumerate() {
+        let line = line.trim();
+        let line_num = line_num + 1;
+        let mut invalid = |reason: String| errors.push(LineError { line_num, reason });
+
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(comment) = line.strip_prefix('#') {
+            if let Some((key, value)) = comment.split_once(':') {
+                let annotation = match key.trim() {
+                    "expect" => value.trim().parse().map(|value| expect = Some(value)),
+                    "timeout" => parse_duration(value.trim()).map(|value| timeout = Some(value)),
+                    "tags" => {
+                        tags = parse_tags(value);
+                        Ok(())
+                    }
+                    "budget" => parse_budgets(value).map(|value| budgets = value),
+                    "label" => {
+                        label = Some(value.trim().to_string());
+                        Ok(())
+                    }
+                    _ => Ok(()),
+                };
+
+                if let Err(err) = annotation {
+                    invalid(format!("invalid annotation: {err}"));
+                }
+            }
+            continue;
+        }
+
+        if let Some(directive) = line.strip_prefix('@') {
+            match directive.parse() {
+                Ok(directive) => directives.push(directive),
+                Err(err) => invalid(format!("invalid directive: {err}")),
+            }
+            directive_line = Some(line_num);
+            continue;
+        }
+        directive_line = None;
+
+        let probes_error = expect == Some(ResponseKind::Error);
+        let msgs = expand(line, &std::mem::take(&mut directives)).and_then(|expansions| {
+            expansions
+                .into_iter()
+                .map(|expansion| {
+                    let msg = parse_raw(&expansion.line);
+                    if matches!(msg, OutgoingMessage::Done | OutgoingMessage::GetStatus)
+                        || (matches!(msg, OutgoingMessage::Invalid(_)) && !probes_error)
+                    {
+                        Err(format!("invalid message{}", expansion.describe()))
+                    } else {
+                        Ok((msg, expansion))
+                    }
+                })
+                .collect::<Result<Vec<_>, _>>()
+        });
+        let msgs = match msgs {
+            Ok(msgs) => msgs,
+            Err(reason) => {
+                invalid(reason);
+                // The annotations belonged to the invalid message, they must not be reported again
+                expect = None;
+                timeout = None;
+                tags.clear();
+                budgets.clear();
+                label = None;
+                continue;
+            }
+        };
+
+        let benchmarks = msgs
+            .iter()
+            .all(|(msg, _)| matches!(msg, OutgoingMessage::Benchmark(..)));
+        if !budgets.is_empty() && !benchmarks {
+            invalid(String::from("budget for a message that is not a benchmark"));
+        }
+        if label.is_some() && !benchmarks {
+            invalid(String::from("label for a message that is not a benchmark"));
+            label = None;
+        }
+
+        for (mut msg, expansion) in msgs {
+            if let OutgoingMessage::Benchmark(_, config) = &mut msg {
+                config.request_id = config.request_id.or(Some(line_num as u32));
+                if let Some(label) = &label {
+                    config.label = Some(expansion.substitute(label));
+                }
+            }
+
+            entries.push(RawEntry {
+                line_num,
+                msg,
+                expect,
+                timeout,
+                tags: tags.clone(),
+                budgets: budgets.clone(),
+            });
+        }
+        expect = None;
+        timeout = None;
+        tags.clear();
+        budgets.clear();
+        label = None;
+    }
+
+    if let Some(line_num) = directive_line {
+        errors.push(LineError {
+            line_num,
+            reason: String::from("directive without a message"),
+        });
+    }
+
+    (entries, errors)
+}
+
+/// Parses a comma separated list of tags
+///
+/// # Arguments
+///
+/// * `value` - the String that should be parsed
+fn parse_tags(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Parses a duration given in milliseconds, seconds or minutes, ex. `500ms`, `600s`, `10m`
+///
+/// # Arguments
+///
+/// * `value` - the String that should be parsed
+pub fn parse_duration(value: &str) -> Result<Duration, String> {
+    let (number, unit) = value.split_at(
+        value
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(value.len()),
+    );
+    let number: u64 = number
+        .parse()
+        .map_err(|_| format!("Invalid duration '{value}'"))?;
+
+    match unit.trim() {
+        "ms" => Ok(Duration::from_millis(number)),
+        "s" => Ok(Duration::from_secs(number)),
+        "m" => Ok(Duration::from_secs(number * 60)),
+        _ => Err(format!(
+            "Invalid duration unit in '{value}', use ms, s or m"
+        )),
+    }
+}
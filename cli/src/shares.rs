@@ -0,0 +1,155 @@
+//! Shares of the phases of benchmark results in their total cycles.
+//!
+//! For every configuration of a benchmark with multiple phases, the mean cycles of every phase are
+//! related to the mean cycles of all phases together. Configurations spending more than
+//! `SETUP_DOMINATES` percent of their cycles in initialization and deinitialization are flagged,
+//! as they measure the overhead of the driver setting up the module rather than the hardware.
+
+use std::fmt::Write;
+
+use benchmark_common::ResultEnvelope;
+use serde::Serialize;
+
+use crate::{csv::columns, statistics::configurations};
+
+/// Percentage of the cycles above which initialization and deinitialization dominate a benchmark
+pub const SETUP_DOMINATES: f64 = 50.0;
+
+/// Share of one phase in the cycles of a configuration
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PhaseShare {
+    /// Name of the phase, numbered if it occurs multiple times in a result, like in CSV files
+    pub phase: String,
+    /// Mean cycles of the phase over all results of the configuration
+    pub mean: f64,
+    /// Percentage of the mean cycles of all phases
+    pub percent: f64,
+}
+
+/// Shares of the phases of one configuration of a benchmark
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ResultShares {
+    /// Name of the benchmark that produced the results
+    pub benchmark: &'static str,
+    /// Index of the first result of the configuration inside the envelope
+    pub first_result: usize,
+    /// The share of every phase, in the order the phases were performed
+    pub phases: Vec<PhaseShare>,
+    /// Percentage of the cycles spent in initialization and deinitialization
+    pub setup: f64,
+}
+
+impl ResultShares {
+    /// True if initialization and deinitialization take more than `SETUP_DOMINATES` percent
+    pub fn setup_dominates(&self) -> bool {
+        self.setup > SETUP_DOMINATES
+    }
+}
+
+/// Computes the shares of the phases of every configuration contained in the results.
+///
+/// Results with a single phase and configurations without any cycles are left out.
+///
+/// # Arguments
+///
+/// * `envelope` - the results of a benchmark
+pub fn shares(envelope: &ResultEnvelope) -> Vec<ResultShares> {
+    configurations(envelope)
+        .into_iter()
+        .filter(|(key, _)| key.phases().len() > 1)
+        .filter_map(|(key, indices)| {
+            let means: Vec<f64> = (0..key.phases().len())
+                .map(|phase| {
+                    indices
+                        .iter()
+                        .map(|index| envelope.results[*index].phases()[phase].1 as f64)
+                        .sum::<f64>()
+                        / indices.len() as f64
+                })
+                .collect();
+            let total: f64 = means.iter().sum();
+            if total == 0.0 {
+                return None;
+            }
+
+            let setup = key
+                .phases()
+                .iter()
+                .zip(&means)
+                .filter(|((name, _), _)| is_setup(name))
+                .map(|(_, mean)| mean)
+                .sum::<f64>();
+
+            Some(ResultShares {
+                benchmark: key.name(),
+                first_result: indices[0],
+                phases: columns(&key)
+                    .into_iter()
+                    .zip(means)
+                    .map(|(phase, mean)| PhaseShare {
+                        phase,
+                        mean,
+                        percent: mean / total * 100.0,
+                    })
+                    .collect(),
+                setup: setup / total * 100.0,
+            })
+        })
+        .collect()
+}
+
+/// True if the phase initializes or deinitializes a module, including the phases of
+/// encryption and decryption and the misspelled `deinitalization` phases
+///
+/// # Arguments
+///
+/// * `phase` - the name of the phase as reported by the result
+fn is_setup(phase: &str) -> bool {
+    phase.contains("initialization") || phase.contains("initalization")
+}
+
+/// Formats shares as tables, one per configuration, as written to .result files.
+/// Configurations dominated by initialization and deinitialization are marked
+///
+/// # Arguments
+///
+/// * `shares` - the shares of the configurations of a benchmark
+pub fn format_shares(shares: &[ResultShares]) -> String {
+    let mut output = String::new();
+
+    for result in shares {
+        writeln!(
+            output,
+            "-- phase shares of {} from result {} --",
+            result.benchmark, result.first_result
+        )
+        .unwrap();
+
+        let width = result
+            .phases
+            .iter()
+            .map(|phase| phase.phase.len())
+            .max()
+            .unwrap_or(0);
+        for phase in &result.phases {
+            writeln!(
+                output,
+                "{:width$} {:>14.1} {:>6.1}%",
+                phase.phase, phase.mean, phase.percent
+            )
+            .unwrap();
+        }
+        if result.setup_dominates() {
+            writeln!(
+                output,
+                "-- initialization dominates: {:.1}% of the cycles --",
+                result.setup
+            )
+            .unwrap();
+        }
+    }
+
+    output
+}
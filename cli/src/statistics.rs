@@ -0,0 +1,209 @@
+//! Statistics of benchmark results.
+//!
+//! The results of all repetitions of a benchmark are summarized per phase, so the distribution
+//! of the cycles does not have to be computed by hand from the raw results. Results of different
+//! configurations, like the combinations of an AES sweep, are summarized separately.
+//! Percentiles use the nearest-rank method, so they are always one of the measured values.
+
+use std::fmt::Write;
+
+use benchmark_common::{Aggregate, BenchmarkResult, ResultEnvelope};
+use serde::Serialize;
+
+use crate::csv::columns;
+
+/// Statistics of the cycles of one phase over multiple results
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Statistics {
+    pub min: u64,
+    pub max: u64,
+    pub mean: f64,
+    pub median: f64,
+    /// Sample standard deviation
+    pub stddev: f64,
+    pub p95: u64,
+    pub p99: u64,
+}
+
+impl Statistics {
+    /// Computes the statistics of the values, returns None if no values are provided
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - the cycles measured for a phase
+    pub fn of(values: &[u64]) -> Option<Statistics> {
+        if values.is_empty() {
+            return None;
+        }
+
+        let mut sorted = values.to_vec();
+        sorted.sort_unstable();
+        let count = sorted.len();
+
+        let mean = sorted.iter().map(|value| *value as f64).sum::<f64>() / count as f64;
+        let median = if count & 1 == 0 {
+            (sorted[count / 2 - 1] as f64 + sorted[count / 2] as f64) / 2.0
+        } else {
+            sorted[count / 2] as f64
+        };
+        let variance = if count > 1 {
+            sorted
+                .iter()
+                .map(|value| (*value as f64 - mean).powi(2))
+                .sum::<f64>()
+                / (count - 1) as f64
+        } else {
+            0.0
+        };
+        let percentile = |p: f64| sorted[((p / 100.0 * count as f64).ceil() as usize).max(1) - 1];
+
+        Some(Statistics {
+            min: sorted[0],
+            max: sorted[count - 1],
+            mean,
+            median,
+            stddev: variance.sqrt(),
+            p95: percentile(95.0),
+            p99: percentile(99.0),
+        })
+    }
+}
+
+/// Statistics of one phase of a benchmark
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PhaseStatistics {
+    /// Name of the phase, numbered if it occurs multiple times in a result, like in CSV files
+    pub phase: String,
+    #[serde(flatten)]
+    pub statistics: Statistics,
+}
+
+/// Statistics of the results of one configuration of a benchmark
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ResultStatistics {
+    /// Name of the benchmark that produced the results
+    pub benchmark: &'static str,
+    /// Index of the first result of the configuration inside the envelope
+    pub first_result: usize,
+    /// Number of results the statistics were computed from
+    pub count: usize,
+    /// The statistics of every phase, in the order the phases were performed
+    pub phases: Vec<PhaseStatistics>,
+}
+
+/// Computes the statistics of every configuration contained in the results.
+///
+/// Only samples are summarized, configurations with fewer than two results are left out.
+///
+/// # Arguments
+///
+/// * `envelope` - the results of a benchmark
+pub fn summarize(envelope: &ResultEnvelope) -> Vec<ResultStatistics> {
+    if envelope.aggregate != Aggregate::Samples {
+        return Vec::new();
+    }
+
+    configurations(envelope)
+        .into_iter()
+        .filter(|(_, indices)| indices.len() > 1)
+        .map(|(key, indices)| {
+            let phases = columns(&key)
+                .into_iter()
+                .enumerate()
+                .filter_map(|(phase, name)| {
+                    let values: Vec<u64> = indices
+                        .iter()
+                        .map(|index| envelope.results[*index].phases()[phase].1)
+                        .collect();
+                    Some(PhaseStatistics {
+                        phase: name,
+                        statistics: Statistics::of(&values)?,
+                    })
+                })
+                .collect();
+
+            ResultStatistics {
+                benchmark: key.name(),
+                first_result: indices[0],
+                count: indices.len(),
+                phases,
+            }
+        })
+        .collect()
+}
+
+/// Groups the results by their configuration, returns every configuration with all of its cycles
+/// set to 0, together with the indices of its results, in the order the configurations occur
+///
+/// # Arguments
+///
+/// * `envelope` - the results of a benchmark
+pub fn configurations(envelope: &ResultEnvelope) -> Vec<(BenchmarkResult, Vec<usize>)> {
+    // Results of the same configuration only differ in their cycles
+    let configuration =
+        |result: &BenchmarkResult| result.with_phases(&vec![0; result.phases().len()]);
+
+    let mut groups: Vec<(BenchmarkResult, Vec<usize>)> = Vec::new();
+    for (index, result) in envelope.results.iter().enumerate() {
+        let key = configuration(result);
+        match groups.iter_mut().find(|(group, _)| *group == key) {
+            Some((_, indices)) => indices.push(index),
+            None => groups.push((key, vec![index])),
+        }
+    }
+
+    groups
+}
+
+/// Formats statistics as tables, one per configuration, as written to .result files
+///
+/// # Arguments
+///
+/// * `statistics` - the statistics of the configurations of a benchmark
+pub fn format_statistics(statistics: &[ResultStatistics]) -> String {
+    let mut output = String::new();
+
+    for result in statistics {
+        writeln!(
+            output,
+            "-- statistics of {} from result {} ({} results) --",
+            result.benchmark, result.first_result, result.count
+        )
+        .unwrap();
+
+        let width = result
+            .phases
+            .iter()
+            .map(|phase| phase.phase.len())
+            .max()
+            .unwrap_or(0)
+            .max("phase".len());
+        writeln!(
+            output,
+            "{:width$} {:>12} {:>12} {:>14} {:>14} {:>14} {:>12} {:>12}",
+            "phase", "min", "max", "mean", "median", "stddev", "p95", "p99"
+        )
+        .unwrap();
+        for phase in &result.phases {
+            let stats = &phase.statistics;
+            writeln!(
+                output,
+                "{:width$} {:>12} {:>12} {:>14.1} {:>14.1} {:>14.1} {:>12} {:>12}",
+                phase.phase,
+                stats.min,
+                stats.max,
+                stats.mean,
+                stats.median,
+                stats.stddev,
+                stats.p95,
+                stats.p99
+            )
+            .unwrap();
+        }
+    }
+
+    output
+}
@@ -0,0 +1,198 @@
+//! Conversion of the cycles of benchmark results into time and throughput.
+//!
+//! Once the frequency of the clock driving the cycle counter is known, reported by the suite as
+//! part of its platform or passed with `--clock-hz`, every phase of every result is annotated with
+//! the time it took in microseconds. Phases processing the data of a dataset, like the computation
+//! of a hash or the encryption of AES-GCM, are additionally annotated with their throughput in MB/s,
+//! derived from the size of the dataset as listed by the suite. Sweeps over the number of AES blocks
+//! use the number of blocks instead, the AES throughput benchmark the number of bytes it generated.
+//! The keys of HMAC do not count as processed data.
+
+use std::fmt::Write;
+
+use benchmark_common::{BenchmarkResult, DatasetDescriptor, DatasetKind, ResultEnvelope};
+use serde::Serialize;
+
+use crate::csv::columns;
+
+/// Bytes of an AES block
+const AES_BLOCK_SIZE: usize = 16;
+
+/// Converts cycles of the suite into time
+#[derive(Debug, Clone, Default)]
+pub struct Clock {
+    /// Frequency of the clock in Hz overriding the frequency reported with the results, if any
+    pub frequency: Option<u64>,
+    /// The datasets listed by the suite, empty if it did not list them
+    pub datasets: Vec<DatasetDescriptor>,
+}
+
+impl Clock {
+    /// Returns the frequency results were measured at, None if it is not known
+    ///
+    /// # Arguments
+    ///
+    /// * `envelope` - the results of a benchmark
+    fn frequency_of(&self, envelope: &ResultEnvelope) -> Option<u64> {
+        self.frequency
+            .or(envelope.platform.clock_frequency)
+            .filter(|&frequency| frequency > 0)
+    }
+}
+
+/// Time and throughput of one phase of a result
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PhaseTime {
+    /// Name of the phase, numbered if it occurs multiple times in a result, like in CSV files
+    pub phase: String,
+    /// Time the phase took in microseconds
+    pub microseconds: f64,
+    /// Megabytes of the dataset processed per second, if the phase processes a dataset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub throughput: Option<f64>,
+}
+
+/// Times of the phases of one result
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ResultTime {
+    /// Name of the benchmark that produced the result
+    pub benchmark: &'static str,
+    /// Index of the result inside the envelope
+    pub result: usize,
+    /// Frequency of the clock the cycles were converted with, in Hz
+    pub frequency: u64,
+    /// The time of every phase, in the order the phases were performed
+    pub phases: Vec<PhaseTime>,
+}
+
+/// Converts the cycles of every result into time, empty if the frequency is not known
+///
+/// # Arguments
+///
+/// * `envelope` - the results of a benchmark
+/// * `clock` - the frequency and the datasets of the suite
+pub fn times(envelope: &ResultEnvelope, clock: &Clock) -> Vec<ResultTime> {
+    let frequency = match clock.frequency_of(envelope) {
+        Some(frequency) => frequency,
+        None => return Vec::new(),
+    };
+
+    envelope
+        .results
+        .iter()
+        .enumerate()
+        .map(|(index, result)| {
+            let bytes = processed_bytes(envelope, result, &clock.datasets);
+            ResultTime {
+                benchmark: result.name(),
+                result: index,
+                frequency,
+                phases: columns(result)
+                    .into_iter()
+                    .zip(result.phases())
+                    .map(|(phase, (name, cycles))| {
+                        let seconds = cycles as f64 / frequency as f64;
+                        PhaseTime {
+                            phase,
+                            microseconds: seconds * 1e6,
+                            throughput: bytes
+                                .filter(|_| processes_data(name) && cycles > 0)
+                                .map(|bytes| bytes as f64 / seconds / 1e6),
+                        }
+                    })
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+/// Returns the number of bytes of data a result processed, None if it is not known
+///
+/// # Arguments
+///
+/// * `envelope` - the results of a benchmark, the requested benchmark is echoed in it
+/// * `result` - one of the results
+/// * `datasets` - the datasets listed by the suite
+fn processed_bytes(
+    envelope: &ResultEnvelope,
+    result: &BenchmarkResult,
+    datasets: &[DatasetDescriptor],
+) -> Option<usize> {
+    match result {
+        BenchmarkResult::AESSweep { blocks, .. } => return Some(blocks * AES_BLOCK_SIZE),
+        BenchmarkResult::AESThroughput { bytes, .. } => return Some(*bytes),
+        _ => {}
+    }
+
+    let requested: Vec<(DatasetKind, usize)> = envelope
+        .benchmark
+        .as_ref()?
+        .datasets()
+        .into_iter()
+        .filter(|(kind, _)| *kind != DatasetKind::HMACKey)
+        .collect();
+    if requested.is_empty() {
+        return None;
+    }
+
+    requested
+        .iter()
+        .map(|(kind, id)| {
+            datasets
+                .iter()
+                .find(|dataset| dataset.kind == *kind && dataset.id == *id)
+                .map(|dataset| dataset.size)
+        })
+        .sum()
+}
+
+/// True if the phase processes the data of the dataset, like the computation of a digest, the
+/// encryption of AES-GCM and ChaCha20-Poly1305 or the generation of random numbers
+///
+/// # Arguments
+///
+/// * `phase` - the name of the phase as reported by the result
+fn processes_data(phase: &str) -> bool {
+    phase.ends_with("computation") || phase == "encryption" || phase == "generation"
+}
+
+/// Formats times as tables, one per result, as written to .result files
+///
+/// # Arguments
+///
+/// * `times` - the times of the results of a benchmark
+pub fn format_times(times: &[ResultTime]) -> String {
+    let mut output = String::new();
+
+    for result in times {
+        writeln!(
+            output,
+            "-- time of {} result {} at {} Hz --",
+            result.benchmark, result.result, result.frequency
+        )
+        .unwrap();
+
+        let width = result
+            .phases
+            .iter()
+            .map(|phase| phase.phase.len())
+            .max()
+            .unwrap_or(0);
+        for phase in &result.phases {
+            write!(
+                output,
+                "{:width$} {:>14.3} us",
+                phase.phase, phase.microseconds
+            )
+            .unwrap();
+            if let Some(throughput) = phase.throughput {
+                write!(output, " {throughput:>10.2} MB/s").unwrap();
+            }
+            output.push('\n');
+        }
+    }
+
+    output
+}
@@ -0,0 +1,135 @@
+//! Transcripts of the communication with the suite.
+//!
+//! Every frame sent to or received from the suite is recorded as a line of the transcript,
+//! together with the time of the host it was sent or received at, independent of what is printed.
+//! The transcript is written as the frames are exchanged, so it is complete up to the last frame
+//! even if the CLI is killed. Lines have the form `<seconds>.<microseconds> <direction> <frame>`,
+//! the seconds counting from the unix epoch:
+//!
+//! * `>` - a frame sent to the suite, without its delimiter
+//! * `<` - a frame received from the suite, without its delimiter. Frames that were cut off by a
+//!   failed read are recorded as far as they were received, followed by a note of the failure
+//! * `#` - a note of the CLI, e.g. the tty it connected to
+//!
+//! When benchmarking on multiple boards in parallel, the tty of the board is written after the time,
+//! e.g. `1792155624.544725 /dev/ttyUSB1 > "GetStatus"`.
+//!
+//! Frames are written as text, backslashes, control characters and bytes that are not ASCII are
+//! escaped like in Rust strings, e.g. `\n` or `\x9f`, so frames of binary codecs are preserved.
+
+use std::{
+    fmt::Write as _,
+    fs::File,
+    io::{self, LineWriter, Write},
+    path::Path,
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// A transcript file, shared by all connections to the suite made during one invocation of the CLI
+#[derive(Clone)]
+pub struct Transcript {
+    file: Arc<Mutex<LineWriter<File>>>,
+    /// Board the lines are marked with, if any
+    board: Option<String>,
+}
+
+impl Transcript {
+    /// Creates the transcript file, replacing an existing file
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - the path of the transcript
+    pub fn create(path: &Path) -> io::Result<Transcript> {
+        Ok(Transcript {
+            file: Arc::new(Mutex::new(LineWriter::new(File::create(path)?))),
+            board: None,
+        })
+    }
+
+    /// Returns a handle to the same transcript, whose lines are marked with the board
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - the identifier of the board
+    pub fn of_board(&self, board: &str) -> Transcript {
+        Transcript {
+            file: self.file.clone(),
+            board: Some(board.to_string()),
+        }
+    }
+
+    /// Records a frame sent to the suite
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - the frame, without its delimiter
+    pub fn sent(&self, frame: &[u8]) {
+        self.record('>', &escape(frame));
+    }
+
+    /// Records a frame received from the suite
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - the frame, without its delimiter
+    pub fn received(&self, frame: &[u8]) {
+        self.record('<', &escape(frame));
+    }
+
+    /// Records a note of the CLI
+    ///
+    /// # Arguments
+    ///
+    /// * `note` - the note
+    pub fn note(&self, note: &str) {
+        self.record('#', note);
+    }
+
+    /// Writes a line with the current time
+    ///
+    /// # Arguments
+    ///
+    /// * `direction` - the character marking the direction of the line
+    /// * `text` - the content of the line
+    fn record(&self, direction: char, text: &str) {
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+
+        let board = match &self.board {
+            Some(board) => format!(" {board}"),
+            None => String::new(),
+        };
+
+        // A transcript that can not be written is not worth aborting the session for
+        if let Ok(mut file) = self.file.lock() {
+            let _ = writeln!(
+                file,
+                "{}.{:06}{board} {direction} {text}",
+                time.as_secs(),
+                time.subsec_micros()
+            );
+        }
+    }
+}
+
+/// Escapes backslashes, control characters and bytes that are not ASCII
+///
+/// # Arguments
+///
+/// * `bytes` - the bytes of a frame
+fn escape(bytes: &[u8]) -> String {
+    let mut escaped = String::with_capacity(bytes.len());
+    for &byte in bytes {
+        match byte {
+            b'\\' => escaped.push_str("\\\\"),
+            b'\n' => escaped.push_str("\\n"),
+            b'\r' => escaped.push_str("\\r"),
+            b'\t' => escaped.push_str("\\t"),
+            b' '..=b'~' => escaped.push(byte as char),
+            _ => write!(escaped, "\\x{byte:02x}").unwrap(),
+        }
+    }
+    escaped
+}
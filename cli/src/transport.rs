@@ -0,0 +1,202 @@
+//! Transports carrying the frames exchanged with the suite.
+//!
+//! Boards are connected over the tty of their UART. Emulators like Verilator and QEMU can expose
+//! the UART over a TCP or Unix domain socket instead, which is used without a pseudo terminal in
+//! between. The endpoint of a suite is either the path of its tty, the address of its TCP socket
+//! prefixed by `tcp:`, e.g. `tcp:localhost:5555`, or the path of its Unix domain socket prefixed by
+//! `unix:`, e.g. `unix:/tmp/qemu-uart.sock`.
+
+use std::{
+    ffi::OsString,
+    fmt::Display,
+    io::{self, ErrorKind, Read, Write},
+    net::TcpStream,
+    os::unix::net::UnixStream,
+    path::PathBuf,
+    str::FromStr,
+    time::Duration,
+};
+
+use serialport::{ClearBuffer, SerialPort, TTYPort};
+
+/// Baud rate of the UART of the suite
+const BAUD_RATE: u32 = 9600;
+
+/// Byte stream connected to the suite
+pub trait Transport: Read + Write + Send {
+    /// Sets the timeout used when reading, reads that time out fail with `ErrorKind::TimedOut`
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - the new timeout
+    fn set_timeout(&mut self, timeout: Duration) -> io::Result<()>;
+
+    /// Discards all input that was received but not yet read
+    fn clear_input(&mut self) -> io::Result<()>;
+
+    /// Returns another handle of the same stream, so reading and writing are buffered separately
+    fn try_clone(&self) -> io::Result<Box<dyn Transport>>;
+}
+
+impl Transport for TTYPort {
+    fn set_timeout(&mut self, timeout: Duration) -> io::Result<()> {
+        SerialPort::set_timeout(self, timeout).map_err(io::Error::from)
+    }
+
+    fn clear_input(&mut self) -> io::Result<()> {
+        self.clear(ClearBuffer::Input).map_err(io::Error::from)
+    }
+
+    fn try_clone(&self) -> io::Result<Box<dyn Transport>> {
+        Ok(Box::new(self.try_clone_native()?))
+    }
+}
+
+/// Stream socket exposing the UART of an emulator
+pub trait Socket: Read + Write + Send + Sized + 'static {
+    /// Sets the timeout of reads, None blocks indefinitely
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()>;
+
+    /// Sets whether reads return immediately if no input was received
+    fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()>;
+
+    /// Returns another handle of the same socket
+    fn try_clone(&self) -> io::Result<Self>;
+}
+
+impl Socket for TcpStream {
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        TcpStream::set_read_timeout(self, timeout)
+    }
+
+    fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        TcpStream::set_nonblocking(self, nonblocking)
+    }
+
+    fn try_clone(&self) -> io::Result<Self> {
+        TcpStream::try_clone(self)
+    }
+}
+
+impl Socket for UnixStream {
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        UnixStream::set_read_timeout(self, timeout)
+    }
+
+    fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        UnixStream::set_nonblocking(self, nonblocking)
+    }
+
+    fn try_clone(&self) -> io::Result<Self> {
+        UnixStream::try_clone(self)
+    }
+}
+
+/// Connection to a suite whose UART is exposed over a socket
+pub struct SocketTransport<S: Socket>(S);
+
+impl<S: Socket> Read for SocketTransport<S> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        // Sockets report a timeout like a read that would block, unlike serial ports
+        self.0.read(buf).map_err(|err| match err.kind() {
+            ErrorKind::WouldBlock => io::Error::new(ErrorKind::TimedOut, err),
+            _ => err,
+        })
+    }
+}
+
+impl<S: Socket> Write for SocketTransport<S> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+impl<S: Socket> Transport for SocketTransport<S> {
+    fn set_timeout(&mut self, timeout: Duration) -> io::Result<()> {
+        self.0.set_read_timeout(Some(timeout))
+    }
+
+    fn clear_input(&mut self) -> io::Result<()> {
+        self.0.set_nonblocking(true)?;
+        let mut buf = [0; 256];
+        let drained = loop {
+            match self.0.read(&mut buf) {
+                Ok(0) => break Err(io::Error::from(ErrorKind::UnexpectedEof)),
+                Ok(_) => {}
+                Err(err) if err.kind() == ErrorKind::WouldBlock => break Ok(()),
+                Err(err) => break Err(err),
+            }
+        };
+        self.0.set_nonblocking(false)?;
+        drained
+    }
+
+    fn try_clone(&self) -> io::Result<Box<dyn Transport>> {
+        Ok(Box::new(SocketTransport(self.0.try_clone()?)))
+    }
+}
+
+/// Where the suite is connected to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Endpoint {
+    /// The tty of the UART, e.g. `/dev/ttyUSB0`
+    Tty(OsString),
+    /// Host and port of a TCP socket exposing the UART, e.g. `localhost:5555`
+    Tcp(String),
+    /// Path of a Unix domain socket exposing the UART, e.g. `/tmp/qemu-uart.sock`
+    Unix(PathBuf),
+}
+
+impl Endpoint {
+    /// Opens the transport to the suite
+    pub fn open(&self) -> io::Result<Box<dyn Transport>> {
+        match self {
+            Endpoint::Tty(tty) => {
+                let port = serialport::new(tty.to_string_lossy(), BAUD_RATE).open_native()?;
+                Ok(Box::new(port))
+            }
+            Endpoint::Tcp(address) => {
+                let stream = TcpStream::connect(address)?;
+                // Frames are small and exchanged one at a time
+                stream.set_nodelay(true)?;
+                Ok(Box::new(SocketTransport(stream)))
+            }
+            Endpoint::Unix(path) => Ok(Box::new(SocketTransport(UnixStream::connect(path)?))),
+        }
+    }
+}
+
+impl FromStr for Endpoint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some(("tcp", address)) if !address.is_empty() => Ok(Endpoint::Tcp(address.to_string())),
+            Some(("unix", path)) if !path.is_empty() => Ok(Endpoint::Unix(PathBuf::from(path))),
+            Some(("tty", path)) if !path.is_empty() => Ok(Endpoint::Tty(OsString::from(path))),
+            _ => Err(format!(
+                "Unknown endpoint '{s}', use tcp:<host>:<port>, unix:<path> or tty:<path>"
+            )),
+        }
+    }
+}
+
+impl From<OsString> for Endpoint {
+    fn from(tty: OsString) -> Self {
+        Endpoint::Tty(tty)
+    }
+}
+
+impl Display for Endpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Endpoint::Tty(tty) => write!(f, "{}", tty.to_string_lossy()),
+            Endpoint::Tcp(address) => write!(f, "tcp:{address}"),
+            Endpoint::Unix(path) => write!(f, "unix:{}", path.display()),
+        }
+    }
+}
@@ -1,51 +1,226 @@
 use std::{
-    ffi::OsString,
     fmt::Write,
-    io::{BufRead, BufReader, BufWriter, Error},
-    time::Duration,
+    io::{BufRead, BufReader, BufWriter, Error, ErrorKind, Read, Write as _},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
-use benchmark_common::{deserialize, serialize, IncomingMessage, OutgoingMessage, SuiteStatus};
-use serialport::TTYPort;
+use crate::{
+    transcript::Transcript,
+    transport::{Endpoint, Transport},
+    ExitCode,
+};
+use benchmark_common::{
+    codec::WireFormat, deserialize_as, parse_resync, serialize_as, BenchmarkConfig, BenchmarkInfo,
+    BenchmarkResult, IncomingMessage, LogLevel, OutgoingMessage, ResultEnvelope, SkipReason,
+    SuiteStatus, VerificationFailure, RESYNC_TOKEN,
+};
+
+type Frame = Result<Vec<u8>, Error>;
+
+/// Timeout used when reading from the transport, unless overridden
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
 
-type Line = Result<String, Error>;
+/// Timeout used when waiting for the response to a Ping,
+/// a responsive suite answers immediately so this is much shorter than the default timeout
+pub const PING_TIMEOUT: Duration = Duration::from_secs(5);
 
-/// SerialConnection, representing a connection over a serial TTYPort
+/// Timeout used when probing whether the suite speaks a wire format,
+/// a suite speaking the format acknowledges the probe immediately
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Factor applied to the duration the suite expects a benchmark to take, when deriving a timeout
+const HINT_FACTOR: u32 = 2;
+
+/// Time added to the duration the suite expects a benchmark to take, when deriving a timeout,
+/// so that short benchmarks are not affected by small delays of the suite
+const HINT_MARGIN: Duration = Duration::from_secs(5);
+
+/// Determines how a connection with the suite is established
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Handshake {
+    /// Expects a freshly started suite, that is waiting for requests
+    Fresh,
+    /// Resynchronizes with a suite that may still be processing requests or sending output of an
+    /// earlier session. Input received before connecting is discarded without being parsed.
+    Resync,
+}
+
+/// Options describing how to connect to the suite
+pub struct ConnectionOptions {
+    /// The tty or socket used to communicate with the suite
+    pub endpoint: Endpoint,
+    /// How the connection with the suite is established
+    pub handshake: Handshake,
+    /// Format of the frames exchanged with the suite, detected during the handshake if None
+    pub format: Option<WireFormat>,
+    /// Transcript every frame exchanged with the suite is recorded in, if any
+    pub transcript: Option<Transcript>,
+    /// Identifier of the board the suite runs on, recorded with the results if set,
+    /// as done when benchmarking on multiple boards
+    pub board: Option<String>,
+}
+
+/// SerialConnection, representing a connection over the transport of an endpoint,
+/// usually the tty of a serial port
 pub struct SerialConnection {
-    writer: BufWriter<TTYPort>,
-    reader: BufReader<TTYPort>,
+    writer: BufWriter<Box<dyn Transport>>,
+    reader: BufReader<Box<dyn Transport>>,
+    /// Format of the frames that are exchanged
+    format: WireFormat,
+    /// Transcript the exchanged frames are recorded in, if any
+    transcript: Option<Transcript>,
 }
 
 impl SerialConnection {
-    /// Creates a new SerialConnection connected to the provided tty
+    /// Creates a new SerialConnection connected to the provided endpoint
     ///
     /// # Arguments
     ///
-    /// * `tty` - the path to the tty to connect to
-    pub fn new(tty: &OsString) -> Result<SerialConnection, serialport::Error> {
-        let port = serialport::new(tty.to_string_lossy(), 9600)
-            .timeout(Duration::from_secs(60))
-            .open_native()?;
+    /// * `endpoint` - the tty or socket to connect to
+    pub fn new(endpoint: &Endpoint) -> Result<SerialConnection, Error> {
+        let mut transport = endpoint.open()?;
+        transport.set_timeout(DEFAULT_TIMEOUT)?;
         let term = SerialConnection {
-            reader: BufReader::new(port.try_clone_native().expect("Failed to clone port")),
-            writer: BufWriter::new(port),
+            reader: BufReader::new(transport.try_clone()?),
+            writer: BufWriter::new(transport),
+            format: WireFormat::DEFAULT,
+            transcript: None,
         };
 
         Ok(term)
     }
 
-    /// Reads a single line form the serial port
-    pub fn read_line(&mut self) -> Line {
+    /// Sets the timeout used when reading from the transport
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - the new timeout
+    pub fn set_timeout(&mut self, timeout: Duration) -> Result<(), Error> {
+        self.reader.get_mut().set_timeout(timeout)
+    }
+
+    /// Sets the format of the frames that are exchanged, which determines their delimiter
+    ///
+    /// # Arguments
+    ///
+    /// * `format` - the new format
+    pub fn set_format(&mut self, format: WireFormat) {
+        self.format = format;
+    }
+
+    /// Returns the format of the frames that are exchanged
+    pub fn format(&self) -> WireFormat {
+        self.format
+    }
+
+    /// Records all frames exchanged from now on in the transcript, check the transcript module
+    ///
+    /// # Arguments
+    ///
+    /// * `transcript` - the transcript, nothing is recorded if None
+    /// * `endpoint` - the endpoint of the connection, noted in the transcript
+    pub fn set_transcript(&mut self, transcript: Option<Transcript>, endpoint: &Endpoint) {
+        if let Some(transcript) = &transcript {
+            transcript.note(&format!("connected to {endpoint}"));
+        }
+        self.transcript = transcript;
+    }
+
+    /// Discards all input that was received but not yet read
+    pub fn discard_input(&mut self) -> Result<(), Error> {
+        let buffered = self.reader.buffer().len();
+        if let Some(transcript) = &self.transcript {
+            transcript.received(self.reader.buffer());
+            transcript.note(&format!(
+                "discarded {buffered} buffered bytes and pending input"
+            ));
+        }
+        self.reader.consume(buffered);
+
+        self.reader.get_mut().clear_input()
+    }
+
+    /// Waits until the suite starts, i.e. until it sends the resync token without an identifier,
+    /// followed by the Ready status, as it does once at startup.
+    /// Fails if the suite does not start before the timeout of the port.
+    ///
+    /// The format of the suite is detected from the delimiter terminating the token,
+    /// and used from then on.
+    pub fn wait_for_startup(&mut self) -> Result<(), Error> {
+        let token = RESYNC_TOKEN.as_bytes();
+        let mut received: Vec<u8> = Vec::new();
+        let mut byte = [0];
+
+        loop {
+            self.reader.read_exact(&mut byte)?;
+            received.push(byte[0]);
+            if received.len() > token.len() + 1 {
+                received.remove(0);
+            }
+
+            let started = WireFormat::SUPPORTED.iter().find(|format| {
+                received.starts_with(token) && received.last() == Some(&format.delimiter())
+            });
+            if let Some(&format) = started {
+                if let Some(transcript) = &self.transcript {
+                    transcript.received(&received);
+                }
+                self.format = format;
+                if let IncomingMessage::Status(SuiteStatus::Ready) =
+                    deserialize_as(format, &self.read_frame()?)
+                {
+                    return Ok(());
+                }
+                received.clear();
+            }
+        }
+    }
+
+    /// Reads a single frame form the serial port, returns it without its delimiter
+    pub fn read_frame(&mut self) -> Frame {
         let mut buf = vec![];
 
-        self.reader.read_until(0xA, &mut buf)?;
+        let delimiter = self.format.delimiter();
+        let read = self.reader.read_until(delimiter, &mut buf);
+        if buf.last() == Some(&delimiter) {
+            buf.pop();
+        }
 
-        Ok(String::from_utf8_lossy(&buf).trim().to_string())
+        if let Some(transcript) = &self.transcript {
+            if read.is_ok() || !buf.is_empty() {
+                transcript.received(&buf);
+            }
+            if let Err(err) = &read {
+                transcript.note(&format!("read failed: {err}"));
+            }
+        }
+
+        read.map(|_| buf)
+    }
+
+    /// Writes a single frame, including its delimiter, to the serial port
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - the frame that should be written
+    pub fn write_frame(&mut self, frame: &[u8]) -> Result<(), Error> {
+        if let Some(transcript) = &self.transcript {
+            transcript.sent(
+                frame
+                    .strip_suffix(&[self.format.delimiter()])
+                    .unwrap_or(frame),
+            );
+        }
+        self.writer.write_all(frame)?;
+        self.writer.flush()
     }
 }
 
 impl Write for SerialConnection {
     fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        if let Some(transcript) = &self.transcript {
+            transcript.sent(s.as_bytes());
+        }
         if std::io::Write::write(&mut self.writer, s.as_bytes()).is_err()
             || std::io::Write::flush(&mut self.writer).is_err()
         {
@@ -56,45 +231,480 @@ impl Write for SerialConnection {
     }
 }
 
+/// Detects the serial port the suite is connected to, by probing every available serial port
+/// until a suite answers, check `SuiteConnection::probe`.
+/// Returns the path of the port, or a description of the failure if no suite answered.
+///
+/// # Arguments
+///
+/// * `format` - the format of the frames exchanged with the suite, all supported formats are
+///   probed if None
+pub fn detect_port(format: Option<WireFormat>) -> Result<Endpoint, String> {
+    let ports = serialport::available_ports()
+        .map_err(|err| format!("Failed to enumerate serial ports: {err}"))?;
+    let formats = match format {
+        Some(format) => vec![format],
+        None => WireFormat::SUPPORTED.to_vec(),
+    };
+
+    for port in &ports {
+        let tty = Endpoint::Tty(port.port_name.clone().into());
+        for &format in &formats {
+            // Ports that can not be opened or fail otherwise, like busy ports, are skipped
+            let found = SerialConnection::new(&tty)
+                .and_then(|serial| SuiteConnection::probe(serial, format));
+            if let Ok(true) = found {
+                eprintln!("Detected suite on {}", port.port_name);
+                return Ok(tty);
+            }
+        }
+    }
+
+    let names: Vec<&str> = ports.iter().map(|port| port.port_name.as_str()).collect();
+    Err(format!(
+        "No suite answered on any serial port, probed: {}",
+        names.join(", ")
+    ))
+}
+
 /// SuiteConnection, representing a connection to a benchmarking suite instance
 ///
 /// Connection is realized over a serial port, and communication is done using
 /// messages.
 ///
 /// For information about supported messages an their (de)serialization check the common create.
-
 pub struct SuiteConnection {
     serial: SerialConnection,
+    /// Round-trip latency measured by the last ping, added to all response timeouts
+    latency: Duration,
+    /// Frequency of the clock of the suite in Hz, used to convert expected cycles into durations
+    clock_frequency: Option<u64>,
+    /// Whether the timeout may be replaced by one derived from the expected cycles of a benchmark
+    hinted: bool,
+    /// Number of frames received from the suite
+    frames: usize,
+    /// Log messages of the suite, collected instead of being printed if collecting was enabled
+    logs: Option<Vec<String>>,
+    /// Number of log messages of the suite with level Warn or Error
+    warnings: usize,
 }
 
 impl SuiteConnection {
     /// Establish a new SuiteConnection over the provided SerialConnection,
     /// fails if any errors occur during communication using the SerialConnection.
     ///
+    /// The handshake starts with a resync, so any output the suite sent before,
+    /// like the status it reports at startup, is skipped and not mistaken for a response.
+    /// If no wire format is provided and this build supports multiple formats, the format is
+    /// detected instead, check `detect_format`.
+    ///
     /// # Arguments
     ///
     /// * `serial` - the serial connection to use to communicate with the suite
-    pub fn new(serial: SerialConnection) -> Result<SuiteConnection, std::io::Error> {
-        let mut conn = SuiteConnection { serial };
+    /// * `handshake` - how the connection with the suite is established
+    /// * `format` - the format of the frames exchanged with the suite, if known
+    pub fn new(
+        serial: SerialConnection,
+        handshake: Handshake,
+        format: Option<WireFormat>,
+    ) -> Result<SuiteConnection, std::io::Error> {
+        let mut conn = SuiteConnection::unconnected(serial);
 
-        conn.send_message(&OutgoingMessage::GetStatus);
+        if handshake == Handshake::Resync {
+            conn.serial.discard_input()?;
+        }
+
+        match format {
+            Some(format) => conn.serial.set_format(format),
+            None if WireFormat::SUPPORTED.len() > 1 => {
+                let format = conn.detect_format()?;
+                eprintln!("Detected wire format: {format:?}");
+                return Ok(conn);
+            }
+            None => {}
+        }
+
+        conn.resync()?;
+
+        conn.send_message(&OutgoingMessage::GetStatus)?;
 
         loop {
-            match conn.read_message() {
-                Ok(msg) => {
-                    if matches!(msg, IncomingMessage::Status(SuiteStatus::Ready)) {
-                        return Ok(conn);
-                    }
+            if matches!(
+                conn.read_message()?,
+                IncomingMessage::Status(SuiteStatus::Ready)
+            ) {
+                return Ok(conn);
+            }
+        }
+    }
+
+    /// Returns a connection over the SerialConnection, without performing a handshake
+    ///
+    /// # Arguments
+    ///
+    /// * `serial` - the serial connection to use to communicate with the suite
+    fn unconnected(serial: SerialConnection) -> SuiteConnection {
+        SuiteConnection {
+            serial,
+            latency: Duration::ZERO,
+            clock_frequency: None,
+            hinted: false,
+            frames: 0,
+            logs: None,
+            warnings: 0,
+        }
+    }
+
+    /// Returns true if a suite speaking the format is connected to the serial port, i.e. if the
+    /// resync token is acknowledged and the suite reports that it is ready within `PROBE_TIMEOUT`.
+    /// Output of other devices is skipped until the time passed.
+    ///
+    /// # Arguments
+    ///
+    /// * `serial` - the serial connection that is probed
+    /// * `format` - the format of the frames exchanged with the suite
+    fn probe(serial: SerialConnection, format: WireFormat) -> Result<bool, std::io::Error> {
+        let mut conn = SuiteConnection::unconnected(serial);
+        conn.serial.set_format(format);
+        conn.set_timeout(PROBE_TIMEOUT)?;
+        conn.serial.discard_input()?;
+
+        let id = format!("probe{}", std::process::id());
+        let mut frame = format!("{RESYNC_TOKEN}{id}").into_bytes();
+        frame.push(format.delimiter());
+        conn.serial.write_frame(&frame)?;
+
+        // Unlike `resync`, devices that keep sending other output are given up on
+        let start = Instant::now();
+        while parse_resync(&String::from_utf8_lossy(&conn.serial.read_frame()?)) != Some(&id) {
+            if start.elapsed() > PROBE_TIMEOUT {
+                return Ok(false);
+            }
+        }
+
+        conn.probe_status()
+    }
+
+    /// Requests the status of the suite and waits until it reports that it is ready,
+    /// fails if any errors occur during communication using the SerialConnection.
+    ///
+    /// Returns false without waiting further, if a message could not be deserialized,
+    /// as the suite does not speak the current wire format.
+    fn probe_status(&mut self) -> Result<bool, std::io::Error> {
+        self.send_message(&OutgoingMessage::GetStatus)?;
+
+        loop {
+            match self.read_message()? {
+                IncomingMessage::Status(SuiteStatus::Ready) => return Ok(true),
+                IncomingMessage::Invalid(_) => return Ok(false),
+                _ => {}
+            }
+        }
+    }
+
+    /// Detects the wire format the suite speaks, by probing all formats supported by this build
+    /// in turn, until the suite answers in one of them or `DEFAULT_TIMEOUT` passed.
+    ///
+    /// A format is probed by resynchronizing with the delimiter of the format, followed by requesting
+    /// the status of the suite. The suite speaks the format, if it acknowledges the resync token
+    /// within `PROBE_TIMEOUT` and its status can be deserialized.
+    /// A probe that was not answered is terminated by the delimiter of the next probe,
+    /// which the suite skips as part of the next resync token.
+    fn detect_format(&mut self) -> Result<WireFormat, std::io::Error> {
+        let start = Instant::now();
+
+        while start.elapsed() < DEFAULT_TIMEOUT {
+            for &format in WireFormat::SUPPORTED {
+                self.serial.set_format(format);
+                self.set_timeout(PROBE_TIMEOUT)?;
+
+                let answered = match self.resync().and_then(|_| self.probe_status()) {
+                    Ok(answered) => answered,
+                    Err(err) if err.kind() == ErrorKind::TimedOut => false,
+                    Err(err) => return Err(err),
+                };
+                if answered {
+                    self.set_timeout(DEFAULT_TIMEOUT)?;
+                    return Ok(format);
                 }
-                Err(x) => return Err(x),
             }
         }
+
+        Err(Error::new(
+            ErrorKind::TimedOut,
+            "Suite did not answer in any supported wire format",
+        ))
     }
 
     /// Read a message sent by the suite,
     /// fails if any errors occur during communication using the SerialConnection.
+    ///
+    /// ResultChunk messages are reassembled and returned as a single BenchmarkResults message,
+    /// carrying the platform descriptor of the first chunk. Fails if a chunk is missing or
+    /// received out of order.
+    /// Packed results are unpacked, an Invalid message is returned if they can not be unpacked.
+    /// Log messages are not returned, instead they are printed to stderr, or collected if
+    /// `collect_logs` was called.
+    /// Accepted messages are not returned either, if they carry a hint of the expected cycles and
+    /// the response timeout was not set explicitly, the timeout is derived from the hint instead.
+    /// Lines containing the resync token are skipped and discard any partially received results.
     pub fn read_message(&mut self) -> Result<IncomingMessage, std::io::Error> {
-        Ok(deserialize(self.serial.read_line()?))
+        let mut envelope: Option<ResultEnvelope> = None;
+        let mut next_index = 0;
+
+        loop {
+            let frame = self.serial.read_frame()?;
+            self.frames += 1;
+
+            if parse_resync(&String::from_utf8_lossy(&frame)).is_some() {
+                envelope = None;
+                next_index = 0;
+                continue;
+            }
+
+            match deserialize_as(self.serial.format(), &frame) {
+                IncomingMessage::ResultChunk {
+                    index,
+                    total,
+                    mut payload,
+                } => {
+                    if index != next_index {
+                        return Err(Error::new(
+                            ErrorKind::InvalidData,
+                            format!("Received result chunk {index}, expected chunk {next_index}"),
+                        ));
+                    }
+                    if !payload.unpack() {
+                        let frame = String::from_utf8_lossy(&frame).trim().to_string();
+                        return Ok(IncomingMessage::Invalid(frame));
+                    }
+
+                    match envelope.as_mut() {
+                        Some(envelope) => {
+                            envelope.results.append(&mut payload.results);
+                            envelope.poll_counts.append(&mut payload.poll_counts);
+                            envelope.virtual_time.append(&mut payload.virtual_time);
+                            envelope
+                                .unaccounted_cycles
+                                .append(&mut payload.unaccounted_cycles);
+                        }
+                        None => envelope = Some(payload),
+                    }
+                    next_index += 1;
+
+                    if next_index >= total {
+                        return Ok(IncomingMessage::BenchmarkResults(
+                            envelope.expect("At least one chunk was received"),
+                        ));
+                    }
+                }
+                IncomingMessage::BenchmarkResults(mut envelope) => {
+                    if !envelope.unpack() {
+                        let frame = String::from_utf8_lossy(&frame).trim().to_string();
+                        return Ok(IncomingMessage::Invalid(frame));
+                    }
+                    return Ok(IncomingMessage::BenchmarkResults(envelope));
+                }
+                IncomingMessage::Log { level, text } => {
+                    if matches!(level, LogLevel::Warn | LogLevel::Error) {
+                        self.warnings += 1;
+                    }
+                    match self.logs.as_mut() {
+                        Some(logs) => logs.push(format!("[suite {level:?}] {text}")),
+                        None => eprintln!("[suite {level:?}] {text}"),
+                    }
+                }
+                IncomingMessage::Accepted {
+                    expected_cycles_hint,
+                    ..
+                } => {
+                    if let Some(timeout) =
+                        expected_cycles_hint.and_then(|cycles| self.hinted_timeout(cycles))
+                    {
+                        self.serial.set_timeout(timeout)?;
+                    }
+                }
+                msg => return Ok(msg),
+            }
+        }
+    }
+
+    /// Resynchronizes the framing of the communication with the suite,
+    /// fails if any errors occur during communication using the SerialConnection.
+    ///
+    /// Sends the resync token and skips all output of the suite, until it acknowledged the token.
+    /// Any partial line the suite received before is discarded together with the token.
+    /// The token carries a unique identifier, so earlier tokens sent by the suite, e.g. at startup,
+    /// are not mistaken for the acknowledgement.
+    pub fn resync(&mut self) -> Result<(), std::io::Error> {
+        let id = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_nanos())
+            .to_string();
+        let mut frame = format!("{RESYNC_TOKEN}{id}").into_bytes();
+        frame.push(self.serial.format().delimiter());
+        self.serial.write_frame(&frame)?;
+
+        while parse_resync(&String::from_utf8_lossy(&self.serial.read_frame()?)) != Some(&id) {}
+
+        Ok(())
+    }
+
+    /// Resynchronizes with a suite that stopped responding, e.g. as it was reset or is still
+    /// performing a benchmark, and waits until it reports that it is ready.
+    /// Fails if the suite did not recover within the timeout,
+    /// or if any other errors occur during communication using the SerialConnection.
+    ///
+    /// The resync token is sent again every `PROBE_TIMEOUT`, as a suite that is starting may miss
+    /// it. The timeout is reset to `DEFAULT_TIMEOUT` afterwards.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - the time the suite has to recover
+    pub fn recover(&mut self, timeout: Duration) -> Result<(), std::io::Error> {
+        let start = Instant::now();
+        self.set_timeout(PROBE_TIMEOUT)?;
+
+        let result = loop {
+            let ready = self.resync().and_then(|()| {
+                self.send_message(&OutgoingMessage::GetStatus)?;
+                loop {
+                    if let IncomingMessage::Status(SuiteStatus::Ready) = self.read_message()? {
+                        return Ok(());
+                    }
+                }
+            });
+            match ready {
+                Err(err) if err.kind() == ErrorKind::TimedOut && start.elapsed() < timeout => {}
+                result => break result,
+            }
+        };
+
+        self.set_timeout(DEFAULT_TIMEOUT)?;
+        result
+    }
+
+    /// Selects whether the log messages of the suite are collected instead of printed,
+    /// check `take_logs`. Collected messages that were not taken are printed once collecting stops
+    ///
+    /// # Arguments
+    ///
+    /// * `collect` - whether log messages are collected
+    pub fn collect_logs(&mut self, collect: bool) {
+        if collect {
+            self.logs.get_or_insert_with(Vec::new);
+        } else {
+            for log in self.logs.take().unwrap_or_default() {
+                eprintln!("{log}");
+            }
+        }
+    }
+
+    /// Returns the log messages of the suite collected since the last call, formatted like printed
+    pub fn take_logs(&mut self) -> Vec<String> {
+        self.logs.as_mut().map(std::mem::take).unwrap_or_default()
+    }
+
+    /// Returns the number of frames received from the suite, including those not returned as messages
+    pub fn frames_received(&self) -> usize {
+        self.frames
+    }
+
+    /// Returns the number of log messages of level Warn or Error received from the suite
+    pub fn warnings_logged(&self) -> usize {
+        self.warnings
+    }
+
+    /// Returns the round-trip latency measured by the last ping, zero if the suite was not pinged
+    pub fn latency(&self) -> Duration {
+        self.latency
+    }
+
+    /// Measures the round-trip latency of the connection, by sending a Ping and waiting for the
+    /// matching Pong. Fails if the suite does not answer within `PING_TIMEOUT`,
+    /// or if any errors occur during communication using the SerialConnection.
+    ///
+    /// Other messages received while waiting, e.g. a Pong to an earlier Ping, are skipped.
+    /// The measured latency is added to all later response timeouts.
+    /// The timeout is reset to `DEFAULT_TIMEOUT` afterwards.
+    pub fn ping(&mut self) -> Result<Duration, std::io::Error> {
+        let value = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.subsec_nanos());
+
+        self.set_timeout(PING_TIMEOUT)?;
+        let start = Instant::now();
+        self.send_message(&OutgoingMessage::Ping(value))?;
+
+        let result = loop {
+            match self.read_message() {
+                Ok(IncomingMessage::Pong(pong)) if pong == value => {
+                    self.latency = start.elapsed();
+                    break Ok(self.latency);
+                }
+                Ok(_) => {}
+                Err(err) => break Err(err),
+            }
+        };
+
+        self.set_timeout(DEFAULT_TIMEOUT)?;
+        result
+    }
+
+    /// Sets the timeout used when waiting for messages sent by the suite
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - the new timeout
+    pub fn set_timeout(&mut self, timeout: Duration) -> Result<(), Error> {
+        self.hinted = false;
+        self.serial.set_timeout(timeout)
+    }
+
+    /// Sets the timeout used when waiting for the response to the next request,
+    /// the round-trip latency measured by the last ping is added to it.
+    ///
+    /// Without an explicit timeout `DEFAULT_TIMEOUT` is used, until the suite accepts a benchmark
+    /// with a hint of the cycles it expects to take. The timeout is then derived from the hint,
+    /// provided the clock frequency of the suite is known.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - the explicit timeout, if any
+    pub fn set_response_timeout(&mut self, timeout: Option<Duration>) -> Result<(), Error> {
+        self.serial
+            .set_timeout(timeout.unwrap_or(DEFAULT_TIMEOUT) + self.latency)?;
+        self.hinted = timeout.is_none();
+        Ok(())
+    }
+
+    /// Sets the frequency of the clock of the suite, which is needed to derive timeouts from the
+    /// cycles the suite expects benchmarks to take
+    ///
+    /// # Arguments
+    ///
+    /// * `clock_frequency` - the frequency in Hz, None if it is not known
+    pub fn set_clock_frequency(&mut self, clock_frequency: Option<u64>) {
+        self.clock_frequency = clock_frequency;
+    }
+
+    /// Returns the timeout derived from the cycles the suite expects a benchmark to take,
+    /// or None if no timeout should be derived
+    ///
+    /// # Arguments
+    ///
+    /// * `cycles` - the cycles the suite expects the benchmark to take
+    fn hinted_timeout(&self, cycles: u64) -> Option<Duration> {
+        let frequency = self.clock_frequency.filter(|&frequency| frequency > 0)?;
+        if !self.hinted {
+            return None;
+        }
+
+        let nanos = cycles as u128 * 1_000_000_000 / frequency as u128;
+        let expected = Duration::from_nanos(u64::try_from(nanos).ok()?);
+        expected
+            .checked_mul(HINT_FACTOR)?
+            .checked_add(HINT_MARGIN + self.latency)
     }
 
     /// Send a message to the suite
@@ -102,7 +712,119 @@ impl SuiteConnection {
     /// # Arguments
     ///
     /// * `msg` - the message that should be sent to the suite
-    pub fn send_message(&mut self, msg: &OutgoingMessage) {
-        writeln!(self.serial, "{}", serialize(msg)).expect("Failed to write to serial");
+    pub fn send_message(&mut self, msg: &OutgoingMessage) -> Result<(), std::io::Error> {
+        self.serial
+            .write_frame(&serialize_as(self.serial.format(), msg))
+    }
+
+    /// Send a message to the suite and read the next message it sends, usually the response
+    ///
+    /// # Arguments
+    ///
+    /// * `msg` - the message that should be sent to the suite
+    pub fn request(&mut self, msg: &OutgoingMessage) -> Result<IncomingMessage, std::io::Error> {
+        self.send_message(msg)?;
+        self.read_message()
+    }
+
+    /// Performs a benchmark once and returns its result
+    ///
+    /// Fails if the suite does not perform the benchmark, check `run_benchmark_with`.
+    ///
+    /// # Arguments
+    ///
+    /// * `info` - the benchmark that should be performed
+    pub fn run_benchmark(
+        &mut self,
+        info: BenchmarkInfo,
+    ) -> Result<BenchmarkResult, BenchmarkError> {
+        let mut envelope = self.run_benchmark_with(info, BenchmarkConfig::samples(1))?;
+        if envelope.results.is_empty() {
+            return Err(BenchmarkError::Unexpected(Box::new(
+                IncomingMessage::BenchmarkResults(envelope),
+            )));
+        }
+        Ok(envelope.results.swap_remove(0))
+    }
+
+    /// Performs a benchmark as configured and returns its results
+    ///
+    /// Fails if any errors occur during communication using the SerialConnection, if the suite
+    /// skips the benchmark, if the output of the benchmark does not match the expected output,
+    /// or if the suite responds with anything but results. The windows of duty-cycled benchmarks
+    /// are not returned, as they are followed by their results.
+    ///
+    /// # Arguments
+    ///
+    /// * `info` - the benchmark that should be performed
+    /// * `config` - how the benchmark should be performed and its results reported
+    pub fn run_benchmark_with(
+        &mut self,
+        info: BenchmarkInfo,
+        config: BenchmarkConfig,
+    ) -> Result<ResultEnvelope, BenchmarkError> {
+        self.send_message(&OutgoingMessage::Benchmark(info, config))
+            .map_err(BenchmarkError::Connection)?;
+
+        loop {
+            match self.read_message().map_err(BenchmarkError::Connection)? {
+                IncomingMessage::Window { .. } => {}
+                IncomingMessage::BenchmarkResults(envelope) => {
+                    return match envelope.verification_failure.clone() {
+                        Some(failure) => Err(BenchmarkError::VerificationFailed(failure)),
+                        None => Ok(envelope),
+                    };
+                }
+                IncomingMessage::Skipped { reason, .. } => {
+                    return Err(BenchmarkError::Skipped(reason))
+                }
+                msg => return Err(BenchmarkError::Unexpected(Box::new(msg))),
+            }
+        }
+    }
+}
+
+/// Failure of a benchmark requested with `SuiteConnection::run_benchmark`
+#[derive(Debug)]
+pub enum BenchmarkError {
+    /// Communicating with the suite failed
+    Connection(Error),
+    /// The suite did not perform the benchmark
+    Skipped(SkipReason),
+    /// The output of the benchmark did not match the expected output
+    VerificationFailed(VerificationFailure),
+    /// The suite responded with something other than results, like an error
+    Unexpected(Box<IncomingMessage>),
+}
+
+impl BenchmarkError {
+    /// Returns the exit code signaling this failure, as used when benchmarking files
+    pub fn exit_code(&self) -> ExitCode {
+        match self {
+            BenchmarkError::Connection(_) => ExitCode::ConnectionFailure,
+            // Skipping is an expected outcome on platforms with fewer modules
+            BenchmarkError::Skipped(_) => ExitCode::Success,
+            BenchmarkError::VerificationFailed(_) => ExitCode::VerificationFailure,
+            BenchmarkError::Unexpected(msg) => {
+                ExitCode::of_message(msg).or(ExitCode::ProtocolError)
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for BenchmarkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BenchmarkError::Connection(err) => write!(f, "Connection lost: {err}"),
+            BenchmarkError::Skipped(reason) => write!(f, "Benchmark was skipped: {reason:?}"),
+            BenchmarkError::VerificationFailed(failure) => {
+                write!(f, "Verification of {} failed", failure.benchmark)?;
+                if let Some(block) = failure.block_index {
+                    write!(f, " at block {block}")?;
+                }
+                Ok(())
+            }
+            BenchmarkError::Unexpected(msg) => write!(f, "Unexpected response: {msg:?}"),
+        }
     }
 }
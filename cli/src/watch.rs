@@ -0,0 +1,59 @@
+//! Watching of the build of the suite, for a tight edit-measure loop during development.
+//!
+//! With `--watch`, the CLI benchmarks the suite, then waits until its ELF changes, e.g. as it was
+//! rebuilt, and benchmarks it again: the new build is launched or flashed, and all files are
+//! processed again. The file is polled, so it is noticed no matter how the build replaces it.
+//! A change is only reported once the file stopped changing, so a build that is still being
+//! written is not flashed.
+
+use std::{
+    fs,
+    path::Path,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+/// Time between two checks of the watched file
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// State of a watched file: its modification time and length, None if it does not exist
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stamp(Option<(SystemTime, u64)>);
+
+impl Stamp {
+    /// Returns the current state of the file
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - the path of the file
+    pub fn of(path: &Path) -> Stamp {
+        Stamp(
+            fs::metadata(path)
+                .and_then(|metadata| Ok((metadata.modified()?, metadata.len())))
+                .ok(),
+        )
+    }
+}
+
+/// Waits until the file changed and stopped changing, returns its new state.
+/// A file that was removed is waited for until it exists again.
+///
+/// # Arguments
+///
+/// * `path` - the path of the file
+/// * `last` - the state of the file the change is relative to
+pub fn wait_for_change(path: &Path, last: Stamp) -> Stamp {
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let current = Stamp::of(path);
+        if current == last || current.0.is_none() {
+            continue;
+        }
+
+        // A file that changed again is still being written, it is checked again next time
+        thread::sleep(POLL_INTERVAL);
+        if Stamp::of(path) == current {
+            return current;
+        }
+    }
+}
@@ -0,0 +1,62 @@
+//! Recovery from benchmarks that hang.
+//!
+//! Without a watchdog, a benchmark the suite does not respond to stops the whole run once the
+//! timeout expired. With a watchdog, a benchmark that does not respond within its deadline is
+//! recorded as hanging and the run continues: if a reset hook is configured, e.g. a command
+//! toggling the reset line of the board, it is run first. The CLI then resynchronizes with the
+//! suite until it reports that it is ready again, and sends the messages following the benchmark
+//! again, as the suite lost them when it was reset.
+//!
+//! Without a reset hook, the suite has to finish the benchmark on its own, the responses to the
+//! messages it processed in the meantime are discarded while resynchronizing.
+
+use std::{process::Command, str::FromStr, time::Duration};
+
+/// Command resetting the board, e.g. `opentitantool gpio write RESET false`
+///
+/// The command is split at whitespace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResetHook {
+    program: String,
+    arguments: Vec<String>,
+}
+
+impl ResetHook {
+    /// Runs the command, returns a description of the failure if it did not succeed
+    pub fn run(&self) -> Result<(), String> {
+        let status = Command::new(&self.program)
+            .args(&self.arguments)
+            .status()
+            .map_err(|err| format!("Failed to start {}: {err}", self.program))?;
+        if !status.success() {
+            return Err(format!("{} failed with {status}", self.program));
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for ResetHook {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut words = s.split_whitespace().map(String::from);
+        match words.next() {
+            Some(program) => Ok(ResetHook {
+                program,
+                arguments: words.collect(),
+            }),
+            None => Err(String::from("The reset hook is empty")),
+        }
+    }
+}
+
+/// Describes how benchmarks that hang are detected and recovered from
+#[derive(Debug, Clone)]
+pub struct Watchdog {
+    /// Time the suite has to respond to a message without an annotated timeout,
+    /// messages annotated with `# timeout` hang once their timeout expired
+    pub deadline: Duration,
+    /// Command resetting the board once a message hangs, if any
+    pub reset: Option<ResetHook>,
+}
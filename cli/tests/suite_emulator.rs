@@ -0,0 +1,695 @@
+//! Performs benchmark files against a mocked suite, so the whole message flow of the CLI is
+//! exercised without hardware.
+//!
+//! The mock runs on a thread of the test, connected to the CLI over a pseudo terminal pair: the
+//! CLI opens the slave side like the tty of a board, the mock speaks the protocol of the suite on
+//! the master side. It acknowledges resync tokens and answers every request, benchmarks with canned
+//! results, until the tty is closed. The master side is only closed then, as buffered frames are
+//! lost when it is closed early. The mock can also listen on a TCP or Unix domain socket instead,
+//! like an emulator exposing the UART over a socket.
+//!
+//! Faults can be injected into the responses to benchmarks, like corrupted lines, frames that are
+//! cut off or split up, delays and disconnects, so the resync, timeout and error paths of the
+//! connection are covered. Every resync token is acknowledged only after unrelated output, a stale
+//! token and a partial frame, like a board that was running before the CLI connected sends them.
+
+use std::{
+    env, fs,
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    os::unix::net::UnixListener,
+    path::PathBuf,
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use benchmark_common::{
+    codec::WireFormat, corpus, parse_resync, BenchmarkInfo, BenchmarkResult, DatasetDescriptor,
+    DatasetKind, IncomingMessage, OutgoingMessage, ResultEnvelope, SuiteStatus,
+    VerificationFailure, RESYNC_TOKEN,
+};
+use cli::{
+    benchmark_raw_file, connect, perform_plan,
+    plan::parse_plan,
+    transport::Endpoint,
+    tty::{BenchmarkError, ConnectionOptions, Handshake},
+    watchdog::Watchdog,
+    ExitCode, FileError, RunOptions,
+};
+use serialport::{SerialPort, TTYPort};
+
+/// Format of the frames exchanged with the mock
+const FORMAT: WireFormat = WireFormat::Json;
+
+/// How the mock answers benchmarks
+#[derive(Clone, Copy)]
+enum Behaviour {
+    /// Every benchmark is answered with canned results
+    Results,
+    /// The results of every benchmark report that its output did not match the expected output
+    VerificationFailure,
+}
+
+/// Fault injected into the response to a benchmark
+#[derive(Clone, Copy)]
+enum Fault {
+    /// A corrupted line is sent before the response
+    Corrupt,
+    /// The first half of the response is sent, followed by a resync token, before the response
+    Cut,
+    /// The response is sent in two parts, with the given time between them
+    Split(Duration),
+    /// The response is sent after the given time
+    Delay(Duration),
+    /// The connection is closed instead of responding
+    Disconnect,
+}
+
+/// A mocked suite, running until the tty or socket was closed
+struct MockSuite {
+    /// Slave side of the pseudo terminal, kept open until the mock is stopped, as the master side
+    /// is closed once no slave is open anymore. None if the mock listens on a socket
+    slave: Option<TTYPort>,
+    /// The tty or socket the CLI connects to
+    endpoint: Endpoint,
+    /// Returns the requests the mock received, in order
+    handle: JoinHandle<Vec<OutgoingMessage>>,
+}
+
+impl MockSuite {
+    /// Starts a mock on a new pseudo terminal pair
+    ///
+    /// # Arguments
+    ///
+    /// * `behaviour` - how benchmarks are answered
+    /// * `faults` - the faults injected into the responses, with the index of the benchmark whose
+    ///   response they are injected into, starting at 0
+    fn spawn(behaviour: Behaviour, faults: &[(usize, Fault)]) -> MockSuite {
+        let (mut master, slave) = TTYPort::pair().expect("Can not create a pseudo terminal");
+        master
+            .set_timeout(Duration::from_secs(30))
+            .expect("Can not set the timeout of the pseudo terminal");
+        let tty = slave.name().expect("The pseudo terminal has no name");
+
+        let faults = faults.to_vec();
+        let handle = thread::spawn(move || {
+            let writer = master.try_clone_native().expect("Can not clone the port");
+            serve(master, writer, behaviour, faults)
+        });
+
+        MockSuite {
+            slave: Some(slave),
+            endpoint: Endpoint::Tty(tty.into()),
+            handle,
+        }
+    }
+
+    /// Starts a mock listening on a TCP socket, like an emulator exposing the UART over TCP
+    ///
+    /// # Arguments
+    ///
+    /// * `behaviour` - how benchmarks are answered
+    /// * `faults` - the faults injected into the responses, check `spawn`
+    fn spawn_tcp(behaviour: Behaviour, faults: &[(usize, Fault)]) -> MockSuite {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Can not bind a socket");
+        let address = listener.local_addr().expect("The socket has no address");
+
+        let faults = faults.to_vec();
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().expect("The CLI did not connect");
+            stream
+                .set_read_timeout(Some(Duration::from_secs(30)))
+                .expect("Can not set the timeout of the socket");
+            let writer = stream.try_clone().expect("Can not clone the socket");
+            serve(stream, writer, behaviour, faults)
+        });
+
+        MockSuite {
+            slave: None,
+            endpoint: Endpoint::Tcp(address.to_string()),
+            handle,
+        }
+    }
+
+    /// Starts a mock listening on a Unix domain socket, like QEMU with `-serial unix:`
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the name of the socket, unique among the tests
+    /// * `behaviour` - how benchmarks are answered
+    /// * `faults` - the faults injected into the responses, check `spawn`
+    fn spawn_unix(name: &str, behaviour: Behaviour, faults: &[(usize, Fault)]) -> MockSuite {
+        let path = raw_file(name, "");
+        fs::remove_file(&path).expect("Can not replace the file by the socket");
+        let listener = UnixListener::bind(&path).expect("Can not bind a socket");
+
+        let faults = faults.to_vec();
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().expect("The CLI did not connect");
+            stream
+                .set_read_timeout(Some(Duration::from_secs(30)))
+                .expect("Can not set the timeout of the socket");
+            let writer = stream.try_clone().expect("Can not clone the socket");
+            serve(stream, writer, behaviour, faults)
+        });
+
+        MockSuite {
+            slave: None,
+            endpoint: Endpoint::Unix(path),
+            handle,
+        }
+    }
+
+    /// Returns the options connecting the CLI to the mock
+    fn connection(&self) -> ConnectionOptions {
+        ConnectionOptions {
+            endpoint: self.endpoint.clone(),
+            handshake: Handshake::Resync,
+            format: Some(FORMAT),
+            transcript: None,
+            board: None,
+        }
+    }
+
+    /// Stops the mock once the CLI closed the tty or socket, returns the requests it received
+    fn requests(self) -> Vec<OutgoingMessage> {
+        drop(self.slave);
+        self.handle.join().expect("The mock panicked")
+    }
+}
+
+/// Answers the requests of the CLI, returns the requests that were received
+///
+/// # Arguments
+///
+/// * `reader` - receives the requests, the master side of the pseudo terminal or the socket
+/// * `writer` - sends the responses over the same pseudo terminal or socket
+/// * `behaviour` - how benchmarks are answered
+/// * `faults` - the faults injected into the responses, by index of the benchmark
+fn serve(
+    reader: impl Read,
+    mut writer: impl Write,
+    behaviour: Behaviour,
+    faults: Vec<(usize, Fault)>,
+) -> Vec<OutgoingMessage> {
+    let mut reader = BufReader::new(reader);
+    let mut requests = Vec::new();
+    let mut benchmarks = 0;
+
+    loop {
+        let mut frame = Vec::new();
+        match reader.read_until(FORMAT.delimiter(), &mut frame) {
+            Ok(0) | Err(_) => return requests,
+            Ok(_) => {}
+        }
+        if frame.last() == Some(&FORMAT.delimiter()) {
+            frame.pop();
+        }
+
+        if let Some(id) = parse_resync(&String::from_utf8_lossy(&frame)) {
+            let mut token = b"booting".to_vec();
+            token.push(FORMAT.delimiter());
+            token.extend_from_slice(RESYNC_TOKEN.as_bytes());
+            token.push(FORMAT.delimiter());
+            token.extend_from_slice(b"{\"Status\":");
+            token.extend_from_slice(format!("{RESYNC_TOKEN}{id}").as_bytes());
+            token.push(FORMAT.delimiter());
+            write(&mut writer, &token);
+            continue;
+        }
+
+        let request: OutgoingMessage = FORMAT
+            .decode(&frame)
+            .unwrap_or_else(|| panic!("Invalid request {}", String::from_utf8_lossy(&frame)));
+        match &request {
+            OutgoingMessage::GetStatus | OutgoingMessage::Reset => {
+                send(&mut writer, &IncomingMessage::Status(SuiteStatus::Ready))
+            }
+            OutgoingMessage::Ping(value) => send(&mut writer, &IncomingMessage::Pong(*value)),
+            OutgoingMessage::GetPlatform => {
+                send(&mut writer, &IncomingMessage::Platform(corpus::platform()))
+            }
+            OutgoingMessage::Benchmark(info, config) => {
+                let fault = faults
+                    .iter()
+                    .find(|(index, _)| *index == benchmarks)
+                    .map(|(_, fault)| *fault);
+                benchmarks += 1;
+
+                let mut envelope = results(info, config.repetitions);
+                envelope.request_id = config.request_id;
+                envelope.label = config.label.clone();
+                if let Behaviour::VerificationFailure = behaviour {
+                    envelope.verification_failure = Some(VerificationFailure {
+                        benchmark: String::from("ExampleSHA256"),
+                        block_index: None,
+                    });
+                }
+                let msg = IncomingMessage::BenchmarkResults(envelope);
+                match fault {
+                    Some(Fault::Disconnect) => {
+                        requests.push(request);
+                        return requests;
+                    }
+                    Some(fault) => inject(&mut writer, &msg, fault),
+                    None => send(&mut writer, &msg),
+                }
+            }
+            OutgoingMessage::ListDatasets => send(
+                &mut writer,
+                &IncomingMessage::Datasets(vec![DatasetDescriptor {
+                    kind: DatasetKind::Hashing,
+                    id: 1,
+                    name: String::from("sha256_256_bytes"),
+                    size: 256,
+                    key_length: None,
+                    mode: None,
+                }]),
+            ),
+            OutgoingMessage::Done => send(&mut writer, &IncomingMessage::Status(SuiteStatus::Done)),
+            _ => panic!("Unexpected request {request:?}"),
+        }
+        requests.push(request);
+    }
+}
+
+/// Sends a message to the CLI
+///
+/// # Arguments
+///
+/// * `writer` - the master side of the pseudo terminal or the socket
+/// * `msg` - the message
+fn send(writer: &mut impl Write, msg: &IncomingMessage) {
+    write(writer, &frame(msg));
+}
+
+/// Sends a message to the CLI, injecting a fault
+///
+/// # Arguments
+///
+/// * `writer` - the master side of the pseudo terminal or the socket
+/// * `msg` - the message
+/// * `fault` - the fault, other than a disconnect
+fn inject(writer: &mut impl Write, msg: &IncomingMessage, fault: Fault) {
+    let frame = frame(msg);
+    let (first, second) = frame.split_at(frame.len() / 2);
+
+    match fault {
+        Fault::Corrupt => {
+            let mut corrupted = first.to_vec();
+            corrupted.extend_from_slice(b"\x9f\x00}");
+            corrupted.push(FORMAT.delimiter());
+            write(writer, &corrupted);
+            write(writer, &frame);
+        }
+        Fault::Cut => {
+            let mut cut = first.to_vec();
+            cut.extend_from_slice(RESYNC_TOKEN.as_bytes());
+            cut.push(FORMAT.delimiter());
+            write(writer, &cut);
+            write(writer, &frame);
+        }
+        Fault::Split(pause) => {
+            write(writer, first);
+            thread::sleep(pause);
+            write(writer, second);
+        }
+        Fault::Delay(delay) => {
+            thread::sleep(delay);
+            write(writer, &frame);
+        }
+        Fault::Disconnect => unreachable!("Disconnects are not sent"),
+    }
+}
+
+/// Returns the frame of a message, including its delimiter
+///
+/// # Arguments
+///
+/// * `msg` - the message
+fn frame(msg: &IncomingMessage) -> Vec<u8> {
+    let mut frame = FORMAT.encode(msg);
+    frame.push(FORMAT.delimiter());
+    frame
+}
+
+/// Writes bytes to the CLI. Fails silently once the CLI closed the tty or socket, when it gave up
+/// waiting
+///
+/// # Arguments
+///
+/// * `writer` - the master side of the pseudo terminal or the socket
+/// * `bytes` - the bytes
+fn write(writer: &mut impl Write, bytes: &[u8]) {
+    let _ = writer.write_all(bytes).and_then(|()| writer.flush());
+}
+
+/// Returns canned results of a benchmark
+///
+/// # Arguments
+///
+/// * `info` - the benchmark
+/// * `repetitions` - the number of results
+fn results(info: &BenchmarkInfo, repetitions: u32) -> ResultEnvelope {
+    let result = BenchmarkResult::ExampleSHA256 {
+        initialization: 10,
+        computation: 200,
+        reading_output: 30,
+    };
+
+    ResultEnvelope {
+        benchmark: Some(info.clone()),
+        config: None,
+        suite_config: None,
+        poll_counts: Vec::new(),
+        virtual_time: Vec::new(),
+        unaccounted_cycles: Vec::new(),
+        verification_failure: None,
+        retries: 0,
+        isolated: false,
+        ..corpus::envelope(vec![result; repetitions as usize])
+    }
+}
+
+/// Writes a raw benchmark file to a temporary directory, returns its path
+///
+/// # Arguments
+///
+/// * `name` - the name of the file, unique among the tests
+/// * `content` - the messages of the file
+fn raw_file(name: &str, content: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("suite_emulator_{}", std::process::id()));
+    fs::create_dir_all(&dir).expect("Can not create the temporary directory");
+    let path = dir.join(name);
+    fs::write(&path, content).expect("Can not write the benchmark file");
+    path
+}
+
+/// Returns the exit code of a processed file
+///
+/// # Arguments
+///
+/// * `result` - the outcome of processing the file
+fn exit_code(result: Result<ExitCode, FileError>) -> ExitCode {
+    result.unwrap_or_else(|err| err.exit_code())
+}
+
+#[test]
+fn results_are_written() {
+    let mock = MockSuite::spawn(Behaviour::Results, &[]);
+    let path = raw_file(
+        "results.bench",
+        "{\"Benchmark\":[\"ExampleSHA256\",{\"repetitions\":3}]}\n\
+         {\"Benchmark\":[\"ExampleSHA256\",{\"repetitions\":2,\"label\":\"second\"}]}\n",
+    );
+
+    let result = benchmark_raw_file(&mock.connection(), path.clone(), &RunOptions::default());
+    assert_eq!(exit_code(result), ExitCode::Success);
+
+    let requests = mock.requests();
+    let benchmarks = requests
+        .iter()
+        .filter(|request| matches!(request, OutgoingMessage::Benchmark(..)))
+        .count();
+    assert_eq!(benchmarks, 2);
+    assert!(matches!(requests.last(), Some(OutgoingMessage::Done)));
+
+    let output = fs::read_to_string(path.with_extension("result")).unwrap();
+    assert_eq!(output.matches("computation: 200").count(), 5);
+    assert!(output.contains("\"second\""));
+    assert!(output.contains("Done"));
+}
+
+#[test]
+fn metrics_are_exported() {
+    // Accepts a single push like the Pushgateway, returns the request
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let pushgateway = format!("http://{}", listener.local_addr().unwrap());
+    let gateway = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = Vec::new();
+        let mut buffer = [0; 4096];
+        while !String::from_utf8_lossy(&request).contains("# EOF\n") {
+            let read = stream.read(&mut buffer).unwrap();
+            assert_ne!(read, 0, "The push was cut off");
+            request.extend_from_slice(&buffer[..read]);
+        }
+        stream.write_all(b"HTTP/1.1 200 OK\r\n\r\n").unwrap();
+        String::from_utf8(request).unwrap()
+    });
+
+    let mock = MockSuite::spawn(Behaviour::Results, &[]);
+    let path = raw_file(
+        "metrics.bench",
+        "{\"Benchmark\":[\"ExampleSHA256\",{\"repetitions\":2,\"label\":\"sha\"}]}\n",
+    );
+    let options = RunOptions {
+        openmetrics: true,
+        pushgateway: Some(pushgateway.parse().unwrap()),
+        ..RunOptions::default()
+    };
+
+    let result = benchmark_raw_file(&mock.connection(), path.clone(), &options);
+    assert_eq!(exit_code(result), ExitCode::Success);
+    mock.requests();
+
+    let metrics = fs::read_to_string(path.with_extension("prom")).unwrap();
+    let samples: Vec<&str> = metrics
+        .lines()
+        .filter(|line| line.starts_with("benchmark_cycles{"))
+        .collect();
+    assert!(!samples.is_empty());
+    assert!(samples
+        .iter()
+        .all(|sample| sample.contains("label=\"sha\"")));
+    assert!(samples
+        .iter()
+        .any(|sample| sample.contains("phase=\"computation\"") && sample.ends_with("} 200")));
+    assert!(metrics.ends_with("# EOF\n"));
+
+    let request = gateway.join().unwrap();
+    assert!(request.starts_with("PUT /metrics/job/cycle_benchmark/file@base64/"));
+    assert!(request.ends_with(&metrics));
+}
+
+#[test]
+fn times_are_annotated() {
+    let mock = MockSuite::spawn(Behaviour::Results, &[]);
+    let path = raw_file(
+        "times.bench",
+        "{\"Benchmark\":[{\"HashingDataSet\":1},{\"repetitions\":1}]}\n",
+    );
+
+    let result = benchmark_raw_file(&mock.connection(), path.clone(), &RunOptions::default());
+    assert_eq!(exit_code(result), ExitCode::Success);
+    assert!(mock
+        .requests()
+        .iter()
+        .any(|request| matches!(request, OutgoingMessage::ListDatasets)));
+
+    // 200 cycles at the 100 MHz of the platform, processing the 256 bytes of the dataset
+    let output = fs::read_to_string(path.with_extension("result")).unwrap();
+    assert!(output.contains("-- time of ExampleSHA256 result 0 at 100000000 Hz --"));
+    assert!(output.contains("2.000 us     128.00 MB/s"));
+    assert!(output.contains("0.100 us\n"));
+}
+
+#[test]
+fn suites_are_reached_over_tcp() {
+    let mock = MockSuite::spawn_tcp(
+        Behaviour::Results,
+        &[(0, Fault::Split(Duration::from_millis(50)))],
+    );
+    let path = raw_file(
+        "tcp.bench",
+        "{\"Benchmark\":[\"ExampleSHA256\",{\"repetitions\":2}]}\n",
+    );
+
+    let result = benchmark_raw_file(&mock.connection(), path.clone(), &RunOptions::default());
+    assert_eq!(exit_code(result), ExitCode::Success);
+    assert!(matches!(
+        mock.requests().last(),
+        Some(OutgoingMessage::Done)
+    ));
+
+    let output = fs::read_to_string(path.with_extension("result")).unwrap();
+    assert_eq!(output.matches("computation: 200").count(), 2);
+}
+
+#[test]
+fn suites_are_reached_over_unix_sockets() {
+    let mock = MockSuite::spawn_unix("unix.sock", Behaviour::Results, &[(0, Fault::Corrupt)]);
+    let path = raw_file(
+        "unix.bench",
+        "{\"Benchmark\":[\"ExampleSHA256\",{\"repetitions\":1}]}\n",
+    );
+
+    let result = benchmark_raw_file(&mock.connection(), path.clone(), &RunOptions::default());
+    assert_eq!(exit_code(result), ExitCode::ProtocolError);
+    assert!(matches!(
+        mock.requests().last(),
+        Some(OutgoingMessage::Done)
+    ));
+
+    let output = fs::read_to_string(path.with_extension("result")).unwrap();
+    assert_eq!(output.matches("computation: 200").count(), 1);
+}
+
+#[test]
+fn benchmarks_are_run_through_the_library() {
+    let mock = MockSuite::spawn_tcp(Behaviour::Results, &[]);
+
+    let mut suite = connect(&mock.connection()).expect("Can not connect to the mock");
+    let result = suite
+        .run_benchmark(BenchmarkInfo::ExampleSHA256)
+        .expect("The benchmark failed");
+    assert!(result.phases().contains(&("computation", 200)));
+    drop(suite);
+
+    assert!(matches!(
+        mock.requests().last(),
+        Some(OutgoingMessage::Benchmark(BenchmarkInfo::ExampleSHA256, config)) if config.repetitions == 1
+    ));
+}
+
+#[test]
+fn plans_are_performed_through_the_library() {
+    let mock = MockSuite::spawn(Behaviour::VerificationFailure, &[]);
+    let plan = parse_plan(
+        "[[section]]\nname = \"sha\"\nrepetitions = 2\nbenchmarks = [\"ExampleSHA256\"]\n",
+    )
+    .unwrap();
+
+    let run = perform_plan(&mock.connection(), &plan, &RunOptions::default())
+        .expect("The plan could not be performed");
+    assert_eq!(run.exit_code, ExitCode::VerificationFailure);
+    assert_eq!(run.sections.len(), 1);
+    assert_eq!(run.sections[0].name, "sha");
+    assert!(matches!(
+        run.sections[0].responses.as_slice(),
+        [(BenchmarkInfo::ExampleSHA256, IncomingMessage::BenchmarkResults(envelope))]
+            if envelope.results.len() == 2
+    ));
+
+    let mut suite = connect(&mock.connection()).expect("Can not connect to the mock");
+    assert!(matches!(
+        suite.run_benchmark(BenchmarkInfo::ExampleSHA256),
+        Err(BenchmarkError::VerificationFailed(_))
+    ));
+}
+
+#[test]
+fn verification_failures_are_reported() {
+    let mock = MockSuite::spawn(Behaviour::VerificationFailure, &[]);
+    let path = raw_file(
+        "verification.bench",
+        "{\"Benchmark\":[\"ExampleSHA256\",{\"repetitions\":1}]}\n",
+    );
+
+    let result = benchmark_raw_file(&mock.connection(), path, &RunOptions::default());
+    assert_eq!(exit_code(result), ExitCode::VerificationFailure);
+    mock.requests();
+}
+
+#[test]
+fn lost_connections_are_reported() {
+    let mock = MockSuite::spawn(Behaviour::Results, &[(1, Fault::Disconnect)]);
+    let path = raw_file(
+        "lost.bench",
+        "{\"Benchmark\":[\"ExampleSHA256\",{\"repetitions\":1}]}\n\
+         {\"Benchmark\":[\"ExampleSHA256\",{\"repetitions\":1}]}\n",
+    );
+
+    let result = benchmark_raw_file(&mock.connection(), path, &RunOptions::default());
+    assert_eq!(exit_code(result), ExitCode::ConnectionFailure);
+    mock.requests();
+}
+
+#[test]
+fn cut_and_split_frames_are_reassembled() {
+    let faults = [
+        (0, Fault::Cut),
+        (1, Fault::Split(Duration::from_millis(50))),
+        (2, Fault::Delay(Duration::from_millis(50))),
+    ];
+    let mock = MockSuite::spawn(Behaviour::Results, &faults);
+    let path = raw_file(
+        "reassembled.bench",
+        "{\"Benchmark\":[\"ExampleSHA256\",{\"repetitions\":1}]}\n\
+         {\"Benchmark\":[\"ExampleSHA256\",{\"repetitions\":1}]}\n\
+         # timeout: 5s\n\
+         {\"Benchmark\":[\"ExampleSHA256\",{\"repetitions\":1}]}\n",
+    );
+
+    let result = benchmark_raw_file(&mock.connection(), path.clone(), &RunOptions::default());
+    assert_eq!(exit_code(result), ExitCode::Success);
+    mock.requests();
+
+    let output = fs::read_to_string(path.with_extension("result")).unwrap();
+    assert_eq!(output.matches("computation: 200").count(), 3);
+}
+
+#[test]
+fn corrupted_lines_are_reported() {
+    let mock = MockSuite::spawn(Behaviour::Results, &[(0, Fault::Corrupt)]);
+    let path = raw_file(
+        "corrupted.bench",
+        "{\"Benchmark\":[\"ExampleSHA256\",{\"repetitions\":1}]}\n",
+    );
+
+    let result = benchmark_raw_file(&mock.connection(), path, &RunOptions::default());
+    assert_eq!(exit_code(result), ExitCode::ProtocolError);
+    mock.requests();
+}
+
+#[test]
+fn late_responses_time_out() {
+    let mock = MockSuite::spawn(
+        Behaviour::Results,
+        &[(0, Fault::Delay(Duration::from_millis(500)))],
+    );
+    let path = raw_file(
+        "late.bench",
+        "# timeout: 100ms\n\
+         {\"Benchmark\":[\"ExampleSHA256\",{\"repetitions\":1}]}\n",
+    );
+
+    let result = benchmark_raw_file(&mock.connection(), path, &RunOptions::default());
+    assert_eq!(exit_code(result), ExitCode::ConnectionFailure);
+    mock.requests();
+}
+
+#[test]
+fn hanging_benchmarks_are_recovered_from() {
+    let mock = MockSuite::spawn(
+        Behaviour::Results,
+        &[(0, Fault::Delay(Duration::from_millis(500)))],
+    );
+    let path = raw_file(
+        "hang.bench",
+        "{\"Benchmark\":[\"ExampleSHA256\",{\"repetitions\":1}]}\n\
+         {\"Benchmark\":[\"ExampleSHA256\",{\"repetitions\":2}]}\n",
+    );
+    let options = RunOptions {
+        watchdog: Some(Watchdog {
+            deadline: Duration::from_millis(100),
+            reset: Some("true".parse().unwrap()),
+        }),
+        ..RunOptions::default()
+    };
+
+    let result = benchmark_raw_file(&mock.connection(), path.clone(), &options);
+    assert_eq!(exit_code(result), ExitCode::Hang);
+
+    // The message following the hanging benchmark is sent again after recovering
+    let requests = mock.requests();
+    let benchmarks = requests
+        .iter()
+        .filter(|request| matches!(request, OutgoingMessage::Benchmark(..)))
+        .count();
+    assert_eq!(benchmarks, 3);
+
+    let output = fs::read_to_string(path.with_extension("result")).unwrap();
+    assert!(output.contains("-- hang: no response within 100ms --"));
+    assert_eq!(output.matches("computation: 200").count(), 2);
+}
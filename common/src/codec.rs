@@ -0,0 +1,311 @@
+//! Codecs used to encode messages into frames, that are exchanged between the Suite and the CLI.
+//!
+//! The codec is selected at compile time, serde_json is used unless the `codec_postcard`
+//! or `codec_cbor` feature is enabled. The Suite and the CLI have to be built with the same codec.
+//!
+//! Every frame is terminated by the delimiter of the codec. Binary codecs use COBS to ensure that
+//! the delimiter does not occur inside a frame.
+//!
+//! The CLI can also select the format at runtime using `WireFormat`, which supports JSON in addition
+//! to the binary codec selected by the features, so one build can talk to suites using either.
+//!
+//! Links that are not 8-bit clean, like some FPGA UART bridges, can be used by enabling the
+//! `frame_armor` feature, which hex encodes every frame of the selected codec between start and end
+//! markers, so only printable characters and newlines are transferred.
+
+#[allow(unused_imports)]
+use alloc::{format, string::String, vec::Vec};
+use core::{marker::PhantomData, str::FromStr};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::hex;
+
+#[cfg(all(feature = "codec_postcard", feature = "codec_cbor"))]
+compile_error!("The features codec_postcard and codec_cbor are mutually exclusive");
+
+/// The codec selected by the enabled features, before armoring
+#[cfg(feature = "codec_postcard")]
+pub type BaseCodec = PostcardCodec;
+/// The codec selected by the enabled features, before armoring
+#[cfg(all(feature = "codec_cbor", not(feature = "codec_postcard")))]
+pub type BaseCodec = CborCodec;
+/// The codec selected by the enabled features, before armoring
+#[cfg(not(any(feature = "codec_postcard", feature = "codec_cbor")))]
+pub type BaseCodec = JsonCodec;
+
+/// The codec selected by the enabled features
+#[cfg(feature = "frame_armor")]
+pub type Codec = ArmoredCodec<BaseCodec>;
+/// The codec selected by the enabled features
+#[cfg(not(feature = "frame_armor"))]
+pub type Codec = BaseCodec;
+
+/// Applies the armor to a codec, if enabled
+#[cfg(feature = "frame_armor")]
+type Armored<C> = ArmoredCodec<C>;
+/// Applies the armor to a codec, if enabled
+#[cfg(not(feature = "frame_armor"))]
+type Armored<C> = C;
+
+/// Format of the frames, selected at runtime.
+///
+/// Every build supports JSON and the binary codec enabled by its features, if any.
+/// If `frame_armor` is enabled, the frames of every format are armored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WireFormat {
+    Json,
+    #[cfg(feature = "codec_postcard")]
+    Postcard,
+    #[cfg(feature = "codec_cbor")]
+    Cbor,
+}
+
+impl WireFormat {
+    /// The format of the codec selected by the enabled features
+    #[cfg(feature = "codec_postcard")]
+    pub const DEFAULT: WireFormat = WireFormat::Postcard;
+    /// The format of the codec selected by the enabled features
+    #[cfg(all(feature = "codec_cbor", not(feature = "codec_postcard")))]
+    pub const DEFAULT: WireFormat = WireFormat::Cbor;
+    /// The format of the codec selected by the enabled features
+    #[cfg(not(any(feature = "codec_postcard", feature = "codec_cbor")))]
+    pub const DEFAULT: WireFormat = WireFormat::Json;
+
+    /// All formats supported by this build, the default one first
+    #[cfg(feature = "codec_postcard")]
+    pub const SUPPORTED: &'static [WireFormat] = &[WireFormat::Postcard, WireFormat::Json];
+    /// All formats supported by this build, the default one first
+    #[cfg(all(feature = "codec_cbor", not(feature = "codec_postcard")))]
+    pub const SUPPORTED: &'static [WireFormat] = &[WireFormat::Cbor, WireFormat::Json];
+    /// All formats supported by this build, the default one first
+    #[cfg(not(any(feature = "codec_postcard", feature = "codec_cbor")))]
+    pub const SUPPORTED: &'static [WireFormat] = &[WireFormat::Json];
+
+    /// Byte terminating every frame of this format
+    pub fn delimiter(self) -> u8 {
+        match self {
+            WireFormat::Json => Armored::<JsonCodec>::DELIMITER,
+            #[cfg(feature = "codec_postcard")]
+            WireFormat::Postcard => Armored::<PostcardCodec>::DELIMITER,
+            #[cfg(feature = "codec_cbor")]
+            WireFormat::Cbor => Armored::<CborCodec>::DELIMITER,
+        }
+    }
+
+    /// Encodes a value into a frame of this format, without the terminating delimiter
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - the value that should be encoded
+    pub fn encode<T: Serialize>(self, value: &T) -> Vec<u8> {
+        match self {
+            WireFormat::Json => Armored::<JsonCodec>::encode(value),
+            #[cfg(feature = "codec_postcard")]
+            WireFormat::Postcard => Armored::<PostcardCodec>::encode(value),
+            #[cfg(feature = "codec_cbor")]
+            WireFormat::Cbor => Armored::<CborCodec>::encode(value),
+        }
+    }
+
+    /// Decodes a frame of this format without the terminating delimiter,
+    /// returns None if the frame is not a valid encoding of a value of type T
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - the frame that should be decoded
+    pub fn decode<T: DeserializeOwned>(self, frame: &[u8]) -> Option<T> {
+        match self {
+            WireFormat::Json => Armored::<JsonCodec>::decode(frame),
+            #[cfg(feature = "codec_postcard")]
+            WireFormat::Postcard => Armored::<PostcardCodec>::decode(frame),
+            #[cfg(feature = "codec_cbor")]
+            WireFormat::Cbor => Armored::<CborCodec>::decode(frame),
+        }
+    }
+}
+
+impl FromStr for WireFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        WireFormat::SUPPORTED
+            .iter()
+            .copied()
+            .find(|format| format!("{format:?}").eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                let supported: Vec<String> = WireFormat::SUPPORTED
+                    .iter()
+                    .map(|format| format!("{format:?}").to_lowercase())
+                    .collect();
+                format!(
+                    "Unsupported wire format '{s}', this build supports {}",
+                    supported.join(", ")
+                )
+            })
+    }
+}
+
+/// Encoding of values into frames
+pub trait MessageCodec {
+    /// Byte terminating every frame, it never occurs inside an encoded frame
+    const DELIMITER: u8;
+
+    /// Encodes a value into a frame, without the terminating delimiter
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - the value that should be encoded
+    fn encode<T: Serialize>(value: &T) -> Vec<u8>;
+
+    /// Decodes a frame without the terminating delimiter,
+    /// returns None if the frame is not a valid encoding of a value of type T
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - the frame that should be decoded
+    fn decode<T: DeserializeOwned>(frame: &[u8]) -> Option<T>;
+}
+
+/// Encodes values as JSON, one value per line
+pub struct JsonCodec;
+
+impl MessageCodec for JsonCodec {
+    const DELIMITER: u8 = b'\n';
+
+    fn encode<T: Serialize>(value: &T) -> Vec<u8> {
+        serde_json::to_vec(value).expect("Can not serialize struct")
+    }
+
+    fn decode<T: DeserializeOwned>(frame: &[u8]) -> Option<T> {
+        serde_json::from_slice(frame).ok()
+    }
+}
+
+/// Encodes values using postcard, framed using COBS
+#[cfg(feature = "codec_postcard")]
+pub struct PostcardCodec;
+
+#[cfg(feature = "codec_postcard")]
+impl MessageCodec for PostcardCodec {
+    const DELIMITER: u8 = 0;
+
+    fn encode<T: Serialize>(value: &T) -> Vec<u8> {
+        cobs_encode(&postcard::to_allocvec(value).expect("Can not serialize struct"))
+    }
+
+    fn decode<T: DeserializeOwned>(frame: &[u8]) -> Option<T> {
+        postcard::from_bytes(&cobs_decode(frame)?).ok()
+    }
+}
+
+/// Encodes values as CBOR, framed using COBS
+#[cfg(feature = "codec_cbor")]
+pub struct CborCodec;
+
+#[cfg(feature = "codec_cbor")]
+impl MessageCodec for CborCodec {
+    const DELIMITER: u8 = 0;
+
+    fn encode<T: Serialize>(value: &T) -> Vec<u8> {
+        let mut encoded = Vec::new();
+        ciborium::ser::into_writer(value, &mut encoded).expect("Can not serialize struct");
+
+        cobs_encode(&encoded)
+    }
+
+    fn decode<T: DeserializeOwned>(frame: &[u8]) -> Option<T> {
+        ciborium::de::from_reader(cobs_decode(frame)?.as_slice()).ok()
+    }
+}
+
+/// Wraps the frames of another codec in ASCII armor: the frame is hex encoded and enclosed
+/// in `ARMOR_START` and `ARMOR_END`, frames are terminated by a newline.
+///
+/// Any bytes before the start marker, e.g. caused by line noise, are ignored when decoding.
+pub struct ArmoredCodec<C>(PhantomData<C>);
+
+/// Marks the start of an armored frame
+pub const ARMOR_START: u8 = b'<';
+/// Marks the end of an armored frame
+pub const ARMOR_END: u8 = b'>';
+
+impl<C: MessageCodec> MessageCodec for ArmoredCodec<C> {
+    const DELIMITER: u8 = b'\n';
+
+    fn encode<T: Serialize>(value: &T) -> Vec<u8> {
+        let frame = C::encode(value);
+        let mut armored = Vec::with_capacity(frame.len() * 2 + 2);
+
+        armored.push(ARMOR_START);
+        hex::encode_into(&frame, &mut armored);
+        armored.push(ARMOR_END);
+
+        armored
+    }
+
+    fn decode<T: DeserializeOwned>(frame: &[u8]) -> Option<T> {
+        let start = frame.iter().rposition(|byte| *byte == ARMOR_START)? + 1;
+        let end = start + frame[start..].iter().position(|byte| *byte == ARMOR_END)?;
+
+        C::decode(&hex::decode(&frame[start..end])?)
+    }
+}
+
+/// Encodes data using consistent overhead byte stuffing, so the result does not contain any zeros
+///
+/// # Arguments
+///
+/// * `data` - the data that should be encoded
+#[allow(dead_code)]
+fn cobs_encode(data: &[u8]) -> Vec<u8> {
+    let mut encoded = Vec::with_capacity(data.len() + data.len() / 254 + 1);
+    let mut code_index = 0;
+    let mut code = 1;
+    encoded.push(0);
+
+    for &byte in data {
+        if byte != 0 {
+            encoded.push(byte);
+            code += 1;
+        }
+
+        if byte == 0 || code == 0xFF {
+            encoded[code_index] = code;
+            code_index = encoded.len();
+            code = 1;
+            encoded.push(0);
+        }
+    }
+    encoded[code_index] = code;
+
+    encoded
+}
+
+/// Decodes data encoded using consistent overhead byte stuffing,
+/// returns None if the data is not a valid encoding
+///
+/// # Arguments
+///
+/// * `encoded` - the encoded data, without the terminating zero
+#[allow(dead_code)]
+fn cobs_decode(encoded: &[u8]) -> Option<Vec<u8>> {
+    let mut data = Vec::with_capacity(encoded.len());
+    let mut index = 0;
+
+    while index < encoded.len() {
+        let code = encoded[index] as usize;
+        let end = index + code;
+        if code == 0 || end > encoded.len() || encoded[index + 1..end].contains(&0) {
+            return None;
+        }
+
+        data.extend_from_slice(&encoded[index + 1..end]);
+        index = end;
+
+        if code < 0xFF && index < encoded.len() {
+            data.push(0);
+        }
+    }
+
+    Some(data)
+}
@@ -0,0 +1,202 @@
+//! Lightweight compression of the values contained in results.
+//!
+//! Results mostly consist of rows of u64s, like the cycles of all phases of a result. Consecutive rows
+//! of the same length are usually similar, as they contain repetitions of the same benchmark.
+//! Every row is therefore encoded as its length, followed by the differences to the previous row
+//! (or to zero, if the previous row has a different length). The differences are zigzag encoded,
+//! so small negative differences stay small, and written as LEB128 varints.
+//!
+//! Self-describing codecs (JSON) carry the encoded bytes as hex string, binary codecs as bytes.
+
+#[allow(unused_imports)]
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::hex;
+
+/// Rows of u64s, delta and varint encoded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackedRows(Vec<u8>);
+
+impl PackedRows {
+    /// Encodes rows of u64s
+    ///
+    /// # Arguments
+    ///
+    /// * `rows` - the rows that should be encoded, they may have different lengths
+    pub fn pack(rows: &[Vec<u64>]) -> PackedRows {
+        let mut encoded = Vec::new();
+        let mut previous: &[u64] = &[];
+
+        for row in rows {
+            write_varint(&mut encoded, row.len() as u64);
+            for (i, value) in row.iter().enumerate() {
+                let reference = reference(previous, row.len(), i);
+                write_varint(&mut encoded, zigzag(value.wrapping_sub(reference) as i64));
+            }
+            previous = row;
+        }
+
+        PackedRows(encoded)
+    }
+
+    /// Decodes the rows, returns None if the data is not a valid encoding
+    pub fn unpack(&self) -> Option<Vec<Vec<u64>>> {
+        let mut rows: Vec<Vec<u64>> = Vec::new();
+        let mut data = self.0.as_slice();
+
+        while !data.is_empty() {
+            let len = read_varint(&mut data)? as usize;
+            // Every value takes at least one byte, which bounds the allocation
+            if len > data.len() {
+                return None;
+            }
+
+            let previous = rows.last().map_or(&[][..], |row| row.as_slice());
+            let mut row = Vec::with_capacity(len);
+            for i in 0..len {
+                let delta = unzigzag(read_varint(&mut data)?) as u64;
+                row.push(reference(previous, len, i).wrapping_add(delta));
+            }
+            rows.push(row);
+        }
+
+        Some(rows)
+    }
+
+    /// Number of bytes of the encoded rows
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// True if no rows were encoded
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Returns the value a value of a row is encoded relative to
+///
+/// # Arguments
+///
+/// * `previous` - the previous row
+/// * `len` - length of the current row
+/// * `index` - index of the value inside the current row
+#[inline]
+fn reference(previous: &[u64], len: usize, index: usize) -> u64 {
+    if previous.len() == len {
+        previous[index]
+    } else {
+        0
+    }
+}
+
+/// Maps signed to unsigned values, so values close to zero are small
+#[inline]
+fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+/// Inverse of `zigzag`
+#[inline]
+fn unzigzag(value: u64) -> i64 {
+    (value >> 1) as i64 ^ -((value & 1) as i64)
+}
+
+/// Appends a value as LEB128 varint
+fn write_varint(data: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        data.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    data.push(value as u8);
+}
+
+/// Reads a LEB128 varint from the start of the data and removes it,
+/// returns None if the data ends before the varint or the varint does not fit into a u64
+fn read_varint(data: &mut &[u8]) -> Option<u64> {
+    let mut value = 0u64;
+
+    for (i, byte) in data.iter().enumerate().take(10) {
+        value |= ((byte & 0x7f) as u64) << (i * 7);
+        if byte & 0x80 == 0 {
+            *data = &data[i + 1..];
+            return Some(value);
+        }
+    }
+
+    None
+}
+
+impl Serialize for PackedRows {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            let mut digits = Vec::new();
+            hex::encode_into(&self.0, &mut digits);
+            serializer.serialize_str(core::str::from_utf8(&digits).expect("Hex digits are ASCII"))
+        } else {
+            serializer.serialize_bytes(&self.0)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for PackedRows {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(PackedRowsVisitor)
+        } else {
+            deserializer.deserialize_bytes(PackedRowsVisitor)
+        }
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for PackedRows {
+    fn schema_name() -> String {
+        String::from("PackedRows")
+    }
+
+    /// Packed rows are described as they are written by self-describing codecs, as hex string
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        let mut schema = String::json_schema(gen).into_object();
+        schema.string().pattern = Some(String::from("^([0-9a-f]{2})*$"));
+        schema.into()
+    }
+}
+
+/// Visitor accepting the hex representation as well as the bytes of packed rows
+struct PackedRowsVisitor;
+
+impl<'de> Visitor<'de> for PackedRowsVisitor {
+    type Value = PackedRows;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("packed rows as hex string or bytes")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<PackedRows, E> {
+        hex::decode(value.as_bytes())
+            .map(PackedRows)
+            .ok_or_else(|| E::custom("invalid hex digits"))
+    }
+
+    fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<PackedRows, E> {
+        Ok(PackedRows(value.to_vec()))
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, value: Vec<u8>) -> Result<PackedRows, E> {
+        Ok(PackedRows(value))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<PackedRows, A::Error> {
+        let mut bytes = Vec::new();
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+        Ok(PackedRows(bytes))
+    }
+}
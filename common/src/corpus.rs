@@ -0,0 +1,467 @@
+//! Canonical messages covering every variant of the messages exchanged between the Suite and the CLI.
+//!
+//! The corpus is used to check that every message survives encoding and decoding with the
+//! selected codec, and serves as reference input when adding codecs or changing the schema.
+//! Every builder returns values with non-default contents, so fields that are lost while
+//! (de)serializing are noticed. When a variant is added to a message, it has to be added here too.
+
+use alloc::{boxed::Box, string::String, vec, vec::Vec};
+
+use crate::{
+    _CliToSuiteMessage, _SuiteToCliMessage, AESSweepMode, Aggregate, BenchmarkConfig,
+    BenchmarkInfo, BenchmarkResult, DatasetDescriptor, DatasetKind, DutyCycle, HashBenchmarkType,
+    LogLevel, ModuleKind, OTBNBenchmarkType, PlatformDescriptor, PollingModule, ResultEnvelope,
+    SkipReason, SuiteConfig, SuiteError, SuiteStatus, SuspendCode, TeardownBenchmarkType,
+    VerificationFailure, VerificationPolicy, WaitStrategy, SCHEMA_VERSION,
+};
+
+/// Returns every benchmark that can be requested, with all of its parameters set
+pub fn benchmark_infos() -> Vec<BenchmarkInfo> {
+    let mut infos = vec![
+        BenchmarkInfo::ExampleSHA256,
+        BenchmarkInfo::ExampleAES256,
+        BenchmarkInfo::ExampleRNG,
+        BenchmarkInfo::ExampleECDSA,
+        BenchmarkInfo::AESTotal,
+        BenchmarkInfo::AESDataSet(1),
+        BenchmarkInfo::GCMDataSet(1),
+        BenchmarkInfo::ChaCha20Poly1305(1),
+        BenchmarkInfo::AESSweep {
+            key_lengths: vec![128, 192, 256],
+            modes: vec![AESSweepMode::ECB, AESSweepMode::CBC, AESSweepMode::CTR],
+            blocks: vec![1, 8],
+        },
+        BenchmarkInfo::AESContextSwitch { blocks: 8 },
+        BenchmarkInfo::AESThroughput { bytes: 4096 },
+        BenchmarkInfo::HashingDataSet(1),
+        BenchmarkInfo::KMACMasking { dataset: 1 },
+        BenchmarkInfo::SHA256Streaming {
+            dataset: 1,
+            bursts: vec![1, 4, 16],
+        },
+        BenchmarkInfo::SHA3 {
+            dataset: 1,
+            digest_lengths: vec![224, 512],
+        },
+        BenchmarkInfo::RNGDataSet(1),
+        BenchmarkInfo::RNGReseed {
+            dataset: 1,
+            additional_data: None,
+        },
+        BenchmarkInfo::RNGReseed {
+            dataset: 1,
+            additional_data: Some(vec![1, 2, 3]),
+        },
+        BenchmarkInfo::RNGGenerateLengths {
+            dataset: 1,
+            lengths: vec![1, 4, 16, 64],
+        },
+        BenchmarkInfo::EntropyLatency,
+        BenchmarkInfo::ECDSA(1),
+    ];
+    infos.extend(
+        hash_types()
+            .into_iter()
+            .map(|hash| BenchmarkInfo::Hash { hash, dataset: 1 }),
+    );
+    infos.extend(
+        [
+            OTBNBenchmarkType::RSA2048Encrypt,
+            OTBNBenchmarkType::RSA2048Decrypt,
+            OTBNBenchmarkType::P256ScalarMult,
+        ]
+        .into_iter()
+        .map(BenchmarkInfo::OTBN),
+    );
+    infos.extend(
+        [
+            TeardownBenchmarkType::AES,
+            TeardownBenchmarkType::KMAC,
+            TeardownBenchmarkType::RNG,
+        ]
+        .into_iter()
+        .map(BenchmarkInfo::Teardown),
+    );
+    infos.extend(
+        [PollingModule::AES, PollingModule::HMAC]
+            .into_iter()
+            .map(|module| BenchmarkInfo::PollingInterval {
+                module,
+                delays: vec![0, 4, 16],
+            }),
+    );
+    infos.push(BenchmarkInfo::MicroBenchmarks);
+    infos.push(BenchmarkInfo::Calibrated(Box::new(
+        BenchmarkInfo::AESDataSet(1),
+    )));
+
+    infos
+}
+
+/// Returns every type of hash computed by the hashing benchmarks
+pub fn hash_types() -> Vec<HashBenchmarkType> {
+    vec![
+        HashBenchmarkType::SHA256,
+        HashBenchmarkType::HmacSHA256 { key_dataset: 1 },
+        HashBenchmarkType::SHAKE128 { output_length: 64 },
+        HashBenchmarkType::SHAKE256 { output_length: 64 },
+        HashBenchmarkType::CSHAKE128 {
+            output_length: 64,
+            function_name: String::from("name"),
+            customization: String::from("bench"),
+        },
+        HashBenchmarkType::CSHAKE256 {
+            output_length: 64,
+            function_name: String::new(),
+            customization: String::from("bench"),
+        },
+        HashBenchmarkType::Kmac256 {
+            key_dataset: 1,
+            output_length: 64,
+            customization: String::from("bench"),
+        },
+    ]
+}
+
+/// Returns configs of benchmarks, the default one used by `BenchmarkConfig::samples`
+/// and one setting every field
+pub fn benchmark_configs() -> Vec<BenchmarkConfig> {
+    vec![
+        BenchmarkConfig::samples(3),
+        BenchmarkConfig {
+            repetitions: 10,
+            warmup_iterations: 2,
+            aggregate: Some(Aggregate::Summary),
+            wait_strategy: WaitStrategy::Nops(16),
+            virtual_time: true,
+            request_id: Some(7),
+            label: Some(String::from("corpus")),
+            retries: 1,
+            duty_cycle: Some(DutyCycle {
+                period: 1_000_000,
+                duration: 60_000_000,
+            }),
+        },
+    ]
+}
+
+/// Returns configs of the Suite, the default one and one differing in every field
+pub fn suite_configs() -> Vec<SuiteConfig> {
+    vec![
+        SuiteConfig::DEFAULT,
+        SuiteConfig {
+            verification: VerificationPolicy::Skip,
+            aggregate: Aggregate::Summary,
+            log_level: LogLevel::Warn,
+            icache: true,
+            compression: true,
+            seed: Some(0x0123_4567_89ab_cdef),
+            isolation: true,
+        },
+    ]
+}
+
+/// Returns a result of every benchmark, with distinct cycles for every phase
+pub fn benchmark_results() -> Vec<BenchmarkResult> {
+    vec![
+        BenchmarkResult::ExampleSHA256 {
+            initialization: 1,
+            computation: 2,
+            reading_output: 3,
+        },
+        BenchmarkResult::ExampleAES256 {
+            enc_initialization: 1,
+            enc_computation: 2,
+            enc_deinitalization: 3,
+            dec_initialization: 4,
+            dec_computation: 5,
+            dec_deinitalization: 6,
+        },
+        BenchmarkResult::ExampleRNG {
+            initialization: 1,
+            generation: 2,
+        },
+        BenchmarkResult::ExampleECDSA {
+            signing: 1,
+            verifying: 2,
+        },
+        BenchmarkResult::AESTotal {
+            computation: 10,
+            input_writes: 1,
+            status_polls: 2,
+            output_reads: 3,
+        },
+        BenchmarkResult::AES {
+            initialization: 1,
+            computation: 2,
+            deinitialization: 3,
+        },
+        BenchmarkResult::AESSweep {
+            key_length: 192,
+            mode: AESSweepMode::CTR,
+            blocks: 8,
+            initialization: 1,
+            computation: 2,
+            deinitialization: 3,
+        },
+        BenchmarkResult::AESThroughput {
+            bytes: 4096,
+            millicycles_per_byte: 0,
+            initialization: 1,
+            computation: 2,
+        },
+        BenchmarkResult::GCM {
+            hash_subkey: 1,
+            encryption: 2,
+            ghash: 3,
+        },
+        BenchmarkResult::ChaCha20Poly1305 {
+            key_setup: 1,
+            encryption: 2,
+            authentication: 3,
+        },
+        BenchmarkResult::Hashing {
+            initialization: 1,
+            computation: 2,
+            reading_output: 3,
+        },
+        BenchmarkResult::HMAC {
+            key_writing: 1,
+            computation: 2,
+            reading_output: 3,
+        },
+        BenchmarkResult::SHA256Streaming {
+            burst: 4,
+            blocks: 2,
+            stalls: 1,
+            cycles_per_block: 1,
+            initialization: 1,
+            computation: 2,
+            reading_output: 3,
+        },
+        BenchmarkResult::SHA3 {
+            digest_length: 384,
+            initialization: 1,
+            computation: 2,
+            reading_output: 3,
+        },
+        BenchmarkResult::XOF {
+            initialization: 1,
+            absorbing: 2,
+            squeezes: vec![3, 4, 5],
+        },
+        BenchmarkResult::KMAC {
+            key_loading: 1,
+            absorbing: 2,
+            squeezes: vec![3, 4],
+        },
+        BenchmarkResult::RNG {
+            initialization: 1,
+            generation: 2,
+        },
+        BenchmarkResult::RNGReseed {
+            initialization: 1,
+            reseed: 2,
+            generation: 3,
+        },
+        BenchmarkResult::RNGGenerateLength {
+            length: 16,
+            blocks: 128,
+            cycles_per_block: 0,
+            initialization: 1,
+            generation: 2,
+        },
+        BenchmarkResult::ECDSA {
+            signing: 1,
+            verifying: 2,
+        },
+        BenchmarkResult::OTBN {
+            loading: 1,
+            execution: 2,
+            reading_output: 3,
+        },
+        BenchmarkResult::Phases(vec![
+            (String::from("constant_key"), 1),
+            (String::from("rekeyed"), 2),
+        ]),
+    ]
+}
+
+/// Returns the descriptor of a simulated platform, with every field set
+pub fn platform() -> PlatformDescriptor {
+    PlatformDescriptor {
+        name: String::from("corpus"),
+        features: vec![String::from("platform_corpus")],
+        clock_frequency: Some(100_000_000),
+        git_hash: Some(String::from("0123456789abcdef")),
+        simulated: true,
+    }
+}
+
+/// Returns an envelope containing the results, with every field set
+///
+/// # Arguments
+///
+/// * `results` - the results contained in the envelope
+pub fn envelope(results: Vec<BenchmarkResult>) -> ResultEnvelope {
+    let phases = |result: &BenchmarkResult, offset: u64| -> Vec<u64> {
+        (0..result.phases().len() as u64)
+            .map(|i| i + offset)
+            .collect()
+    };
+
+    ResultEnvelope {
+        schema_version: SCHEMA_VERSION,
+        platform: platform(),
+        request_id: Some(7),
+        label: Some(String::from("corpus")),
+        benchmark: Some(BenchmarkInfo::ExampleSHA256),
+        config: Some(BenchmarkConfig::samples(results.len() as u32)),
+        suite_config: Some(SuiteConfig::DEFAULT),
+        aggregate: Aggregate::Samples,
+        wait_strategy: WaitStrategy::Backoff,
+        poll_counts: results.iter().map(|result| phases(result, 1)).collect(),
+        virtual_time: results.iter().map(|result| phases(result, 2)).collect(),
+        unaccounted_cycles: (0..results.len() as u64).collect(),
+        verification_failure: Some(VerificationFailure {
+            benchmark: String::from("ExampleSHA256"),
+            block_index: Some(1),
+        }),
+        retries: 1,
+        isolated: true,
+        packed: None,
+        results,
+    }
+}
+
+/// Returns every message the CLI can send to the Suite,
+/// every benchmark is combined with every config
+pub fn cli_to_suite_messages() -> Vec<_CliToSuiteMessage> {
+    let mut messages = vec![
+        _CliToSuiteMessage::GetStatus,
+        _CliToSuiteMessage::GetCapabilities,
+        _CliToSuiteMessage::GetPlatform,
+        _CliToSuiteMessage::ListDatasets,
+        _CliToSuiteMessage::Done,
+        _CliToSuiteMessage::Reset,
+        _CliToSuiteMessage::Ping(42),
+        _CliToSuiteMessage::Replay,
+        _CliToSuiteMessage::Unsupported(String::from("Future")),
+        _CliToSuiteMessage::Invalid(String::from("{")),
+    ];
+    messages.extend(
+        [
+            SuspendCode::Success,
+            SuspendCode::PanicOnSuite,
+            SuspendCode::TestFailure,
+            SuspendCode::WatchdogTimeout,
+            SuspendCode::UserRequested(3),
+            SuspendCode::Unknown(0x2_0000),
+        ]
+        .into_iter()
+        .map(_CliToSuiteMessage::Suspend),
+    );
+    messages.extend(
+        suite_configs()
+            .into_iter()
+            .map(_CliToSuiteMessage::SetConfig),
+    );
+    for info in benchmark_infos() {
+        for config in benchmark_configs() {
+            messages.push(_CliToSuiteMessage::Benchmark(info.clone(), config));
+        }
+    }
+
+    messages
+}
+
+/// Returns every message the Suite can send to the CLI, results are sent as plain
+/// and as packed envelope
+pub fn suite_to_cli_messages() -> Vec<_SuiteToCliMessage> {
+    let mut packed = envelope(benchmark_results());
+    packed.pack();
+
+    let mut messages = vec![
+        _SuiteToCliMessage::Status(SuiteStatus::Ready),
+        _SuiteToCliMessage::Status(SuiteStatus::Done),
+        _SuiteToCliMessage::Status(SuiteStatus::Unknown),
+        _SuiteToCliMessage::Capabilities(vec![
+            ModuleKind::SHA256,
+            ModuleKind::AES,
+            ModuleKind::RNG,
+            ModuleKind::OTBN,
+            ModuleKind::KMAC,
+            ModuleKind::Entropy,
+            ModuleKind::Unknown,
+        ]),
+        _SuiteToCliMessage::Platform(platform()),
+        _SuiteToCliMessage::Datasets(vec![
+            DatasetDescriptor {
+                kind: DatasetKind::AES,
+                id: 0,
+                name: String::from("aes256_ctr_4_blocks"),
+                size: 64,
+                key_length: Some(256),
+                mode: Some(String::from("CTR")),
+            },
+            DatasetDescriptor {
+                kind: DatasetKind::Hashing,
+                id: 1,
+                name: String::from("sha256_256_bytes"),
+                size: 256,
+                key_length: None,
+                mode: None,
+            },
+        ]),
+        _SuiteToCliMessage::Log {
+            level: LogLevel::Info,
+            text: String::from("corpus"),
+        },
+        _SuiteToCliMessage::BenchmarkResults(envelope(benchmark_results())),
+        _SuiteToCliMessage::BenchmarkResults(packed.clone()),
+        _SuiteToCliMessage::Pong(42),
+        _SuiteToCliMessage::Skipped {
+            reason: SkipReason::ModuleUnavailable,
+            request_id: Some(7),
+        },
+        _SuiteToCliMessage::Config(SuiteConfig::DEFAULT),
+        _SuiteToCliMessage::Accepted {
+            expected_cycles_hint: Some(1000),
+            request_id: Some(7),
+        },
+        _SuiteToCliMessage::Unsupported(String::from("Future")),
+        _SuiteToCliMessage::ResultChunk {
+            index: 1,
+            total: 2,
+            payload: packed,
+        },
+        _SuiteToCliMessage::Window {
+            index: 1,
+            timestamp: 1_000_000,
+            results: envelope(benchmark_results()),
+        },
+        _SuiteToCliMessage::DutyCycleDone {
+            windows: 60,
+            overruns: 1,
+            request_id: Some(7),
+        },
+        _SuiteToCliMessage::Invalid(String::from("{")),
+    ];
+    messages.extend(
+        [
+            SuiteError::InvalidMessage(String::from("{")),
+            SuiteError::ModuleUnavailable,
+            SuiteError::UnknownDataset,
+            SuiteError::VerificationFailed,
+            SuiteError::HardwareFault,
+            SuiteError::UnsupportedWaitStrategy,
+            SuiteError::UnsupportedConfig,
+            SuiteError::UnsupportedMessage(String::from("Future")),
+            SuiteError::Unknown,
+        ]
+        .into_iter()
+        .map(_SuiteToCliMessage::Error),
+    );
+
+    messages
+}
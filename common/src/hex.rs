@@ -0,0 +1,43 @@
+//! Hex encoding of bytes, used where binary data has to be transferred as printable characters.
+//!
+//! Bytes are encoded as two lowercase digits each, decoding accepts lowercase digits only.
+
+use alloc::vec::Vec;
+
+/// Digits used by the hex encoding
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Appends the hex encoding of the bytes to the output
+///
+/// # Arguments
+///
+/// * `bytes` - the bytes that should be encoded
+/// * `output` - the buffer the digits are appended to
+pub(crate) fn encode_into(bytes: &[u8], output: &mut Vec<u8>) {
+    output.reserve(bytes.len() * 2);
+    for byte in bytes {
+        output.push(HEX_DIGITS[(byte >> 4) as usize]);
+        output.push(HEX_DIGITS[(byte & 0xf) as usize]);
+    }
+}
+
+/// Decodes hex digits to bytes, returns None if they contain an invalid digit or their number is odd
+///
+/// # Arguments
+///
+/// * `digits` - the digits that should be decoded
+pub(crate) fn decode(digits: &[u8]) -> Option<Vec<u8>> {
+    let digit = |digit: u8| match digit {
+        b'0'..=b'9' => Some(digit - b'0'),
+        b'a'..=b'f' => Some(digit - b'a' + 10),
+        _ => None,
+    };
+
+    if digits.len() & 1 != 0 {
+        return None;
+    }
+    digits
+        .chunks(2)
+        .map(|pair| Some(digit(pair[0])? << 4 | digit(pair[1])?))
+        .collect()
+}
@@ -2,31 +2,74 @@
 //! Suite and the CLI.
 //!
 //! It also provides functions for (de)serializing messages.
-//! The current implementation for (de)serialization uses serde_json, which may be changed
-//! in the future.
+//! The format used for (de)serialization is determined by the codec selected using features,
+//! check the codec module for more information.
+//!
+//! To allow an older CLI to understand messages of a newer Suite, the schema evolves compatibly:
+//! - unit-only enums sent by the Suite contain an `Unknown` variant, that replaces unknown variants
+//! - fields are only added with a default value and never removed
+//! - messages that are well-formed but unknown are deserialized as Unsupported by either side,
+//!   if the codec is self-describing (JSON, CBOR), the Suite responds to them with an
+//!   UnsupportedMessage error
 
-#![no_std]
+// The JSON Schema derives rely on the prelude of std, the schema is only generated by the CLI
+#![cfg_attr(not(feature = "schema"), no_std)]
 
 extern crate alloc;
+
+pub mod codec;
+pub mod compression;
+pub mod corpus;
+mod hex;
 #[allow(unused_imports)]
 use alloc::string::String;
 
+use alloc::boxed::Box;
+use alloc::vec;
 use alloc::vec::Vec;
+#[allow(unused_imports)]
+use codec::{Codec, MessageCodec, WireFormat};
+use compression::PackedRows;
 use serde::{Deserialize, Serialize};
 
 /// Messages sent from the CLI to the Suite
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum _CliToSuiteMessage {
     /// Requests the current status of the Suite
     GetStatus,
+    /// Requests the Suite to report the modules available on its platform
+    GetCapabilities,
+    /// Requests the Suite to describe its platform and build
+    GetPlatform,
+    /// Requests the Suite to describe all datasets the benchmarks can be performed on
+    ListDatasets,
     /// Signals to the Suite that the CLI is done sending requests,
     /// and that the Suite should respond with a status update,
     /// once it completed all requests
     Done,
     /// Requests the Suite to suspend with the given code
-    Suspend(u32),
-    /// Requests the Suite to perform a benchmark, n times and return the result
-    Benchmark(BenchmarkInfo, u8),
+    Suspend(SuspendCode),
+    /// Requests the Suite to return all modules to the state they had after booting,
+    /// the Suite responds with its status once it is ready again
+    Reset,
+    /// Requests the Suite to respond with a Pong carrying the same value,
+    /// used to measure the round-trip latency and to check that the Suite is responsive
+    Ping(u32),
+    /// Requests the Suite to perform a benchmark as configured and return the results
+    Benchmark(BenchmarkInfo, BenchmarkConfig),
+    /// Requests the Suite to apply the config to all following benchmarks,
+    /// the Suite responds with the config that is active afterwards
+    SetConfig(SuiteConfig),
+    /// Requests the Suite to respond with canned results instead of performing a benchmark,
+    /// used to check that the CLI can process results of every kind on any platform.
+    /// The Suite responds with `corpus::envelope(corpus::benchmark_results())`
+    Replay,
+    /// Represents a well-formed message of a newer schema, that is not understood by this build,
+    /// it should not be sent intentionally, rather it is returned when such a message is deserialized
+    ///
+    /// The first parameter is the name of the message
+    Unsupported(String),
     /// Represents an Invalid message, it should not be sent intentionally,
     /// rather it is returned when an invalid message is deserialized
     ///
@@ -35,24 +78,620 @@ pub enum _CliToSuiteMessage {
 }
 
 /// Represents all the information necessary to perform a benchmark
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum BenchmarkInfo {
     /// Run the example SHA256 benchmark
     ExampleSHA256,
     ExampleAES256,
     ExampleRNG,
     ExampleECDSA,
+    /// Run an AES256 throughput benchmark, that only samples the cycle counter
+    /// before and after encrypting all blocks
+    AESTotal,
+    /// Run the AES benchmark on the AES dataset with the given id
+    AESDataSet(usize),
+    /// Run the AES-GCM benchmark on the GCM dataset with the given id, composed of
+    /// AES-CTR in hardware and GHASH in software
+    GCMDataSet(usize),
+    /// Run ChaCha20-Poly1305 in software on the GCM dataset with the given id,
+    /// as a baseline for the AES-GCM benchmark
+    ChaCha20Poly1305(usize),
+    /// Run the AES benchmark for every combination of the given key lengths in bits, modes and
+    /// numbers of blocks, using a fixed key and plaintext
+    ///
+    /// The combinations are performed in this order, with the number of blocks changing fastest.
+    AESSweep {
+        key_lengths: Vec<u32>,
+        modes: Vec<AESSweepMode>,
+        blocks: Vec<usize>,
+    },
+    /// Run AES-256 ECB on the given number of blocks twice using a fixed plaintext, once with a
+    /// constant key and once re-keying the module between every block, alternating between two keys
+    /// as if the module was shared by two tenants. The phases are reported as `Phases` result
+    AESContextSwitch {
+        blocks: usize,
+    },
+    /// Run AES-256 CTR on the given number of bytes, a multiple of 16 of at most 16 KiB, using the
+    /// pipelined loop of `AESTotal`. The input is generated by the suite from the seed of the
+    /// config, or a fixed seed if none is set, so the pipeline can be saturated by far more blocks
+    /// than a dataset contains. The ciphertext is verified by decrypting it again
+    AESThroughput {
+        bytes: usize,
+    },
+    /// Run the hashing benchmark on the hashing dataset with the given id
+    HashingDataSet(usize),
+    /// Run a hashing benchmark of the given type on the hashing dataset with the given id,
+    /// `HashingDataSet(id)` is equivalent to using `HashBenchmarkType::SHA256`
+    Hash {
+        hash: HashBenchmarkType,
+        dataset: usize,
+    },
+    /// Compute SHAKE128 of the hashing dataset with the given id three times, unmasked and masked
+    /// using entropy expanded from a software seed and fetched from the EDN. Every computation,
+    /// from setting up the module until the first output block was read, is reported as a phase
+    /// (`unmasked`, `software`, `edn`) of a `Phases` result
+    KMACMasking {
+        dataset: usize,
+    },
+    /// Compute the SHA256 digest of the hashing dataset with the given id once for every burst
+    /// size, writing the given number of words (1 to 16) into the message FIFO at once. Before
+    /// every burst the fill level of the FIFO is polled, a burst that does not fit waits for the
+    /// FIFO to drain. Every computation is reported as `SHA256Streaming` result, in order
+    SHA256Streaming {
+        dataset: usize,
+        bursts: Vec<u32>,
+    },
+    /// Compute the SHA3 digest of the hashing dataset with the given id once for every digest
+    /// length in bits (224, 256, 384 or 512). Every computation is reported as `SHA3` result,
+    /// in order
+    SHA3 {
+        dataset: usize,
+        digest_lengths: Vec<u32>,
+    },
+    /// Run the random number generation benchmark on the RNG dataset with the given id
+    RNGDataSet(usize),
+    /// Run the random number generation benchmark on the RNG dataset with the given id,
+    /// reseeding the module between instantiating it and generating the numbers.
+    ///
+    /// The seed material of the reseed is taken from the entropy source, unless additional data
+    /// of at most 12 words is provided, which is used instead.
+    RNGReseed {
+        dataset: usize,
+        #[serde(default)]
+        additional_data: Option<Vec<u32>>,
+    },
+    /// Run the random number generation benchmark on the RNG dataset with the given id once for
+    /// every generate length (1 to 64), requesting that many numbers with a single generate
+    /// command. Every run is reported as `RNGGenerateLength` result, in order
+    RNGGenerateLengths {
+        dataset: usize,
+        lengths: Vec<u32>,
+    },
+    /// Measure how long it takes until seeds of the entropy source are available, starting with
+    /// the entropy source, the CSRNG and the EDN stopped. The phases are reported as `Phases`
+    /// result: `cold_seed` for enabling the entropy source until the first number of the CSRNG
+    /// instantiated from it was generated, `warm_seed` for reseeding the CSRNG from the running
+    /// entropy source and `edn_seed` for enabling the EDN until it was instantiated
+    EntropyLatency,
+    /// Run the ECDSA benchmark on the ECDSA dataset with the given id
+    ECDSA(usize),
+    /// Run a program of the given type on the OTBN
+    OTBN(OTBNBenchmarkType),
+    /// Measure how long a module takes to securely wipe its state after it was used,
+    /// the phases are reported as `Phases` result
+    Teardown(TeardownBenchmarkType),
+    /// Perform the same operation on the given module once for every delay, waiting the given
+    /// number of nops between two polls of its status register. Every operation, from setting
+    /// up the module until its output was read, is reported as a phase (`nops_<delay>`) of a
+    /// `Phases` result, so the latency added by polling less often is visible next to the
+    /// number of polls saved
+    PollingInterval {
+        module: PollingModule,
+        delays: Vec<u32>,
+    },
+    /// Measure the overhead of the measurement itself, reported as phases of a `Phases` result:
+    /// `get_cycle` for reading the cycle counter twice in a row, `sample` for an empty phase,
+    /// which is the overhead contained in every phase of other benchmarks, and `call` for a phase
+    /// only containing a call to a function that is not inlined
+    MicroBenchmarks,
+    /// Run `MicroBenchmarks` immediately before and after the given benchmark, which can not be
+    /// calibrated itself. The results of all three are reported one after the other, so the
+    /// overhead is measured in the same state of the machine as the benchmark
+    Calibrated(Box<BenchmarkInfo>),
+}
+
+impl BenchmarkInfo {
+    /// Returns the module that is required to perform the benchmark,
+    /// or None if the benchmark is performed in software only
+    pub fn required_module(&self) -> Option<ModuleKind> {
+        match self {
+            BenchmarkInfo::ExampleSHA256 => Some(ModuleKind::SHA256),
+            BenchmarkInfo::ExampleAES256 => Some(ModuleKind::AES),
+            BenchmarkInfo::ExampleRNG => Some(ModuleKind::RNG),
+            BenchmarkInfo::ExampleECDSA => Some(ModuleKind::OTBN),
+            BenchmarkInfo::AESTotal => Some(ModuleKind::AES),
+            BenchmarkInfo::AESDataSet(_) => Some(ModuleKind::AES),
+            BenchmarkInfo::GCMDataSet(_) => Some(ModuleKind::AES),
+            BenchmarkInfo::AESSweep { .. } => Some(ModuleKind::AES),
+            BenchmarkInfo::AESContextSwitch { .. } => Some(ModuleKind::AES),
+            BenchmarkInfo::AESThroughput { .. } => Some(ModuleKind::AES),
+            BenchmarkInfo::ChaCha20Poly1305(_) => None,
+            BenchmarkInfo::HashingDataSet(_) => Some(ModuleKind::SHA256),
+            BenchmarkInfo::Hash { hash, .. } => Some(hash.required_module()),
+            BenchmarkInfo::KMACMasking { .. } => Some(ModuleKind::KMAC),
+            BenchmarkInfo::SHA256Streaming { .. } => Some(ModuleKind::SHA256),
+            BenchmarkInfo::SHA3 { .. } => Some(ModuleKind::KMAC),
+            BenchmarkInfo::RNGDataSet(_) => Some(ModuleKind::RNG),
+            BenchmarkInfo::RNGReseed { .. } => Some(ModuleKind::RNG),
+            BenchmarkInfo::RNGGenerateLengths { .. } => Some(ModuleKind::RNG),
+            BenchmarkInfo::EntropyLatency => Some(ModuleKind::Entropy),
+            BenchmarkInfo::ECDSA(_) => Some(ModuleKind::OTBN),
+            BenchmarkInfo::OTBN(_) => Some(ModuleKind::OTBN),
+            BenchmarkInfo::Teardown(module) => Some(module.required_module()),
+            BenchmarkInfo::PollingInterval { module, .. } => Some(module.required_module()),
+            BenchmarkInfo::MicroBenchmarks => None,
+            BenchmarkInfo::Calibrated(target) => target.required_module(),
+        }
+    }
+
+    /// Returns the kind and id of every dataset the benchmark is performed on
+    pub fn datasets(&self) -> Vec<(DatasetKind, usize)> {
+        match self {
+            BenchmarkInfo::AESDataSet(id) => vec![(DatasetKind::AES, *id)],
+            BenchmarkInfo::GCMDataSet(id) | BenchmarkInfo::ChaCha20Poly1305(id) => {
+                vec![(DatasetKind::GCM, *id)]
+            }
+            BenchmarkInfo::HashingDataSet(id)
+            | BenchmarkInfo::KMACMasking { dataset: id }
+            | BenchmarkInfo::SHA256Streaming { dataset: id, .. }
+            | BenchmarkInfo::SHA3 { dataset: id, .. } => {
+                vec![(DatasetKind::Hashing, *id)]
+            }
+            BenchmarkInfo::Hash {
+                hash:
+                    HashBenchmarkType::HmacSHA256 { key_dataset }
+                    | HashBenchmarkType::Kmac256 { key_dataset, .. },
+                dataset,
+            } => vec![
+                (DatasetKind::Hashing, *dataset),
+                (DatasetKind::HMACKey, *key_dataset),
+            ],
+            BenchmarkInfo::Hash { dataset, .. } => vec![(DatasetKind::Hashing, *dataset)],
+            BenchmarkInfo::RNGDataSet(id)
+            | BenchmarkInfo::RNGReseed { dataset: id, .. }
+            | BenchmarkInfo::RNGGenerateLengths { dataset: id, .. } => {
+                vec![(DatasetKind::RNG, *id)]
+            }
+            BenchmarkInfo::ECDSA(id) => vec![(DatasetKind::ECDSA, *id)],
+            BenchmarkInfo::Calibrated(target) => target.datasets(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Type of hash computed by a hashing benchmark
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum HashBenchmarkType {
+    /// Plain SHA256 digest of the dataset
+    SHA256,
+    /// HMAC-SHA256 of the dataset, using the HMAC key dataset with the given id
+    HmacSHA256 { key_dataset: usize },
+    /// SHAKE128 output of the given number of bytes
+    SHAKE128 { output_length: usize },
+    /// SHAKE256 output of the given number of bytes
+    SHAKE256 { output_length: usize },
+    /// cSHAKE128 output of the given number of bytes, using the given function name
+    /// and customization string, which may be at most 40 bytes long in total
+    CSHAKE128 {
+        output_length: usize,
+        function_name: String,
+        customization: String,
+    },
+    /// cSHAKE256 output of the given number of bytes, using the given function name
+    /// and customization string, which may be at most 40 bytes long in total
+    CSHAKE256 {
+        output_length: usize,
+        function_name: String,
+        customization: String,
+    },
+    /// KMAC256 output of the given number of bytes, keyed with the HMAC key dataset with the
+    /// given id and using the given customization string, which may be at most 36 bytes long
+    Kmac256 {
+        key_dataset: usize,
+        output_length: usize,
+        #[serde(default)]
+        customization: String,
+    },
+}
+
+impl HashBenchmarkType {
+    /// Returns the module that is required to compute the hash
+    pub fn required_module(&self) -> ModuleKind {
+        match self {
+            HashBenchmarkType::SHA256 | HashBenchmarkType::HmacSHA256 { .. } => ModuleKind::SHA256,
+            HashBenchmarkType::SHAKE128 { .. }
+            | HashBenchmarkType::SHAKE256 { .. }
+            | HashBenchmarkType::CSHAKE128 { .. }
+            | HashBenchmarkType::CSHAKE256 { .. }
+            | HashBenchmarkType::Kmac256 { .. } => ModuleKind::KMAC,
+        }
+    }
+}
+
+/// Program performed by an OTBN benchmark
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum OTBNBenchmarkType {
+    /// RSA-2048 modular exponentiation with the public exponent 65537
+    RSA2048Encrypt,
+    /// RSA-2048 modular exponentiation with a private exponent
+    RSA2048Decrypt,
+    /// Multiplication of a point on the P-256 curve with a scalar
+    P256ScalarMult,
+}
+
+/// Module whose teardown is measured by a teardown benchmark
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum TeardownBenchmarkType {
+    /// Clearing the key, iv and input registers (`clear_input`),
+    /// followed by clearing the output registers (`clear_output`)
+    AES,
+    /// Finishing a SHAKE128 computation, until the state was wiped (`done`)
+    KMAC,
+    /// Uninstantiating the CSRNG (`uninstantiate`)
+    RNG,
+}
+
+impl TeardownBenchmarkType {
+    /// Returns the module whose teardown is measured
+    pub fn required_module(&self) -> ModuleKind {
+        match self {
+            TeardownBenchmarkType::AES => ModuleKind::AES,
+            TeardownBenchmarkType::KMAC => ModuleKind::KMAC,
+            TeardownBenchmarkType::RNG => ModuleKind::RNG,
+        }
+    }
+}
+
+/// Module whose status register is polled by a polling interval benchmark
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum PollingModule {
+    /// Encrypting the plaintext of the AES sweeps using AES-256 ECB
+    AES,
+    /// Computing the MAC of the first hashing dataset using the first HMAC key
+    HMAC,
+}
+
+impl PollingModule {
+    /// Returns the module whose status register is polled
+    pub fn required_module(&self) -> ModuleKind {
+        match self {
+            PollingModule::AES => ModuleKind::AES,
+            PollingModule::HMAC => ModuleKind::SHA256,
+        }
+    }
+}
+
+/// AES modes that can be used in an AES sweep
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum AESSweepMode {
+    ECB,
+    CBC,
+    CTR,
+}
+
+/// Configures how often a benchmark is performed and how its results are reported
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct BenchmarkConfig {
+    /// Number of repetitions whose results are reported
+    pub repetitions: u32,
+    /// Number of repetitions performed beforehand, whose results are discarded
+    #[serde(default)]
+    pub warmup_iterations: u32,
+    /// How the results of the repetitions are reported,
+    /// if not provided the aggregation of the Suite config is used
+    #[serde(default)]
+    pub aggregate: Option<Aggregate>,
+    /// How the modules wait for the hardware while performing the benchmark
+    #[serde(default)]
+    pub wait_strategy: WaitStrategy,
+    /// Whether the value of the platform timer is sampled in addition to the cycle counter
+    #[serde(default)]
+    pub virtual_time: bool,
+    /// Identifies the request, the results carry the same id so they can be matched to it
+    #[serde(default)]
+    pub request_id: Option<u32>,
+    /// Name describing the request, the results carry the same label
+    #[serde(default)]
+    pub label: Option<String>,
+    /// Number of times a repetition may be performed again, after its output did not match the
+    /// expected output or a module raised a hardware alert. The modules are reset before every retry
+    #[serde(default)]
+    pub retries: u32,
+    /// Performs the benchmark repeatedly at a fixed period instead of once, check `DutyCycle`
+    #[serde(default)]
+    pub duty_cycle: Option<DutyCycle>,
+}
+
+impl BenchmarkConfig {
+    /// Returns a config reporting the results of all repetitions, without warmup
+    ///
+    /// # Arguments
+    ///
+    /// * `repetitions` - how often the benchmark should be performed
+    pub fn samples(repetitions: u32) -> BenchmarkConfig {
+        BenchmarkConfig {
+            repetitions,
+            warmup_iterations: 0,
+            aggregate: Some(Aggregate::Samples),
+            wait_strategy: WaitStrategy::Spin,
+            virtual_time: false,
+            request_id: None,
+            label: None,
+            retries: 0,
+            duty_cycle: None,
+        }
+    }
+}
+
+/// Repeats a benchmark at a fixed period for a fixed duration, e.g. to observe how its timing
+/// drifts under sustained load.
+///
+/// Every repetition of the benchmark as configured is called a window. The first window starts
+/// immediately, every following window starts `period` cycles after the previous one, or as soon
+/// as the previous one finished if it took longer. No window is started after `duration` cycles.
+/// The results of every window are sent as `Window` message as soon as they are available,
+/// the request is answered with `DutyCycleDone` after the last window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DutyCycle {
+    /// Cycles between the starts of consecutive windows, must not be 0
+    pub period: u64,
+    /// Cycles after the start of the first window, after which no window is started
+    pub duration: u64,
+}
+
+/// Determines how the results of the repetitions of a benchmark are reported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum Aggregate {
+    /// The results of all repetitions are reported
+    Samples,
+    /// The minimum, mean and maximum of every phase over all repetitions are reported,
+    /// as three results in this order
+    Summary,
+    /// An aggregation that is not known to this build
+    #[serde(other)]
+    Unknown,
+}
+
+// Deriving Default for enums is not supported by the pinned toolchain,
+// so the enums of the schema implement it manually
+#[allow(clippy::derivable_impls)]
+impl Default for Aggregate {
+    fn default() -> Self {
+        Aggregate::Samples
+    }
+}
+
+impl Aggregate {
+    /// Returns the statistic reported by the result at the provided index of an envelope,
+    /// None if the results are not summarized
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - the index of the result in its envelope
+    pub fn statistic(self, index: usize) -> Option<&'static str> {
+        match self {
+            Aggregate::Summary => ["min", "mean", "max"].get(index).copied(),
+            _ => None,
+        }
+    }
+}
+
+/// Configuration of the Suite, that applies to all benchmarks until it is changed again
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SuiteConfig {
+    /// Whether the outputs of the benchmarks are verified
+    #[serde(default)]
+    pub verification: VerificationPolicy,
+    /// How the results of the repetitions are reported,
+    /// unless the config of a benchmark specifies it
+    #[serde(default)]
+    pub aggregate: Aggregate,
+    /// Log messages with a lower level are not sent by the Suite
+    #[serde(default)]
+    pub log_level: LogLevel,
+    /// Whether the instruction cache of the core is enabled,
+    /// it can only be enabled on platforms with an instruction cache
+    #[serde(default)]
+    pub icache: bool,
+    /// Whether result envelopes are packed before sending them, check `ResultEnvelope::pack`
+    #[serde(default)]
+    pub compression: bool,
+    /// Seed of the pseudo random choices of the Suite, like the key of AES sweeps and the software
+    /// entropy used for masking, so they can be reproduced. None uses the fixed values built into
+    /// the Suite
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// Whether the modules are reset and the heap is initialized again before every request,
+    /// so every benchmark starts from the same state of the machine. The cycles of earlier
+    /// benchmarks are forgotten as well, so no hints of the expected cycles are provided
+    #[serde(default)]
+    pub isolation: bool,
+}
+
+impl SuiteConfig {
+    /// The config of the Suite after booting
+    pub const DEFAULT: SuiteConfig = SuiteConfig {
+        verification: VerificationPolicy::Verify,
+        aggregate: Aggregate::Samples,
+        log_level: LogLevel::Debug,
+        icache: false,
+        compression: false,
+        seed: None,
+        isolation: false,
+    };
+}
+
+impl Default for SuiteConfig {
+    fn default() -> Self {
+        SuiteConfig::DEFAULT
+    }
+}
+
+/// Determines whether the outputs of the benchmarks are compared to the expected outputs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum VerificationPolicy {
+    /// The outputs are verified after every repetition, outside of the measured phases
+    Verify,
+    /// The outputs are not verified, which shortens benchmarks with many repetitions
+    Skip,
+    /// A policy that is not known to this build
+    #[serde(other)]
+    Unknown,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for VerificationPolicy {
+    fn default() -> Self {
+        VerificationPolicy::Verify
+    }
+}
+
+/// Determines how the modules wait for the hardware to become ready,
+/// every strategy polls the same status register but spends the time between two polls differently
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum WaitStrategy {
+    /// The status register is polled in a tight loop
+    Spin,
+    /// The delay between two polls starts small and is doubled after every poll, up to a limit
+    Backoff,
+    /// The given number of nops is executed between two polls,
+    /// the number can be calibrated to the expected latency of the hardware
+    Nops(u32),
+    /// The core waits for an interrupt (`wfi`) between two polls,
+    /// only supported on platforms routing the interrupts of the modules to the core
+    Interrupt,
+    /// A strategy that is not known to this build
+    #[serde(other)]
+    Unknown,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for WaitStrategy {
+    fn default() -> Self {
+        WaitStrategy::Spin
+    }
+}
+
+/// Represents a module that may be available on the platform of the Suite
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum ModuleKind {
+    SHA256,
+    AES,
+    RNG,
+    /// The big number accelerator, used by the ECDSA library
+    OTBN,
+    /// The keccak based hashing module
+    KMAC,
+    /// The entropy source seeding the CSRNG and the EDN distributing its numbers to other modules
+    Entropy,
+    /// A module that is not known to this build
+    #[serde(other)]
+    Unknown,
 }
 
 /// Messages sent from the Suite to the CLI
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "schema",
+    derive(schemars::JsonSchema),
+    schemars(rename = "SuiteMessage")
+)]
 pub enum _SuiteToCliMessage {
     /// Signals the current status of the Suite
     Status(SuiteStatus),
+    /// Lists the modules available on the platform of the Suite
+    Capabilities(Vec<ModuleKind>),
+    /// Describes the platform and build of the Suite
+    Platform(PlatformDescriptor),
+    /// Describes all datasets available on the Suite
+    Datasets(Vec<DatasetDescriptor>),
     /// Notifies the CLI that an error occurred on the Suite
-    Error(String),
+    Error(SuiteError),
+    /// Diagnostic output of the Suite, that is not a response to any request
+    Log { level: LogLevel, text: String },
     /// Requests the Suite to perform a benchmark, n times and return the result
-    BenchmarkResults(Vec<BenchmarkResult>),
+    BenchmarkResults(ResultEnvelope),
+    /// Response to a Ping, carrying the value of the Ping
+    Pong(u32),
+    /// Signals that a benchmark was not performed, because the platform can not perform it.
+    /// In contrast to an Error, this is an expected outcome on platforms with fewer modules
+    Skipped {
+        /// Why the benchmark was skipped
+        reason: SkipReason,
+        /// Id of the request of the skipped benchmark, if the request provided one
+        request_id: Option<u32>,
+    },
+    /// The config of the Suite, sent in response to SetConfig
+    Config(SuiteConfig),
+    /// Sent as soon as a Benchmark request is received, before the benchmark is performed
+    Accepted {
+        /// Cycles the Suite expects the benchmark to take, derived from earlier runs of the same
+        /// benchmark, None if it was not performed before
+        expected_cycles_hint: Option<u64>,
+        /// Id of the accepted request, if the request provided one
+        request_id: Option<u32>,
+    },
+    /// Represents a well-formed message of a newer schema, that is not understood by this build,
+    /// it should not be sent intentionally, rather it is returned when such a message is deserialized
+    ///
+    /// The first parameter is the name of the message
+    Unsupported(String),
+    /// Part of a BenchmarkResults message that was split up, because it was too large to be
+    /// transferred in a single message.
+    /// The CLI reassembles all chunks, in order of their index, into a single BenchmarkResults message.
+    ResultChunk {
+        /// Index of this chunk, starting at 0
+        index: u32,
+        /// Total number of chunks the results were split into
+        total: u32,
+        /// The results contained in this chunk, every chunk carries the metadata of the results,
+        /// but only the first chunk carries the platform descriptor
+        payload: ResultEnvelope,
+    },
+    /// The results of a window of a duty-cycled benchmark, sent as soon as the window finished.
+    /// The results are neither packed nor split into chunks, so windows should report few results,
+    /// e.g. by using `Aggregate::Summary`
+    Window {
+        /// Index of the window, starting at 0
+        index: u32,
+        /// Cycles between the start of the first window and the start of this window
+        timestamp: u64,
+        /// The results of the window
+        results: ResultEnvelope,
+    },
+    /// Response to a duty-cycled benchmark, sent after its last window
+    DutyCycleDone {
+        /// Number of windows that were performed
+        windows: u32,
+        /// Number of windows that started late, because the previous window took longer than
+        /// the period
+        overruns: u32,
+        /// Id of the request of the benchmark, if the request provided one
+        request_id: Option<u32>,
+    },
     /// Represents an Invalid message, it should not be sent intentionally,
     /// rather it is returned when an invalid message is deserialized
     ///
@@ -60,8 +699,249 @@ pub enum _SuiteToCliMessage {
     Invalid(String),
 }
 
+/// Wraps the results of a benchmark together with metadata describing how they were produced,
+/// so that they can be interpreted on their own
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ResultEnvelope {
+    /// Version of the schema used by the Suite that produced the results,
+    /// 0 if the Suite predates schema versioning
+    #[serde(default)]
+    pub schema_version: u32,
+    /// The platform and build of the Suite that produced the results
+    pub platform: PlatformDescriptor,
+    /// Id of the request that produced the results, if the request provided one
+    #[serde(default)]
+    pub request_id: Option<u32>,
+    /// Label of the request that produced the results, if the request provided one
+    #[serde(default)]
+    pub label: Option<String>,
+    /// The benchmark that was requested, None if the Suite predates echoing it
+    #[serde(default)]
+    pub benchmark: Option<BenchmarkInfo>,
+    /// The config the benchmark was requested with, None if the Suite predates echoing it
+    #[serde(default)]
+    pub config: Option<BenchmarkConfig>,
+    /// The Suite config that was active while performing the benchmark,
+    /// None if the Suite predates echoing it
+    #[serde(default)]
+    pub suite_config: Option<SuiteConfig>,
+    /// How the results of the repetitions of the benchmark were aggregated
+    #[serde(default)]
+    pub aggregate: Aggregate,
+    /// How the modules waited for the hardware while performing the benchmark
+    #[serde(default)]
+    pub wait_strategy: WaitStrategy,
+    /// The results of the benchmark
+    pub results: Vec<BenchmarkResult>,
+    /// Number of times the Suite polled a status register while waiting for the hardware,
+    /// for every phase of the result at the same index, in the order of its phases
+    #[serde(default)]
+    pub poll_counts: Vec<Vec<u64>>,
+    /// Ticks of the platform timer for every phase of the result at the same index,
+    /// in the order of its phases, empty if the timer was not sampled
+    #[serde(default)]
+    pub virtual_time: Vec<Vec<u64>>,
+    /// Cycles the benchmark spent outside of the measured phases of the result at the same index,
+    /// e.g. for setting up inputs, empty if the Suite predates reporting them
+    #[serde(default)]
+    pub unaccounted_cycles: Vec<u64>,
+    /// The first verification failure, if an output of the benchmark did not match the expected output.
+    /// The results contain the measurements up to and including the failed one
+    #[serde(default)]
+    pub verification_failure: Option<VerificationFailure>,
+    /// Number of repetitions that were performed again, because their output did not match the
+    /// expected output or a module raised a hardware alert
+    #[serde(default)]
+    pub retries: u32,
+    /// Whether the benchmark started from reset modules and a freshly initialized heap,
+    /// check `SuiteConfig::isolation`
+    #[serde(default)]
+    pub isolated: bool,
+    /// The values of the results and the lists above in compressed form,
+    /// present if the envelope was packed, check `pack` for details
+    #[serde(default)]
+    pub packed: Option<PackedValues>,
+}
+
+/// Values of a packed result envelope
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PackedValues {
+    /// The cycles of the phases of every result
+    pub cycles: PackedRows,
+    pub poll_counts: PackedRows,
+    pub virtual_time: PackedRows,
+    /// The unaccounted cycles of every result, as rows with a single value
+    pub unaccounted_cycles: PackedRows,
+}
+
+impl ResultEnvelope {
+    /// True if the results were measured in a cycle accurate simulation and the timer
+    /// was sampled, so repeating the benchmark on the same build yields exactly the same results
+    pub fn is_simulation_deterministic(&self) -> bool {
+        self.platform.simulated && !self.virtual_time.is_empty()
+    }
+
+    /// Compresses the values of the results, the poll counts, the virtual time and the
+    /// unaccounted cycles, which is worthwhile for envelopes containing many results.
+    ///
+    /// The results keep their parameters but all of their phases are set to 0,
+    /// the lists are emptied. `unpack` restores the original values.
+    pub fn pack(&mut self) {
+        if self.packed.is_some() {
+            return;
+        }
+
+        let cycles: Vec<Vec<u64>> = self
+            .results
+            .iter()
+            .map(|result| {
+                result
+                    .phases()
+                    .into_iter()
+                    .map(|(_, cycles)| cycles)
+                    .collect()
+            })
+            .collect();
+        let unaccounted_cycles: Vec<Vec<u64>> = self
+            .unaccounted_cycles
+            .iter()
+            .map(|cycles| alloc::vec![*cycles])
+            .collect();
+
+        self.packed = Some(PackedValues {
+            cycles: PackedRows::pack(&cycles),
+            poll_counts: PackedRows::pack(&self.poll_counts),
+            virtual_time: PackedRows::pack(&self.virtual_time),
+            unaccounted_cycles: PackedRows::pack(&unaccounted_cycles),
+        });
+        for (result, cycles) in self.results.iter_mut().zip(cycles) {
+            *result = result.with_phases(&alloc::vec![0; cycles.len()]);
+        }
+        self.poll_counts = Vec::new();
+        self.virtual_time = Vec::new();
+        self.unaccounted_cycles = Vec::new();
+    }
+
+    /// Restores the values of a packed envelope, does nothing if the envelope is not packed.
+    ///
+    /// Returns false, leaving the envelope unchanged, if the packed values are invalid or do not
+    /// match the results.
+    pub fn unpack(&mut self) -> bool {
+        let packed = match &self.packed {
+            Some(packed) => packed,
+            None => return true,
+        };
+
+        let (cycles, poll_counts, virtual_time, unaccounted_cycles) = match (
+            packed.cycles.unpack(),
+            packed.poll_counts.unpack(),
+            packed.virtual_time.unpack(),
+            packed.unaccounted_cycles.unpack(),
+        ) {
+            (Some(cycles), Some(poll_counts), Some(virtual_time), Some(unaccounted_cycles)) => {
+                (cycles, poll_counts, virtual_time, unaccounted_cycles)
+            }
+            _ => return false,
+        };
+
+        let matching = cycles.len() == self.results.len()
+            && self
+                .results
+                .iter()
+                .zip(&cycles)
+                .all(|(result, cycles)| result.phases().len() == cycles.len())
+            && unaccounted_cycles.iter().all(|row| row.len() == 1);
+        if !matching {
+            return false;
+        }
+
+        for (result, cycles) in self.results.iter_mut().zip(&cycles) {
+            *result = result.with_phases(cycles);
+        }
+        self.poll_counts = poll_counts;
+        self.virtual_time = virtual_time;
+        self.unaccounted_cycles = unaccounted_cycles.into_iter().map(|row| row[0]).collect();
+        self.packed = None;
+        true
+    }
+}
+
+/// Describes the platform the Suite runs on and how the Suite was built
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PlatformDescriptor {
+    /// Name of the platform
+    pub name: String,
+    /// Cargo features the Suite was built with
+    pub features: Vec<String>,
+    /// Frequency of the clock driving the cycle counter in Hz, if known
+    pub clock_frequency: Option<u64>,
+    /// Git hash of the commit the Suite was built from, if known
+    pub git_hash: Option<String>,
+    /// Whether the platform is a cycle accurate simulation
+    #[serde(default)]
+    pub simulated: bool,
+}
+
+/// Kind of benchmarks a dataset is used for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum DatasetKind {
+    AES,
+    /// Datasets with known ciphertexts and tags for the AES-GCM benchmarks
+    GCM,
+    Hashing,
+    /// Keys used by the HMAC benchmarks
+    HMACKey,
+    RNG,
+    ECDSA,
+    /// A kind that is not known to this build
+    #[serde(other)]
+    Unknown,
+}
+
+impl DatasetKind {
+    /// Returns the number of datasets of this kind available on the suite, the ids of valid
+    /// datasets are smaller. The suite declares its datasets with these lengths, so requests can be
+    /// validated without connecting to it
+    pub const fn count(&self) -> usize {
+        match self {
+            DatasetKind::AES => 4,
+            DatasetKind::GCM => 2,
+            DatasetKind::Hashing => 5,
+            DatasetKind::HMACKey => 3,
+            DatasetKind::RNG => 3,
+            DatasetKind::ECDSA => 2,
+            DatasetKind::Unknown => 0,
+        }
+    }
+}
+
+/// Describes a dataset available on the Suite
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DatasetDescriptor {
+    /// Kind of benchmarks the dataset is used for
+    pub kind: DatasetKind,
+    /// Id used to reference the dataset in a BenchmarkInfo of the matching kind
+    pub id: usize,
+    /// Name describing the dataset
+    pub name: String,
+    /// Number of bytes that are processed, or generated for RNG datasets
+    pub size: usize,
+    /// Length of the key in bits, if the dataset contains a key
+    #[serde(default)]
+    pub key_length: Option<u32>,
+    /// Abbreviation of the AES mode used, ex. `CTR`, for AES datasets
+    #[serde(default)]
+    pub mode: Option<String>,
+}
+
 /// Represents all the results of a single benchmark
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum BenchmarkResult {
     ExampleSHA256 {
         initialization: u64,
@@ -84,13 +964,675 @@ pub enum BenchmarkResult {
         signing: u64,
         verifying: u64,
     },
+    /// Cycles taken to encrypt all blocks, without any instrumentation inside the loop
+    ///
+    /// The remaining fields break down the time spent inside the loop of a second, instrumented
+    /// encryption of the same blocks. They are sub-totals and not included in the total.
+    AESTotal {
+        computation: u64,
+        input_writes: u64,
+        status_polls: u64,
+        output_reads: u64,
+    },
+    AES {
+        initialization: u64,
+        computation: u64,
+        deinitialization: u64,
+    },
+    /// Cycles of one combination of an AES sweep
+    AESSweep {
+        key_length: u32,
+        mode: AESSweepMode,
+        blocks: usize,
+        initialization: u64,
+        computation: u64,
+        deinitialization: u64,
+    },
+    /// Cycles of AES on `bytes` bytes of generated input, `millicycles_per_byte` is the computation
+    /// divided by the number of bytes, in thousandths of a cycle
+    AESThroughput {
+        bytes: usize,
+        millicycles_per_byte: u64,
+        initialization: u64,
+        computation: u64,
+    },
+    /// Cycles of an AES-GCM encryption, the first two phases are performed by the AES module,
+    /// computing the tag using GHASH is performed in software
+    GCM {
+        hash_subkey: u64,
+        encryption: u64,
+        ghash: u64,
+    },
+    /// Cycles of a ChaCha20-Poly1305 encryption performed in software
+    ChaCha20Poly1305 {
+        key_setup: u64,
+        encryption: u64,
+        authentication: u64,
+    },
+    Hashing {
+        initialization: u64,
+        computation: u64,
+        reading_output: u64,
+    },
+    HMAC {
+        key_writing: u64,
+        computation: u64,
+        reading_output: u64,
+    },
+    /// Cycles of a SHA256 digest whose input was written into the message FIFO in bursts of
+    /// `burst` words. `stalls` counts the bursts that had to wait for the FIFO to drain,
+    /// `cycles_per_block` is the computation divided by the number of 512 bit `blocks` absorbed
+    SHA256Streaming {
+        burst: u32,
+        blocks: usize,
+        stalls: u32,
+        cycles_per_block: u64,
+        initialization: u64,
+        computation: u64,
+        reading_output: u64,
+    },
+    /// Cycles of a SHA3 digest of `digest_length` bits
+    SHA3 {
+        digest_length: u32,
+        initialization: u64,
+        computation: u64,
+        reading_output: u64,
+    },
+    /// Cycles of an extendable output function, reading every block of the output is measured
+    /// separately, all but the first block have to be squeezed before reading them
+    XOF {
+        initialization: u64,
+        absorbing: u64,
+        squeezes: Vec<u64>,
+    },
+    /// Cycles of a KMAC, loading the key includes setting up the module, the output is read
+    /// like the one of an XOF
+    KMAC {
+        key_loading: u64,
+        absorbing: u64,
+        squeezes: Vec<u64>,
+    },
+    RNG {
+        initialization: u64,
+        generation: u64,
+    },
+    RNGReseed {
+        initialization: u64,
+        reseed: u64,
+        generation: u64,
+    },
+    /// Cycles of generating `blocks` 128 bit numbers with generate commands of `length` numbers
+    /// each, `cycles_per_block` is the generation divided by the number of blocks
+    RNGGenerateLength {
+        length: u32,
+        blocks: usize,
+        cycles_per_block: u64,
+        initialization: u64,
+        generation: u64,
+    },
+    ECDSA {
+        signing: u64,
+        verifying: u64,
+    },
+    /// Cycles of a program performed on the OTBN, loading includes writing the inputs
+    OTBN {
+        loading: u64,
+        execution: u64,
+        reading_output: u64,
+    },
+    /// Cycles of named phases, in the order they were performed.
+    /// Allows adding benchmarks to the Suite without adding a result for them
+    Phases(Vec<(String, u64)>),
+}
+
+impl BenchmarkResult {
+    /// Returns the name of the benchmark that produced this result
+    pub fn name(&self) -> &'static str {
+        match self {
+            BenchmarkResult::ExampleSHA256 { .. } => "ExampleSHA256",
+            BenchmarkResult::ExampleAES256 { .. } => "ExampleAES256",
+            BenchmarkResult::ExampleRNG { .. } => "ExampleRNG",
+            BenchmarkResult::ExampleECDSA { .. } => "ExampleECDSA",
+            BenchmarkResult::AESTotal { .. } => "AESTotal",
+            BenchmarkResult::AES { .. } => "AES",
+            BenchmarkResult::AESSweep { .. } => "AESSweep",
+            BenchmarkResult::AESThroughput { .. } => "AESThroughput",
+            BenchmarkResult::GCM { .. } => "GCM",
+            BenchmarkResult::ChaCha20Poly1305 { .. } => "ChaCha20Poly1305",
+            BenchmarkResult::Hashing { .. } => "Hashing",
+            BenchmarkResult::HMAC { .. } => "HMAC",
+            BenchmarkResult::SHA256Streaming { .. } => "SHA256Streaming",
+            BenchmarkResult::SHA3 { .. } => "SHA3",
+            BenchmarkResult::XOF { .. } => "XOF",
+            BenchmarkResult::KMAC { .. } => "KMAC",
+            BenchmarkResult::RNG { .. } => "RNG",
+            BenchmarkResult::RNGReseed { .. } => "RNGReseed",
+            BenchmarkResult::RNGGenerateLength { .. } => "RNGGenerateLength",
+            BenchmarkResult::ECDSA { .. } => "ECDSA",
+            BenchmarkResult::OTBN { .. } => "OTBN",
+            BenchmarkResult::Phases(_) => "Phases",
+        }
+    }
+
+    /// Returns the number of cycles measured for every phase of the benchmark,
+    /// in the order the phases were performed
+    pub fn phases(&self) -> Vec<(&str, u64)> {
+        match *self {
+            BenchmarkResult::ExampleSHA256 {
+                initialization,
+                computation,
+                reading_output,
+            } => alloc::vec![
+                ("initialization", initialization),
+                ("computation", computation),
+                ("reading_output", reading_output),
+            ],
+            BenchmarkResult::ExampleAES256 {
+                enc_initialization,
+                enc_computation,
+                enc_deinitalization,
+                dec_initialization,
+                dec_computation,
+                dec_deinitalization,
+            } => alloc::vec![
+                ("enc_initialization", enc_initialization),
+                ("enc_computation", enc_computation),
+                ("enc_deinitalization", enc_deinitalization),
+                ("dec_initialization", dec_initialization),
+                ("dec_computation", dec_computation),
+                ("dec_deinitalization", dec_deinitalization),
+            ],
+            BenchmarkResult::ExampleRNG {
+                initialization,
+                generation,
+            } => alloc::vec![
+                ("initialization", initialization),
+                ("generation", generation)
+            ],
+            BenchmarkResult::ExampleECDSA { signing, verifying }
+            | BenchmarkResult::ECDSA { signing, verifying } => {
+                alloc::vec![("signing", signing), ("verifying", verifying)]
+            }
+            BenchmarkResult::AESTotal {
+                computation,
+                input_writes,
+                status_polls,
+                output_reads,
+            } => alloc::vec![
+                ("computation", computation),
+                ("input_writes", input_writes),
+                ("status_polls", status_polls),
+                ("output_reads", output_reads),
+            ],
+            BenchmarkResult::AES {
+                initialization,
+                computation,
+                deinitialization,
+            }
+            | BenchmarkResult::AESSweep {
+                initialization,
+                computation,
+                deinitialization,
+                ..
+            } => alloc::vec![
+                ("initialization", initialization),
+                ("computation", computation),
+                ("deinitialization", deinitialization),
+            ],
+            BenchmarkResult::AESThroughput {
+                initialization,
+                computation,
+                ..
+            } => alloc::vec![
+                ("initialization", initialization),
+                ("computation", computation)
+            ],
+            BenchmarkResult::GCM {
+                hash_subkey,
+                encryption,
+                ghash,
+            } => alloc::vec![
+                ("hash_subkey", hash_subkey),
+                ("encryption", encryption),
+                ("ghash", ghash),
+            ],
+            BenchmarkResult::ChaCha20Poly1305 {
+                key_setup,
+                encryption,
+                authentication,
+            } => alloc::vec![
+                ("key_setup", key_setup),
+                ("encryption", encryption),
+                ("authentication", authentication),
+            ],
+            BenchmarkResult::Hashing {
+                initialization,
+                computation,
+                reading_output,
+            }
+            | BenchmarkResult::SHA256Streaming {
+                initialization,
+                computation,
+                reading_output,
+                ..
+            }
+            | BenchmarkResult::SHA3 {
+                initialization,
+                computation,
+                reading_output,
+                ..
+            } => alloc::vec![
+                ("initialization", initialization),
+                ("computation", computation),
+                ("reading_output", reading_output),
+            ],
+            BenchmarkResult::HMAC {
+                key_writing,
+                computation,
+                reading_output,
+            } => alloc::vec![
+                ("key_writing", key_writing),
+                ("computation", computation),
+                ("reading_output", reading_output),
+            ],
+            BenchmarkResult::XOF {
+                initialization,
+                absorbing,
+                ref squeezes,
+            } => {
+                let mut phases =
+                    alloc::vec![("initialization", initialization), ("absorbing", absorbing)];
+                phases.extend(squeezes.iter().map(|cycles| ("squeeze", *cycles)));
+                phases
+            }
+            BenchmarkResult::KMAC {
+                key_loading,
+                absorbing,
+                ref squeezes,
+            } => {
+                let mut phases =
+                    alloc::vec![("key_loading", key_loading), ("absorbing", absorbing)];
+                phases.extend(squeezes.iter().map(|cycles| ("squeeze", *cycles)));
+                phases
+            }
+            BenchmarkResult::RNG {
+                initialization,
+                generation,
+            }
+            | BenchmarkResult::RNGGenerateLength {
+                initialization,
+                generation,
+                ..
+            } => alloc::vec![
+                ("initialization", initialization),
+                ("generation", generation)
+            ],
+            BenchmarkResult::RNGReseed {
+                initialization,
+                reseed,
+                generation,
+            } => alloc::vec![
+                ("initialization", initialization),
+                ("reseed", reseed),
+                ("generation", generation),
+            ],
+            BenchmarkResult::OTBN {
+                loading,
+                execution,
+                reading_output,
+            } => alloc::vec![
+                ("loading", loading),
+                ("execution", execution),
+                ("reading_output", reading_output),
+            ],
+            BenchmarkResult::Phases(ref phases) => phases
+                .iter()
+                .map(|(name, cycles)| (name.as_str(), *cycles))
+                .collect(),
+        }
+    }
+
+    /// Returns the generic representation of this result, consisting of the names and cycles
+    /// of all of its phases
+    pub fn to_phases(&self) -> BenchmarkResult {
+        BenchmarkResult::Phases(
+            self.phases()
+                .into_iter()
+                .map(|(name, cycles)| (String::from(name), cycles))
+                .collect(),
+        )
+    }
+
+    /// Returns the number of cycles measured over all phases of the benchmark,
+    /// phases that only break down another phase are not counted
+    pub fn total(&self) -> u64 {
+        match *self {
+            BenchmarkResult::AESTotal { computation, .. } => computation,
+            _ => self.phases().iter().map(|(_, cycles)| cycles).sum(),
+        }
+    }
+
+    /// Returns a result of the same benchmark, with the provided number of cycles for every phase
+    ///
+    /// # Panics
+    ///
+    /// If fewer values than phases are provided.
+    ///
+    /// # Arguments
+    ///
+    /// * `cycles` - the cycles of every phase, in the order returned by `phases`
+    pub fn with_phases(&self, cycles: &[u64]) -> BenchmarkResult {
+        match self {
+            BenchmarkResult::ExampleSHA256 { .. } => BenchmarkResult::ExampleSHA256 {
+                initialization: cycles[0],
+                computation: cycles[1],
+                reading_output: cycles[2],
+            },
+            BenchmarkResult::ExampleAES256 { .. } => BenchmarkResult::ExampleAES256 {
+                enc_initialization: cycles[0],
+                enc_computation: cycles[1],
+                enc_deinitalization: cycles[2],
+                dec_initialization: cycles[3],
+                dec_computation: cycles[4],
+                dec_deinitalization: cycles[5],
+            },
+            BenchmarkResult::ExampleRNG { .. } => BenchmarkResult::ExampleRNG {
+                initialization: cycles[0],
+                generation: cycles[1],
+            },
+            BenchmarkResult::ExampleECDSA { .. } => BenchmarkResult::ExampleECDSA {
+                signing: cycles[0],
+                verifying: cycles[1],
+            },
+            BenchmarkResult::AESTotal { .. } => BenchmarkResult::AESTotal {
+                computation: cycles[0],
+                input_writes: cycles[1],
+                status_polls: cycles[2],
+                output_reads: cycles[3],
+            },
+            BenchmarkResult::AES { .. } => BenchmarkResult::AES {
+                initialization: cycles[0],
+                computation: cycles[1],
+                deinitialization: cycles[2],
+            },
+            BenchmarkResult::AESSweep {
+                key_length,
+                mode,
+                blocks,
+                ..
+            } => BenchmarkResult::AESSweep {
+                key_length: *key_length,
+                mode: *mode,
+                blocks: *blocks,
+                initialization: cycles[0],
+                computation: cycles[1],
+                deinitialization: cycles[2],
+            },
+            BenchmarkResult::AESThroughput { bytes, .. } => BenchmarkResult::AESThroughput {
+                bytes: *bytes,
+                millicycles_per_byte: cycles[1] * 1000 / (*bytes).max(1) as u64,
+                initialization: cycles[0],
+                computation: cycles[1],
+            },
+            BenchmarkResult::GCM { .. } => BenchmarkResult::GCM {
+                hash_subkey: cycles[0],
+                encryption: cycles[1],
+                ghash: cycles[2],
+            },
+            BenchmarkResult::ChaCha20Poly1305 { .. } => BenchmarkResult::ChaCha20Poly1305 {
+                key_setup: cycles[0],
+                encryption: cycles[1],
+                authentication: cycles[2],
+            },
+            BenchmarkResult::Hashing { .. } => BenchmarkResult::Hashing {
+                initialization: cycles[0],
+                computation: cycles[1],
+                reading_output: cycles[2],
+            },
+            BenchmarkResult::HMAC { .. } => BenchmarkResult::HMAC {
+                key_writing: cycles[0],
+                computation: cycles[1],
+                reading_output: cycles[2],
+            },
+            BenchmarkResult::SHA256Streaming {
+                burst,
+                blocks,
+                stalls,
+                ..
+            } => BenchmarkResult::SHA256Streaming {
+                burst: *burst,
+                blocks: *blocks,
+                stalls: *stalls,
+                cycles_per_block: cycles[1] / (*blocks).max(1) as u64,
+                initialization: cycles[0],
+                computation: cycles[1],
+                reading_output: cycles[2],
+            },
+            BenchmarkResult::SHA3 { digest_length, .. } => BenchmarkResult::SHA3 {
+                digest_length: *digest_length,
+                initialization: cycles[0],
+                computation: cycles[1],
+                reading_output: cycles[2],
+            },
+            BenchmarkResult::XOF { squeezes, .. } => BenchmarkResult::XOF {
+                initialization: cycles[0],
+                absorbing: cycles[1],
+                squeezes: cycles[2..2 + squeezes.len()].to_vec(),
+            },
+            BenchmarkResult::KMAC { squeezes, .. } => BenchmarkResult::KMAC {
+                key_loading: cycles[0],
+                absorbing: cycles[1],
+                squeezes: cycles[2..2 + squeezes.len()].to_vec(),
+            },
+            BenchmarkResult::RNG { .. } => BenchmarkResult::RNG {
+                initialization: cycles[0],
+                generation: cycles[1],
+            },
+            BenchmarkResult::RNGReseed { .. } => BenchmarkResult::RNGReseed {
+                initialization: cycles[0],
+                reseed: cycles[1],
+                generation: cycles[2],
+            },
+            BenchmarkResult::RNGGenerateLength { length, blocks, .. } => {
+                BenchmarkResult::RNGGenerateLength {
+                    length: *length,
+                    blocks: *blocks,
+                    cycles_per_block: cycles[1] / (*blocks).max(1) as u64,
+                    initialization: cycles[0],
+                    generation: cycles[1],
+                }
+            }
+            BenchmarkResult::ECDSA { .. } => BenchmarkResult::ECDSA {
+                signing: cycles[0],
+                verifying: cycles[1],
+            },
+            BenchmarkResult::OTBN { .. } => BenchmarkResult::OTBN {
+                loading: cycles[0],
+                execution: cycles[1],
+                reading_output: cycles[2],
+            },
+            BenchmarkResult::Phases(phases) => BenchmarkResult::Phases(
+                phases
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (name, _))| (name.clone(), cycles[i]))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Represents an error that occurred on the Suite while handling a request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum SuiteError {
+    /// The Suite received a message it could not deserialize
+    ///
+    /// The first parameter is the unparsed message
+    InvalidMessage(String),
+    /// The module required to perform the request is not present on the platform
+    ModuleUnavailable,
+    /// The requested dataset does not exist on the Suite
+    UnknownDataset,
+    /// The output produced by the hardware did not match the expected output
+    VerificationFailed,
+    /// The hardware signaled a fault while performing the request
+    HardwareFault,
+    /// The requested wait strategy is not supported by the platform
+    UnsupportedWaitStrategy,
+    /// The requested config can not be applied on the platform
+    UnsupportedConfig,
+    /// The Suite received a well-formed message of a newer schema, that it does not understand
+    ///
+    /// The first parameter is the name of the message
+    UnsupportedMessage(String),
+    /// An error that is not known to this build
+    #[serde(other)]
+    Unknown,
+}
+
+/// Well-known codes the Suite suspends with, the platform signals them to its environment,
+/// e.g. as exit status of the simulator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum SuspendCode {
+    /// The Suite finished executing
+    Success,
+    /// The Suite panicked
+    PanicOnSuite,
+    /// A test of the Suite failed
+    TestFailure,
+    /// The Suite stopped responding and was stopped by a watchdog
+    WatchdogTimeout,
+    /// The user requested the suspension with a custom code
+    UserRequested(u16),
+    /// A numeric code that is neither well-known nor requested by the user
+    Unknown(u32),
+}
+
+impl SuspendCode {
+    /// Returns the numeric code signaled to the environment of the Suite,
+    /// custom codes of the user are offset by 0x10000 to keep them apart from well-known codes
+    pub fn code(self) -> u32 {
+        match self {
+            SuspendCode::Success => 0,
+            SuspendCode::PanicOnSuite => 101,
+            SuspendCode::TestFailure => 102,
+            SuspendCode::WatchdogTimeout => 103,
+            SuspendCode::UserRequested(code) => 0x1_0000 | code as u32,
+            SuspendCode::Unknown(code) => code,
+        }
+    }
+
+    /// Returns the suspend code corresponding to a numeric code, the inverse of `code`
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - the numeric code, as returned by `code`
+    pub fn from_code(code: u32) -> SuspendCode {
+        match code {
+            0 => SuspendCode::Success,
+            101 => SuspendCode::PanicOnSuite,
+            102 => SuspendCode::TestFailure,
+            103 => SuspendCode::WatchdogTimeout,
+            code if code & 0xFFFF_0000 == 0x1_0000 => SuspendCode::UserRequested(code as u16),
+            code => SuspendCode::Unknown(code),
+        }
+    }
+}
+
+impl core::fmt::Display for SuspendCode {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SuspendCode::Success => write!(f, "finished successfully"),
+            SuspendCode::PanicOnSuite => write!(f, "the suite panicked"),
+            SuspendCode::TestFailure => write!(f, "a test of the suite failed"),
+            SuspendCode::WatchdogTimeout => write!(f, "the suite stopped responding"),
+            SuspendCode::UserRequested(code) => write!(f, "requested by the user with code {code}"),
+            SuspendCode::Unknown(code) => write!(f, "unknown code {code:#x}"),
+        }
+    }
+}
+
+/// Describes an output of a benchmark that did not match the expected output
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct VerificationFailure {
+    /// Name of the result of the benchmark whose output did not match
+    pub benchmark: String,
+    /// Index of the first block of the output that did not match,
+    /// None if the output is not divided into blocks
+    pub block_index: Option<u32>,
+}
+
+/// Reason why the Suite skipped a benchmark
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum SkipReason {
+    /// A module required to perform the benchmark is not present on the platform
+    ModuleUnavailable,
+    /// A reason that is not known to this build
+    #[serde(other)]
+    Unknown,
+}
+
+/// Severity of a log message sent by the Suite
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+    /// A level that is not known to this build
+    #[serde(other)]
+    Unknown,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for LogLevel {
+    fn default() -> Self {
+        LogLevel::Debug
+    }
 }
 
 /// Represents the status of the Suite
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum SuiteStatus {
     Ready,
     Done,
+    /// A status that is not known to this build
+    #[serde(other)]
+    Unknown,
+}
+
+/// Version of the message schema, incremented whenever messages or their contents are changed
+pub const SCHEMA_VERSION: u32 = 43;
+
+/// Token used by either side to resynchronize the framing of the communication,
+/// after it was disturbed, e.g. by line noise or an interrupted session.
+///
+/// A line containing the token is never parsed as a message, any content preceding the last token
+/// on the same line is discarded. The token may be followed by an identifier on the same line.
+/// The Suite acknowledges a received token by sending it back together with its identifier,
+/// once all requests received before it were handled.
+/// The Suite also sends the token without an identifier once at startup.
+pub const RESYNC_TOKEN: &str = "~RESYNC~";
+
+/// Returns the identifier following the resync token,
+/// or None if the provided line does not contain the resync token
+///
+/// # Arguments
+///
+/// * `line` - the received line
+pub fn parse_resync(line: &str) -> Option<&str> {
+    // The delimiters of all codecs are trimmed, so a line terminated by the delimiter of another
+    // codec is still recognized, which allows probing the codec of the other side.
+    // Only the last token counts, earlier ones belong to probes that were not answered
+    line.rfind(RESYNC_TOKEN).map(|start| {
+        line[start + RESYNC_TOKEN.len()..].trim_matches(|c: char| c.is_whitespace() || c == '\0')
+    })
 }
 
 /// Alias for messages sent from the CLI to the Suite, when building the CLI
@@ -107,27 +1649,102 @@ pub type OutgoingMessage = _SuiteToCliMessage;
 #[cfg(feature = "suite")]
 pub type IncomingMessage = _CliToSuiteMessage;
 
-/// Serializes an outgoing message to a String that can be exchanged
+/// Serializes an outgoing message to a frame that can be exchanged,
+/// including the terminating delimiter
 ///
 /// # Arguments
 ///
 /// * `value` - the message that should be serialized
 #[cfg(any(feature = "cli", feature = "suite"))]
-pub fn serialize(value: &OutgoingMessage) -> String {
-    serde_json::to_string(&value).expect("Can not serialize struct")
+pub fn serialize(value: &OutgoingMessage) -> Vec<u8> {
+    let mut frame = Codec::encode(value);
+    frame.push(Codec::DELIMITER);
+    frame
 }
 
-/// Deserializes a String to an incoming message
+/// Serializes an outgoing message to a frame of the given format,
+/// including the terminating delimiter
 ///
 /// # Arguments
 ///
-/// * `value` - the String that should be deserialized
+/// * `format` - the format of the frame
+/// * `value` - the message that should be serialized
+#[cfg(feature = "cli")]
+pub fn serialize_as(format: WireFormat, value: &OutgoingMessage) -> Vec<u8> {
+    let mut frame = format.encode(value);
+    frame.push(format.delimiter());
+    frame
+}
+
+/// Deserializes a frame to an incoming message
+///
+/// # Arguments
+///
+/// * `frame` - the frame that should be deserialized, without the terminating delimiter
 #[cfg(any(feature = "cli", feature = "suite"))]
-pub fn deserialize(value: String) -> IncomingMessage {
-    if let Ok(value) = serde_json::from_str(&value) {
-        value
-    } else {
-        IncomingMessage::Invalid(value)
+pub fn deserialize(frame: &[u8]) -> IncomingMessage {
+    deserialize_as(WireFormat::DEFAULT, frame)
+}
+
+/// Deserializes a frame of the given format to an incoming message
+///
+/// # Arguments
+///
+/// * `format` - the format of the frame
+/// * `frame` - the frame that should be deserialized, without the terminating delimiter
+#[cfg(any(feature = "cli", feature = "suite"))]
+pub fn deserialize_as(format: WireFormat, frame: &[u8]) -> IncomingMessage {
+    if let Some(value) = format.decode(frame) {
+        return value;
+    }
+
+    if let Some(VariantName(name)) = format.decode(frame) {
+        return IncomingMessage::Unsupported(name);
+    }
+
+    IncomingMessage::Invalid(String::from_utf8_lossy(frame).trim().into())
+}
+
+/// Name of the variant of an externally tagged enum, deserialized without knowing the enum,
+/// the content of the variant is ignored
+#[cfg(any(feature = "cli", feature = "suite"))]
+struct VariantName(String);
+
+#[cfg(any(feature = "cli", feature = "suite"))]
+impl<'de> Deserialize<'de> for VariantName {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct VariantNameVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for VariantNameVisitor {
+            type Value = VariantName;
+
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+                formatter.write_str("a variant of an enum")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, name: &str) -> Result<Self::Value, E> {
+                Ok(VariantName(name.into()))
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> Result<Self::Value, A::Error> {
+                use serde::de::{Error, IgnoredAny};
+
+                let (name, IgnoredAny) = map
+                    .next_entry::<String, IgnoredAny>()?
+                    .ok_or_else(|| A::Error::custom("missing variant"))?;
+
+                if map.next_key::<IgnoredAny>()?.is_some() {
+                    return Err(A::Error::custom("more than one variant"));
+                }
+
+                Ok(VariantName(name))
+            }
+        }
+
+        deserializer.deserialize_any(VariantNameVisitor)
     }
 }
 
@@ -140,7 +1757,7 @@ pub fn deserialize(value: String) -> IncomingMessage {
 pub fn parse_raw(value: &str) -> _CliToSuiteMessage {
     use crate::alloc::string::ToString;
 
-    if let Ok(value) = serde_json::from_str(&value) {
+    if let Ok(value) = serde_json::from_str(value) {
         value
     } else {
         _CliToSuiteMessage::Invalid(value.to_string())
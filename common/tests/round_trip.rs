@@ -0,0 +1,211 @@
+//! Checks that every message of the corpus survives encoding and decoding with the selected codec,
+//! and that the corpus covers every variant of the messages.
+
+use benchmark_common::{
+    _CliToSuiteMessage, _SuiteToCliMessage,
+    codec::{Codec, MessageCodec},
+    corpus, BenchmarkInfo, BenchmarkResult, HashBenchmarkType, ResultEnvelope, SuspendCode,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::collections::BTreeSet;
+
+/// Encodes and decodes the value, returns the first and the second encoding
+fn round_trip<T: Serialize + DeserializeOwned + std::fmt::Debug>(value: &T) -> (Vec<u8>, Vec<u8>) {
+    let encoded = Codec::encode(value);
+    assert!(!encoded.contains(&Codec::DELIMITER), "{value:?}");
+    let decoded: T = Codec::decode(&encoded).unwrap_or_else(|| panic!("{value:?}"));
+
+    (encoded, Codec::encode(&decoded))
+}
+
+#[test]
+fn cli_to_suite_messages_round_trip() {
+    for msg in corpus::cli_to_suite_messages() {
+        let (encoded, reencoded) = round_trip(&msg);
+        assert_eq!(encoded, reencoded, "{msg:?}");
+    }
+}
+
+#[test]
+fn suite_to_cli_messages_round_trip() {
+    for msg in corpus::suite_to_cli_messages() {
+        let (encoded, reencoded) = round_trip(&msg);
+        assert_eq!(encoded, reencoded, "{msg:?}");
+    }
+}
+
+#[test]
+fn packed_envelope_unpacks_to_original() {
+    let original = corpus::envelope(corpus::benchmark_results());
+    let mut packed = original.clone();
+    packed.pack();
+
+    let mut decoded: ResultEnvelope = Codec::decode(&Codec::encode(&packed)).unwrap();
+    assert!(decoded.unpack());
+    assert_eq!(Codec::encode(&decoded), Codec::encode(&original));
+}
+
+#[test]
+fn suspend_codes_round_trip() {
+    for code in [
+        SuspendCode::Success,
+        SuspendCode::PanicOnSuite,
+        SuspendCode::TestFailure,
+        SuspendCode::WatchdogTimeout,
+        SuspendCode::UserRequested(0),
+        SuspendCode::UserRequested(3),
+        SuspendCode::UserRequested(u16::MAX),
+        SuspendCode::Unknown(1),
+        SuspendCode::Unknown(0x2_0000),
+        SuspendCode::Unknown(u32::MAX),
+    ] {
+        assert_eq!(SuspendCode::from_code(code.code()), code);
+    }
+}
+
+#[test]
+#[cfg(all(
+    any(feature = "cli", feature = "suite"),
+    not(feature = "codec_postcard")
+))]
+fn unknown_messages_are_unsupported() {
+    use benchmark_common::{deserialize, IncomingMessage};
+
+    /// A message of a newer schema
+    #[derive(Serialize)]
+    enum Newer {
+        Future { value: u32 },
+        Later,
+    }
+
+    for (msg, name) in [
+        (Newer::Future { value: 1 }, "Future"),
+        (Newer::Later, "Later"),
+    ] {
+        match deserialize(&Codec::encode(&msg)) {
+            IncomingMessage::Unsupported(unsupported) => assert_eq!(unsupported, name),
+            other => panic!("{other:?}"),
+        }
+    }
+}
+
+/// Asserts that the values cover every variant matched by the patterns, which have to match
+/// every variant of the type, so new variants can not be forgotten
+macro_rules! assert_covers {
+    ($values:expr, $($pattern:pat => $name:literal),+ $(,)?) => {{
+        let seen: BTreeSet<&str> = $values
+            .iter()
+            .map(|value| match value {
+                $($pattern => $name),+
+            })
+            .collect();
+        let missing: Vec<&str> = [$($name),+]
+            .into_iter()
+            .filter(|name| !seen.contains(name))
+            .collect();
+        assert!(missing.is_empty(), "variants missing from the corpus: {missing:?}");
+    }};
+}
+
+#[test]
+fn corpus_covers_every_variant() {
+    assert_covers!(
+        &corpus::cli_to_suite_messages(),
+        _CliToSuiteMessage::GetStatus => "GetStatus",
+        _CliToSuiteMessage::GetCapabilities => "GetCapabilities",
+        _CliToSuiteMessage::GetPlatform => "GetPlatform",
+        _CliToSuiteMessage::ListDatasets => "ListDatasets",
+        _CliToSuiteMessage::Done => "Done",
+        _CliToSuiteMessage::Suspend(_) => "Suspend",
+        _CliToSuiteMessage::Reset => "Reset",
+        _CliToSuiteMessage::Ping(_) => "Ping",
+        _CliToSuiteMessage::Benchmark(..) => "Benchmark",
+        _CliToSuiteMessage::SetConfig(_) => "SetConfig",
+        _CliToSuiteMessage::Replay => "Replay",
+        _CliToSuiteMessage::Unsupported(_) => "Unsupported",
+        _CliToSuiteMessage::Invalid(_) => "Invalid",
+    );
+    assert_covers!(
+        &corpus::suite_to_cli_messages(),
+        _SuiteToCliMessage::Status(_) => "Status",
+        _SuiteToCliMessage::Capabilities(_) => "Capabilities",
+        _SuiteToCliMessage::Platform(_) => "Platform",
+        _SuiteToCliMessage::Datasets(_) => "Datasets",
+        _SuiteToCliMessage::Error(_) => "Error",
+        _SuiteToCliMessage::Log { .. } => "Log",
+        _SuiteToCliMessage::BenchmarkResults(_) => "BenchmarkResults",
+        _SuiteToCliMessage::Pong(_) => "Pong",
+        _SuiteToCliMessage::Skipped { .. } => "Skipped",
+        _SuiteToCliMessage::Config(_) => "Config",
+        _SuiteToCliMessage::Accepted { .. } => "Accepted",
+        _SuiteToCliMessage::Unsupported(_) => "Unsupported",
+        _SuiteToCliMessage::ResultChunk { .. } => "ResultChunk",
+        _SuiteToCliMessage::Window { .. } => "Window",
+        _SuiteToCliMessage::DutyCycleDone { .. } => "DutyCycleDone",
+        _SuiteToCliMessage::Invalid(_) => "Invalid",
+    );
+    assert_covers!(
+        &corpus::benchmark_infos(),
+        BenchmarkInfo::ExampleSHA256 => "ExampleSHA256",
+        BenchmarkInfo::ExampleAES256 => "ExampleAES256",
+        BenchmarkInfo::ExampleRNG => "ExampleRNG",
+        BenchmarkInfo::ExampleECDSA => "ExampleECDSA",
+        BenchmarkInfo::AESTotal => "AESTotal",
+        BenchmarkInfo::AESDataSet(_) => "AESDataSet",
+        BenchmarkInfo::GCMDataSet(_) => "GCMDataSet",
+        BenchmarkInfo::ChaCha20Poly1305(_) => "ChaCha20Poly1305",
+        BenchmarkInfo::AESSweep { .. } => "AESSweep",
+        BenchmarkInfo::AESContextSwitch { .. } => "AESContextSwitch",
+        BenchmarkInfo::AESThroughput { .. } => "AESThroughput",
+        BenchmarkInfo::HashingDataSet(_) => "HashingDataSet",
+        BenchmarkInfo::Hash { .. } => "Hash",
+        BenchmarkInfo::KMACMasking { .. } => "KMACMasking",
+        BenchmarkInfo::SHA256Streaming { .. } => "SHA256Streaming",
+        BenchmarkInfo::SHA3 { .. } => "SHA3",
+        BenchmarkInfo::RNGDataSet(_) => "RNGDataSet",
+        BenchmarkInfo::RNGReseed { .. } => "RNGReseed",
+        BenchmarkInfo::RNGGenerateLengths { .. } => "RNGGenerateLengths",
+        BenchmarkInfo::EntropyLatency => "EntropyLatency",
+        BenchmarkInfo::ECDSA(_) => "ECDSA",
+        BenchmarkInfo::OTBN(_) => "OTBN",
+        BenchmarkInfo::Teardown(_) => "Teardown",
+        BenchmarkInfo::PollingInterval { .. } => "PollingInterval",
+        BenchmarkInfo::MicroBenchmarks => "MicroBenchmarks",
+        BenchmarkInfo::Calibrated(_) => "Calibrated",
+    );
+    assert_covers!(
+        &corpus::hash_types(),
+        HashBenchmarkType::SHA256 => "SHA256",
+        HashBenchmarkType::HmacSHA256 { .. } => "HmacSHA256",
+        HashBenchmarkType::SHAKE128 { .. } => "SHAKE128",
+        HashBenchmarkType::SHAKE256 { .. } => "SHAKE256",
+        HashBenchmarkType::CSHAKE128 { .. } => "CSHAKE128",
+        HashBenchmarkType::CSHAKE256 { .. } => "CSHAKE256",
+        HashBenchmarkType::Kmac256 { .. } => "Kmac256",
+    );
+    assert_covers!(
+        &corpus::benchmark_results(),
+        BenchmarkResult::ExampleSHA256 { .. } => "ExampleSHA256",
+        BenchmarkResult::ExampleAES256 { .. } => "ExampleAES256",
+        BenchmarkResult::ExampleRNG { .. } => "ExampleRNG",
+        BenchmarkResult::ExampleECDSA { .. } => "ExampleECDSA",
+        BenchmarkResult::AESTotal { .. } => "AESTotal",
+        BenchmarkResult::AES { .. } => "AES",
+        BenchmarkResult::AESSweep { .. } => "AESSweep",
+        BenchmarkResult::AESThroughput { .. } => "AESThroughput",
+        BenchmarkResult::GCM { .. } => "GCM",
+        BenchmarkResult::ChaCha20Poly1305 { .. } => "ChaCha20Poly1305",
+        BenchmarkResult::Hashing { .. } => "Hashing",
+        BenchmarkResult::HMAC { .. } => "HMAC",
+        BenchmarkResult::SHA256Streaming { .. } => "SHA256Streaming",
+        BenchmarkResult::SHA3 { .. } => "SHA3",
+        BenchmarkResult::XOF { .. } => "XOF",
+        BenchmarkResult::KMAC { .. } => "KMAC",
+        BenchmarkResult::RNG { .. } => "RNG",
+        BenchmarkResult::RNGReseed { .. } => "RNGReseed",
+        BenchmarkResult::RNGGenerateLength { .. } => "RNGGenerateLength",
+        BenchmarkResult::ECDSA { .. } => "ECDSA",
+        BenchmarkResult::OTBN { .. } => "OTBN",
+        BenchmarkResult::Phases(_) => "Phases",
+    );
+}
@@ -30,6 +30,8 @@ OPENTITAN_LIBS_PATH = CWD.joinpath("target", "opentitan_libs")
 OPENTITAN_LIBS = [
     "sw/device/lib/crypto/ecdsa_p256/libsw_lib_crypto_ecdsa_p256.a",
     "sw/otbn/libp256_ecdsa.a",
+    "sw/otbn/librsa.a",
+    "sw/otbn/libp256_scalar_mult.a",
     "sw/device/lib/crypto/libsw_lib_crypto_otbn_util.a",
     "sw/device/lib/crypto/drivers/libsw_lib_crypto_otbn.a",
 ]
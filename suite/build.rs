@@ -2,6 +2,7 @@ use std::env;
 use std::fs::File;
 use std::io::Write;
 use std::path::Path;
+use std::process::Command;
 
 /// Customizes the build process of the suite to use the appropriate memory file for linking.
 ///
@@ -27,7 +28,22 @@ fn main() {
         .expect("Missing opentitan libraries, OPENTITAN_LIBS_PATH environment variable");
     println!("cargo:rustc-link-search={}", ot_libs);
 
+    // Embed the git hash of the firmware, so it can be reported alongside all results
+    let git_hash = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success());
+    if let Some(output) = git_hash {
+        println!(
+            "cargo:rustc-env=SUITE_GIT_HASH={}",
+            String::from_utf8_lossy(&output.stdout).trim()
+        );
+    }
+
     println!("cargo:rerun-if-changed=memory/qemu_virt.x");
     println!("cargo:rerun-if-changed=memory/verilator_earlgrey.x");
     println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=../.git/HEAD");
+    println!("cargo:rerun-if-changed=../.git/refs");
 }
@@ -0,0 +1,2630 @@
+use core::{arch::asm, cell::Cell, ops::Sub};
+
+use alloc::{boxed::Box, format, string::String, vec::Vec};
+use benchmark_common::{
+    AESSweepMode, Aggregate, BenchmarkConfig, BenchmarkInfo, BenchmarkResult, DutyCycle,
+    HashBenchmarkType, OTBNBenchmarkType, PollingModule, ResultEnvelope, SuiteError,
+    TeardownBenchmarkType, VerificationFailure, VerificationPolicy, WaitStrategy, SCHEMA_VERSION,
+};
+
+use crate::{
+    datasets::{
+        self,
+        aes::AESDataset,
+        ecdsa::ECDSADataset,
+        gcm::GCMDataset,
+        hashing::{HMACKey, HashingDataset},
+        rng::RNGDataset,
+    },
+    libs::chacha20poly1305::{chacha20_xor, Poly1305},
+    modules::{
+        self, AESKeyLength, AESMode, AESOperation, KMACEntropy, KMACModule, ModuleRef, SHA3Length,
+        TimerModule, XOFStrength,
+    },
+    platform::{self, Platform},
+    runtime::{self, Global},
+};
+
+/// Timer sampled in addition to the cycle counter, if virtual time was requested
+static VIRTUAL_TIMER: Global<Option<ModuleRef<dyn TimerModule>>> = Global::new(None);
+
+/// Cycles a single iteration of recently performed benchmarks took, used to predict how long they
+/// take when they are requested again. Oldest entries first
+static ITERATION_CYCLES: Global<Vec<(BenchmarkInfo, u64)>> = Global::new(Vec::new());
+
+/// Number of benchmarks whose iteration cycles are remembered
+const ITERATION_CYCLES_CAPACITY: usize = 16;
+
+/// True if the outputs of the benchmarks should be compared to the expected outputs,
+/// as determined by the Suite config
+fn verification_enabled() -> bool {
+    runtime::config().verification != VerificationPolicy::Skip
+}
+
+/// An output of a benchmark that did not match the expected output
+struct Mismatch {
+    /// Index of the first mismatching block, None if the output is not divided into blocks
+    block_index: Option<u32>,
+}
+
+/// The first mismatch of the benchmark that is currently performed
+static MISMATCH: Global<Option<Mismatch>> = Global::new(None);
+
+/// Records a mismatch, unless the current benchmark already had one
+///
+/// # Arguments
+///
+/// * `block_index` - index of the first mismatching block, if the output is divided into blocks
+fn record_mismatch(block_index: Option<u32>) {
+    MISMATCH.with(|mismatch| {
+        if mismatch.is_none() {
+            *mismatch = Some(Mismatch { block_index });
+        }
+    });
+}
+
+/// Compares the blocks of an output to the expected blocks, if verification is enabled.
+/// Outputs of a different length mismatch at the end of the shorter one
+///
+/// # Arguments
+///
+/// * `expected` - the expected blocks
+/// * `output` - the blocks produced by the benchmark
+fn verify_blocks<T: PartialEq>(expected: &[T], output: &[T]) {
+    if !verification_enabled() {
+        return;
+    }
+
+    let mismatch = expected
+        .iter()
+        .zip(output)
+        .position(|(expected, output)| expected != output)
+        .or_else(|| (expected.len() != output.len()).then(|| expected.len().min(output.len())));
+    if let Some(index) = mismatch {
+        record_mismatch(Some(index as u32));
+    }
+}
+
+/// Compares an output to the expected output, if verification is enabled
+///
+/// # Arguments
+///
+/// * `expected` - the expected output
+/// * `output` - the output produced by the benchmark
+fn verify<T: PartialEq>(expected: &T, output: &T) {
+    if verification_enabled() && expected != output {
+        record_mismatch(None);
+    }
+}
+
+/// Returns the machine cycle counter
+///
+/// Adapted from Fig. 10.1 on Page 61 of ["Volume I: RISC-V Unprivileged ISA V20191213"]
+/// (https://riscv.org/wp-content/uploads/2019/12/riscv-spec-20191213.pdf)
+///
+/// The `rdcycle x` and `rdcycleh x` instructions have been replaced by
+/// `csrr x, mcycle` and `csrr x, mcycleh` respectively, so this function can be called
+/// while being in machine mode.
+#[inline]
+#[allow(dead_code)]
+pub fn get_cycle() -> u64 {
+    let counter_lo: u32;
+    let counter_hi: u32;
+
+    unsafe {
+        asm!(
+            "1: csrr {hi_old}, mcycleh",
+            "csrr {lo}, mcycle",
+            "csrr {hi}, mcycleh",
+            "bne {hi_old}, {hi}, 1b",
+            hi_old = out(reg) _,
+            lo = out(reg) counter_lo,
+            hi = out(reg) counter_hi
+        );
+    }
+    ((counter_hi as u64) << 32u64) + counter_lo as u64
+}
+
+/// The cycle counter, the number of status polls and the platform timer, sampled at the same time
+///
+/// Subtracting two samples yields the cycles, polls and timer ticks that passed between them.
+#[derive(Clone, Copy)]
+pub struct Sample {
+    /// Cycle counter, ending the phases that end at this sample
+    pub cycles: u64,
+    pub polls: u64,
+    /// Value of the platform timer, 0 if virtual time was not requested
+    pub mtime: u64,
+    /// Cycle counter after reading the platform timer, starting the phases that start at this
+    /// sample. Equal to `cycles` if virtual time was not requested
+    resumed: u64,
+}
+
+impl Sample {
+    /// Creates the difference of two samples from the cycles, polls and timer ticks between them,
+    /// for phases that are not measured by subtracting samples
+    pub fn elapsed(cycles: u64, polls: u64, mtime: u64) -> Sample {
+        Sample {
+            cycles,
+            polls,
+            mtime,
+            resumed: cycles,
+        }
+    }
+}
+
+impl Sub for Sample {
+    type Output = Sample;
+
+    fn sub(self, rhs: Sample) -> Sample {
+        Sample::elapsed(
+            self.cycles - rhs.resumed,
+            self.polls - rhs.polls,
+            self.mtime - rhs.mtime,
+        )
+    }
+}
+
+/// Samples the cycle counter and the number of status polls performed by the modules,
+/// as well as the platform timer if virtual time was requested
+///
+/// The platform timer is read between two reads of the cycle counter, so reading it is neither
+/// counted in the phases ending at the sample nor in those starting at it.
+#[inline]
+pub fn sample() -> Sample {
+    VIRTUAL_TIMER.with(|timer| match timer {
+        Some(timer) => {
+            let cycles = get_cycle();
+            let polls = modules::poll_count();
+            let mtime = timer.mtime();
+
+            Sample {
+                cycles,
+                polls,
+                mtime,
+                resumed: get_cycle(),
+            }
+        }
+        None => {
+            let polls = modules::poll_count();
+            let cycles = get_cycle();
+
+            Sample::elapsed(cycles, polls, 0)
+        }
+    })
+}
+
+/// The result of a single run of a benchmark, together with the status polls
+/// and timer ticks of every phase
+pub struct Measurement {
+    pub result: BenchmarkResult,
+    /// Number of status polls performed in every phase, in the order of `result.phases()`
+    pub polls: Vec<u64>,
+    /// Ticks of the platform timer in every phase, in the order of `result.phases()`
+    pub mtime: Vec<u64>,
+    /// Cycles the benchmark spent outside of its measured phases
+    pub unaccounted: u64,
+    /// The first output of the benchmark that did not match the expected output
+    pub verification_failure: Option<VerificationFailure>,
+}
+
+impl Measurement {
+    /// Creates a measurement from a result and the samples of its phases
+    ///
+    /// # Arguments
+    ///
+    /// * `result` - the result of the benchmark
+    /// * `phases` - the difference between the samples taken around every phase,
+    ///   in the order of `result.phases()`
+    pub fn new(result: BenchmarkResult, phases: &[Sample]) -> Measurement {
+        debug_assert_eq!(result.phases().len(), phases.len());
+
+        Measurement {
+            result,
+            polls: phases.iter().map(|phase| phase.polls).collect(),
+            mtime: phases.iter().map(|phase| phase.mtime).collect(),
+            unaccounted: 0,
+            verification_failure: None,
+        }
+    }
+}
+
+/// Performs a benchmark as configured and returns its results,
+/// or the error that prevented the benchmark from being performed.
+///
+/// The warmup iterations are performed first and their results are discarded.
+/// Without an aggregation in the config, the aggregation of the Suite config is used.
+/// Unknown aggregations are handled like `Aggregate::Samples`, unknown wait strategies like
+/// `WaitStrategy::Spin`.
+/// The status polls and timer ticks of every phase are aggregated in the same way as the cycles.
+/// The cycles spent outside of the measured phases, e.g. for setting up the inputs, are reported
+/// as unaccounted cycles of every result.
+/// Requests consisting of multiple benchmarks, like AES sweeps, report the results of all
+/// benchmarks one after the other.
+/// A repetition whose output does not match the expected output, or during which a module raised
+/// a hardware alert, is performed again after resetting the modules, up to the configured number of
+/// retries. If an output still does not match the expected output, no further repetitions or
+/// benchmarks are performed and the results up to the failed one are reported together with the failure.
+///
+/// # Arguments
+///
+/// * `info` - the benchmark that should be performed
+/// * `config` - how often the benchmark is performed and how its results are reported
+pub fn run(info: &BenchmarkInfo, config: &BenchmarkConfig) -> Result<ResultEnvelope, SuiteError> {
+    let benchmarks = benchmarks(info)?;
+
+    let wait_strategy = match config.wait_strategy {
+        WaitStrategy::Interrupt if !platform::current().supports_wait_for_interrupt() => {
+            return Err(SuiteError::UnsupportedWaitStrategy)
+        }
+        WaitStrategy::Unknown => WaitStrategy::Spin,
+        strategy => strategy,
+    };
+
+    let timer = if config.virtual_time {
+        Some(
+            platform::current()
+                .get_timer_module()
+                .ok_or(SuiteError::ModuleUnavailable)?,
+        )
+    } else {
+        None
+    };
+
+    modules::set_wait_strategy(wait_strategy);
+    VIRTUAL_TIMER.set(timer);
+    let start = get_cycle();
+    let measurements = measure(&benchmarks, config);
+    let cycles = get_cycle() - start;
+    VIRTUAL_TIMER.set(None);
+    modules::set_wait_strategy(WaitStrategy::Spin);
+    let (measurements, aggregate, retries) = measurements?;
+    record_iteration_cycles(info, cycles / iterations(config).max(1));
+
+    let verification_failure = measurements
+        .iter()
+        .find_map(|measurement| measurement.verification_failure.clone());
+
+    let mut results = Vec::with_capacity(measurements.len());
+    let mut poll_counts = Vec::with_capacity(measurements.len());
+    let mut virtual_time = Vec::new();
+    let mut unaccounted_cycles = Vec::with_capacity(measurements.len());
+    for measurement in measurements {
+        results.push(measurement.result);
+        poll_counts.push(measurement.polls);
+        unaccounted_cycles.push(measurement.unaccounted);
+        if config.virtual_time {
+            virtual_time.push(measurement.mtime);
+        }
+    }
+
+    Ok(ResultEnvelope {
+        schema_version: SCHEMA_VERSION,
+        platform: platform::current().descriptor(),
+        request_id: config.request_id,
+        label: config.label.clone(),
+        benchmark: Some(info.clone()),
+        config: Some(config.clone()),
+        suite_config: Some(runtime::config()),
+        aggregate,
+        wait_strategy,
+        results,
+        poll_counts,
+        virtual_time,
+        unaccounted_cycles,
+        verification_failure,
+        retries,
+        isolated: runtime::isolated(),
+        packed: None,
+    })
+}
+
+/// Performs a benchmark as configured in every window of a duty cycle, check `DutyCycle`.
+/// The results of every window are passed to `report` as soon as the window finished.
+///
+/// Returns the number of windows and of windows that started late,
+/// or the error that prevented a window from being performed.
+///
+/// # Arguments
+///
+/// * `info` - the benchmark that is requested
+/// * `config` - how the benchmark is performed in every window
+/// * `duty_cycle` - when the windows are started
+/// * `report` - called with the index, the start relative to the first window and the results
+///   of every window
+pub fn run_duty_cycled(
+    info: &BenchmarkInfo,
+    config: &BenchmarkConfig,
+    duty_cycle: &DutyCycle,
+    mut report: impl FnMut(u32, u64, ResultEnvelope),
+) -> Result<(u32, u32), SuiteError> {
+    if duty_cycle.period == 0 {
+        return Err(SuiteError::InvalidMessage(String::from(
+            "The period of a duty cycle must not be 0",
+        )));
+    }
+
+    let mut windows = 0;
+    let mut overruns = 0;
+    let start = get_cycle();
+    while (windows as u64).saturating_mul(duty_cycle.period) < duty_cycle.duration {
+        let scheduled = start + windows as u64 * duty_cycle.period;
+        let mut now = get_cycle();
+        if windows > 0 && now > scheduled {
+            overruns += 1;
+        }
+        while now < scheduled {
+            now = get_cycle();
+        }
+
+        report(windows, now - start, run(info, config)?);
+        windows += 1;
+    }
+
+    Ok((windows, overruns))
+}
+
+/// Returns the cycles a benchmark is expected to take, based on the last time it was performed,
+/// or None if it was not performed recently
+///
+/// # Arguments
+///
+/// * `info` - the benchmark that is requested
+/// * `config` - how often the benchmark is performed
+pub fn expected_cycles(info: &BenchmarkInfo, config: &BenchmarkConfig) -> Option<u64> {
+    ITERATION_CYCLES.with(|entries| {
+        entries
+            .iter()
+            .find(|(benchmark, _)| benchmark == info)
+            .map(|(_, cycles)| cycles.saturating_mul(iterations(config)))
+    })
+}
+
+/// Forgets the cycles of every benchmark performed so far, releasing their memory on the heap
+pub fn forget_iteration_cycles() {
+    ITERATION_CYCLES.set(Vec::new());
+}
+
+/// Remembers the cycles a single iteration of a benchmark took, replacing earlier values
+///
+/// # Arguments
+///
+/// * `info` - the benchmark that was performed
+/// * `cycles` - the cycles of a single iteration
+fn record_iteration_cycles(info: &BenchmarkInfo, cycles: u64) {
+    ITERATION_CYCLES.with(|entries| {
+        entries.retain(|(benchmark, _)| benchmark != info);
+        if entries.len() >= ITERATION_CYCLES_CAPACITY {
+            entries.remove(0);
+        }
+        entries.push((info.clone(), cycles));
+    });
+}
+
+/// Returns how often a benchmark is performed, including the warmup iterations
+///
+/// # Arguments
+///
+/// * `config` - how often the benchmark is performed
+fn iterations(config: &BenchmarkConfig) -> u64 {
+    config.warmup_iterations as u64 + config.repetitions as u64
+}
+
+/// A benchmark that can be performed repeatedly, fails with `SuiteError::ModuleUnavailable`
+/// if a required module is not available, or with `SuiteError::HardwareFault` if a module faulted
+type Benchmark = Box<dyn Fn() -> Result<Measurement, SuiteError>>;
+
+/// Wraps a benchmark that only fails if a required module is not available
+///
+/// # Arguments
+///
+/// * `benchmark` - the benchmark, returning None if a required module is not available
+fn available(benchmark: impl Fn() -> Option<Measurement> + 'static) -> Benchmark {
+    Box::new(move || benchmark().ok_or(SuiteError::ModuleUnavailable))
+}
+
+/// Returns the benchmarks that have to be performed for a request, in order
+///
+/// # Arguments
+///
+/// * `info` - the requested benchmark
+fn benchmarks(info: &BenchmarkInfo) -> Result<Vec<Benchmark>, SuiteError> {
+    let benchmark: Benchmark = match *info {
+        BenchmarkInfo::ExampleSHA256 => available(examples::sha256_benchmark),
+        BenchmarkInfo::ExampleAES256 => available(examples::aes256_benchmark),
+        BenchmarkInfo::ExampleRNG => available(examples::rng_benchmark),
+        BenchmarkInfo::ExampleECDSA => available(examples::ecdsa_benchmark),
+        BenchmarkInfo::AESTotal => available(examples::aes256_total_benchmark),
+        BenchmarkInfo::AESDataSet(id) => {
+            let dataset = datasets::aes::DATASETS
+                .get(id)
+                .ok_or(SuiteError::UnknownDataset)?;
+            available(move || aes_benchmark(dataset))
+        }
+        BenchmarkInfo::GCMDataSet(id) => {
+            let dataset = datasets::gcm::DATASETS
+                .get(id)
+                .ok_or(SuiteError::UnknownDataset)?;
+            available(move || gcm_benchmark(dataset))
+        }
+        BenchmarkInfo::ChaCha20Poly1305(id) => {
+            let dataset = datasets::gcm::DATASETS
+                .get(id)
+                .ok_or(SuiteError::UnknownDataset)?;
+            available(move || Some(chacha20_poly1305_benchmark(dataset)))
+        }
+        BenchmarkInfo::AESSweep {
+            ref key_lengths,
+            ref modes,
+            ref blocks,
+        } => return aes_sweep(key_lengths, modes, blocks),
+        BenchmarkInfo::AESContextSwitch { blocks } => {
+            if blocks == 0 || blocks > AES_MAX_BLOCKS {
+                return Err(SuiteError::InvalidMessage(format!(
+                    "AES context switches encrypt between 1 and {AES_MAX_BLOCKS} blocks, not {blocks}"
+                )));
+            }
+            available(move || aes_context_switch_benchmark(blocks))
+        }
+        BenchmarkInfo::AESThroughput { bytes } => {
+            if bytes == 0 || bytes > AES_THROUGHPUT_MAX_BYTES || bytes % AES_BLOCK_BYTES != 0 {
+                return Err(SuiteError::InvalidMessage(format!(
+                    "AES throughput benchmarks process a multiple of {AES_BLOCK_BYTES} bytes \
+                     between {AES_BLOCK_BYTES} and {AES_THROUGHPUT_MAX_BYTES}, not {bytes}"
+                )));
+            }
+            available(move || aes_throughput_benchmark(bytes / AES_BLOCK_BYTES))
+        }
+        BenchmarkInfo::HashingDataSet(id) => {
+            let dataset = datasets::hashing::DATASETS
+                .get(id)
+                .ok_or(SuiteError::UnknownDataset)?;
+            available(move || hashing_benchmark(dataset))
+        }
+        BenchmarkInfo::Hash {
+            ref hash,
+            dataset: id,
+        } => {
+            let dataset = datasets::hashing::DATASETS
+                .get(id)
+                .ok_or(SuiteError::UnknownDataset)?;
+            match *hash {
+                HashBenchmarkType::SHA256 => available(move || hashing_benchmark(dataset)),
+                HashBenchmarkType::HmacSHA256 { key_dataset } => {
+                    let key = datasets::hashing::KEYS
+                        .get(key_dataset)
+                        .ok_or(SuiteError::UnknownDataset)?;
+                    available(move || hmac_benchmark(key, dataset, &key.macs[id]))
+                }
+                HashBenchmarkType::SHAKE128 { output_length } => {
+                    let expected = known_output(dataset.shake_outputs[0], output_length)?;
+                    available(move || xof_benchmark(dataset, XOFStrength::L128, None, expected))
+                }
+                HashBenchmarkType::SHAKE256 { output_length } => {
+                    let expected = known_output(dataset.shake_outputs[1], output_length)?;
+                    available(move || xof_benchmark(dataset, XOFStrength::L256, None, expected))
+                }
+                HashBenchmarkType::CSHAKE128 {
+                    output_length,
+                    ref function_name,
+                    ref customization,
+                }
+                | HashBenchmarkType::CSHAKE256 {
+                    output_length,
+                    ref function_name,
+                    ref customization,
+                } => {
+                    if function_name.len() + customization.len() > CSHAKE_MAX_CUSTOMIZATION {
+                        return Err(SuiteError::InvalidMessage(String::from(
+                            "cSHAKE function name and customization string are too long",
+                        )));
+                    }
+                    let (strength, index) = match hash {
+                        HashBenchmarkType::CSHAKE128 { .. } => (XOFStrength::L128, 0),
+                        _ => (XOFStrength::L256, 1),
+                    };
+                    let known = datasets::hashing::CSHAKE_OUTPUTS
+                        .iter()
+                        .find(|known| {
+                            known.dataset == id
+                                && known.function_name == function_name
+                                && known.customization == customization
+                        })
+                        .ok_or_else(|| {
+                            SuiteError::InvalidMessage(format!(
+                                "The cSHAKE output of hashing dataset {id} is not known for this \
+                                 function name and customization string"
+                            ))
+                        })?;
+                    let expected = known_output(known.outputs[index], output_length)?;
+                    available(move || {
+                        xof_benchmark(
+                            dataset,
+                            strength,
+                            Some((
+                                known.function_name.as_bytes(),
+                                known.customization.as_bytes(),
+                            )),
+                            expected,
+                        )
+                    })
+                }
+                HashBenchmarkType::Kmac256 {
+                    key_dataset,
+                    output_length,
+                    ref customization,
+                } => {
+                    let key = datasets::hashing::KEYS
+                        .get(key_dataset)
+                        .ok_or(SuiteError::UnknownDataset)?;
+                    if KMAC_FUNCTION_NAME.len() + customization.len() > CSHAKE_MAX_CUSTOMIZATION {
+                        return Err(SuiteError::InvalidMessage(String::from(
+                            "KMAC customization string is too long",
+                        )));
+                    }
+                    let known = datasets::hashing::KMAC_TAGS
+                        .iter()
+                        .find(|known| {
+                            known.dataset == id
+                                && known.key == key_dataset
+                                && known.customization == customization
+                                && known.tag.len() * 4 == output_length
+                        })
+                        .ok_or_else(|| {
+                            SuiteError::InvalidMessage(format!(
+                                "The KMAC256 tag of {output_length} bytes of hashing dataset {id} is \
+                                 not known for key {key_dataset} and this customization string"
+                            ))
+                        })?;
+                    available(move || {
+                        kmac_benchmark(dataset, key, known.customization.as_bytes(), known.tag)
+                    })
+                }
+            }
+        }
+        BenchmarkInfo::KMACMasking { dataset: id } => {
+            let dataset = datasets::hashing::DATASETS
+                .get(id)
+                .ok_or(SuiteError::UnknownDataset)?;
+            available(move || kmac_masking_benchmark(dataset))
+        }
+        BenchmarkInfo::SHA256Streaming {
+            dataset: id,
+            ref bursts,
+        } => {
+            let dataset = datasets::hashing::DATASETS
+                .get(id)
+                .ok_or(SuiteError::UnknownDataset)?;
+            return sha256_streaming(dataset, bursts);
+        }
+        BenchmarkInfo::SHA3 {
+            dataset: id,
+            ref digest_lengths,
+        } => {
+            let dataset = datasets::hashing::DATASETS
+                .get(id)
+                .ok_or(SuiteError::UnknownDataset)?;
+            return sha3_digests(dataset, digest_lengths);
+        }
+        BenchmarkInfo::RNGDataSet(id) => {
+            let dataset = datasets::rng::DATASETS
+                .get(id)
+                .ok_or(SuiteError::UnknownDataset)?;
+            available(move || rng_benchmark(dataset))
+        }
+        BenchmarkInfo::RNGReseed {
+            dataset: id,
+            ref additional_data,
+        } => {
+            let dataset = datasets::rng::DATASETS
+                .get(id)
+                .ok_or(SuiteError::UnknownDataset)?;
+            if additional_data
+                .as_ref()
+                .map_or(false, |data| data.len() > RNG_MAX_ADDITIONAL_DATA)
+            {
+                return Err(SuiteError::InvalidMessage(format!(
+                    "At most {RNG_MAX_ADDITIONAL_DATA} words of additional data can be used to reseed"
+                )));
+            }
+            let additional_data = additional_data.clone();
+            available(move || rng_reseed_benchmark(dataset, additional_data.as_deref()))
+        }
+        BenchmarkInfo::RNGGenerateLengths {
+            dataset: id,
+            ref lengths,
+        } => {
+            let dataset = datasets::rng::DATASETS
+                .get(id)
+                .ok_or(SuiteError::UnknownDataset)?;
+            return rng_generate_lengths(dataset, lengths);
+        }
+        BenchmarkInfo::EntropyLatency => available(entropy_latency_benchmark),
+        BenchmarkInfo::ECDSA(id) => {
+            let dataset = datasets::ecdsa::DATASETS
+                .get(id)
+                .ok_or(SuiteError::UnknownDataset)?;
+            available(move || ecdsa_benchmark(dataset))
+        }
+        BenchmarkInfo::OTBN(benchmark) => Box::new(move || otbn_benchmark(benchmark)),
+        BenchmarkInfo::Teardown(module) => available(move || teardown_benchmark(module)),
+        BenchmarkInfo::PollingInterval { module, ref delays } => {
+            if delays.is_empty() {
+                return Err(SuiteError::InvalidMessage(String::from(
+                    "Polling interval benchmarks need at least one delay",
+                )));
+            }
+            let delays = delays.clone();
+            available(move || polling_interval_benchmark(module, &delays))
+        }
+        BenchmarkInfo::MicroBenchmarks => available(|| Some(micro_benchmarks())),
+        BenchmarkInfo::Calibrated(ref target) => {
+            if matches!(**target, BenchmarkInfo::Calibrated(_)) {
+                return Err(SuiteError::InvalidMessage(String::from(
+                    "Calibrated benchmarks can not be calibrated again",
+                )));
+            }
+            let mut benchmarks: Vec<Benchmark> =
+                alloc::vec![available(|| Some(micro_benchmarks()))];
+            benchmarks.extend(self::benchmarks(target)?);
+            benchmarks.push(available(|| Some(micro_benchmarks())));
+            return Ok(benchmarks);
+        }
+    };
+
+    Ok(alloc::vec![benchmark])
+}
+
+/// Performs the warmup iterations and repetitions of every benchmark, one benchmark after
+/// the other, returns the measurements to report and the aggregation that was applied
+///
+/// # Arguments
+///
+/// * `benchmarks` - the benchmarks that should be performed
+/// * `config` - how often every benchmark is performed and how its results are reported
+fn measure(
+    benchmarks: &[Benchmark],
+    config: &BenchmarkConfig,
+) -> Result<(Vec<Measurement>, Aggregate, u32), SuiteError> {
+    let aggregate = match config.aggregate.unwrap_or(runtime::config().aggregate) {
+        Aggregate::Summary => Aggregate::Summary,
+        Aggregate::Samples | Aggregate::Unknown => Aggregate::Samples,
+    };
+
+    let retries = Cell::new(0);
+    let mut measurements = Vec::new();
+    for benchmark in benchmarks {
+        let benchmark = || retried(&**benchmark, config.retries, &retries);
+
+        for _ in 0..config.warmup_iterations {
+            let measurement = benchmark()?;
+            if measurement.verification_failure.is_some() {
+                measurements.push(measurement);
+                return Ok((measurements, aggregate, retries.get()));
+            }
+        }
+
+        match aggregate {
+            Aggregate::Summary => measurements.extend(summarize(&benchmark, config.repetitions)?),
+            _ => {
+                for _ in 0..config.repetitions {
+                    let measurement = benchmark()?;
+                    let failed = measurement.verification_failure.is_some();
+                    measurements.push(measurement);
+                    if failed {
+                        break;
+                    }
+                }
+            }
+        }
+
+        if measurements.last().map_or(false, |measurement| {
+            measurement.verification_failure.is_some()
+        }) {
+            break;
+        }
+    }
+
+    Ok((measurements, aggregate, retries.get()))
+}
+
+/// Performs a benchmark once, and again after resetting the modules while its output does not
+/// match the expected output or a module raised a hardware alert, up to the provided number of
+/// retries. Returns the last measurement, or the error of the benchmark.
+///
+/// # Arguments
+///
+/// * `benchmark` - the benchmark that should be performed
+/// * `retries` - how often the benchmark may be performed again
+/// * `performed` - counts the retries that were performed
+fn retried(
+    benchmark: &dyn Fn() -> Result<Measurement, SuiteError>,
+    retries: u32,
+    performed: &Cell<u32>,
+) -> Result<Measurement, SuiteError> {
+    let mut measurement = timed(benchmark)?;
+
+    for _ in 0..retries {
+        if measurement.verification_failure.is_none() && !runtime::alerted() {
+            break;
+        }
+
+        // Safety: benchmarks do not hold references to the modules between repetitions
+        if let Err(err) = unsafe { runtime::reset() } {
+            error!("Reset before retry failed: {err}");
+            break;
+        }
+        performed.set(performed.get() + 1);
+        measurement = timed(benchmark)?;
+    }
+
+    Ok(measurement)
+}
+
+/// Performs a benchmark once and determines the cycles it spent outside of its measured phases
+/// as well as whether its outputs matched the expected outputs,
+/// returns the error of the benchmark if it failed.
+///
+/// AES total results only measure a single phase covering the others, so `total()` is used
+/// instead of the sum of all phases.
+///
+/// # Arguments
+///
+/// * `benchmark` - the benchmark that should be performed
+fn timed(
+    benchmark: &dyn Fn() -> Result<Measurement, SuiteError>,
+) -> Result<Measurement, SuiteError> {
+    MISMATCH.set(None);
+
+    let start = get_cycle();
+    let mut measurement = benchmark()?;
+    let duration = get_cycle() - start;
+
+    measurement.unaccounted = duration.saturating_sub(measurement.result.total());
+    if let Some(mismatch) = MISMATCH.with(Option::take) {
+        measurement.verification_failure = Some(VerificationFailure {
+            benchmark: String::from(measurement.result.name()),
+            block_index: mismatch.block_index,
+        });
+    }
+    Ok(measurement)
+}
+
+/// Performs a benchmark repeatedly, returns the minimum, mean and maximum of every phase
+/// as three results in this order, or the error of the benchmark.
+///
+/// Only the aggregates are kept in memory, not the results of every repetition.
+/// If an output does not match the expected output, only the failed result is returned.
+///
+/// # Arguments
+///
+/// * `benchmark` - the benchmark that should be performed
+/// * `repetitions` - how often the benchmark is performed
+fn summarize(
+    benchmark: &dyn Fn() -> Result<Measurement, SuiteError>,
+    repetitions: u32,
+) -> Result<Vec<Measurement>, SuiteError> {
+    if repetitions == 0 {
+        return Ok(Vec::new());
+    }
+
+    // The cycles of all phases, followed by the polls and the timer ticks of all phases
+    // and the unaccounted cycles
+    let values = |measurement: &Measurement| -> Vec<u64> {
+        let cycles = measurement
+            .result
+            .phases()
+            .into_iter()
+            .map(|(_, cycles)| cycles);
+        cycles
+            .chain(measurement.polls.iter().copied())
+            .chain(measurement.mtime.iter().copied())
+            .chain(core::iter::once(measurement.unaccounted))
+            .collect()
+    };
+
+    let first = benchmark()?;
+    if first.verification_failure.is_some() {
+        return Ok(Vec::from([first]));
+    }
+    let mut min = values(&first);
+    let mut max = min.clone();
+    let mut sum = min.clone();
+
+    for _ in 1..repetitions {
+        let measurement = benchmark()?;
+        if measurement.verification_failure.is_some() {
+            return Ok(Vec::from([measurement]));
+        }
+        for (i, value) in values(&measurement).into_iter().enumerate() {
+            min[i] = min[i].min(value);
+            max[i] = max[i].max(value);
+            sum[i] += value;
+        }
+    }
+
+    let mean: Vec<u64> = sum.iter().map(|sum| sum / repetitions as u64).collect();
+    let phases = first.polls.len();
+
+    Ok([min, mean, max]
+        .iter()
+        .map(|values| Measurement {
+            result: first.result.with_phases(&values[..phases]),
+            polls: values[phases..2 * phases].to_vec(),
+            mtime: values[2 * phases..values.len() - 1].to_vec(),
+            unaccounted: values[values.len() - 1],
+            verification_failure: None,
+        })
+        .collect())
+}
+
+/// Maximum number of blocks of an AES dataset
+const AES_MAX_BLOCKS: usize = 8;
+
+/// Runs the AES benchmark on a dataset, the ciphertext is verified by decrypting it again
+///
+/// # Arguments
+///
+/// * `dataset` - the dataset the benchmark is performed on
+fn aes_benchmark(dataset: &AESDataset) -> Option<Measurement> {
+    let aes_module = platform::current().get_aes_module()?;
+    let blocks = dataset.plaintext.len();
+    let mut enc_buffer = [0u128; AES_MAX_BLOCKS];
+    let mut dec_buffer = [0u128; AES_MAX_BLOCKS];
+
+    let c_1 = sample();
+    aes_module.init_aes(
+        dataset.key_length,
+        AESOperation::Encrypt,
+        dataset.mode,
+        &dataset.key_share0,
+        &dataset.key_share1,
+    );
+    let c_2 = sample();
+    aes_module.execute(dataset.plaintext, &mut enc_buffer[..blocks]);
+    let c_3 = sample();
+    aes_module.deinitialize();
+    let c_4 = sample();
+
+    aes_module.init_aes(
+        dataset.key_length,
+        AESOperation::Decrypt,
+        dataset.mode,
+        &dataset.key_share0,
+        &dataset.key_share1,
+    );
+    aes_module.execute(&enc_buffer[..blocks], &mut dec_buffer[..blocks]);
+    aes_module.deinitialize();
+
+    verify_blocks(dataset.plaintext, &dec_buffer[..blocks]);
+
+    let phases = [c_2 - c_1, c_3 - c_2, c_4 - c_3];
+    Some(Measurement::new(
+        BenchmarkResult::AES {
+            initialization: phases[0].cycles,
+            computation: phases[1].cycles,
+            deinitialization: phases[2].cycles,
+        },
+        &phases,
+    ))
+}
+
+/// Key used by the AES sweeps, only the first `key_length` bits are used
+const AES_SWEEP_KEY: [u32; 8] = [
+    0x603d_eb10,
+    0x15ca_71be,
+    0x2b73_aef0,
+    0x857d_7781,
+    0x1f35_2c07,
+    0x3b61_08d7,
+    0x2d98_10a3,
+    0x0914_dff4,
+];
+
+/// Stream of the seed the key of the AES sweeps is derived from, check `runtime::seeded_words`
+const AES_SWEEP_SEED_STREAM: u64 = 1;
+
+/// IV used by the AES sweeps, for modes that require one
+const AES_SWEEP_IV: u128 = 0x0001_0203_0405_0607_0809_0a0b_0c0d_0e0f;
+
+/// Plaintext of the AES sweeps, the first `blocks` blocks are encrypted
+static AES_SWEEP_PLAINTEXT: [u128; AES_MAX_BLOCKS] = [
+    0x6bc1_bee2_2e40_9f96_e93d_7e11_7393_172a,
+    0xae2d_8a57_1e03_ac9c_9eb7_6fac_45af_8e51,
+    0x30c8_1c46_a35c_e411_e5fb_c119_1a0a_52ef,
+    0xf69f_2445_df4f_9b17_ad2b_417b_e66c_3710,
+    0xffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff,
+    0x0000_0000_0000_0000_0000_0000_0000_0000,
+    0x0000_1111_2222_3333_4444_5555_6666_7777,
+    0x1234_4321_abcd_dcba_affa_afaf_0100_0010,
+];
+
+/// Ciphertext of the plaintext of the AES sweeps, encrypted with AES-256 in ECB mode using the key
+/// of the AES sweeps, precomputed using the python cryptography module
+static AES_SWEEP_CIPHERTEXT: [u128; AES_MAX_BLOCKS] = [
+    0x2569_2595_40d3_c2b0_5705_2dc9_3d1e_7930,
+    0x1d86_c7f8_2de4_5947_6295_d0ac_164f_5ace,
+    0x6d6d_5ea8_4a85_3de1_9a35_2b68_edec_a064,
+    0x3c19_fe78_5c05_02e1_b27e_4f48_3444_b5a0,
+    0x9b64_4d93_f065_a5d4_43e0_8823_f5a5_cf4d,
+    0x3944_7c46_6fb5_e74c_0a16_1b34_41bd_804a,
+    0x3f9c_231d_0e65_cfaa_daff_aedd_615a_5a4a,
+    0x13d0_5f1b_d862_c7e9_b71e_8518_3d1f_abc0,
+];
+
+/// Key of the second tenant of the AES context switch benchmark,
+/// the first tenant uses the key of the AES sweeps
+const AES_CONTEXT_SWITCH_KEY: [u32; 8] = [
+    0x8e73_b0f7,
+    0xda0e_6452,
+    0xc810_f32b,
+    0x8090_79e5,
+    0x62f8_ead2,
+    0x522c_6b7b,
+    0xe6fb_7a2d,
+    0x1b6e_90c2,
+];
+
+/// Runs the AES context switch benchmark, encrypting the plaintext of the AES sweeps once with
+/// a constant key and once re-keying the module before every block, alternating between two keys.
+/// The blocks encrypted with the first key in both runs are verified against each other
+///
+/// # Arguments
+///
+/// * `blocks` - the number of blocks that are encrypted, at most `AES_MAX_BLOCKS`
+fn aes_context_switch_benchmark(blocks: usize) -> Option<Measurement> {
+    let aes_module = platform::current().get_aes_module()?;
+    let plaintext = &AES_SWEEP_PLAINTEXT[..blocks];
+    let keys = [&AES_SWEEP_KEY, &AES_CONTEXT_SWITCH_KEY];
+    let mut constant = [0u128; AES_MAX_BLOCKS];
+    let mut rekeyed = [0u128; AES_MAX_BLOCKS];
+
+    let c_1 = sample();
+    aes_module.init_aes(
+        AESKeyLength::Aes256,
+        AESOperation::Encrypt,
+        AESMode::ECB,
+        keys[0],
+        &[0; 8],
+    );
+    aes_module.execute(plaintext, &mut constant[..blocks]);
+    aes_module.deinitialize();
+    let c_2 = sample();
+    for (index, block) in plaintext.iter().enumerate() {
+        aes_module.init_aes(
+            AESKeyLength::Aes256,
+            AESOperation::Encrypt,
+            AESMode::ECB,
+            keys[index % 2],
+            &[0; 8],
+        );
+        aes_module.execute(core::slice::from_ref(block), &mut rekeyed[index..index + 1]);
+        aes_module.deinitialize();
+    }
+    let c_3 = sample();
+
+    // Only the blocks encrypted with the first key have an expected output
+    let mut expected = rekeyed;
+    for index in (0..blocks).step_by(2) {
+        expected[index] = constant[index];
+    }
+    verify_blocks(&expected[..blocks], &rekeyed[..blocks]);
+
+    let phases = [c_2 - c_1, c_3 - c_2];
+    Some(Measurement::new(
+        BenchmarkResult::Phases(alloc::vec![
+            (String::from("constant_key"), phases[0].cycles),
+            (String::from("rekeyed"), phases[1].cycles),
+        ]),
+        &phases,
+    ))
+}
+
+/// Bytes of an AES block
+const AES_BLOCK_BYTES: usize = 16;
+
+/// Maximum number of bytes processed by the AES throughput benchmark, the size of its buffer
+const AES_THROUGHPUT_MAX_BYTES: usize = 16 * 1024;
+
+/// Stream of the seed the input of the AES throughput benchmark is generated from,
+/// check `runtime::seeded_word`
+const AES_THROUGHPUT_SEED_STREAM: u64 = 3;
+
+/// Seed the input of the AES throughput benchmark is generated from if the config sets none
+const AES_THROUGHPUT_SEED: u64 = 0x5eed_0000_0000_0ae5;
+
+/// Buffer the AES throughput benchmark encrypts in place, it is too large for the heap
+static AES_THROUGHPUT_BUFFER: Global<[u128; AES_THROUGHPUT_MAX_BYTES / AES_BLOCK_BYTES]> =
+    Global::new([0; AES_THROUGHPUT_MAX_BYTES / AES_BLOCK_BYTES]);
+
+/// Returns a block of the input of the AES throughput benchmark
+///
+/// # Arguments
+///
+/// * `seed` - the seed the input is generated from
+/// * `index` - the index of the block
+fn aes_throughput_block(seed: u64, index: usize) -> u128 {
+    (0..4).fold(0, |block, word| {
+        block << 32
+            | runtime::seeded_word(seed, AES_THROUGHPUT_SEED_STREAM, index * 4 + word) as u128
+    })
+}
+
+/// Runs the AES throughput benchmark, encrypting generated blocks in place using AES-256 CTR.
+/// The blocks are generated before the measurement, the ciphertext is verified by decrypting it
+/// again and comparing it to the generated blocks
+///
+/// # Arguments
+///
+/// * `blocks` - the number of blocks that are encrypted, at most fitting into the buffer
+fn aes_throughput_benchmark(blocks: usize) -> Option<Measurement> {
+    let aes_module = platform::current().get_aes_module()?;
+    let seed = runtime::config().seed.unwrap_or(AES_THROUGHPUT_SEED);
+    let key = runtime::seeded_words(AES_SWEEP_SEED_STREAM).unwrap_or(AES_SWEEP_KEY);
+    AES_THROUGHPUT_BUFFER.with(|buffer| {
+        let buffer = &mut buffer[..blocks];
+        for (index, block) in buffer.iter_mut().enumerate() {
+            *block = aes_throughput_block(seed, index);
+        }
+
+        let c_1 = sample();
+        aes_module.init_aes(
+            AESKeyLength::Aes256,
+            AESOperation::Encrypt,
+            AESMode::CTR { iv: AES_SWEEP_IV },
+            &key,
+            &[0; 8],
+        );
+        let c_2 = sample();
+        aes_module.execute_inplace(buffer);
+        let c_3 = sample();
+        aes_module.deinitialize();
+
+        if verification_enabled() {
+            aes_module.init_aes(
+                AESKeyLength::Aes256,
+                AESOperation::Decrypt,
+                AESMode::CTR { iv: AES_SWEEP_IV },
+                &key,
+                &[0; 8],
+            );
+            aes_module.execute_inplace(buffer);
+            aes_module.deinitialize();
+
+            if let Some(index) = buffer
+                .iter()
+                .enumerate()
+                .position(|(index, block)| *block != aes_throughput_block(seed, index))
+            {
+                record_mismatch(Some(index as u32));
+            }
+        }
+
+        let phases = [c_2 - c_1, c_3 - c_2];
+        let bytes = blocks * AES_BLOCK_BYTES;
+        Some(Measurement::new(
+            BenchmarkResult::AESThroughput {
+                bytes,
+                millicycles_per_byte: phases[1].cycles * 1000 / bytes as u64,
+                initialization: phases[0].cycles,
+                computation: phases[1].cycles,
+            },
+            &phases,
+        ))
+    })
+}
+
+/// Returns an AES benchmark for every combination of the parameters of an AES sweep,
+/// with the number of blocks changing fastest
+///
+/// # Arguments
+///
+/// * `key_lengths` - the lengths of the key in bits
+/// * `modes` - the AES modes
+/// * `blocks` - the numbers of blocks that are encrypted
+fn aes_sweep(
+    key_lengths: &[u32],
+    modes: &[AESSweepMode],
+    blocks: &[usize],
+) -> Result<Vec<Benchmark>, SuiteError> {
+    if let Some(count) = blocks
+        .iter()
+        .find(|count| **count == 0 || **count > AES_MAX_BLOCKS)
+    {
+        return Err(SuiteError::InvalidMessage(format!(
+            "AES sweeps encrypt between 1 and {AES_MAX_BLOCKS} blocks, not {count}"
+        )));
+    }
+
+    // With a seed, every campaign sweeps its own key, without one the fixed key is used
+    let key = runtime::seeded_words(AES_SWEEP_SEED_STREAM).unwrap_or(AES_SWEEP_KEY);
+    let mut benchmarks: Vec<Benchmark> = Vec::new();
+    for &bits in key_lengths {
+        let key_length = match bits {
+            128 => AESKeyLength::Aes128,
+            192 => AESKeyLength::Aes192,
+            256 => AESKeyLength::Aes256,
+            _ => {
+                return Err(SuiteError::InvalidMessage(format!(
+                    "{bits} bits is not a valid AES key length"
+                )))
+            }
+        };
+        for &sweep_mode in modes {
+            let mode = match sweep_mode {
+                AESSweepMode::ECB => AESMode::ECB,
+                AESSweepMode::CBC => AESMode::CBC { iv: AES_SWEEP_IV },
+                AESSweepMode::CTR => AESMode::CTR { iv: AES_SWEEP_IV },
+            };
+            for &count in blocks {
+                let dataset = AESDataset {
+                    name: "aes_sweep",
+                    key_length,
+                    mode,
+                    key_share0: key,
+                    key_share1: [0; 8],
+                    plaintext: &AES_SWEEP_PLAINTEXT[..count],
+                };
+                benchmarks.push(available(move || {
+                    let measurement = aes_benchmark(&dataset)?;
+                    let result = match measurement.result {
+                        BenchmarkResult::AES {
+                            initialization,
+                            computation,
+                            deinitialization,
+                        } => BenchmarkResult::AESSweep {
+                            key_length: bits,
+                            mode: sweep_mode,
+                            blocks: count,
+                            initialization,
+                            computation,
+                            deinitialization,
+                        },
+                        result => result,
+                    };
+
+                    Some(Measurement {
+                        result,
+                        ..measurement
+                    })
+                }));
+            }
+        }
+    }
+
+    Ok(benchmarks)
+}
+
+/// Runs the AES-GCM benchmark on a dataset, the ciphertext and tag are verified against the dataset
+///
+/// The hash subkey and the encrypted initial counter block, as well as the ciphertext are
+/// computed by the AES module, the tag is computed in software using GHASH.
+///
+/// # Arguments
+///
+/// * `dataset` - the dataset the benchmark is performed on
+fn gcm_benchmark(dataset: &GCMDataset) -> Option<Measurement> {
+    let aes_module = platform::current().get_aes_module()?;
+    let blocks = dataset.plaintext.len();
+    let mut subkey_buffer = [0u128; 2];
+    let mut enc_buffer = [0u128; AES_MAX_BLOCKS];
+
+    let c_1 = sample();
+    aes_module.init_aes(
+        AESKeyLength::Aes256,
+        AESOperation::Encrypt,
+        AESMode::ECB,
+        &dataset.key,
+        &[0; 8],
+    );
+    aes_module.execute(&[0, dataset.j0], &mut subkey_buffer);
+    aes_module.deinitialize();
+    let c_2 = sample();
+    aes_module.init_aes(
+        AESKeyLength::Aes256,
+        AESOperation::Encrypt,
+        AESMode::CTR {
+            iv: gcm_inc32(dataset.j0),
+        },
+        &dataset.key,
+        &[0; 8],
+    );
+    aes_module.execute(dataset.plaintext, &mut enc_buffer[..blocks]);
+    aes_module.deinitialize();
+    let c_3 = sample();
+    let tag = ghash(subkey_buffer[0], dataset.aad, &enc_buffer[..blocks]) ^ subkey_buffer[1];
+    let c_4 = sample();
+
+    verify_blocks(dataset.ciphertext, &enc_buffer[..blocks]);
+    verify(&dataset.tag, &tag);
+
+    let phases = [c_2 - c_1, c_3 - c_2, c_4 - c_3];
+    Some(Measurement::new(
+        BenchmarkResult::GCM {
+            hash_subkey: phases[0].cycles,
+            encryption: phases[1].cycles,
+            ghash: phases[2].cycles,
+        },
+        &phases,
+    ))
+}
+
+/// Runs ChaCha20-Poly1305 in software on a GCM dataset, using its IV as nonce,
+/// the ciphertext and tag are verified against the dataset
+///
+/// # Arguments
+///
+/// * `dataset` - the dataset the benchmark is performed on
+fn chacha20_poly1305_benchmark(dataset: &GCMDataset) -> Measurement {
+    let blocks = dataset.plaintext.len();
+    let nonce = [
+        dataset.j0 as u32,
+        (dataset.j0 >> 32) as u32,
+        (dataset.j0 >> 64) as u32,
+    ];
+    let lengths = (dataset.aad.len() as u128 * 16) | (blocks as u128 * 16) << 64;
+    let mut enc_buffer = [0u128; AES_MAX_BLOCKS];
+
+    let c_1 = sample();
+    let mut poly1305 = Poly1305::for_chacha20(&dataset.key, &nonce);
+    let c_2 = sample();
+    chacha20_xor(
+        &dataset.key,
+        1,
+        &nonce,
+        dataset.plaintext,
+        &mut enc_buffer[..blocks],
+    );
+    let c_3 = sample();
+    poly1305.update(dataset.aad);
+    poly1305.update(&enc_buffer[..blocks]);
+    poly1305.update(&[lengths]);
+    let tag = poly1305.finalize();
+    let c_4 = sample();
+
+    verify_blocks(dataset.chacha20_ciphertext, &enc_buffer[..blocks]);
+    verify(&dataset.poly1305_tag, &tag);
+
+    let phases = [c_2 - c_1, c_3 - c_2, c_4 - c_3];
+    Measurement::new(
+        BenchmarkResult::ChaCha20Poly1305 {
+            key_setup: phases[0].cycles,
+            encryption: phases[1].cycles,
+            authentication: phases[2].cycles,
+        },
+        &phases,
+    )
+}
+
+/// Increments the 32 bit big endian counter in the last 4 bytes of a GCM counter block
+///
+/// # Arguments
+///
+/// * `block` - the counter block, in the convention of the AES module
+fn gcm_inc32(block: u128) -> u128 {
+    let counter = ((block >> 96) as u32)
+        .swap_bytes()
+        .wrapping_add(1)
+        .swap_bytes();
+
+    (block & !(0xffff_ffff << 96)) | (counter as u128) << 96
+}
+
+/// Computes GHASH over the additional data and the ciphertext, as described by NIST SP 800-38D
+///
+/// All blocks use the convention of the AES module and are byte swapped before the
+/// multiplications, as GCM interprets them as big endian numbers.
+///
+/// # Arguments
+///
+/// * `subkey` - the hash subkey, the encryption of the zero block
+/// * `aad` - the additional authenticated data
+/// * `ciphertext` - the ciphertext
+fn ghash(subkey: u128, aad: &[u128], ciphertext: &[u128]) -> u128 {
+    let subkey = subkey.swap_bytes();
+    let lengths = ((aad.len() as u128 * 128) << 64) | ciphertext.len() as u128 * 128;
+
+    let mut state = 0;
+    for block in aad.iter().chain(ciphertext) {
+        state = gf128_mul(state ^ block.swap_bytes(), subkey);
+    }
+    state = gf128_mul(state ^ lengths, subkey);
+
+    state.swap_bytes()
+}
+
+/// Multiplies two elements of GF(2^128) using the bit order of GCM
+fn gf128_mul(x: u128, y: u128) -> u128 {
+    const R: u128 = 0xe1 << 120;
+
+    let mut result = 0;
+    let mut v = x;
+    for i in (0..128).rev() {
+        if (y >> i) & 1 == 1 {
+            result ^= v;
+        }
+        v = if v & 1 == 1 { (v >> 1) ^ R } else { v >> 1 };
+    }
+
+    result
+}
+
+/// Runs the hashing benchmark on a dataset, the digest is verified against the dataset
+///
+/// # Arguments
+///
+/// * `dataset` - the dataset the benchmark is performed on
+fn hashing_benchmark(dataset: &HashingDataset) -> Option<Measurement> {
+    let hmac_module = platform::current().get_sha256_module()?;
+    let mut output = [0u32; 8];
+
+    let c_1 = sample();
+    hmac_module.init_sha256();
+    let c_2 = sample();
+    hmac_module.write_input(dataset.input);
+    hmac_module.wait_for_completion();
+    let c_3 = sample();
+    hmac_module.read_digest(&mut output);
+    let c_4 = sample();
+
+    verify_blocks(&dataset.digest, &output);
+
+    let phases = [c_2 - c_1, c_3 - c_2, c_4 - c_3];
+    Some(Measurement::new(
+        BenchmarkResult::Hashing {
+            initialization: phases[0].cycles,
+            computation: phases[1].cycles,
+            reading_output: phases[2].cycles,
+        },
+        &phases,
+    ))
+}
+
+/// Runs the HMAC-SHA256 benchmark on a dataset
+///
+/// # Arguments
+///
+/// * `key` - the key the MAC is computed with
+/// * `dataset` - the dataset the benchmark is performed on
+/// * `mac` - the expected MAC of the dataset
+fn hmac_benchmark(key: &HMACKey, dataset: &HashingDataset, mac: &[u32; 8]) -> Option<Measurement> {
+    let hmac_module = platform::current().get_sha256_module()?;
+    let mut output = [0u32; 8];
+
+    let c_1 = sample();
+    hmac_module.init_hmac(&key.key);
+    let c_2 = sample();
+    hmac_module.write_input(dataset.input);
+    hmac_module.wait_for_completion();
+    let c_3 = sample();
+    hmac_module.read_digest(&mut output);
+    let c_4 = sample();
+
+    verify_blocks(mac, &output);
+
+    let phases = [c_2 - c_1, c_3 - c_2, c_4 - c_3];
+    Some(Measurement::new(
+        BenchmarkResult::HMAC {
+            key_writing: phases[0].cycles,
+            computation: phases[1].cycles,
+            reading_output: phases[2].cycles,
+        },
+        &phases,
+    ))
+}
+
+/// Maximum number of words written into the message FIFO at once by a streaming benchmark,
+/// the depth of the FIFO
+const SHA256_STREAMING_MAX_BURST: u32 = 16;
+
+/// Bytes of a block absorbed by SHA256
+const SHA256_BLOCK_BYTES: usize = 64;
+
+/// Bytes SHA256 appends to a message at least when padding it, the end marker and the length
+const SHA256_MIN_PADDING: usize = 9;
+
+/// Returns a streaming benchmark for every burst size, check `BenchmarkInfo::SHA256Streaming`
+///
+/// # Arguments
+///
+/// * `dataset` - the dataset the benchmarks are performed on
+/// * `bursts` - the numbers of words written into the message FIFO at once
+fn sha256_streaming(
+    dataset: &'static HashingDataset,
+    bursts: &[u32],
+) -> Result<Vec<Benchmark>, SuiteError> {
+    if bursts.is_empty() {
+        return Err(SuiteError::InvalidMessage(String::from(
+            "Streaming benchmarks need at least one burst size",
+        )));
+    }
+    if let Some(burst) = bursts
+        .iter()
+        .find(|burst| **burst == 0 || **burst > SHA256_STREAMING_MAX_BURST)
+    {
+        return Err(SuiteError::InvalidMessage(format!(
+            "Streaming benchmarks write between 1 and {SHA256_STREAMING_MAX_BURST} words at once, not {burst}"
+        )));
+    }
+
+    Ok(bursts
+        .iter()
+        .map(|&burst| -> Benchmark {
+            available(move || sha256_streaming_benchmark(dataset, burst))
+        })
+        .collect())
+}
+
+/// Computes the SHA256 digest of a dataset, writing its words into the message FIFO in bursts.
+/// Before every burst the fill level of the FIFO is polled, a burst that does not fit waits until
+/// the FIFO drained enough. The digest is verified against the dataset
+///
+/// # Arguments
+///
+/// * `dataset` - the dataset the benchmark is performed on
+/// * `burst` - the number of words written into the message FIFO at once
+fn sha256_streaming_benchmark(dataset: &HashingDataset, burst: u32) -> Option<Measurement> {
+    let hmac_module = platform::current().get_sha256_module()?;
+    let capacity = hmac_module.fifo_capacity();
+    let mut output = [0u32; 8];
+    let mut stalls = 0;
+
+    let c_1 = sample();
+    hmac_module.init_sha256();
+    let c_2 = sample();
+    hmac_module.start_message();
+    for words in dataset.input.chunks(burst as usize) {
+        let room = capacity.saturating_sub(words.len() as u32);
+        if hmac_module.get_fifo_elements() > room {
+            stalls += 1;
+            modules::wait_until(|| hmac_module.get_fifo_elements() <= room);
+        }
+        for word in words {
+            hmac_module.write_word(*word);
+        }
+    }
+    hmac_module.wait_for_completion();
+    let c_3 = sample();
+    hmac_module.read_digest(&mut output);
+    let c_4 = sample();
+
+    verify_blocks(&dataset.digest, &output);
+
+    // The padding is absorbed as well, it adds a block if it does not fit into the last one
+    let blocks = (dataset.input.len() * 4 + SHA256_MIN_PADDING + SHA256_BLOCK_BYTES - 1)
+        / SHA256_BLOCK_BYTES;
+    let phases = [c_2 - c_1, c_3 - c_2, c_4 - c_3];
+    Some(Measurement::new(
+        BenchmarkResult::SHA256Streaming {
+            burst,
+            blocks,
+            stalls,
+            cycles_per_block: phases[1].cycles / blocks as u64,
+            initialization: phases[0].cycles,
+            computation: phases[1].cycles,
+            reading_output: phases[2].cycles,
+        },
+        &phases,
+    ))
+}
+
+/// Returns a SHA3 benchmark for every digest length, check `BenchmarkInfo::SHA3`
+///
+/// # Arguments
+///
+/// * `dataset` - the dataset the benchmarks are performed on
+/// * `digest_lengths` - the lengths of the digests in bits
+fn sha3_digests(
+    dataset: &'static HashingDataset,
+    digest_lengths: &[u32],
+) -> Result<Vec<Benchmark>, SuiteError> {
+    if digest_lengths.is_empty() {
+        return Err(SuiteError::InvalidMessage(String::from(
+            "SHA3 benchmarks need at least one digest length",
+        )));
+    }
+    let lengths = digest_lengths
+        .iter()
+        .map(|bits| {
+            SHA3Length::from_bits(*bits).ok_or_else(|| {
+                SuiteError::InvalidMessage(format!(
+                    "SHA3 digests are 224, 256, 384 or 512 bits long, not {bits}"
+                ))
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(lengths
+        .into_iter()
+        .map(|length| -> Benchmark { available(move || sha3_benchmark(dataset, length)) })
+        .collect())
+}
+
+/// Computes the SHA3 digest of the given length of a dataset, the digest is verified against the
+/// dataset
+///
+/// # Arguments
+///
+/// * `dataset` - the dataset the benchmark is performed on
+/// * `length` - the length of the digest
+fn sha3_benchmark(dataset: &HashingDataset, length: SHA3Length) -> Option<Measurement> {
+    let kmac_module = platform::current().get_kmac_module()?;
+    let words = length.digest_words();
+    let mut output = [0u32; 16];
+
+    let c_1 = sample();
+    kmac_module.init_sha3(length);
+    let c_2 = sample();
+    kmac_module.write_input(dataset.input);
+    kmac_module.process();
+    let c_3 = sample();
+    kmac_module.read_output(&mut output[..words]);
+    let c_4 = sample();
+
+    kmac_module.finish();
+
+    let expected = match length {
+        SHA3Length::L224 => dataset.sha3_digests[0],
+        SHA3Length::L256 => dataset.sha3_digests[1],
+        SHA3Length::L384 => dataset.sha3_digests[2],
+        SHA3Length::L512 => dataset.sha3_digests[3],
+    };
+    verify_blocks(expected, &output[..words]);
+
+    let phases = [c_2 - c_1, c_3 - c_2, c_4 - c_3];
+    Some(Measurement::new(
+        BenchmarkResult::SHA3 {
+            digest_length: words as u32 * 32,
+            initialization: phases[0].cycles,
+            computation: phases[1].cycles,
+            reading_output: phases[2].cycles,
+        },
+        &phases,
+    ))
+}
+
+/// Maximum number of bytes of the function name and customization string of cSHAKE combined
+const CSHAKE_MAX_CUSTOMIZATION: usize = 40;
+
+/// Returns the words of a known output of an extendable output function that are read for an
+/// output length, fails if the driver can not read that many bytes or the output is not known
+/// that far
+///
+/// # Arguments
+///
+/// * `output` - the known output
+/// * `output_length` - number of bytes that are read
+fn known_output(
+    output: &'static [u32],
+    output_length: usize,
+) -> Result<&'static [u32], SuiteError> {
+    if output_length == 0 || output_length % 4 != 0 || output_length > output.len() * 4 {
+        return Err(SuiteError::InvalidMessage(format!(
+            "Output lengths have to be whole words of up to {} bytes, not {output_length}",
+            output.len() * 4
+        )));
+    }
+
+    Ok(&output[..output_length / 4])
+}
+
+/// Reads the output of an extendable output function, squeezing the state again for every block
+/// after the first one, reading every block is measured separately
+///
+/// # Arguments
+///
+/// * `kmac_module` - the module computing the function, its input was absorbed already
+/// * `strength` - the security strength of the function, it determines the words per block
+/// * `output` - the buffer the output is read into, its length determines the number of blocks
+/// * `start` - the sample taken after absorbing the input
+/// * `phases` - the phases of the benchmark, the phase of every block is appended
+fn squeeze_output(
+    kmac_module: &dyn KMACModule,
+    strength: XOFStrength,
+    output: &mut [u32],
+    start: Sample,
+    phases: &mut Vec<Sample>,
+) {
+    let mut last = start;
+    for (block, words) in output.chunks_mut(strength.rate_words()).enumerate() {
+        if block > 0 {
+            kmac_module.squeeze();
+        }
+        kmac_module.read_output(words);
+        let c_n = sample();
+        phases.push(c_n - last);
+        last = c_n;
+    }
+}
+
+/// Runs the SHAKE or cSHAKE benchmark on a dataset, reading every output block is measured
+/// separately, the output is verified against the known output
+///
+/// # Arguments
+///
+/// * `dataset` - the dataset the benchmark is performed on, its digest is not used
+/// * `strength` - the security strength of the function
+/// * `customization` - function name and customization string if cSHAKE should be used
+/// * `expected` - the expected output, its length determines the number of words that are read
+fn xof_benchmark(
+    dataset: &HashingDataset,
+    strength: XOFStrength,
+    customization: Option<(&[u8], &[u8])>,
+    expected: &[u32],
+) -> Option<Measurement> {
+    let kmac_module = platform::current().get_kmac_module()?;
+    let blocks = (expected.len() + strength.rate_words() - 1) / strength.rate_words();
+
+    // Allocate everything beforehand, so no allocations are measured
+    let mut output = alloc::vec![0u32; expected.len()];
+    let mut phases = Vec::with_capacity(2 + blocks);
+
+    let c_1 = sample();
+    kmac_module.init_xof(strength, customization);
+    let c_2 = sample();
+    kmac_module.write_input(dataset.input);
+    kmac_module.process();
+    let c_3 = sample();
+    phases.push(c_2 - c_1);
+    phases.push(c_3 - c_2);
+
+    squeeze_output(&*kmac_module, strength, &mut output, c_3, &mut phases);
+
+    kmac_module.finish();
+
+    verify_blocks(expected, &output);
+
+    Some(Measurement::new(
+        BenchmarkResult::XOF {
+            initialization: phases[0].cycles,
+            absorbing: phases[1].cycles,
+            squeezes: phases[2..].iter().map(|phase| phase.cycles).collect(),
+        },
+        &phases,
+    ))
+}
+
+/// Function name of KMAC, it counts towards the limit of the customization string of cSHAKE
+const KMAC_FUNCTION_NAME: &str = "KMAC";
+
+/// Runs the KMAC256 benchmark on a dataset, loading the key, absorbing the input and reading
+/// every output block are measured separately, the tag is verified against the known tag
+///
+/// # Arguments
+///
+/// * `dataset` - the dataset the benchmark is performed on, its digest is not used
+/// * `key` - the key the KMAC is computed with, its MACs are not used
+/// * `customization` - the customization string
+/// * `expected` - the expected tag, its length determines the output length
+fn kmac_benchmark(
+    dataset: &HashingDataset,
+    key: &HMACKey,
+    customization: &[u8],
+    expected: &[u32],
+) -> Option<Measurement> {
+    let kmac_module = platform::current().get_kmac_module()?;
+    let strength = XOFStrength::L256;
+    let blocks = (expected.len() + strength.rate_words() - 1) / strength.rate_words();
+
+    // Allocate everything beforehand, so no allocations are measured
+    let mut output = alloc::vec![0u32; expected.len()];
+    let mut phases = Vec::with_capacity(2 + blocks);
+
+    let c_1 = sample();
+    kmac_module.init_kmac(strength, &key.key, customization);
+    let c_2 = sample();
+    kmac_module.write_input(dataset.input);
+    kmac_module.write_output_length(expected.len() * 32);
+    kmac_module.process();
+    let c_3 = sample();
+    phases.push(c_2 - c_1);
+    phases.push(c_3 - c_2);
+
+    squeeze_output(&*kmac_module, strength, &mut output, c_3, &mut phases);
+
+    kmac_module.finish();
+
+    verify_blocks(expected, &output);
+
+    Some(Measurement::new(
+        BenchmarkResult::KMAC {
+            key_loading: phases[0].cycles,
+            absorbing: phases[1].cycles,
+            squeezes: phases[2..].iter().map(|phase| phase.cycles).collect(),
+        },
+        &phases,
+    ))
+}
+
+/// Number of words read by the KMAC masking benchmark, the first output block of SHAKE128
+const KMAC_MASKING_OUTPUT_WORDS: usize = 168 / 4;
+
+/// Stream of the seed the software entropy of the KMAC masking benchmark is expanded from,
+/// check `runtime::seeded_words`
+const KMAC_MASKING_SEED_STREAM: u64 = 2;
+
+/// Runs the KMAC masking benchmark on a dataset, computing SHAKE128 unmasked and masked with
+/// every entropy source. The outputs of the masked computations are verified against the
+/// unmasked output, as masking must not change the result
+///
+/// # Arguments
+///
+/// * `dataset` - the dataset the benchmark is performed on, its digest is not used
+fn kmac_masking_benchmark(dataset: &HashingDataset) -> Option<Measurement> {
+    let mut kmac_module = platform::current().get_kmac_module()?;
+    let entropies = [
+        KMACEntropy::Unmasked,
+        KMACEntropy::Software,
+        KMACEntropy::EDN,
+    ];
+    let mut outputs = [[0u32; KMAC_MASKING_OUTPUT_WORDS]; 3];
+    let mut phases = Vec::with_capacity(entropies.len());
+
+    kmac_module.set_entropy_seed(runtime::seeded_words(KMAC_MASKING_SEED_STREAM));
+    for (index, entropy) in entropies.iter().enumerate() {
+        kmac_module.set_entropy(*entropy);
+        let c_1 = sample();
+        kmac_module.init_xof(XOFStrength::L128, None);
+        kmac_module.write_input(dataset.input);
+        kmac_module.process();
+        kmac_module.read_output(&mut outputs[index]);
+        kmac_module.finish();
+        kmac_module.wait_for_idle();
+        let c_2 = sample();
+        phases.push(c_2 - c_1);
+    }
+    kmac_module.set_entropy(KMACEntropy::Unmasked);
+    kmac_module.set_entropy_seed(None);
+
+    verify_blocks(&[outputs[0], outputs[0]], &outputs[1..]);
+
+    Some(Measurement::new(
+        BenchmarkResult::Phases(alloc::vec![
+            (String::from("unmasked"), phases[0].cycles),
+            (String::from("software"), phases[1].cycles),
+            (String::from("edn"), phases[2].cycles),
+        ]),
+        &phases,
+    ))
+}
+
+/// Runs the random number generation benchmark on a dataset
+///
+/// # Arguments
+///
+/// * `dataset` - the dataset the benchmark is performed on
+fn rng_benchmark(dataset: &RNGDataset) -> Option<Measurement> {
+    let rng_module = platform::current().get_rng_module()?;
+    let seed = dataset.seed.map(|seed| seed.to_vec());
+
+    let c_1 = sample();
+    rng_module.init_rng(seed);
+    let c_2 = sample();
+    for _ in 0..dataset.count {
+        rng_module.generate();
+    }
+    let c_3 = sample();
+
+    let phases = [c_2 - c_1, c_3 - c_2];
+    Some(Measurement::new(
+        BenchmarkResult::RNG {
+            initialization: phases[0].cycles,
+            generation: phases[1].cycles,
+        },
+        &phases,
+    ))
+}
+
+/// Input absorbed by the KMAC before its teardown is measured
+const TEARDOWN_KMAC_INPUT: [u32; 4] = [0x6162_6364, 0x6566_6768, 0x696a_6b6c, 0x6d6e_6f70];
+
+/// Runs a teardown benchmark, the module is used beforehand so it holds state that is wiped.
+/// Only the teardown is measured, its phases are reported as `Phases` result
+///
+/// # Arguments
+///
+/// * `module` - the module whose teardown is measured
+fn teardown_benchmark(module: TeardownBenchmarkType) -> Option<Measurement> {
+    let platform = platform::current();
+
+    let (names, phases): (&[&str], Vec<Sample>) = match module {
+        TeardownBenchmarkType::AES => {
+            let aes_module = platform.get_aes_module()?;
+            let mut output = [0u128; 1];
+            aes_module.init_aes(
+                AESKeyLength::Aes256,
+                AESOperation::Encrypt,
+                AESMode::ECB,
+                &AES_SWEEP_KEY,
+                &[0; 8],
+            );
+            aes_module.execute(&AES_SWEEP_PLAINTEXT[..1], &mut output);
+
+            let c_1 = sample();
+            aes_module.clear_input();
+            let c_2 = sample();
+            aes_module.clear_output();
+            let c_3 = sample();
+
+            aes_module.deinitialize();
+            (
+                &["clear_input", "clear_output"],
+                alloc::vec![c_2 - c_1, c_3 - c_2],
+            )
+        }
+        TeardownBenchmarkType::KMAC => {
+            let kmac_module = platform.get_kmac_module()?;
+            let mut output = [0u32; 1];
+            kmac_module.init_xof(XOFStrength::L128, None);
+            kmac_module.write_input(&TEARDOWN_KMAC_INPUT);
+            kmac_module.process();
+            kmac_module.read_output(&mut output);
+
+            let c_1 = sample();
+            kmac_module.finish();
+            kmac_module.wait_for_idle();
+            let c_2 = sample();
+
+            (&["done"], alloc::vec![c_2 - c_1])
+        }
+        TeardownBenchmarkType::RNG => {
+            let rng_module = platform.get_rng_module()?;
+            rng_module.init_rng(None);
+            rng_module.generate();
+
+            let c_1 = sample();
+            rng_module.uninstantiate();
+            let c_2 = sample();
+
+            (&["uninstantiate"], alloc::vec![c_2 - c_1])
+        }
+    };
+
+    let result = names
+        .iter()
+        .zip(&phases)
+        .map(|(name, phase)| (String::from(*name), phase.cycles))
+        .collect();
+    Some(Measurement::new(BenchmarkResult::Phases(result), &phases))
+}
+
+/// Runs the polling interval benchmark, performing the same operation on the module once for
+/// every delay, waiting the given number of nops between two polls of its status register.
+/// The outputs of all operations are verified, the wait strategy is restored afterwards
+///
+/// # Arguments
+///
+/// * `module` - the module whose status register is polled
+/// * `delays` - the number of nops between two polls of every operation
+fn polling_interval_benchmark(module: PollingModule, delays: &[u32]) -> Option<Measurement> {
+    let platform = platform::current();
+    let strategy = modules::wait_strategy();
+    let mut phases = Vec::with_capacity(delays.len());
+
+    match module {
+        PollingModule::AES => {
+            let aes_module = platform.get_aes_module()?;
+            let mut output = [0u128; AES_MAX_BLOCKS];
+
+            for delay in delays {
+                modules::set_wait_strategy(WaitStrategy::Nops(*delay));
+                let c_1 = sample();
+                aes_module.init_aes(
+                    AESKeyLength::Aes256,
+                    AESOperation::Encrypt,
+                    AESMode::ECB,
+                    &AES_SWEEP_KEY,
+                    &[0; 8],
+                );
+                aes_module.execute(&AES_SWEEP_PLAINTEXT, &mut output);
+                aes_module.deinitialize();
+                let c_2 = sample();
+                phases.push(c_2 - c_1);
+
+                verify_blocks(&AES_SWEEP_CIPHERTEXT, &output);
+            }
+        }
+        PollingModule::HMAC => {
+            let hmac_module = platform.get_sha256_module()?;
+            let key = &datasets::hashing::KEYS[0];
+            let dataset = &datasets::hashing::DATASETS[0];
+            let mut output = [0u32; 8];
+
+            for delay in delays {
+                modules::set_wait_strategy(WaitStrategy::Nops(*delay));
+                let c_1 = sample();
+                hmac_module.init_hmac(&key.key);
+                hmac_module.write_input(dataset.input);
+                hmac_module.wait_for_completion();
+                hmac_module.read_digest(&mut output);
+                let c_2 = sample();
+                phases.push(c_2 - c_1);
+
+                verify_blocks(&key.macs[0], &output);
+            }
+        }
+    }
+    modules::set_wait_strategy(strategy);
+
+    let result = delays
+        .iter()
+        .zip(&phases)
+        .map(|(delay, phase)| (format!("nops_{delay}"), phase.cycles))
+        .collect();
+    Some(Measurement::new(BenchmarkResult::Phases(result), &phases))
+}
+
+/// An empty function, called by the micro benchmarks to measure the overhead of a call
+#[inline(never)]
+fn empty_function() {}
+
+/// Measures the overhead of the measurement itself, reported as `Phases` result
+/// (`get_cycle`, `sample`, `call`), check `BenchmarkInfo::MicroBenchmarks`
+fn micro_benchmarks() -> Measurement {
+    let c_1 = get_cycle();
+    let c_2 = get_cycle();
+    let counter = Sample::elapsed(c_2 - c_1, 0, 0);
+
+    let s_1 = sample();
+    let s_2 = sample();
+
+    // Reading the function pointer volatile keeps the compiler from removing the call
+    let function: fn() = empty_function;
+    let s_3 = sample();
+    // Safety: the pointer refers to a local variable
+    let call = unsafe { core::ptr::read_volatile(&function) };
+    call();
+    let s_4 = sample();
+
+    let phases = [counter, s_2 - s_1, s_4 - s_3];
+    Measurement::new(
+        BenchmarkResult::Phases(alloc::vec![
+            (String::from("get_cycle"), phases[0].cycles),
+            (String::from("sample"), phases[1].cycles),
+            (String::from("call"), phases[2].cycles),
+        ]),
+        &phases,
+    )
+}
+
+/// Maximum number of words of additional data used by a reseed
+const RNG_MAX_ADDITIONAL_DATA: usize = 12;
+
+/// Runs the random number generation benchmark on a dataset, the module is reseeded
+/// after it was instantiated
+///
+/// # Arguments
+///
+/// * `dataset` - the dataset the benchmark is performed on
+/// * `additional_data` - seed material of the reseed, or None to use the entropy source
+fn rng_reseed_benchmark(
+    dataset: &RNGDataset,
+    additional_data: Option<&[u32]>,
+) -> Option<Measurement> {
+    let rng_module = platform::current().get_rng_module()?;
+    let seed = dataset.seed.map(|seed| seed.to_vec());
+
+    let c_1 = sample();
+    rng_module.init_rng(seed);
+    let c_2 = sample();
+    rng_module.reseed(additional_data);
+    let c_3 = sample();
+    for _ in 0..dataset.count {
+        rng_module.generate();
+    }
+    let c_4 = sample();
+
+    let phases = [c_2 - c_1, c_3 - c_2, c_4 - c_3];
+    Some(Measurement::new(
+        BenchmarkResult::RNGReseed {
+            initialization: phases[0].cycles,
+            reseed: phases[1].cycles,
+            generation: phases[2].cycles,
+        },
+        &phases,
+    ))
+}
+
+/// Maximum number of random numbers requested by a single generate command of the generate length
+/// benchmarks, the size of their buffer
+const RNG_MAX_GENERATE_LENGTH: u32 = 64;
+
+/// Returns a generate length benchmark for every length, check `BenchmarkInfo::RNGGenerateLengths`
+///
+/// # Arguments
+///
+/// * `dataset` - the dataset the benchmarks are performed on
+/// * `lengths` - the numbers of random numbers requested by a single generate command
+fn rng_generate_lengths(
+    dataset: &'static RNGDataset,
+    lengths: &[u32],
+) -> Result<Vec<Benchmark>, SuiteError> {
+    if lengths.is_empty() {
+        return Err(SuiteError::InvalidMessage(String::from(
+            "Generate length benchmarks need at least one length",
+        )));
+    }
+    if let Some(length) = lengths
+        .iter()
+        .find(|length| **length == 0 || **length > RNG_MAX_GENERATE_LENGTH)
+    {
+        return Err(SuiteError::InvalidMessage(format!(
+            "Generate commands request between 1 and {RNG_MAX_GENERATE_LENGTH} numbers, not {length}"
+        )));
+    }
+
+    Ok(lengths
+        .iter()
+        .map(|&length| -> Benchmark {
+            available(move || rng_generate_length_benchmark(dataset, length))
+        })
+        .collect())
+}
+
+/// Runs the random number generation benchmark on a dataset, requesting the numbers using
+/// generate commands of the given length. The last command requests the remaining numbers if
+/// the number of the dataset is not a multiple of the length
+///
+/// # Arguments
+///
+/// * `dataset` - the dataset the benchmark is performed on
+/// * `length` - the number of random numbers requested by a single generate command
+fn rng_generate_length_benchmark(dataset: &RNGDataset, length: u32) -> Option<Measurement> {
+    let rng_module = platform::current().get_rng_module()?;
+    let seed = dataset.seed.map(|seed| seed.to_vec());
+    let mut output = [0u128; RNG_MAX_GENERATE_LENGTH as usize];
+
+    let c_1 = sample();
+    rng_module.init_rng(seed);
+    let c_2 = sample();
+    let mut remaining = dataset.count;
+    while remaining > 0 {
+        let count = remaining.min(length as usize);
+        rng_module.generate_blocks(&mut output[..count]);
+        remaining -= count;
+    }
+    let c_3 = sample();
+
+    let phases = [c_2 - c_1, c_3 - c_2];
+    Some(Measurement::new(
+        BenchmarkResult::RNGGenerateLength {
+            length,
+            blocks: dataset.count,
+            cycles_per_block: phases[1].cycles / dataset.count.max(1) as u64,
+            initialization: phases[0].cycles,
+            generation: phases[1].cycles,
+        },
+        &phases,
+    ))
+}
+
+/// Runs the entropy latency benchmark, measuring how long it takes until seeds are available.
+/// The entropy source, the CSRNG and the EDN are stopped beforehand, they are running again
+/// afterwards. The phases are reported as `Phases` result
+fn entropy_latency_benchmark() -> Option<Measurement> {
+    let platform = platform::current();
+    let entropy_src = platform.get_entropy_src_module()?;
+    let rng_module = platform.get_rng_module()?;
+    let edn_module = platform.get_edn_module()?;
+
+    // The EDN uses the CSRNG, which is seeded by the entropy source
+    edn_module.disable();
+    rng_module.uninstantiate();
+    entropy_src.disable();
+
+    // The first number can only be generated once the seed of the instantiation was available
+    let c_1 = sample();
+    entropy_src.enable();
+    rng_module.init_rng(None);
+    rng_module.generate();
+    let c_2 = sample();
+    rng_module.reseed(None);
+    let c_3 = sample();
+    edn_module.enable();
+    let c_4 = sample();
+
+    let phases = [c_2 - c_1, c_3 - c_2, c_4 - c_3];
+    Some(Measurement::new(
+        BenchmarkResult::Phases(alloc::vec![
+            (String::from("cold_seed"), phases[0].cycles),
+            (String::from("warm_seed"), phases[1].cycles),
+            (String::from("edn_seed"), phases[2].cycles),
+        ]),
+        &phases,
+    ))
+}
+
+/// Runs the ECDSA benchmark on a dataset, the signature is verified using the public key,
+/// returns None if the OTBN is not available on the current platform
+///
+/// # Arguments
+///
+/// * `dataset` - the dataset the benchmark is performed on
+#[allow(unused_variables)]
+fn ecdsa_benchmark(dataset: &ECDSADataset) -> Option<Measurement> {
+    #[cfg(feature = "platform_verilator_earlgrey")]
+    {
+        use crate::libs::ecdsa::{
+            ecdsa_p256_message_digest_t, ecdsa_p256_private_key_t, ecdsa_p256_public_key_t,
+            ecdsa_p256_sign, ecdsa_p256_signature_t, ecdsa_p256_verify, hardened_bool_t,
+        };
+
+        let priv_key = ecdsa_p256_private_key_t {
+            d: dataset.private_key,
+        };
+        let pub_key = ecdsa_p256_public_key_t {
+            x: dataset.public_key_x,
+            y: dataset.public_key_y,
+        };
+        let digest = ecdsa_p256_message_digest_t { h: dataset.digest };
+        let mut signature = ecdsa_p256_signature_t {
+            r: [0; 8],
+            s: [0; 8],
+        };
+        let mut verification_result = hardened_bool_t::HardenedBoolInvalid;
+
+        let c_1 = sample();
+        unsafe {
+            ecdsa_p256_sign(&digest, &priv_key, &mut signature);
+        }
+        let c_2 = sample();
+        unsafe {
+            ecdsa_p256_verify(&signature, &digest, &pub_key, &mut verification_result);
+        }
+        let c_3 = sample();
+
+        verify(&hardened_bool_t::HardenedBoolTrue, &verification_result);
+
+        let phases = [c_2 - c_1, c_3 - c_2];
+        return Some(Measurement::new(
+            BenchmarkResult::ECDSA {
+                signing: phases[0].cycles,
+                verifying: phases[1].cycles,
+            },
+            &phases,
+        ));
+    }
+    #[allow(unreachable_code)]
+    None
+}
+
+/// Runs a program on the OTBN, the output is verified against the OTBN datasets,
+/// fails if the OTBN is not available on the current platform or reports an error
+///
+/// # Arguments
+///
+/// * `benchmark` - the program that is performed
+#[allow(unused_variables)]
+fn otbn_benchmark(benchmark: OTBNBenchmarkType) -> Result<Measurement, SuiteError> {
+    let otbn_module = platform::current()
+        .get_otbn_module()
+        .ok_or(SuiteError::ModuleUnavailable)?;
+
+    // The apps are only linked for earlgrey
+    #[cfg(feature = "platform_verilator_earlgrey")]
+    {
+        use crate::libs::otbn::{
+            p256_scalar_mult_app, rsa_app, RSA_MODE_DECRYPT, RSA_MODE_ENCRYPT,
+        };
+
+        /// Maximum number of words read from the OTBN after a program was executed
+        const OTBN_MAX_OUTPUT: usize = 64;
+
+        let rsa = &datasets::otbn::RSA_2048;
+        let p256 = &datasets::otbn::P256_SCALAR_MULT;
+        // Number of 256 bit limbs of an RSA-2048 modulus
+        let rsa_limbs = [2048 / 256];
+
+        // The variables written before and read after the execution
+        let (app, inputs, outputs): (_, Vec<(usize, &[u32])>, Vec<(usize, &[u32])>) =
+            match benchmark {
+                OTBNBenchmarkType::RSA2048Encrypt => {
+                    let (app, symbols) = rsa_app();
+                    (
+                        app,
+                        alloc::vec![
+                            (symbols.mode, &[RSA_MODE_ENCRYPT]),
+                            (symbols.n_limbs, &rsa_limbs),
+                            (symbols.modulus, rsa.modulus),
+                            (symbols.inout, rsa.plaintext),
+                        ],
+                        alloc::vec![(symbols.inout, rsa.ciphertext)],
+                    )
+                }
+                OTBNBenchmarkType::RSA2048Decrypt => {
+                    let (app, symbols) = rsa_app();
+                    (
+                        app,
+                        alloc::vec![
+                            (symbols.mode, &[RSA_MODE_DECRYPT]),
+                            (symbols.n_limbs, &rsa_limbs),
+                            (symbols.modulus, rsa.modulus),
+                            (symbols.exp, rsa.private_exponent),
+                            (symbols.inout, rsa.ciphertext),
+                        ],
+                        alloc::vec![(symbols.inout, rsa.plaintext)],
+                    )
+                }
+                OTBNBenchmarkType::P256ScalarMult => {
+                    let (app, symbols) = p256_scalar_mult_app();
+                    (
+                        app,
+                        alloc::vec![
+                            (symbols.k0, &p256.scalar),
+                            (symbols.k1, &[0; 8]),
+                            (symbols.x, &p256.x),
+                            (symbols.y, &p256.y),
+                        ],
+                        alloc::vec![(symbols.x, &p256.product_x), (symbols.y, &p256.product_y)],
+                    )
+                }
+            };
+        let mut output = [0u32; OTBN_MAX_OUTPUT];
+
+        let c_1 = sample();
+        otbn_module.load_app(&app);
+        for (offset, data) in &inputs {
+            otbn_module.write_dmem(*offset, data);
+        }
+        let c_2 = sample();
+        if let Err(error_bits) = otbn_module.execute() {
+            error!("OTBN execution failed with error bits {error_bits:#x}");
+            return Err(SuiteError::HardwareFault);
+        }
+        let c_3 = sample();
+        let mut position = 0;
+        for (offset, expected) in &outputs {
+            otbn_module.read_dmem(*offset, &mut output[position..position + expected.len()]);
+            position += expected.len();
+        }
+        let c_4 = sample();
+
+        let expected: Vec<u32> = outputs
+            .iter()
+            .flat_map(|(_, expected)| expected.iter().copied())
+            .collect();
+        verify_blocks(&expected, &output[..expected.len()]);
+
+        let phases = [c_2 - c_1, c_3 - c_2, c_4 - c_3];
+        return Ok(Measurement::new(
+            BenchmarkResult::OTBN {
+                loading: phases[0].cycles,
+                execution: phases[1].cycles,
+                reading_output: phases[2].cycles,
+            },
+            &phases,
+        ));
+    }
+    #[allow(unreachable_code)]
+    Err(SuiteError::ModuleUnavailable)
+}
+
+pub mod examples {
+    #![allow(dead_code)]
+    use alloc::vec;
+    use benchmark_common::BenchmarkResult;
+
+    #[cfg(feature = "platform_verilator_earlgrey")]
+    use crate::libs::ecdsa::{
+        ecdsa_p256_message_digest_t, ecdsa_p256_private_key_t, ecdsa_p256_public_key_t,
+        ecdsa_p256_sign, ecdsa_p256_signature_t, ecdsa_p256_verify, hardened_bool_t,
+    };
+    use crate::{
+        modules::{AESKeyLength, AESMode, AESOperation},
+        platform::{self, Platform},
+    };
+
+    use super::{sample, verify_blocks, Measurement, Sample};
+
+    /// Runs an example benchmark for the SHA256 module
+    pub fn sha256_benchmark() -> Option<Measurement> {
+        if let Some(hmac_module) = platform::current().get_sha256_module() {
+            let input = [
+                0xdf3f6198, 0x04a92fdb, 0x4057192d, 0xc43dd748, 0xea778adc, 0x52bc498c, 0xe80524c0,
+                0x14b81119, 0xdf3f6198, 0x04a92fdb, 0x4057192d, 0xc43dd748, 0xea778adc, 0x52bc498c,
+                0xe80524c0, 0x14b81119, 0xdf3f6198, 0x04a92fdb, 0x4057192d, 0xc43dd748, 0xea778adc,
+                0x52bc498c, 0xe80524c0, 0x14b81119, 0xdf3f6198, 0x04a92fdb, 0x4057192d, 0xc43dd748,
+                0xea778adc, 0x52bc498c, 0xe80524c0, 0x14b81119, 0xdf3f6198, 0x04a92fdb, 0x4057192d,
+                0xc43dd748, 0xea778adc, 0x52bc498c, 0xe80524c0, 0x14b81119, 0xdf3f6198, 0x04a92fdb,
+                0x4057192d, 0xc43dd748, 0xea778adc, 0x52bc498c, 0xe80524c0, 0x14b81119, 0xdf3f6198,
+                0x04a92fdb, 0x4057192d, 0xc43dd748, 0xea778adc, 0x52bc498c, 0xe80524c0, 0x14b81119,
+                0xdf3f6198, 0x04a92fdb, 0x4057192d, 0xc43dd748, 0xea778adc, 0x52bc498c, 0xe80524c0,
+                0x14b81119,
+            ];
+
+            let mut output = [0u32; 8];
+
+            let cycle1 = sample();
+            hmac_module.init_sha256();
+            let cycle2 = sample();
+            hmac_module.write_input(&input);
+            hmac_module.wait_for_completion();
+            let cycle3 = sample();
+            hmac_module.read_digest(&mut output);
+            let cycle4 = sample();
+
+            verify_blocks(
+                &[
+                    // precomputed by sha2 crate
+                    0xa24ef743, 0xed238e92, 0x8f5fe495, 0x7959a1fa, 0x06b1d250, 0x147ed98d,
+                    0xd817e3b2, 0xb32854ae,
+                ],
+                &output,
+            );
+
+            let phases = [cycle2 - cycle1, cycle3 - cycle2, cycle4 - cycle3];
+            Some(Measurement::new(
+                BenchmarkResult::ExampleSHA256 {
+                    initialization: phases[0].cycles,
+                    computation: phases[1].cycles,
+                    reading_output: phases[2].cycles,
+                },
+                &phases,
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Runs an example benchmark for the AES module
+    pub fn aes256_benchmark() -> Option<Measurement> {
+        if let Some(aes_module) = platform::current().get_aes_module() {
+            let key_share0: [u32; 8] = [
+                0x0000_1111,
+                0x2222_3333,
+                0x4444_5555,
+                0x6666_7777,
+                0x0000_1111,
+                0x2222_3333,
+                0x4444_5555,
+                0x6666_7777,
+            ];
+            let key_share1: [u32; 8] = [0; 8];
+            let iv = 0xcccc_cccc_cccc_cccc_cccc_cccc_cccc_cccc;
+            let plaintext: [u128; 4] = [
+                0xffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff,
+                0x0000_0000_0000_0000_0000_0000_0000_0000,
+                0x0000_1111_2222_3333_4444_5555_6666_7777,
+                0x1234_4321_abcd_dcba_affa_afaf_0100_0010,
+            ];
+            let mut enc_buffer: [u128; 4] = [0, 0, 0, 0];
+            let mut dec_buffer: [u128; 4] = [0, 0, 0, 0];
+
+            let enc_c_1 = sample();
+            aes_module.init_aes(
+                AESKeyLength::Aes256,
+                AESOperation::Encrypt,
+                AESMode::CTR { iv },
+                &key_share0,
+                &key_share1,
+            );
+            let enc_c_2 = sample();
+            aes_module.execute(&plaintext, &mut enc_buffer);
+            let enc_c_3 = sample();
+            aes_module.deinitialize();
+            let enc_c_4 = sample();
+
+            let dec_c_1 = sample();
+            aes_module.init_aes(
+                AESKeyLength::Aes256,
+                AESOperation::Decrypt,
+                AESMode::CTR { iv },
+                &key_share0,
+                &key_share1,
+            );
+            let dec_c_2 = sample();
+            aes_module.execute(&enc_buffer, &mut dec_buffer);
+            let dec_c_3 = sample();
+            aes_module.deinitialize();
+            let dec_c_4 = sample();
+
+            verify_blocks(&plaintext, &dec_buffer);
+
+            let phases = [
+                enc_c_2 - enc_c_1,
+                enc_c_3 - enc_c_2,
+                enc_c_4 - enc_c_3,
+                dec_c_2 - dec_c_1,
+                dec_c_3 - dec_c_2,
+                dec_c_4 - dec_c_3,
+            ];
+            Some(Measurement::new(
+                BenchmarkResult::ExampleAES256 {
+                    enc_initialization: phases[0].cycles,
+                    enc_computation: phases[1].cycles,
+                    enc_deinitalization: phases[2].cycles,
+                    dec_initialization: phases[3].cycles,
+                    dec_computation: phases[4].cycles,
+                    dec_deinitalization: phases[5].cycles,
+                },
+                &phases,
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Number of blocks encrypted by the AES throughput benchmark
+    const AES_TOTAL_BLOCKS: usize = 64;
+
+    /// Runs an AES256 throughput benchmark with minimal instrumentation.
+    ///
+    /// Initialization, preparation of the buffers, deinitialization and verification are performed
+    /// outside of the measured region, the cycle counter is only sampled directly before and after
+    /// all blocks are encrypted. This yields an upper bound for the throughput of the AES module,
+    /// the encryption of a single block takes at most `computation / AES_TOTAL_BLOCKS` cycles.
+    ///
+    /// The blocks are then encrypted a second time using the instrumented loop,
+    /// to break down where the driver spends its cycles.
+    pub fn aes256_total_benchmark() -> Option<Measurement> {
+        if let Some(aes_module) = platform::current().get_aes_module() {
+            let key_share0: [u32; 8] = [
+                0x0000_1111,
+                0x2222_3333,
+                0x4444_5555,
+                0x6666_7777,
+                0x0000_1111,
+                0x2222_3333,
+                0x4444_5555,
+                0x6666_7777,
+            ];
+            let key_share1: [u32; 8] = [0; 8];
+            let iv = 0xcccc_cccc_cccc_cccc_cccc_cccc_cccc_cccc;
+            let mut plaintext = [0u128; AES_TOTAL_BLOCKS];
+            for (i, block) in plaintext.iter_mut().enumerate() {
+                *block = (i as u128) * 0x0101_0101_0101_0101_0101_0101_0101_0101;
+            }
+            let mut enc_buffer = [0u128; AES_TOTAL_BLOCKS];
+            let mut dec_buffer = [0u128; AES_TOTAL_BLOCKS];
+
+            aes_module.init_aes(
+                AESKeyLength::Aes256,
+                AESOperation::Encrypt,
+                AESMode::CTR { iv },
+                &key_share0,
+                &key_share1,
+            );
+
+            let c_1 = sample();
+            aes_module.execute(&plaintext, &mut enc_buffer);
+            let c_2 = sample();
+
+            aes_module.deinitialize();
+
+            aes_module.init_aes(
+                AESKeyLength::Aes256,
+                AESOperation::Encrypt,
+                AESMode::CTR { iv },
+                &key_share0,
+                &key_share1,
+            );
+            let p_1 = sample();
+            let profile = aes_module.execute_profiled(&plaintext, &mut dec_buffer);
+            let p_2 = sample();
+            aes_module.deinitialize();
+
+            verify_blocks(&enc_buffer, &dec_buffer);
+
+            aes_module.init_aes(
+                AESKeyLength::Aes256,
+                AESOperation::Decrypt,
+                AESMode::CTR { iv },
+                &key_share0,
+                &key_share1,
+            );
+            aes_module.execute(&enc_buffer, &mut dec_buffer);
+            aes_module.deinitialize();
+
+            verify_blocks(&plaintext, &dec_buffer);
+
+            // Only the status register is polled inside the loop, the timer is not sampled there
+            let phases = [
+                c_2 - c_1,
+                Sample::elapsed(profile.input_writes, 0, 0),
+                Sample::elapsed(profile.status_polls, (p_2 - p_1).polls, 0),
+                Sample::elapsed(profile.output_reads, 0, 0),
+            ];
+            Some(Measurement::new(
+                BenchmarkResult::AESTotal {
+                    computation: phases[0].cycles,
+                    input_writes: phases[1].cycles,
+                    status_polls: phases[2].cycles,
+                    output_reads: phases[3].cycles,
+                },
+                &phases,
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Runs an example benchmark for the rng module
+    pub fn rng_benchmark() -> Option<Measurement> {
+        if let Some(rng_module) = platform::current().get_rng_module() {
+            let seed = Some(vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+            let mut random_numbers = [0; 32];
+
+            let cycle1 = sample();
+            rng_module.init_rng(seed);
+            let cycle2 = sample();
+            for num in &mut random_numbers[..] {
+                *num = rng_module.generate();
+            }
+            let cycle3 = sample();
+
+            let phases = [cycle2 - cycle1, cycle3 - cycle2];
+            Some(Measurement::new(
+                BenchmarkResult::ExampleRNG {
+                    initialization: phases[0].cycles,
+                    generation: phases[1].cycles,
+                },
+                &phases,
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Runs an example benchmark for the ecdsa library
+    pub fn ecdsa_benchmark() -> Option<Measurement> {
+        #[cfg(feature = "platform_verilator_earlgrey")]
+        {
+            // public and private part of the ECDSA key was manually generated.
+            let priv_key = ecdsa_p256_private_key_t {
+                d: [
+                    0xe32ae325, 0xba720dd6, 0x7a61c7bf, 0x042a9ce2, 0x1caf1e98, 0xdada301d,
+                    0x209ab209, 0x69d57c5c,
+                ],
+            };
+            let pub_key = ecdsa_p256_public_key_t {
+                x: [
+                    0x2119818f, 0x4bf23e33, 0xa6730cc3, 0x7f88c59f, 0xd73e9dab, 0x0e28969b,
+                    0x4560410e, 0xda6152c2,
+                ],
+                y: [
+                    0x9dccc8a7, 0xf2f07fac, 0xb22c083e, 0xf519656d, 0x86ed498a, 0x9eceefab,
+                    0x82219250, 0x54b75d6a,
+                ],
+            };
+            let digest = ecdsa_p256_message_digest_t {
+                h: [
+                    0x9dccc8a7, 0xf2f07fac, 0xb22c083e, 0xf519656d, 0x86ed498a, 0x9eceefab,
+                    0x82219250, 0x54b75d6a,
+                ],
+            };
+            let mut signed_digest_buffer = ecdsa_p256_signature_t {
+                r: [0; 8],
+                s: [0; 8],
+            };
+            let mut verification_result = hardened_bool_t::HardenedBoolInvalid;
+
+            let c_1 = sample();
+            unsafe {
+                ecdsa_p256_sign(&digest, &priv_key, &mut signed_digest_buffer);
+            }
+            let c_2 = sample();
+            unsafe {
+                ecdsa_p256_verify(
+                    &signed_digest_buffer,
+                    &digest,
+                    &pub_key,
+                    &mut verification_result,
+                );
+            }
+            let c_3 = sample();
+
+            super::verify(&hardened_bool_t::HardenedBoolTrue, &verification_result);
+
+            let phases = [c_2 - c_1, c_3 - c_2];
+            return Some(Measurement::new(
+                BenchmarkResult::ExampleECDSA {
+                    signing: phases[0].cycles,
+                    verifying: phases[1].cycles,
+                },
+                &phases,
+            ));
+        }
+        #[allow(unreachable_code)]
+        None
+    }
+}
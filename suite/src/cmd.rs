@@ -1,11 +1,38 @@
-use alloc::{format, vec::Vec};
-use benchmark_common::{BenchmarkResult, IncomingMessage, OutgoingMessage, SuiteStatus};
+use alloc::vec::Vec;
+use benchmark_common::{
+    corpus, IncomingMessage, ModuleKind, OutgoingMessage, SkipReason, SuiteError, SuiteStatus,
+};
 
 use crate::{
-    benchmark::examples,
+    benchmark, datasets,
     platform::{self, Platform},
+    runtime,
 };
 
+/// Resets the modules, forgets earlier benchmarks and initializes the heap again
+/// if isolation is configured, so the next request starts from the same state of the machine.
+/// Whether this succeeded is reported with the results of the next benchmark
+pub fn isolate() {
+    if !runtime::config().isolation {
+        runtime::set_isolated(false);
+        return;
+    }
+
+    benchmark::forget_iteration_cycles();
+    // Safety: no references to the modules and no allocations are held between requests
+    let isolated = match unsafe { runtime::reset() } {
+        Ok(()) => unsafe { runtime::reinit_heap() },
+        Err(err) => {
+            error!("Reset failed: {err}");
+            false
+        }
+    };
+    if !isolated {
+        warn!("Failed to isolate the next request");
+    }
+    runtime::set_isolated(isolated);
+}
+
 /// Takes an IncomingMessage and decides how to respond to it
 ///
 /// # Arguments
@@ -15,27 +42,95 @@ pub fn run_cmd(cmd: IncomingMessage) -> Option<OutgoingMessage> {
     match cmd {
         IncomingMessage::Suspend(value) => platform::current().suspend(value),
         IncomingMessage::Invalid(msg) => {
-            Some(OutgoingMessage::Error(format!("Invalid message: {msg}")))
+            Some(OutgoingMessage::Error(SuiteError::InvalidMessage(msg)))
+        }
+        IncomingMessage::Unsupported(name) => {
+            Some(OutgoingMessage::Error(SuiteError::UnsupportedMessage(name)))
         }
         IncomingMessage::Done => Some(OutgoingMessage::Status(SuiteStatus::Done)),
         IncomingMessage::GetStatus => Some(OutgoingMessage::Status(SuiteStatus::Ready)),
-        IncomingMessage::Benchmark(info, n) => {
-            let mut results: Vec<BenchmarkResult> = Vec::new();
-
-            for _ in 0..n {
-                let result = match info {
-                    benchmark_common::BenchmarkInfo::ExampleSHA256 => examples::sha256_benchmark(),
-                    benchmark_common::BenchmarkInfo::ExampleAES256 => examples::aes256_benchmark(),
-                    benchmark_common::BenchmarkInfo::ExampleRNG => examples::rng_benchmark(),
-                    benchmark_common::BenchmarkInfo::ExampleECDSA => examples::ecdsa_benchmark(),
-                };
-
-                if let Some(result) = result {
-                    results.push(result)
-                }
+        IncomingMessage::Ping(value) => Some(OutgoingMessage::Pong(value)),
+        // Safety: no references to the modules are held between requests
+        IncomingMessage::Reset => match unsafe { runtime::reset() } {
+            Ok(()) => Some(OutgoingMessage::Status(SuiteStatus::Ready)),
+            Err(err) => {
+                error!("Reset failed: {err}");
+                Some(OutgoingMessage::Error(SuiteError::HardwareFault))
             }
+        },
+        IncomingMessage::GetCapabilities => Some(OutgoingMessage::Capabilities(capabilities())),
+        IncomingMessage::GetPlatform => {
+            Some(OutgoingMessage::Platform(platform::current().descriptor()))
+        }
+        IncomingMessage::ListDatasets => Some(OutgoingMessage::Datasets(datasets::descriptors())),
+        IncomingMessage::Replay => Some(OutgoingMessage::BenchmarkResults(corpus::envelope(
+            corpus::benchmark_results(),
+        ))),
+        IncomingMessage::SetConfig(config) => match runtime::set_config(config) {
+            Ok(()) => Some(OutgoingMessage::Config(runtime::config())),
+            Err(err) => Some(OutgoingMessage::Error(err)),
+        },
+        IncomingMessage::Benchmark(info, config) => {
+            runtime::send_message(&OutgoingMessage::Accepted {
+                expected_cycles_hint: benchmark::expected_cycles(&info, &config),
+                request_id: config.request_id,
+            });
+
+            let response = match &config.duty_cycle {
+                Some(duty_cycle) => {
+                    let report = |index, timestamp, results| {
+                        runtime::send_message(&OutgoingMessage::Window {
+                            index,
+                            timestamp,
+                            results,
+                        })
+                    };
+                    benchmark::run_duty_cycled(&info, &config, duty_cycle, report).map(
+                        |(windows, overruns)| OutgoingMessage::DutyCycleDone {
+                            windows,
+                            overruns,
+                            request_id: config.request_id,
+                        },
+                    )
+                }
+                None => benchmark::run(&info, &config).map(OutgoingMessage::BenchmarkResults),
+            };
 
-            Some(OutgoingMessage::BenchmarkResults(results))
+            match response {
+                Ok(msg) => Some(msg),
+                Err(SuiteError::ModuleUnavailable) => Some(OutgoingMessage::Skipped {
+                    reason: SkipReason::ModuleUnavailable,
+                    request_id: config.request_id,
+                }),
+                Err(err) => Some(OutgoingMessage::Error(err)),
+            }
         }
     }
 }
+
+/// Returns the modules available on the current platform
+fn capabilities() -> Vec<ModuleKind> {
+    let platform = platform::current();
+    let mut modules = Vec::new();
+
+    if platform.get_sha256_module().is_some() {
+        modules.push(ModuleKind::SHA256);
+    }
+    if platform.get_aes_module().is_some() {
+        modules.push(ModuleKind::AES);
+    }
+    if platform.get_rng_module().is_some() {
+        modules.push(ModuleKind::RNG);
+    }
+    if platform.get_kmac_module().is_some() {
+        modules.push(ModuleKind::KMAC);
+    }
+    if platform.get_otbn_module().is_some() {
+        modules.push(ModuleKind::OTBN);
+    }
+    if platform.get_entropy_src_module().is_some() && platform.get_edn_module().is_some() {
+        modules.push(ModuleKind::Entropy);
+    }
+
+    modules
+}
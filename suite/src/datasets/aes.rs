@@ -0,0 +1,120 @@
+//! Datasets for the AES benchmarks.
+//!
+//! The results are verified by decrypting the ciphertext again and comparing it to the plaintext.
+
+use benchmark_common::DatasetKind;
+
+use crate::modules::{AESKeyLength, AESMode};
+
+/// Key, configuration and plaintext used to perform an AES benchmark
+pub struct AESDataset {
+    /// Name describing the dataset
+    pub name: &'static str,
+    pub key_length: AESKeyLength,
+    pub mode: AESMode,
+    /// First share of the key, only the first `key_length` bits are used
+    pub key_share0: [u32; 8],
+    /// Second share of the key, the key is computed by XORing both shares
+    pub key_share1: [u32; 8],
+    /// The blocks that are encrypted
+    pub plaintext: &'static [u128],
+}
+
+/// All AES datasets, referenced by their index
+pub static DATASETS: [AESDataset; DatasetKind::AES.count()] = [
+    AESDataset {
+        name: "aes256_ctr_4_blocks",
+        key_length: AESKeyLength::Aes256,
+        mode: AESMode::CTR {
+            iv: 0xcccc_cccc_cccc_cccc_cccc_cccc_cccc_cccc,
+        },
+        key_share0: [
+            0x0000_1111,
+            0x2222_3333,
+            0x4444_5555,
+            0x6666_7777,
+            0x0000_1111,
+            0x2222_3333,
+            0x4444_5555,
+            0x6666_7777,
+        ],
+        key_share1: [0; 8],
+        plaintext: &[
+            0xffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff,
+            0x0000_0000_0000_0000_0000_0000_0000_0000,
+            0x0000_1111_2222_3333_4444_5555_6666_7777,
+            0x1234_4321_abcd_dcba_affa_afaf_0100_0010,
+        ],
+    },
+    AESDataset {
+        name: "aes128_ecb_1_block",
+        key_length: AESKeyLength::Aes128,
+        mode: AESMode::ECB,
+        key_share0: [
+            0x2b7e_1516,
+            0x28ae_d2a6,
+            0xabf7_1588,
+            0x09cf_4f3c,
+            0,
+            0,
+            0,
+            0,
+        ],
+        key_share1: [0; 8],
+        plaintext: &[0x6bc1_bee2_2e40_9f96_e93d_7e11_7393_172a],
+    },
+    AESDataset {
+        name: "aes192_cbc_2_blocks",
+        key_length: AESKeyLength::Aes192,
+        mode: AESMode::CBC {
+            iv: 0x0001_0203_0405_0607_0809_0a0b_0c0d_0e0f,
+        },
+        key_share0: [
+            0x8e73_b0f7,
+            0xda0e_6452,
+            0xc810_f32b,
+            0x8090_79e5,
+            0x62f8_ead2,
+            0x522c_6b7b,
+            0,
+            0,
+        ],
+        key_share1: [0; 8],
+        plaintext: &[
+            0x6bc1_bee2_2e40_9f96_e93d_7e11_7393_172a,
+            0xae2d_8a57_1e03_ac9c_9eb7_6fac_45af_8e51,
+        ],
+    },
+    AESDataset {
+        name: "aes256_ecb_5_blocks",
+        key_length: AESKeyLength::Aes256,
+        mode: AESMode::ECB,
+        key_share0: [
+            0x603d_eb10,
+            0x15ca_71be,
+            0x2b73_aef0,
+            0x857d_7781,
+            0x1f35_2c07,
+            0x3b61_08d7,
+            0x2d98_10a3,
+            0x0914_dff4,
+        ],
+        key_share1: [
+            0x0101_0101,
+            0x0202_0202,
+            0x0303_0303,
+            0x0404_0404,
+            0x0505_0505,
+            0x0606_0606,
+            0x0707_0707,
+            0x0808_0808,
+        ],
+        plaintext: &[
+            0x6bc1_bee2_2e40_9f96_e93d_7e11_7393_172a,
+            0xae2d_8a57_1e03_ac9c_9eb7_6fac_45af_8e51,
+            0x30c8_1c46_a35c_e411_e5fb_c119_1a0a_52ef,
+            0xf69f_2445_df4f_9b17_ad2b_417b_e66c_3710,
+            0x0000_0000_0000_0000_0000_0000_0000_0000,
+        ],
+    },
+];
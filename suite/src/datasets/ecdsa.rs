@@ -0,0 +1,64 @@
+//! Datasets for the ECDSA benchmarks.
+//!
+//! All values are P-256 numbers, given as 8 words with the most significant word first.
+//! The results are verified by verifying the signature that was produced.
+
+use benchmark_common::DatasetKind;
+
+/// Key pair and message digest used to perform an ECDSA benchmark
+// The keys are only read on platforms providing the OTBN
+#[allow(dead_code)]
+pub struct ECDSADataset {
+    /// Name describing the dataset
+    pub name: &'static str,
+    pub private_key: [u32; 8],
+    /// x coordinate of the public key
+    pub public_key_x: [u32; 8],
+    /// y coordinate of the public key
+    pub public_key_y: [u32; 8],
+    /// SHA256 digest of the message that is signed
+    pub digest: [u32; 8],
+}
+
+/// All ECDSA datasets, referenced by their index
+pub static DATASETS: [ECDSADataset; DatasetKind::ECDSA.count()] = [
+    ECDSADataset {
+        name: "p256_example",
+        private_key: [
+            0xe32ae325, 0xba720dd6, 0x7a61c7bf, 0x042a9ce2, 0x1caf1e98, 0xdada301d, 0x209ab209,
+            0x69d57c5c,
+        ],
+        public_key_x: [
+            0x2119818f, 0x4bf23e33, 0xa6730cc3, 0x7f88c59f, 0xd73e9dab, 0x0e28969b, 0x4560410e,
+            0xda6152c2,
+        ],
+        public_key_y: [
+            0x9dccc8a7, 0xf2f07fac, 0xb22c083e, 0xf519656d, 0x86ed498a, 0x9eceefab, 0x82219250,
+            0x54b75d6a,
+        ],
+        digest: [
+            0x9dccc8a7, 0xf2f07fac, 0xb22c083e, 0xf519656d, 0x86ed498a, 0x9eceefab, 0x82219250,
+            0x54b75d6a,
+        ],
+    },
+    ECDSADataset {
+        name: "p256_quick_brown_fox",
+        private_key: [
+            0xae49de9c, 0xf79e7dc7, 0xad24fa16, 0x6236bd4e, 0x96b90fbb, 0x97f6e6f6, 0x86dcdbca,
+            0xe451a5fa,
+        ],
+        public_key_x: [
+            0x176cb77b, 0x64a9408b, 0x8377cf61, 0xf984348d, 0x2b8fa932, 0xda6a5eb6, 0x77fc408f,
+            0x67816f19,
+        ],
+        public_key_y: [
+            0x2ff85430, 0xd6457701, 0x27c911ba, 0xbf5e1270, 0x3a28e75f, 0x9a48970b, 0x2561ff53,
+            0xeff9ec21,
+        ],
+        // SHA256 of "The quick brown fox jumps over the lazy dog"
+        digest: [
+            0xd7a8fbb3, 0x07d78094, 0x69ca9abc, 0xb0082e4f, 0x8d5651e4, 0x6d3cdb76, 0x2d02d0bf,
+            0x37c9e592,
+        ],
+    },
+];
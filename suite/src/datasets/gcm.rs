@@ -0,0 +1,100 @@
+//! Datasets for the AES-GCM benchmarks.
+//!
+//! The same datasets are used by the ChaCha20-Poly1305 baseline, with the IV as nonce, so the
+//! results of both AEADs can be compared directly.
+//!
+//! The ciphertexts and tags were precomputed using the AESGCM and ChaCha20Poly1305 implementations
+//! of the python cryptography module. All blocks use the convention of the AES module, one u128
+//! is interpreted as 4 consecutive little endian u32s.
+
+use benchmark_common::DatasetKind;
+
+/// Key, initial counter block, additional data and plaintext used to perform an AES-GCM benchmark
+pub struct GCMDataset {
+    /// Name describing the dataset
+    pub name: &'static str,
+    /// The AES256 key
+    pub key: [u32; 8],
+    /// The initial counter block J0, consisting of the 96 bit IV followed by the counter 1
+    pub j0: u128,
+    /// Additional data that is authenticated but not encrypted
+    pub aad: &'static [u128],
+    /// The blocks that are encrypted
+    pub plaintext: &'static [u128],
+    /// The expected ciphertext
+    pub ciphertext: &'static [u128],
+    /// The expected authentication tag
+    pub tag: u128,
+    /// The expected ciphertext of ChaCha20-Poly1305
+    pub chacha20_ciphertext: &'static [u128],
+    /// The expected authentication tag of ChaCha20-Poly1305
+    pub poly1305_tag: u128,
+}
+
+/// All AES-GCM datasets, referenced by their index
+pub static DATASETS: [GCMDataset; DatasetKind::GCM.count()] = [
+    GCMDataset {
+        name: "aes256_gcm_4_blocks_1_aad",
+        key: [
+            0x0000_1111,
+            0x2222_3333,
+            0x4444_5555,
+            0x6666_7777,
+            0x0000_1111,
+            0x2222_3333,
+            0x4444_5555,
+            0x6666_7777,
+        ],
+        j0: 0x0100_0000_88f8_cade_addb_cefa_beba_feca,
+        aad: &[0xfeed_face_dead_beef_feed_face_dead_beef],
+        plaintext: &[
+            0xffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff,
+            0x0000_0000_0000_0000_0000_0000_0000_0000,
+            0x0000_1111_2222_3333_4444_5555_6666_7777,
+            0x1234_4321_abcd_dcba_affa_afaf_0100_0010,
+        ],
+        ciphertext: &[
+            0x76cd_16b9_260b_3b96_1776_cd14_cba6_1ea6,
+            0x3bdf_e89e_848f_ce7a_f98c_a4d6_477f_6fff,
+            0x5f10_9b2c_cd75_59e2_d8ec_aa7c_fe98_5b3c,
+            0xbfdf_6bb9_dff5_49eb_0e8b_7a9a_8ebb_aaae,
+        ],
+        tag: 0x7a94_a4b2_de66_f509_99b9_ea3f_a5b8_ee93,
+        chacha20_ciphertext: &[
+            0xd215_c884_7d36_087c_cb95_396a_a135_47ea,
+            0x992c_0d5b_fa0f_f9d9_281c_5131_fbae_093c,
+            0x2372_9c80_0b3b_fe82_a923_d899_3123_d37c,
+            0x35dc_6248_92b1_68c4_9829_670b_3cfe_d431,
+        ],
+        poly1305_tag: 0x8abc_1327_7e80_c699_d84d_b24e_e151_0ca9,
+    },
+    GCMDataset {
+        name: "aes256_gcm_2_blocks",
+        key: [
+            0x8561_6e27,
+            0xfcd8_ab2d,
+            0x6218_cd69,
+            0xb876_335b,
+            0xe75a_5245,
+            0xaa1d_9e75,
+            0x553f_3be1,
+            0x4fd6_4b05,
+        ],
+        j0: 0x0100_0000_0b0a_0908_0706_0504_0302_0100,
+        aad: &[],
+        plaintext: &[
+            0x12bb_b300_8e5d_392b_eeab_2332_be17_833e,
+            0xa1f0_6916_0d57_f83a_a0ba_1311_1e98_709f,
+        ],
+        ciphertext: &[
+            0x784b_59cf_2680_7c45_8e2e_45e9_9bd9_db19,
+            0x65d3_8572_c324_83ad_d99e_c2a5_5fed_a95e,
+        ],
+        tag: 0x50a7_26b2_90ad_d6f6_6d1d_9b7a_c8d6_ad4e,
+        chacha20_ciphertext: &[
+            0x7ba1_c2da_ece4_847d_36d8_c601_5974_7101,
+            0xc248_7e48_aaf6_0c45_64d5_16b3_c917_6a67,
+        ],
+        poly1305_tag: 0xb3f7_0962_70df_05a9_c5df_e310_3abc_6c54,
+    },
+];
@@ -0,0 +1,668 @@
+//! Datasets for the hashing benchmarks.
+//!
+//! The last two datasets and the last key are the inputs and the key of the samples of
+//! NIST SP 800-185, so the cSHAKE and KMAC256 outputs can be verified against published values.
+
+use benchmark_common::DatasetKind;
+
+/// Number of words of the precomputed SHAKE and cSHAKE outputs, longer outputs can not be verified
+pub const XOF_OUTPUT_WORDS: usize = 128;
+
+/// Input and expected digest used to perform a hashing benchmark
+pub struct HashingDataset {
+    /// Name describing the dataset
+    pub name: &'static str,
+    /// The data that is hashed
+    pub input: &'static [u32],
+    /// SHA256 digest of the input, precomputed by the sha2 crate
+    pub digest: [u32; 8],
+    /// SHA3-224, SHA3-256, SHA3-384 and SHA3-512 digests of the input, in this order,
+    /// precomputed by the python hashlib module
+    pub sha3_digests: [&'static [u32]; 4],
+    /// The first `XOF_OUTPUT_WORDS` words of the SHAKE128 and SHAKE256 output of the input,
+    /// in this order, precomputed by the python hashlib module
+    pub shake_outputs: [&'static [u32; XOF_OUTPUT_WORDS]; 2],
+}
+
+/// Key used to perform an HMAC benchmark, together with the expected MACs
+pub struct HMACKey {
+    /// Name describing the key
+    pub name: &'static str,
+    /// The 256 bit key, most significant word first
+    pub key: [u32; 8],
+    /// HMAC-SHA256 of the input of every hashing dataset, in the order of `DATASETS`,
+    /// precomputed by the python hmac module
+    pub macs: [[u32; 8]; DatasetKind::Hashing.count()],
+}
+
+/// Expected cSHAKE output of a hashing dataset for a function name and customization string
+pub struct CSHAKEOutput {
+    /// Index of the hashing dataset in `DATASETS`
+    pub dataset: usize,
+    /// The function name
+    pub function_name: &'static str,
+    /// The customization string
+    pub customization: &'static str,
+    /// The first `XOF_OUTPUT_WORDS` words of the cSHAKE128 and cSHAKE256 output, in this order
+    pub outputs: [&'static [u32; XOF_OUTPUT_WORDS]; 2],
+}
+
+/// Expected KMAC256 tag of a hashing dataset for a key and customization string
+pub struct KMACTag {
+    /// Index of the hashing dataset in `DATASETS`
+    pub dataset: usize,
+    /// Index of the key in `KEYS`
+    pub key: usize,
+    /// The customization string
+    pub customization: &'static str,
+    /// The tag, its length is part of the input of KMAC, so only tags of this length are known
+    pub tag: &'static [u32],
+}
+
+/// Input of the largest dataset, smaller datasets use a prefix of it
+static INPUT: [u32; 64] = [
+    0xdf3f6198, 0x04a92fdb, 0x4057192d, 0xc43dd748, 0xea778adc, 0x52bc498c, 0xe80524c0, 0x14b81119,
+    0xdf3f6198, 0x04a92fdb, 0x4057192d, 0xc43dd748, 0xea778adc, 0x52bc498c, 0xe80524c0, 0x14b81119,
+    0xdf3f6198, 0x04a92fdb, 0x4057192d, 0xc43dd748, 0xea778adc, 0x52bc498c, 0xe80524c0, 0x14b81119,
+    0xdf3f6198, 0x04a92fdb, 0x4057192d, 0xc43dd748, 0xea778adc, 0x52bc498c, 0xe80524c0, 0x14b81119,
+    0xdf3f6198, 0x04a92fdb, 0x4057192d, 0xc43dd748, 0xea778adc, 0x52bc498c, 0xe80524c0, 0x14b81119,
+    0xdf3f6198, 0x04a92fdb, 0x4057192d, 0xc43dd748, 0xea778adc, 0x52bc498c, 0xe80524c0, 0x14b81119,
+    0xdf3f6198, 0x04a92fdb, 0x4057192d, 0xc43dd748, 0xea778adc, 0x52bc498c, 0xe80524c0, 0x14b81119,
+    0xdf3f6198, 0x04a92fdb, 0x4057192d, 0xc43dd748, 0xea778adc, 0x52bc498c, 0xe80524c0, 0x14b81119,
+];
+
+/// Input of samples 2, 4, 5 and 6 of NIST SP 800-185, the bytes 0x00 to 0xc7
+static SP800_185_INPUT: [u32; 50] = [
+    0x00010203, 0x04050607, 0x08090a0b, 0x0c0d0e0f, 0x10111213, 0x14151617, 0x18191a1b, 0x1c1d1e1f,
+    0x20212223, 0x24252627, 0x28292a2b, 0x2c2d2e2f, 0x30313233, 0x34353637, 0x38393a3b, 0x3c3d3e3f,
+    0x40414243, 0x44454647, 0x48494a4b, 0x4c4d4e4f, 0x50515253, 0x54555657, 0x58595a5b, 0x5c5d5e5f,
+    0x60616263, 0x64656667, 0x68696a6b, 0x6c6d6e6f, 0x70717273, 0x74757677, 0x78797a7b, 0x7c7d7e7f,
+    0x80818283, 0x84858687, 0x88898a8b, 0x8c8d8e8f, 0x90919293, 0x94959697, 0x98999a9b, 0x9c9d9e9f,
+    0xa0a1a2a3, 0xa4a5a6a7, 0xa8a9aaab, 0xacadaeaf, 0xb0b1b2b3, 0xb4b5b6b7, 0xb8b9babb, 0xbcbdbebf,
+    0xc0c1c2c3, 0xc4c5c6c7,
+];
+
+/// All hashing datasets, referenced by their index
+pub static DATASETS: [HashingDataset; DatasetKind::Hashing.count()] = [
+    HashingDataset {
+        name: "sha256_256_bytes",
+        input: &INPUT,
+        digest: [
+            0xa24ef743, 0xed238e92, 0x8f5fe495, 0x7959a1fa, 0x06b1d250, 0x147ed98d, 0xd817e3b2,
+            0xb32854ae,
+        ],
+        sha3_digests: [
+            &[
+                0x8a7f5d95, 0x2eeefaab, 0x33bb3418, 0xfdcd8e4a, 0xbe393319, 0x83b97863, 0x5557d652,
+            ],
+            &[
+                0x96302235, 0x640f292e, 0x01e764ca, 0xb06284ff, 0xc008c570, 0x2a51580f, 0xe370f29b,
+                0x50f0569e,
+            ],
+            &[
+                0x010f9dae, 0xf4f3a587, 0x93db84a8, 0x6c5ca066, 0x044eebb0, 0x824f9ff6, 0x3c2f2d5e,
+                0x8fc0c8f2, 0x15ee41a5, 0x468192c5, 0x5ad2cf5e, 0xd98f06b0,
+            ],
+            &[
+                0x322534f8, 0x5f678968, 0xab20f758, 0x33d90b3e, 0xd9cda591, 0xaa51ae5f, 0xa6093941,
+                0x70852f20, 0x709de2f4, 0xd63fda36, 0x23c86501, 0x57beda6b, 0xab716732, 0x77fc8975,
+                0x3829de30, 0x37deb413,
+            ],
+        ],
+        shake_outputs: [
+            &[
+                0x2bb19fc3, 0xf74c7b21, 0x2a8ba6ce, 0xc3f740de, 0x29b98da3, 0xba00037b, 0xeee848de,
+                0x9db158c9, 0x139e54b1, 0x6ae319b6, 0x76584e97, 0xefbab893, 0xe6ae1eab, 0x35412e24,
+                0xfddc93ee, 0x498197a8, 0xc33829ca, 0x44643495, 0xa48edde6, 0x5cf5749a, 0xe38d91c0,
+                0x63183925, 0x473f4405, 0x5dc27818, 0x42bf105e, 0x77a59aa2, 0xea1651f2, 0xbb8698bc,
+                0x5c01c898, 0x3f986664, 0x2e6167de, 0xadc2a2a6, 0x000c7884, 0x0564fbd8, 0xa80eab0c,
+                0xf96c4900, 0xfcd061c1, 0x2c5c88f7, 0xf3d2eb91, 0xbc2e54cf, 0x41531f8d, 0x51079a1b,
+                0x7d6de39d, 0xdf5a31a2, 0xc2d429c8, 0x92910336, 0xa45de0d5, 0x7cf5a8cb, 0x59c847f3,
+                0x8927051c, 0x5594666d, 0x838b1e8f, 0x7b9c4fe3, 0x97c68d91, 0xf3d5de76, 0xe9ec80a1,
+                0xf1ef6b1c, 0x8233e836, 0x06f8e96b, 0x9db3d162, 0xc6ce9c72, 0x74d5d7b2, 0xf4b25558,
+                0xcc3f178e, 0x4036ff88, 0x392f533a, 0xf1029019, 0x8cf600dc, 0xf6130024, 0xf3b9fda4,
+                0x1a81d3c9, 0xf767b705, 0xa6490182, 0xf5d3a441, 0x6bfe18db, 0xd784337f, 0x5d8786c7,
+                0x44b663cd, 0x411f16c5, 0x087baf45, 0xabca4a46, 0xe310878d, 0xd74aeb78, 0x3f860d69,
+                0x283a4e1e, 0x193afdb5, 0x2ec26b47, 0x7ed44451, 0xdb3da3be, 0x724651da, 0x2b0e0965,
+                0xa405e30e, 0xb41d0174, 0x82558da1, 0x23fdc99f, 0x17f4d1bb, 0x92118c23, 0xa16a6416,
+                0x3fc3f041, 0xae02507f, 0xd6c43009, 0x2b0d1a36, 0x07e65b49, 0x12706243, 0xb4c70751,
+                0x750cf4f4, 0xd3a39a71, 0x45ebf3b8, 0x4665e6f5, 0x4ae9fbbe, 0x0f0a6721, 0x39e0bfe0,
+                0xad67030e, 0xacd5c236, 0x318abe2e, 0x0335f948, 0xeef1335f, 0x053ce933, 0xdd2aefa7,
+                0x5d6c7882, 0xeb490442, 0x597eae5c, 0x0dc4e7f3, 0xf6ec7515, 0x0d769a90, 0xa3feffd9,
+                0xbb2315f0, 0xdeafbe65,
+            ],
+            &[
+                0x268c0d5f, 0xe6c5ae42, 0x3decfed1, 0xcd5e57d5, 0x09d32084, 0x4f5f45c7, 0x33ed7778,
+                0xfef9a983, 0xeaa1dcd3, 0xf2e36c6d, 0xcb67c5d3, 0xa1a9528a, 0xca90d894, 0x3a453a2e,
+                0x23f56187, 0x584220f1, 0x0cd6c97f, 0x143c52e5, 0x40de6e8b, 0x2624d2df, 0xb7b56985,
+                0x1cb34b4a, 0x85fc19b3, 0xf20567bb, 0x3407bf08, 0x1b10a12f, 0xb4094d10, 0xb0c42bc5,
+                0xbfc7b904, 0xf0c7d35a, 0x5f106e75, 0x24cfd764, 0x0c2874fa, 0xd2361ebe, 0xc7d043ca,
+                0xb2f73c40, 0xaab36279, 0xc58d674e, 0xd3999c29, 0x69099404, 0x5e183aad, 0x00492680,
+                0x102480ab, 0x6cd9ce69, 0x1c86d2f4, 0xdc492394, 0x4089e950, 0xf4d6a880, 0x182f07ec,
+                0x3f97a15e, 0x9b4c1f00, 0xf5a56162, 0x52bbf535, 0xb599ace5, 0x142c3f89, 0x2426ae7c,
+                0xe7769a1c, 0xc8855f60, 0x2a62d112, 0xad304d5f, 0xc90c5303, 0x6e4b331f, 0x3f41400d,
+                0xbabfd9ca, 0x2886394b, 0x6bc7ad55, 0x85f69658, 0x89a77fdb, 0xa9bf14ce, 0x4f0dbee0,
+                0x093487be, 0x75a7a116, 0xfbafce07, 0xc9cba7ba, 0x5f0dd4b9, 0x41bf719a, 0x3ce5fd09,
+                0xd944c6dc, 0x96c4d296, 0x1278a969, 0xe3c617e5, 0xe4b6e445, 0x4238d222, 0xa1d94b06,
+                0x069ac547, 0xc46315b1, 0x141a68ed, 0xe34bfe88, 0x54a1e04f, 0x6ae5c3e6, 0xe08353c4,
+                0x9b4c7e6b, 0x443a795b, 0xa86ea08d, 0x6e90dc0f, 0xa13c4de7, 0xe67e5795, 0x9a2ba7c1,
+                0x4bd8fa56, 0x38fdf6c3, 0xd35d85b3, 0xdad4048c, 0xfc53a506, 0x8f0904c8, 0x9c2e5d42,
+                0x4f475205, 0xaa6a0556, 0x2752ee54, 0x87ab781a, 0x9344bac4, 0xcfcc81f5, 0xeb5af6c2,
+                0x7e2dc9f6, 0xf72a2239, 0x59f25cea, 0xb9986a99, 0x365eddd5, 0xd7622ff9, 0x3b6826e2,
+                0x73c948aa, 0x57b982c7, 0xef704360, 0xd72af337, 0xd7cf4127, 0x7b0d166f, 0x85b9032e,
+                0x5d4df03d, 0x2b88c761,
+            ],
+        ],
+    },
+    HashingDataset {
+        name: "sha256_64_bytes",
+        input: &[
+            0xdf3f6198, 0x04a92fdb, 0x4057192d, 0xc43dd748, 0xea778adc, 0x52bc498c, 0xe80524c0,
+            0x14b81119, 0xdf3f6198, 0x04a92fdb, 0x4057192d, 0xc43dd748, 0xea778adc, 0x52bc498c,
+            0xe80524c0, 0x14b81119,
+        ],
+        digest: [
+            0xe62c14fe, 0x7d5bacda, 0xeca6344a, 0x60495d03, 0xbb3fc77a, 0x54858e98, 0x8d6f3f99,
+            0x1ad5dd8f,
+        ],
+        sha3_digests: [
+            &[
+                0xea5629c4, 0x3bd45001, 0x73c9f48b, 0xf857b1b8, 0x299e9c48, 0xe3a5a6fa, 0x78e293aa,
+            ],
+            &[
+                0x7c968c88, 0xfa340484, 0x09fafb2a, 0xd3768d45, 0xe7ff6ca4, 0x7a374891, 0xf0fc216d,
+                0xdc0e9aa7,
+            ],
+            &[
+                0x35e478b7, 0xb8108b9f, 0x4c3ac526, 0xdada42bc, 0x48550f67, 0x9d0f0422, 0x7222692e,
+                0xca7bced7, 0x1fa1e0d6, 0x77ea4db0, 0xbaaa41da, 0x757439f8,
+            ],
+            &[
+                0x41a1d8d1, 0x4834fc8d, 0x1d6c7ff4, 0xc5644243, 0xff4443ea, 0x37c023fb, 0xd8f31cb3,
+                0xca622d16, 0x337dae62, 0x6f75e99d, 0x6662bdae, 0x381d151c, 0xd5361bfe, 0x05f922fc,
+                0xa836c0d2, 0x13e89be0,
+            ],
+        ],
+        shake_outputs: [
+            &[
+                0xd6c03b29, 0x45b8f259, 0xfb8ea9cf, 0xc36c57e1, 0x6d7cd47b, 0x35dea08e, 0xb336aeca,
+                0x7c1fd0df, 0x0ee9c044, 0xc387a690, 0x96cc1ebc, 0x12656569, 0x3213b82f, 0xe69bcc78,
+                0xce10c198, 0x6d94aedc, 0x485764f2, 0x03b83fe9, 0xf111966f, 0xe8524119, 0x351375d3,
+                0x978ea5f9, 0xbeda226b, 0x800de420, 0xccc4abf4, 0x59e6e9f1, 0xbfca2259, 0x5896b940,
+                0xd2d7086a, 0x6dd32a52, 0x56d843de, 0xbfc96fe9, 0xcc2bbf9c, 0x3dfac202, 0x5924cf70,
+                0x59faa66a, 0x73e6044f, 0x71b9400d, 0xa85cbdba, 0x73d44225, 0x5b7bd3ad, 0x49d47e9a,
+                0xc058d6bc, 0xc12ea9bd, 0xaa340556, 0x4f93ffad, 0xac965735, 0xdaf36480, 0x1c6b6ef6,
+                0x2a3f2d97, 0xfc844eae, 0x9cb34be5, 0xe49feb3e, 0xf8a838a7, 0xd61a3b0a, 0x359251a3,
+                0xfc309299, 0xd7fc8d4e, 0x47dcedc1, 0x368f24fd, 0xe75a903d, 0x4fee7385, 0x9f8bf564,
+                0x540df79b, 0x51ae8ee2, 0xace60fb9, 0x7114e420, 0xf2ff002f, 0x26d208f1, 0x39258079,
+                0x9ed24283, 0x88e9beb9, 0x581d9ec2, 0xfdc45ba5, 0x691739a4, 0x697a3907, 0x389d59e1,
+                0xcb0fb2df, 0xb5592c6d, 0x72a2cab1, 0x6986b6ba, 0x9544450e, 0x4f0a3215, 0x8c5a030b,
+                0xbf04f142, 0x0ab42668, 0x137487ed, 0xa237c777, 0x5fcdcff4, 0xf95e3713, 0x42256455,
+                0x9909960a, 0xf9ebbe06, 0x1beaf660, 0xf5580515, 0xe6a88f40, 0x71c98111, 0xe2fa3aab,
+                0x231e852c, 0x2c053a2b, 0x1abf8f2d, 0x2db694e5, 0x0be6307d, 0x21b0a85a, 0xaed7b8ce,
+                0x0e7ea9ff, 0xc08317e0, 0x3ecfa246, 0x8cc553ce, 0x674ba9c6, 0x95e1f6eb, 0xa3aa6b82,
+                0x2f615a35, 0x2c915fa8, 0xe4e5321b, 0xa0f30625, 0xd6d5dbdf, 0xd4e9fa2a, 0x47f38ed1,
+                0xabf6b1cc, 0x1f577912, 0xea01b344, 0x6ec8ef8b, 0x6156c323, 0x3caff607, 0xc102ff41,
+                0xac808c73, 0x9391a1d1,
+            ],
+            &[
+                0xfdaa82d4, 0xf40e8ae0, 0x0d2c36f6, 0xb66e54fd, 0x7ec70ee4, 0x4f46a867, 0x3dd73244,
+                0x2bde6b23, 0xdaa6dc65, 0x9727efc8, 0x98d0611b, 0xc135b386, 0xa7d1ee49, 0x8196b811,
+                0xb6428c34, 0xa4f72bd1, 0x4568f56d, 0x976401ed, 0x619cd8ea, 0x7015f13f, 0x6f392305,
+                0x44c0f3fe, 0x364477cf, 0xa6e0d951, 0x0a8ea418, 0x51348d18, 0xc04665f0, 0x1e3e876b,
+                0x1ccbf0dd, 0xc00850b2, 0x95e17668, 0x91334ff2, 0x18e426e7, 0xff7ed227, 0x3aa870fb,
+                0xe08f92eb, 0xf59831ee, 0xd2b6aa97, 0x310afe55, 0x1f2e7b81, 0xb0bc2270, 0x48140a9f,
+                0x8cf29721, 0x415705c6, 0xc3709a9e, 0x66b8d5c3, 0xeeb16fe9, 0xa5048bfb, 0xca08ae84,
+                0xd3268d52, 0x49c01f69, 0x1979d0a5, 0x077558c4, 0xbd32b1ed, 0x8cc481c0, 0xcba52344,
+                0xc56b4f6b, 0xc794f2cd, 0x4b6a326c, 0x571053eb, 0x24da5e32, 0x215052bd, 0x16647ece,
+                0xd1ffa7da, 0x35817077, 0xb5e140bc, 0xec658bfd, 0x23b99b21, 0xe8b13fd5, 0x63e46470,
+                0xa91b49b4, 0x7c98a3a7, 0xfabcf54d, 0xa93a58ae, 0x21afad89, 0x435a3c12, 0x28894ccb,
+                0x2ff727b6, 0xe860f733, 0x7ae33d42, 0xa4e95306, 0xacdac88c, 0x59bf94bb, 0x3fd4d010,
+                0xfbb6b558, 0x510bd64a, 0xdb5708a3, 0x1613506f, 0x485821e3, 0x90538326, 0x5bf72852,
+                0xef3689e5, 0x60f5f129, 0x2d20ff57, 0xb12c928b, 0x3b41d900, 0xf18af3a1, 0x8da82bf4,
+                0xa4e968dc, 0x72205ee2, 0x3002d8fb, 0x6d006c5f, 0x17efb785, 0xf479dbf2, 0xe3d03eca,
+                0x5469d3db, 0x3a9fe50e, 0x457ef554, 0x5b3187e5, 0x8e85f755, 0x0b6afa88, 0x2dc11b4c,
+                0x24c127c5, 0x6b9da2b6, 0xb9d5fc43, 0x05dea9a4, 0xabdf2833, 0x90bd73f4, 0x0ebd71f4,
+                0xa3131219, 0x092ad4c8, 0x610f47c9, 0xe3ea736e, 0x60d48503, 0x614223b8, 0xc10ae4fc,
+                0xee0072ef, 0x042d9559,
+            ],
+        ],
+    },
+    HashingDataset {
+        name: "sha256_4_bytes",
+        input: &[0xdf3f6198],
+        digest: [
+            0x871628b3, 0x04313a61, 0x1fd48dba, 0xe241b9c2, 0x44291cfd, 0x2aa8751d, 0x74b0ffe6,
+            0x1b41eba8,
+        ],
+        sha3_digests: [
+            &[
+                0xd23a8659, 0x008d5e0d, 0x229b4981, 0x8d868305, 0x011fc3db, 0xe9c5d029, 0x0f90c1f0,
+            ],
+            &[
+                0xfd0c9b12, 0xc2611153, 0xe51c0568, 0xdff1f220, 0xcb241a41, 0x92a0d7ff, 0x5f1a4bb2,
+                0x284e4deb,
+            ],
+            &[
+                0x2fe5799c, 0xbac91199, 0x8497300f, 0xeef22701, 0x1a2df6b9, 0xa86f7967, 0x72efcd19,
+                0x9f76e3eb, 0x439f8666, 0x0a79efa9, 0xb7e4bd10, 0x2847acb7,
+            ],
+            &[
+                0x2fd2d15d, 0x2b8f3c17, 0x67ba5746, 0x409e38ec, 0x6b3751f3, 0x2afbc49c, 0xbcf260e6,
+                0xdc9e6a01, 0xe21d0a71, 0x2c885406, 0x949a4394, 0x9ba6558d, 0x3ce5dac5, 0x164ab74e,
+                0x7ed09bd9, 0x555a12f9,
+            ],
+        ],
+        shake_outputs: [
+            &[
+                0x6f8145a9, 0x09037506, 0x8efc2356, 0x59408dc7, 0x879be698, 0x0ab91acf, 0x0cf92bf2,
+                0x2f17f0c5, 0x13b3ccd5, 0xa1aaaa75, 0x7cf32150, 0x28d60dc7, 0x6a1abe75, 0xe4300d62,
+                0x9d01c87d, 0x4f82e522, 0xf578f417, 0xc83dff69, 0x68626474, 0xfa9839e3, 0xb1199265,
+                0x91e20316, 0xb4375e92, 0x0e35d2d7, 0x026846e3, 0x8220952b, 0xc8c09395, 0xb9f45b06,
+                0x01a4a8c6, 0x9fc1b971, 0x10d05df3, 0x4056bc86, 0x51b70c9c, 0xdd2e3d15, 0x521f212f,
+                0x2f6efc2e, 0x1ab25f1d, 0x6b85bbac, 0xe68e057d, 0x6eb8ce08, 0x20003e2e, 0x3b66ac41,
+                0x00ac5137, 0x29335d55, 0x8aee4a5f, 0x21ae0ede, 0xf8e45125, 0x678eb63c, 0x707980cc,
+                0x86fc1cc6, 0x634c2e79, 0x8a295e05, 0xe06e808e, 0x94f7a012, 0xb589a11f, 0x88cb2e95,
+                0xbcb928c4, 0x729a8b30, 0x830ad5e5, 0xe8b8a0e5, 0x48dcdae7, 0x75d5803c, 0x1610be6a,
+                0xe9d12e10, 0xbccb8e57, 0xca71a323, 0x9c0e2d7d, 0xe6bcdee6, 0x0a955799, 0xd5165b2b,
+                0x7de72b26, 0xbd490f36, 0x5b7211de, 0x6652eb41, 0x8ebecbb8, 0x478fd0a6, 0x62c8540c,
+                0x345fef64, 0x21b9f36b, 0xf1a667cd, 0x0d060997, 0x473562b1, 0x04a5cc9b, 0xb57f1df0,
+                0xce321aaf, 0xce6c9766, 0xc09a4c56, 0x21f49962, 0xf23eac41, 0x1b7a2f20, 0xe3a3ad94,
+                0x30eb9c47, 0xe30a1266, 0xa937349f, 0x20a57cc1, 0xee41d84c, 0xc3315a33, 0xcf24f8dc,
+                0x24519206, 0x12de173a, 0x3b7f61f1, 0x13609d2e, 0x9bbffa94, 0xbfafc2cb, 0xd91f591d,
+                0xe9918f0a, 0x2f4ab9e5, 0xe19fafa8, 0x84f3d763, 0x6b0ee820, 0xb35772da, 0x7d0b1515,
+                0xf8c8ec02, 0x7b6bc255, 0xb5ca1db9, 0xe6aa2a8f, 0xd491139e, 0xe276a96f, 0xb2c26600,
+                0x7e90afed, 0xbf9b0411, 0xdc84243d, 0x8d9a75e9, 0xd719022f, 0x7192138f, 0x967cc289,
+                0x880d1ec6, 0xbd9cd4a7,
+            ],
+            &[
+                0xb95ee3db, 0xdce368bd, 0x0b4aa9f5, 0x2c423995, 0x0f92407c, 0x77e4f891, 0x2e97bc94,
+                0xfbc0da64, 0x5287af33, 0xac7ecc4a, 0xcfb5c546, 0x305d489d, 0x16e43453, 0xecfec9f2,
+                0xb3c8cec4, 0x78420823, 0x5f193da4, 0x33cb81a5, 0x35f5c3fe, 0xd43f3e9c, 0x30b0e041,
+                0x2f96eb66, 0x9225f835, 0xea43d665, 0x398d9c25, 0xc9e647d4, 0x04ebb544, 0x5f306d95,
+                0x9f4b6ed1, 0xcd1d15e2, 0xd54d3612, 0x3d740cdc, 0x7d06d753, 0x70caf169, 0x524e8f8f,
+                0x2f55fd24, 0x5680dde3, 0x3116ca46, 0x022fe312, 0x130966ca, 0x5a207ee2, 0xe919f47b,
+                0xe261aee1, 0x06651baf, 0x8a6448d8, 0xba9876d8, 0x2b0eaeef, 0x3da5ce40, 0x2e647561,
+                0xd4a9d5fe, 0x82fffd2c, 0x4e0c9660, 0x10af1868, 0xd9416cdd, 0xfe73a745, 0xcb276026,
+                0xaf4c129e, 0xde7cc2ff, 0x1c453c3c, 0x14e2b308, 0x6d5353ec, 0x8f6984f6, 0x489ea5e7,
+                0xae1f1a14, 0x5df1a748, 0x3effcec3, 0xcd3749a4, 0x8c6830b8, 0x1e4271b3, 0x1209a445,
+                0x56fc13c8, 0xb99e08a6, 0xadcb3ae1, 0x06c989d6, 0x63f9045b, 0x71f6cd0c, 0x04014214,
+                0xa7f2a45e, 0xdb9264b6, 0xb491f0db, 0x5bf82b20, 0x5b013c83, 0x8342ef94, 0xf06cb7fd,
+                0x13c25b54, 0x4ee72f3e, 0x415a767a, 0x91a9f04c, 0xc648b9c7, 0xe8497831, 0x35fd9241,
+                0xf9c62537, 0xca6bc3ef, 0xb5ea7214, 0xb10a2a0f, 0x019ba1d7, 0x0c34c19e, 0x8f8ed258,
+                0x3d6a383f, 0xe894e0f2, 0xda7a2eb8, 0x22c40477, 0x5afd0a37, 0x34cba339, 0xe0dc9493,
+                0x0e2f4902, 0xbef657d7, 0xdb595383, 0xd07cfc50, 0xc56bf6e6, 0xd36c2e02, 0x54633213,
+                0xb7417b1a, 0xd639368c, 0x938b2c2c, 0x2a41e733, 0xa77bf7eb, 0x6f64f945, 0xa51bea22,
+                0x20d2631c, 0xbd5626e8, 0x5e429890, 0x8db27ec2, 0xc3b0623f, 0x0afb1702, 0x82f3a989,
+                0x5fc412af, 0xe4333bd3,
+            ],
+        ],
+    },
+    HashingDataset {
+        name: "sp800_185_4_bytes",
+        input: &[0x00010203],
+        digest: [
+            0x054edec1, 0xd0211f62, 0x4fed0cbc, 0xa9d4f940, 0x0b0e491c, 0x43742af2, 0xc5b0abeb,
+            0xf0c990d8,
+        ],
+        sha3_digests: [
+            &[
+                0x4fb067ed, 0x1266b8f4, 0x87f609f1, 0x2350ae92, 0xccda9a19, 0x7d7da4a8, 0x31724d7d,
+            ],
+            &[
+                0x33bad543, 0x0899ed6f, 0x8beaf3e7, 0x32b2a2ca, 0xd1d40b7c, 0x9de0cfcd, 0xc7e0bc07,
+                0x56803a10,
+            ],
+            &[
+                0x0f555b91, 0x3fe5bcf7, 0xa9d3e3c3, 0xdb63eba5, 0xef404c3c, 0xf386a9c1, 0x45c40215,
+                0x69daf66b, 0xd374806c, 0x1fc5915b, 0xb0c27468, 0xf47044f2,
+            ],
+            &[
+                0x8a012a2d, 0x7ffa17ea, 0xb1b63c2e, 0x6cceab15, 0x97623362, 0xe7cff2f1, 0xd81bb1d1,
+                0x813d27ab, 0x344eed99, 0x87e55fcb, 0x4315c4f8, 0x004d4c54, 0x1a0cff1a, 0xd050e5b1,
+                0xd39ec91b, 0xf68cf5e7,
+            ],
+        ],
+        shake_outputs: [
+            &[
+                0x0b0cc28e, 0x60e37698, 0xb411234b, 0x1158a5d4, 0x26364404, 0x32a28e8b, 0x8df5be04,
+                0x208878f9, 0x906685c2, 0xf1e1ca90, 0x1bc5c419, 0xd9ac786a, 0x658c74d5, 0xf1a73536,
+                0xf7638853, 0xd53325e3, 0x0652ebba, 0x829dd82c, 0xb355863b, 0x23ba35e7, 0x2cbc7f0a,
+                0x88b789af, 0x3d1668a4, 0x26a144bf, 0x90cc4136, 0x422a0f49, 0x80cc826e, 0xbd914810,
+                0x4ce7fc36, 0x3ec7e6ed, 0x7595d176, 0x39f7bda3, 0xaae78ce6, 0x2a963ff5, 0xb359b461,
+                0xb2f8f258, 0xafe28371, 0x7262a96c, 0x18f08f4f, 0x0ae09261, 0xc8ff85e5, 0x0bd2fd90,
+                0xca563175, 0x8280e7f0, 0xfa368858, 0x17d65618, 0x4019f1a6, 0xbed6f163, 0x66236570,
+                0xfb5514e5, 0x60d514fb, 0x72e1133d, 0x7137d38f, 0xf02a499a, 0xee0b4df5, 0xf4c47fd1,
+                0xfd4b8c0d, 0xbbe8b023, 0xb7cbe9a8, 0xd992343c, 0x3f949ab4, 0x19524e37, 0x6ca25312,
+                0x459c22c3, 0x575bba63, 0x6e5793c5, 0x1ac9dc42, 0x2d6b5644, 0xf7e3d2e6, 0x3a79b76c,
+                0xfd43e0e1, 0x4441eead, 0xe8696876, 0x65435df3, 0xf8cf59b4, 0xe5516df8, 0x5bb41f29,
+                0x656fbfb9, 0xcf6e88c2, 0xf0f00c1e, 0x3a4aa48a, 0xc1c9bb95, 0xd8fdff7e, 0x1ebf975c,
+                0xa96dea4f, 0xbceceda6, 0xcf6e1a45, 0xe168677d, 0xb679eca9, 0x688a582e, 0x403e37b6,
+                0x17806bd5, 0x768933b2, 0x476e3397, 0x4cf8251a, 0x8055d17b, 0x37130cec, 0x5cd0dff1,
+                0xc00ba7e7, 0x51ca083a, 0x7ccef5da, 0xaa440388, 0x99676c3c, 0xfb64fecf, 0xc3a29625,
+                0xfd67955c, 0xa42098f9, 0xbc6cf051, 0x808c44c5, 0x1b90f4be, 0x67195fb9, 0x3b549493,
+                0xee56a374, 0x6bac7568, 0xd675eda8, 0xcde530f2, 0x83a896df, 0x4fe84e06, 0xc3672ad5,
+                0x89aa2aed, 0x9216cc7d, 0xfb20ce2a, 0x32df8671, 0x1f574484, 0x18f3fc3b, 0x313b7dd6,
+                0x0d3444ac, 0x03081802,
+            ],
+            &[
+                0x48b8d57a, 0x5f8c29d0, 0x32604921, 0x6380aa85, 0xd2d7a58b, 0x784f5a49, 0xe980ca93,
+                0x409e3d4b, 0xac255093, 0x71f937ef, 0x3224820e, 0xda0af091, 0x5c10d07e, 0x2df78baf,
+                0xe7208d23, 0xf36388a9, 0x42130c01, 0xd78ab09d, 0x553a8acb, 0x47e820d2, 0x7279616e,
+                0xd506367a, 0x8f84d999, 0xa596e445, 0xdf3b0972, 0x51b3a705, 0x57460d26, 0xa3f8a017,
+                0xec11b345, 0x32f147c0, 0x9c70edd7, 0xe9bb51e7, 0x7b288a72, 0xd81a2a77, 0x08c9b90d,
+                0x477a47e2, 0xb7e91657, 0x3cb29164, 0x119167a9, 0x5b2eeaac, 0x4053cb08, 0x4b5070eb,
+                0x2805688c, 0x8a61c2b5, 0x1df75436, 0x5ba9a8af, 0xc7c6bf89, 0x7cc2d70c, 0x66212be2,
+                0xa41f0ccf, 0x4020418f, 0x915a400e, 0x78884908, 0xdd2aeb05, 0xe11f7744, 0x75311458,
+                0x05ba057f, 0x7d69a848, 0x5432366f, 0xef117942, 0xef2637a5, 0xb39ff021, 0x4c4b515e,
+                0x2a903615, 0x510df78b, 0xee5b285a, 0x3c8e05ca, 0xf3b671c1, 0xd8a97c04, 0xc100e5ee,
+                0x5403275f, 0xc9159133, 0x4025abb4, 0x7b48232f, 0xd24c4c9c, 0x93cd00ad, 0xe915beaa,
+                0xf9a2bf41, 0x8bd21f44, 0xddeaa15d, 0xbfca9564, 0xd9be1b22, 0x7dae271d, 0x0d666219,
+                0xca7bcb11, 0x3ed27784, 0x3db1ae04, 0x4f590bc4, 0x4378997c, 0x4071027f, 0x322dffa9,
+                0x5b296314, 0xf313a0c9, 0x09a578d9, 0x65cf7dde, 0xf3b00dfe, 0x4595d1da, 0xf6ae464c,
+                0x94eab348, 0x3fcb1db0, 0xd105c2cb, 0x48eaf5c5, 0x946b24f8, 0x19d73d71, 0x9eff95a6,
+                0x4f18571d, 0xb6711463, 0xa92db7de, 0xfc228432, 0x614df732, 0x3a8d5906, 0xa4cb8aa8,
+                0x814b2818, 0xd07065b9, 0x00a5ce64, 0x2799d0e0, 0x14707c72, 0x482799f7, 0xa371cc75,
+                0xd4134d34, 0x4be086d5, 0x37b571a7, 0xd645e0e8, 0xd43e4093, 0x127bc1b2, 0x9ea2febe,
+                0x553061e1, 0x7f290ea4,
+            ],
+        ],
+    },
+    HashingDataset {
+        name: "sp800_185_200_bytes",
+        input: &SP800_185_INPUT,
+        digest: [
+            0x1901da1c, 0x9f699b48, 0xf6b2636e, 0x65cbf73a, 0xbf99d044, 0x1ef67f5c, 0x540a42f7,
+            0x051dec6f,
+        ],
+        sha3_digests: [
+            &[
+                0x8bcd90db, 0xc5379549, 0xb5e78a1f, 0xbe24ae12, 0x0d92caef, 0x17750461, 0x262b1e97,
+            ],
+            &[
+                0x5f728f63, 0xbf5ee48c, 0x77f453c0, 0x490398fa, 0x645b8d4c, 0x4e56be9a, 0x41cfec34,
+                0x4d6ca899,
+            ],
+            &[
+                0xb13febb1, 0xb3c54a7c, 0x6b69367f, 0x693a1d1f, 0x3145709b, 0x6ddef23f, 0xf1587413,
+                0x3ea1fb9c, 0xfa48ee7f, 0xf4ec9aa9, 0x87dea641, 0xe33ccdf7,
+            ],
+            &[
+                0xea5d05f1, 0x9348dd58, 0x97933547, 0x93a15f37, 0xa73b4c0b, 0xb4e750b9, 0xa00757df,
+                0xce2f8b65, 0xa64191bb, 0x9b137de0, 0x0feef647, 0x4cfd47ab, 0xf7880efb, 0xc51614a5,
+                0x715df12c, 0xfe0caee3,
+            ],
+        ],
+        shake_outputs: [
+            &[
+                0x0c4234ca, 0x1e31801a, 0xe606f8b8, 0xd8e0665c, 0x66f42a21, 0xd601c268, 0x1858a92c,
+                0x79ad5d69, 0xe143c3b1, 0x393dd894, 0xe7abd562, 0x1b0d877f, 0x3573a342, 0x45e6b911,
+                0xf6710816, 0x64a5fa53, 0xf778886c, 0xb56bdba6, 0x0b2e8d21, 0xbd5b68b2, 0xf03f7db4,
+                0x5fab8bec, 0x05d58692, 0x27359673, 0x93f6c999, 0x91150acb, 0x1dcbfe12, 0xe5479397,
+                0x5742408b, 0x347feede, 0xabfeb77f, 0x9bbc70f3, 0xb1402430, 0x9f530cc8, 0x919ed69e,
+                0x58b9b8ec, 0xe0cf40db, 0x1b7a33d1, 0x329885e9, 0xca4004b1, 0xfba4bad3, 0x49b3f98d,
+                0x635b9775, 0xfc9cb102, 0x7c1e4317, 0x56302e10, 0x9614ff26, 0x9d8415f4, 0x3b504fbd,
+                0xff98605f, 0x9bf8a5ac, 0x0120f6e2, 0x403cc38f, 0xc07c6dfe, 0x2575f52f, 0x208cdf03,
+                0x0b9fbdc2, 0x0ecf6cbf, 0xf7ff8e22, 0x744c70b2, 0x5e3fa55e, 0xca18d67f, 0x3767f095,
+                0xf0385626, 0x4588cf1f, 0xd09f29da, 0x759c2e84, 0x9b1f345f, 0xeebde0f2, 0x71a418c1,
+                0x2e126fbe, 0x086095b9, 0x433e06a8, 0x4f609a0c, 0x91793cc7, 0x379342c5, 0x822870da,
+                0x2c37ea46, 0x4a0ad2d7, 0x78678a33, 0xd40bc054, 0xdfe5f39f, 0xcf3dae74, 0xa1e11e5c,
+                0x62dfab35, 0xb73cd2ec, 0xf088cc55, 0xd9724862, 0xc7641051, 0xd76a5242, 0x64402261,
+                0xd3d8f601, 0xfee6ee2f, 0x71a7379d, 0x317fde49, 0x4491ec87, 0x3fa6be71, 0xb2cf3888,
+                0xff169e5a, 0x98c7fa85, 0x04691ad6, 0xc16511bc, 0x16f3f46c, 0xe2be1d7a, 0x495c0e3a,
+                0x96c1107c, 0x577cd1ef, 0x0532f524, 0x7efae7c9, 0xf797a245, 0x3d35b911, 0x9550adf0,
+                0xe4f120ec, 0x71c459ee, 0x762f1263, 0xb2ec201a, 0x6c3b7c54, 0x4be5d98a, 0x18b8846c,
+                0xd7ea7157, 0xedf67f03, 0x2326273a, 0x8348b334, 0x043eaadc, 0xcff17512, 0xe2dca94f,
+                0xfc4c3060, 0x55468bd7,
+            ],
+            &[
+                0x4ee1ca03, 0x272b05d3, 0xbfb1e1c7, 0x9a967f82, 0x3b9fc5e4, 0xbb3987b1, 0xba9e9cb5,
+                0xafb07a5e, 0xe3a07fbd, 0x457a9436, 0x4964a841, 0xe7f466e5, 0xa022e21a, 0xb7f673c1,
+                0x8ba98cdb, 0x1d5aecfa, 0xe62268b0, 0x68f1e4bf, 0x9ee9853b, 0xcce08dcd, 0x491c629a,
+                0xa218b60d, 0x3d453e83, 0xa554eb17, 0x6cfef972, 0x9e99ff3a, 0x8127c49e, 0x3c3cf19a,
+                0xd26018ed, 0x796fedce, 0x98c5f867, 0xec2bacbd, 0xb8012cc5, 0x2b76e6d2, 0x4a80fa36,
+                0x92d02a03, 0x634b34b2, 0xfb336232, 0xe4c027dc, 0xa0cc4bd0, 0x3a01f1ce, 0xc8c35ad0,
+                0xe51687fa, 0xd4e18ebc, 0x23a75851, 0xd466979d, 0x59db7391, 0xb61702a7, 0xfc85a116,
+                0x2bdbaaea, 0xb6994991, 0x62f551da, 0x8b0c839f, 0x88ff96b8, 0xdd790156, 0x06526ab7,
+                0x8fd1c101, 0x660de856, 0x53340f3d, 0x1dac2a22, 0xbcf1a2be, 0xf88d742d, 0xe9006c2d,
+                0x5b6d8acd, 0x586b6bee, 0x76f85ccc, 0xbf94e387, 0xc53c23e7, 0x16c670c4, 0xdb23c679,
+                0x01358ae6, 0x4f3f0cce, 0xdfa05b29, 0xe84e1a11, 0xa635bfe7, 0x9e4bd653, 0xc28884ec,
+                0x4034ed55, 0x16947d4d, 0xc5449032, 0xd6091dfd, 0x6e5a573b, 0x323f2624, 0xff12898b,
+                0xb7a12d8c, 0xdd489cd1, 0xf8965b6c, 0xa22de789, 0xbb91292d, 0x030c277f, 0x2716a37b,
+                0x3f46f0f2, 0x7354e3c9, 0x1c456d08, 0x6fe06689, 0x6ad6c6ee, 0x2b716835, 0x7432a603,
+                0xb2142f74, 0xdadd2b5a, 0x50c28ed6, 0x2975d38f, 0x0314cb0f, 0x07d693d3, 0xf30c36bb,
+                0xca65e420, 0x4ed7e1d1, 0x1108c111, 0x67b09f46, 0xbaa4bb69, 0x8009a42b, 0x3b115419,
+                0xa92b501d, 0xd11f6b2f, 0x465c0b31, 0xa481ec02, 0x12b70ba6, 0x4359472a, 0x4b1691d2,
+                0x3add4872, 0xdf0edea6, 0x0e40f2dd, 0x4901d73b, 0xf4accedf, 0xb236c102, 0x04f73a5f,
+                0xb46b3a19, 0xe6a6cb34,
+            ],
+        ],
+    },
+];
+
+/// All HMAC keys, referenced by their index
+pub static KEYS: [HMACKey; DatasetKind::HMACKey.count()] = [
+    HMACKey {
+        name: "hmac_key_counting",
+        key: [
+            0x00010203, 0x04050607, 0x08090a0b, 0x0c0d0e0f, 0x10111213, 0x14151617, 0x18191a1b,
+            0x1c1d1e1f,
+        ],
+        macs: [
+            [
+                0x04d8dac7, 0xc49d0081, 0x7b95dddb, 0xc0d517df, 0xaa0b023e, 0xab8fb467, 0xe33bdad6,
+                0x78a12d77,
+            ],
+            [
+                0x53aee60c, 0x3a2c5586, 0x996ee638, 0xb8b95d7d, 0xce61eb0e, 0xc7bcadd2, 0x3667005f,
+                0x45905742,
+            ],
+            [
+                0x8503e640, 0xe90183eb, 0xc2978936, 0x2f0963a6, 0x05d3cfe7, 0xb19576e9, 0xff71f5dc,
+                0xbe735f14,
+            ],
+            [
+                0x52fa805b, 0x50abf8df, 0xcab98df5, 0x09bb333b, 0xe21ae17d, 0x2014a42e, 0xb8d477c1,
+                0x71e65473,
+            ],
+            [
+                0xc4d78316, 0xaa3de9ce, 0x6bd0b2e6, 0x1c4f4dd6, 0xbdf0ec95, 0xaab84ab8, 0x7fc2a119,
+                0x03991ad3,
+            ],
+        ],
+    },
+    HMACKey {
+        name: "hmac_key_mixed",
+        key: [
+            0xa5a5a5a5, 0x5a5a5a5a, 0xdeadbeef, 0xcafebabe, 0x01234567, 0x89abcdef, 0xfedcba98,
+            0x76543210,
+        ],
+        macs: [
+            [
+                0x881c001b, 0x78f993ec, 0xf1d2dacc, 0x8b39c7a5, 0xd19b5e98, 0xdf91b818, 0x7cfdf31b,
+                0xd17b5256,
+            ],
+            [
+                0xc3b2c6cd, 0xbcb0caf9, 0x02c712bd, 0x7ea2d86b, 0xf5a5607b, 0x7ada590d, 0x89bd7f29,
+                0x08323007,
+            ],
+            [
+                0x36504ca7, 0xc027f528, 0x94440f63, 0xfe97339b, 0xa0c25268, 0xd9b09f08, 0x17459f7f,
+                0xbf12ef4c,
+            ],
+            [
+                0x33d23452, 0x3b086082, 0xf08f00ab, 0x804c889a, 0x07c839be, 0xbebb844d, 0xd6517ac2,
+                0x05f0092f,
+            ],
+            [
+                0xb11a5ca4, 0x01e4a6d2, 0xba9e937b, 0x9004d1a8, 0xc34cdf36, 0x57f81aa5, 0xaf5f2f58,
+                0xfba7009b,
+            ],
+        ],
+    },
+    HMACKey {
+        name: "sp800_185_key",
+        key: [
+            0x40414243, 0x44454647, 0x48494a4b, 0x4c4d4e4f, 0x50515253, 0x54555657, 0x58595a5b,
+            0x5c5d5e5f,
+        ],
+        macs: [
+            [
+                0xa2ba2258, 0x1d7d324d, 0xe3501ff0, 0xfeed4470, 0xa2137747, 0x2af14960, 0x9a8d5176,
+                0x606b0050,
+            ],
+            [
+                0x29fe4e49, 0xec39234e, 0xec28ac8c, 0x61682cb1, 0x89f9d62e, 0xfc146b9d, 0x2472c2ec,
+                0x43b6dd9e,
+            ],
+            [
+                0x2e019dd3, 0xc83e119c, 0x71310ef2, 0xbffc2e9b, 0xc713b642, 0x45877978, 0xb9c7a6e8,
+                0x18ec3e28,
+            ],
+            [
+                0x208d9003, 0x9fdd8417, 0x18e4c4ea, 0x6c07ad72, 0x3b8bd890, 0xb34bbe48, 0x31fb07ed,
+                0xd7084743,
+            ],
+            [
+                0x92de3357, 0x9b093fc3, 0x1d57dbd0, 0x009d4cf8, 0x7e286984, 0x5a53a674, 0x2df9c73d,
+                0x922527c0,
+            ],
+        ],
+    },
+];
+
+/// Expected cSHAKE outputs, the first words of every output are samples 1 to 4 of NIST SP 800-185
+pub static CSHAKE_OUTPUTS: [CSHAKEOutput; 2] = [
+    CSHAKEOutput {
+        dataset: 3,
+        function_name: "",
+        customization: "Email Signature",
+        outputs: [
+            &[
+                0xc1c36925, 0xb6409a04, 0xf1b504fc, 0xbca9d82b, 0x4017277c, 0xb5ed2b20, 0x65fc1d38,
+                0x14d5aaf5, 0x9cbce830, 0x079c452a, 0xbdeb8753, 0x66a49ebf, 0xe75b89ef, 0x17396e34,
+                0x898e9048, 0x30b0e136, 0xf192cc06, 0x2bd2e116, 0xa07fe6eb, 0x9b4fc9ba, 0x254d7dbf,
+                0x6ec9860c, 0x5ba38686, 0xea294dd7, 0x72c1fad2, 0x0e4214aa, 0xd5394a26, 0x7101e4c9,
+                0xd09ce802, 0x81db7e91, 0x70d6052a, 0xbe6e5a93, 0x5713e2c6, 0x2365f59c, 0x9a7df5a9,
+                0x8e4040ff, 0x70e85060, 0x107f596a, 0xcdbf876e, 0x678d73f2, 0xd4494302, 0x226219ac,
+                0xbf98d704, 0x86aff1d5, 0xbbb1d516, 0x2e0209b5, 0xafcc7a07, 0x294a5309, 0x45c3bc0b,
+                0x351a0577, 0xcadae684, 0xf050f7e8, 0xa5853a08, 0x6acbced4, 0xb1c9e98d, 0x96cb1df6,
+                0x6273e43f, 0xefede4ba, 0xf40fb954, 0x182618a4, 0xe909478b, 0x0f8efe5b, 0x1f4c4cc1,
+                0x57142084, 0x764532b3, 0x1ec51cc9, 0x88ab664d, 0x0aa51454, 0xfb9732fc, 0x5ed32a8a,
+                0xaed3f49c, 0xd79db21c, 0xa71a0f5e, 0x766eb884, 0x6ca64705, 0xa4621ac8, 0x3d23d91c,
+                0xeb1958d3, 0xb26a1def, 0x4a18d6a8, 0x3c73c2db, 0x70792810, 0x23743f4d, 0x627d05cd,
+                0x8e83e077, 0x3e747982, 0xf71a3afd, 0xf9e9dde8, 0x759f47b3, 0x861d9e76, 0x53fcb9a2,
+                0x63be8481, 0xa70ec88b, 0x80a7ea08, 0xebe270b1, 0xfc5b1f01, 0x1e489ef0, 0xc581f504,
+                0x9cf1cf1d, 0xe889a104, 0x00182e08, 0x88c639ea, 0x33463d78, 0xd167aa11, 0x6ce84a96,
+                0x609dd731, 0x4f3d4074, 0xd4048105, 0x92989dbe, 0xce3f5e5a, 0x90520546, 0x81db4a8e,
+                0xc35a3228, 0xafd23beb, 0xd917c67a, 0xb608b14a, 0x85896577, 0xb8e6639e, 0x6fa566dc,
+                0xe1d3fea1, 0x943f5f6a, 0xc5da1053, 0x619c76bf, 0x93563e31, 0xb0f1701d, 0x64ab9a4b,
+                0x3db1b21b, 0xaa9c9eec,
+            ],
+            &[
+                0xd008828e, 0x2b80ac9d, 0x2218ffee, 0x1d070c48, 0xb8e4c87b, 0xff32c969, 0x9d5b6896,
+                0xeee0edd1, 0x64020e2b, 0xe0560858, 0xd9c00c03, 0x7e34a969, 0x37c561a7, 0x4c412bb4,
+                0xc7464695, 0x27281c8c, 0x7bdf2d26, 0x919fb465, 0xd4853783, 0x9cb922cb, 0xa3388d3a,
+                0xb346656d, 0xfa598e4e, 0x26f63597, 0x4916a256, 0xc00112c0, 0x0455f3a1, 0xde467682,
+                0xf86ffdc3, 0xa6d096b4, 0x2c5d0e68, 0xff63a449, 0x40692f42, 0xd684f1c1, 0x62a01a88,
+                0x79d8f339, 0x22ba0884, 0x0c7b5130, 0x896f6b37, 0x7718132c, 0xc09f532a, 0x57f647c8,
+                0x27bd4584, 0x2f3a1f6f, 0x807209f0, 0x4fdf4ab8, 0x4a5feb1a, 0xe27b713f, 0x6eeb8844,
+                0x7d1ea671, 0x324eb3eb, 0x442faab9, 0xa548824f, 0xe496f0f1, 0xad92097a, 0x9a8bad81,
+                0x81517a8c, 0xa0f4d25f, 0x0e8fa71c, 0x222d73b7, 0x635f68e8, 0x4a0b00de, 0x08c3ae4c,
+                0xc5c5297f, 0x5aec26e2, 0xfb1b5e46, 0x127f4d28, 0xde5166bc, 0x2b88be94, 0x205b47ef,
+                0x908822ad, 0x278104eb, 0x57d594ee, 0x183beb79, 0x5081decc, 0xdd10aba5, 0x4d248ce1,
+                0xb51e0190, 0x24a981c3, 0x08bf04bb, 0x76a9a523, 0x70e7501f, 0x1c05f69b, 0x8d24354d,
+                0x7e3e6e49, 0x0b9bc8b3, 0x2b786982, 0xa82408ee, 0x7277ff88, 0x824a3aea, 0xeb19ebc0,
+                0xecc33c07, 0x093a8d03, 0xeaf34e85, 0x2c57a61a, 0x53def762, 0x548b480c, 0x2b53e82f,
+                0xd9e73a52, 0x332f26cf, 0x36d0aea0, 0xcf36ceee, 0x6256dac2, 0xfc0f6fb7, 0x199c6fc5,
+                0x3e37a728, 0xa69339dd, 0x2eb8974c, 0x538230b3, 0x3d9e8b6f, 0xb9e142fe, 0xe73ccb0c,
+                0x545022c3, 0x003dcf5d, 0xa6a64de5, 0xaa2c082a, 0xed4876a5, 0xea803cec, 0xf16d39db,
+                0x977cecca, 0xdd0b482c, 0x28ff45a7, 0x74d87f57, 0x6605bcce, 0xee441fab, 0xf1b95861,
+                0x2cfdab66, 0x7b2d3365,
+            ],
+        ],
+    },
+    CSHAKEOutput {
+        dataset: 4,
+        function_name: "",
+        customization: "Email Signature",
+        outputs: [
+            &[
+                0xc5221d50, 0xe4f822d9, 0x6a2e8881, 0xa961420f, 0x294b7b24, 0xfe3d2094, 0xbaed2c65,
+                0x24cc166b, 0x6afa3764, 0x99e3cbcb, 0xb4cf61fe, 0x4d063473, 0xbbe56950, 0x04a7df73,
+                0x241b37e7, 0x156c7d95, 0x2cb5500a, 0x6c23bf76, 0xac009644, 0xecc615b5, 0x7c2e4b26,
+                0xff84949f, 0x80c6ec25, 0x9626aef8, 0xdefafe66, 0x57e2772d, 0x27722e43, 0xe1ad9f67,
+                0xceae6afa, 0x2c0f9277, 0x35ec89af, 0x89a7cbe9, 0xeb05953b, 0xae790804, 0x797cdc0a,
+                0xf1fa0d9d, 0x59157343, 0x6597e6ac, 0xc1d71f3c, 0x445a300c, 0x1a17a6e9, 0x2a25d5b9,
+                0x4cfddf59, 0xf33ea1d6, 0x195dff6f, 0xf1f5e03e, 0x26720f37, 0x014db197, 0x3d5fcc75,
+                0x22ac092b, 0x71d77035, 0xe7cb411a, 0x8f1ce65d, 0x5d24e256, 0x6b135044, 0xa09e1027,
+                0x7b779ad3, 0xc7bf4bba, 0x2a6665f7, 0xccbd451d, 0x517b6930, 0x31176294, 0x1c5c99b8,
+                0x34237c27, 0xdc209916, 0x73f5fe64, 0x6e4c324e, 0xc584ff80, 0xc0781705, 0x014054cc,
+                0x9c3fa98c, 0x3f9e5371, 0x9d0ff802, 0x812e389c, 0x7799637b, 0x27885824, 0xa88cd5d0,
+                0x9d52b730, 0x8a64bed4, 0x869e0f9c, 0x3bffa74a, 0x270afb9e, 0xd50a69b2, 0xc8f85918,
+                0x9e7eb756, 0x02f576f6, 0x8ca3df98, 0x643bed03, 0x8a14e98d, 0xdbc9e618, 0x8b4eaf19,
+                0x552b35f4, 0xac6abd92, 0xb7c17177, 0x0c02b8a5, 0xe83e53e4, 0x3e3a4587, 0xa4450071,
+                0xf38f1712, 0xb16d3980, 0xa3a6b7d6, 0x5722d40b, 0xff36b21a, 0x19fbc8c9, 0x7df503f3,
+                0x73ffc4f6, 0xf7042c35, 0x77cdf039, 0x42b6b39c, 0x2d0d098e, 0xb17287c9, 0x64a451c6,
+                0x23ae28b3, 0xc29e483c, 0xb15f74b2, 0xaf39eb3e, 0x51f911d0, 0x55dfc8db, 0x0bc96e5d,
+                0x5b99feb5, 0xcfd2a83b, 0xb80992bd, 0x70ee95f2, 0x2fa1416f, 0x09899a7b, 0x90d4123b,
+                0x12e5ee7b, 0xfd195784,
+            ],
+            &[
+                0x07dc27b1, 0x1e51fbac, 0x75bc7b3c, 0x1d983e8b, 0x4b85fb1d, 0xefaf2189, 0x12ac8643,
+                0x02730917, 0x27f42b17, 0xed1df63e, 0x8ec118f0, 0x4b23633c, 0x1dfb1574, 0xc8fb55cb,
+                0x45da8e25, 0xafb092bb, 0xda604fe5, 0xc6ac1cdd, 0x0735ef81, 0x67d797e8, 0x8703dc78,
+                0xf33898d4, 0x5353ff59, 0xfced6702, 0xaf2b6409, 0x9f50461c, 0xd5a787ce, 0xdfca60c0,
+                0x3e6cec10, 0x8a0f01f0, 0x2609097c, 0xe6455419, 0x7edbc53c, 0x2f1dcabf, 0x74270679,
+                0x928e948a, 0x279f95c8, 0xad49a8c9, 0x082fca8f, 0xee432d7e, 0x194cc4a5, 0xdd0bfa21,
+                0x54f1c003, 0x90555a9a, 0x62de449a, 0xa4ee999c, 0xf29b707c, 0xcec2d262, 0xad2b6aa4,
+                0xc134d07b, 0x50552e94, 0x7e67abeb, 0x3eed06c9, 0x2cfe9c88, 0xc3f4a21b, 0x1d641729,
+                0xbfac748d, 0xc2aaf0ff, 0xcb17e7c6, 0x706d4b35, 0xd0708487, 0xd46b489d, 0x922ef3dc,
+                0xc67e7f75, 0x343b4b6a, 0xe7f2a151, 0x98155dbc, 0x4b349844, 0x767aefc7, 0xb0395bfb,
+                0x526b1e8d, 0xe79d208c, 0x7f24270f, 0xab74738f, 0x92291273, 0x8381cad6, 0x349dafba,
+                0xd319f0bd, 0xaab1ce0e, 0x4d981e18, 0x11cb5d38, 0x9e938afc, 0xde685c37, 0x26c2c79a,
+                0x75ce69f1, 0x32de5269, 0x373d0986, 0xec7e7ed9, 0x21173bb5, 0x5ab93004, 0x2db46512,
+                0x2fb1096e, 0x3f569264, 0xaa5833e7, 0x21ee6968, 0xb4670c72, 0xca5c83c7, 0x6dbdac43,
+                0x9f12854d, 0xad9b0895, 0xe7301bfa, 0x2d6d0d93, 0xc0e671aa, 0x9b2353ea, 0x22c8c416,
+                0x07a5a6e1, 0x89990579, 0x95c8730b, 0x42452a7b, 0xf640fa20, 0x50de2a4a, 0xd622c199,
+                0x5835345a, 0x69583669, 0xd9176d75, 0x94ed959f, 0x1a3e7eb4, 0x624471f3, 0x519b91b5,
+                0x0dbb4a7f, 0x50ed6ec4, 0x3f076010, 0xc678530d, 0x144edf4b, 0x418bcad4, 0x9c1c1ee0,
+                0xa671737e, 0x1e2a5e9c,
+            ],
+        ],
+    },
+];
+
+/// Expected KMAC256 tags, samples 4 to 6 of NIST SP 800-185
+pub static KMAC_TAGS: [KMACTag; 3] = [
+    KMACTag {
+        dataset: 3,
+        key: 2,
+        customization: "My Tagged Application",
+        tag: &[
+            0x20c570c3, 0x1346f703, 0xc9ac36c6, 0x1c03cb64, 0xc3970d0c, 0xfc787e9b, 0x79599d27,
+            0x3a68d2f7, 0xf69d4cc3, 0xde9d104a, 0x351689f2, 0x7cf6f595, 0x1f0103f3, 0x3f4f2487,
+            0x1024d9c2, 0x7773a8dd,
+        ],
+    },
+    KMACTag {
+        dataset: 4,
+        key: 2,
+        customization: "",
+        tag: &[
+            0x75358cf3, 0x9e41494e, 0x94970792, 0x7cee0af2, 0x0a3ff553, 0x904c86b0, 0x8f21cc41,
+            0x4bcfd691, 0x589d27cf, 0x5e15369c, 0xbbff8b9a, 0x4c2eb178, 0x00855d02, 0x35ff635d,
+            0xa82533ec, 0x6b759b69,
+        ],
+    },
+    KMACTag {
+        dataset: 4,
+        key: 2,
+        customization: "My Tagged Application",
+        tag: &[
+            0xb58618f7, 0x1f92e1d5, 0x6c1b8c55, 0xddd7cd18, 0x8b97b4ca, 0x4d99831e, 0xb2699a83,
+            0x7da2e4d9, 0x70fbacfd, 0xe50033ae, 0xa585f1a2, 0x708510c3, 0x2d078808, 0x01bd1828,
+            0x98fe4768, 0x76fc8965,
+        ],
+    },
+];
@@ -0,0 +1,92 @@
+//! Datasets the benchmarks can be performed on.
+//!
+//! Every kind of benchmark has its own list of datasets, a dataset is referenced by its index
+//! in that list. The CLI can query a description of all datasets using ListDatasets.
+
+pub mod aes;
+pub mod ecdsa;
+pub mod gcm;
+pub mod hashing;
+pub mod otbn;
+pub mod rng;
+
+use alloc::{string::String, vec::Vec};
+use benchmark_common::{DatasetDescriptor, DatasetKind};
+
+/// Returns a description of every dataset available on the suite
+pub fn descriptors() -> Vec<DatasetDescriptor> {
+    let aes = aes::DATASETS
+        .iter()
+        .enumerate()
+        .map(|(id, dataset)| DatasetDescriptor {
+            kind: DatasetKind::AES,
+            id,
+            name: String::from(dataset.name),
+            size: dataset.plaintext.len() * 16,
+            key_length: Some(dataset.key_length.bits()),
+            mode: Some(String::from(dataset.mode.name())),
+        });
+    let gcm = gcm::DATASETS
+        .iter()
+        .enumerate()
+        .map(|(id, dataset)| DatasetDescriptor {
+            kind: DatasetKind::GCM,
+            id,
+            name: String::from(dataset.name),
+            size: dataset.plaintext.len() * 16,
+            key_length: Some(256),
+            mode: Some(String::from("GCM")),
+        });
+    let hashing = hashing::DATASETS
+        .iter()
+        .enumerate()
+        .map(|(id, dataset)| DatasetDescriptor {
+            kind: DatasetKind::Hashing,
+            id,
+            name: String::from(dataset.name),
+            size: dataset.input.len() * 4,
+            key_length: None,
+            mode: None,
+        });
+    let hmac_keys = hashing::KEYS
+        .iter()
+        .enumerate()
+        .map(|(id, key)| DatasetDescriptor {
+            kind: DatasetKind::HMACKey,
+            id,
+            name: String::from(key.name),
+            size: key.key.len() * 4,
+            key_length: Some(256),
+            mode: None,
+        });
+    let rng = rng::DATASETS
+        .iter()
+        .enumerate()
+        .map(|(id, dataset)| DatasetDescriptor {
+            kind: DatasetKind::RNG,
+            id,
+            name: String::from(dataset.name),
+            size: dataset.count * 16,
+            key_length: None,
+            mode: None,
+        });
+
+    let ecdsa = ecdsa::DATASETS
+        .iter()
+        .enumerate()
+        .map(|(id, dataset)| DatasetDescriptor {
+            kind: DatasetKind::ECDSA,
+            id,
+            name: String::from(dataset.name),
+            size: dataset.digest.len() * 4,
+            key_length: Some(256),
+            mode: None,
+        });
+
+    aes.chain(gcm)
+        .chain(hashing)
+        .chain(hmac_keys)
+        .chain(rng)
+        .chain(ecdsa)
+        .collect()
+}
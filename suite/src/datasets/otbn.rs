@@ -0,0 +1,106 @@
+//! Datasets for the OTBN benchmarks.
+//!
+//! All numbers are given as little endian words, with the least significant word first,
+//! as they are stored in the data memory of the OTBN.
+//! The expected outputs were precomputed using the python cryptography module.
+// The datasets are only read on platforms providing the OTBN apps
+#![allow(dead_code)]
+
+/// RSA-2048 key and a message encrypted with it
+pub struct RSADataset {
+    /// The modulus, the public exponent is 65537
+    pub modulus: &'static [u32],
+    pub private_exponent: &'static [u32],
+    pub plaintext: &'static [u32],
+    /// The plaintext raised to the public exponent
+    pub ciphertext: &'static [u32],
+}
+
+/// Scalar and point used for a P-256 scalar multiplication
+pub struct P256Dataset {
+    pub scalar: [u32; 8],
+    /// x coordinate of the point that is multiplied
+    pub x: [u32; 8],
+    /// y coordinate of the point that is multiplied
+    pub y: [u32; 8],
+    /// x coordinate of the product
+    pub product_x: [u32; 8],
+    /// y coordinate of the product
+    pub product_y: [u32; 8],
+}
+
+/// Dataset of the RSA-2048 benchmarks
+pub static RSA_2048: RSADataset = RSADataset {
+    modulus: &[
+        0xd2943615, 0xb7c14481, 0xc9a7bea0, 0xecb73535, 0xbaf9e1f6, 0x09ee07f1, 0x451d6aa0,
+        0x61cb00fc, 0x98d5b35c, 0x6ebcc685, 0x2ddc4df3, 0x5290bf38, 0x91de9c5d, 0x1a308765,
+        0xe84b4e63, 0x4c784ffe, 0xae5984c0, 0xfd597f81, 0xf619ed04, 0xc6f8ee51, 0xa3e0351c,
+        0xa49f6f4a, 0x234d286a, 0x136f99f3, 0xed81a02f, 0x0223151c, 0x55677f63, 0xdcac2e8b,
+        0x39f2b8b2, 0x9686dc09, 0x4dcd1348, 0x691ea7c5, 0x29ef494c, 0xc04c7aa6, 0xf37f9827,
+        0x8d4707ee, 0x80b3c0e3, 0xe15ce0d4, 0x3a81cfd0, 0x02fe549b, 0x9c319d68, 0x61e2c939,
+        0xa6efca04, 0x896ad286, 0xe2274d5f, 0xbb3ec017, 0xd61c1560, 0xbbcadbe2, 0x1f1e7441,
+        0xe110f1d6, 0xb9b48cb7, 0x99f308db, 0x18ee14a4, 0x293d0acc, 0x416b84e7, 0x2db88b57,
+        0x150f212a, 0xe95b1af8, 0x5d5aaf7e, 0x74bbba1a, 0x82829158, 0x5c3ab846, 0xed91d914,
+        0xbc8eeeb5,
+    ],
+    private_exponent: &[
+        0xbabd0ab1, 0xfdf6e08e, 0x1ae14643, 0x36db9ee4, 0x296c23ea, 0xcf0a6cac, 0xf56cb773,
+        0x94ef31d5, 0xa1b2792c, 0xeb697225, 0x61271c9a, 0x09fad056, 0xc0e129df, 0x774c2d1a,
+        0x45ac9315, 0xf9403c18, 0xee8bc471, 0x39327e6e, 0x1ed2721b, 0xe32f5d8f, 0x577ca481,
+        0xa6228ae4, 0x8af5c2b7, 0x14080934, 0xb6b463e5, 0x7e5002ec, 0x20ab7f7d, 0xa5ea9cf2,
+        0x6b96ac62, 0x63a82680, 0x15c3571e, 0xd10b348d, 0xe073cf29, 0x132d3c7a, 0x8d6a565d,
+        0xfef34489, 0xed3b7624, 0x5ca13b13, 0xf196fd86, 0x6f9a107a, 0x0f5b67c7, 0x26fe4099,
+        0x87d55c49, 0xbce70029, 0xfdddb6a7, 0x9a4009f8, 0xe8ce4687, 0x5ae3aa09, 0xfe678e2b,
+        0xf4c4768b, 0xf18c294b, 0xb844a9a3, 0xd603a7aa, 0x92d61af7, 0x6e8b5b8f, 0xc759a5b5,
+        0x42e20319, 0x3bcc094f, 0x8e1febd8, 0x74af421a, 0x7ad0c52a, 0x90b432fb, 0x104e9765,
+        0x2f9c57a2,
+    ],
+    plaintext: &[
+        0x7857dd86, 0x2e84496e, 0xba6f875c, 0x940eee3c, 0x4dc2a627, 0x33406bc4, 0xe325faa6,
+        0xb938451e, 0x68fb90d7, 0xc1d8fac1, 0xb7740a63, 0xc2354e2b, 0x43e58844, 0x887e8400,
+        0x3ec33dd6, 0xa2da95a8, 0xd0055979, 0xbc38d756, 0x7f90ade7, 0x5aab0a37, 0x6a769806,
+        0x86ff0de2, 0xba4e6c36, 0x9d9b532a, 0xf6978770, 0x37d72e4a, 0x4f3d4e7b, 0x8b389064,
+        0xb43d4318, 0x548a84a5, 0x84f42b4b, 0x131db618, 0xbb3a6a06, 0xc63d5f77, 0xffe9ec11,
+        0xddfa7fa4, 0x34d57084, 0xb070e384, 0xc0a9c8be, 0xba49c19f, 0x77f1caf0, 0xecb736d8,
+        0xb5834f4c, 0xd4dd79d3, 0xdf354788, 0xa70b967a, 0x25fbab1b, 0x88177abd, 0x366dadc0,
+        0xde11ee00, 0x6972f683, 0xfaaced22, 0x0ef8e010, 0xc54be01c, 0x597538cb, 0xa0f09780,
+        0x6aa45fe0, 0x7744001a, 0x1fca7da2, 0xb9ae5c8f, 0xbe564059, 0x2393446a, 0xc34797c4,
+        0x0053bdf6,
+    ],
+    ciphertext: &[
+        0x487499f5, 0xb766ed80, 0x6cfe85b3, 0x0957b007, 0x842b5f48, 0x5161b242, 0x164bbca1,
+        0x154c6d14, 0x4eb84492, 0x7012d787, 0x266185e9, 0x6be57dc3, 0x97e013e5, 0xd5e25f8e,
+        0x3c65c7a8, 0x67baa1ad, 0xcdfde6d7, 0x2ad6b6b9, 0x74d24232, 0xd06dd026, 0x08449d1e,
+        0xc938ddf8, 0x76d1f237, 0x9d409906, 0xbd8165cb, 0xeeed078f, 0x6c356881, 0x21168e99,
+        0xc14ac4da, 0x3fb87638, 0xf8b11fb2, 0xf8c7b929, 0x450a2062, 0x135123c3, 0x6b929369,
+        0x94702479, 0xbf9de378, 0x783daad4, 0xb4b084d7, 0xabc4d031, 0xd86adfe8, 0xf44e085b,
+        0xe1c502ec, 0x52942b34, 0x5f797e01, 0x132b5585, 0x6f48550d, 0x5f45ae5e, 0x14673379,
+        0x55e97ad1, 0xb0ee8516, 0x285982db, 0x845d1182, 0x238138df, 0x3a0d66d9, 0x8f1fa59d,
+        0x3a93f7b7, 0xc89a1db0, 0x8d2ad4be, 0x04073c8a, 0xe2f41443, 0xe5045c74, 0x30cd5d08,
+        0x1e0af08b,
+    ],
+};
+
+/// Dataset of the P-256 scalar multiplication benchmark, the point is the base point of the curve
+pub static P256_SCALAR_MULT: P256Dataset = P256Dataset {
+    scalar: [
+        0x63eb18aa, 0x54b1070f, 0x58655852, 0xcb2d34ea, 0x337e6853, 0x5352d63f, 0x6d433715,
+        0x350a8822,
+    ],
+    x: [
+        0xd898c296, 0xf4a13945, 0x2deb33a0, 0x77037d81, 0x63a440f2, 0xf8bce6e5, 0xe12c4247,
+        0x6b17d1f2,
+    ],
+    y: [
+        0x37bf51f5, 0xcbb64068, 0x6b315ece, 0x2bce3357, 0x7c0f9e16, 0x8ee7eb4a, 0xfe1a7f9b,
+        0x4fe342e2,
+    ],
+    product_x: [
+        0xf1b19130, 0x6c415b4a, 0x0dc2d234, 0xc5475fdb, 0x57c234d8, 0x8e3cc28f, 0x17c23752,
+        0x75a58606,
+    ],
+    product_y: [
+        0x200efa3f, 0x06f9af9d, 0xb6c55dc2, 0x49f471a2, 0xf7b2b9bd, 0x08ad12d9, 0xda2ed506,
+        0x31404bb6,
+    ],
+};
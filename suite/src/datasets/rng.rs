@@ -0,0 +1,32 @@
+//! Datasets for the random number generation benchmarks.
+
+use benchmark_common::DatasetKind;
+
+/// Seed and amount of random numbers used to perform a random number generation benchmark
+pub struct RNGDataset {
+    /// Name describing the dataset
+    pub name: &'static str,
+    /// Seed the module is instantiated with, or None to use the entropy source
+    pub seed: Option<&'static [u32]>,
+    /// Number of 128 bit random numbers that are generated
+    pub count: usize,
+}
+
+/// All random number generation datasets, referenced by their index
+pub static DATASETS: [RNGDataset; DatasetKind::RNG.count()] = [
+    RNGDataset {
+        name: "seeded_32_numbers",
+        seed: Some(&[0; 12]),
+        count: 32,
+    },
+    RNGDataset {
+        name: "seeded_128_numbers",
+        seed: Some(&[0; 12]),
+        count: 128,
+    },
+    RNGDataset {
+        name: "unseeded_32_numbers",
+        seed: None,
+        count: 32,
+    },
+];
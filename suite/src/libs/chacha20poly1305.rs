@@ -0,0 +1,217 @@
+//! Software implementation of ChaCha20 and Poly1305 as described by RFC 8439
+//!
+//! It is used as a baseline for the AEAD benchmarks and therefore works on the same
+//! representation as the AES module: one u128 is interpreted as 4 consecutive little endian u32s.
+
+/// Constants of the first row of the ChaCha20 state, "expand 32-byte k"
+const CHACHA20_CONSTANTS: [u32; 4] = [0x6170_7865, 0x3320_646e, 0x7962_2d32, 0x6b20_6574];
+
+/// Mask of a 26 bit limb of the Poly1305 accumulator
+const POLY1305_LIMB_MASK: u32 = 0x3ff_ffff;
+
+/// Performs a ChaCha20 quarter round on the given words of the state
+#[inline]
+fn quarter_round(state: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+    state[a] = state[a].wrapping_add(state[b]);
+    state[d] = (state[d] ^ state[a]).rotate_left(16);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_left(12);
+    state[a] = state[a].wrapping_add(state[b]);
+    state[d] = (state[d] ^ state[a]).rotate_left(8);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_left(7);
+}
+
+/// Computes a block of the ChaCha20 keystream
+///
+/// # Arguments
+///
+/// * `key` - the key as 8 little endian u32s
+/// * `counter` - the block counter
+/// * `nonce` - the nonce as 3 little endian u32s
+pub fn chacha20_block(key: &[u32; 8], counter: u32, nonce: &[u32; 3]) -> [u32; 16] {
+    let mut initial = [0u32; 16];
+    initial[..4].copy_from_slice(&CHACHA20_CONSTANTS);
+    initial[4..12].copy_from_slice(key);
+    initial[12] = counter;
+    initial[13..].copy_from_slice(nonce);
+
+    let mut state = initial;
+    for _ in 0..10 {
+        quarter_round(&mut state, 0, 4, 8, 12);
+        quarter_round(&mut state, 1, 5, 9, 13);
+        quarter_round(&mut state, 2, 6, 10, 14);
+        quarter_round(&mut state, 3, 7, 11, 15);
+        quarter_round(&mut state, 0, 5, 10, 15);
+        quarter_round(&mut state, 1, 6, 11, 12);
+        quarter_round(&mut state, 2, 7, 8, 13);
+        quarter_round(&mut state, 3, 4, 9, 14);
+    }
+
+    for (word, initial) in state.iter_mut().zip(initial) {
+        *word = word.wrapping_add(initial);
+    }
+    state
+}
+
+/// Encrypts/Decrypts the input using ChaCha20, starting with the given block counter
+///
+/// # Panics
+///
+/// If the input and output slice do not have the same size.
+///
+/// # Arguments
+///
+/// * `key` - the key as 8 little endian u32s
+/// * `counter` - the block counter of the first block
+/// * `nonce` - the nonce as 3 little endian u32s
+/// * `input` - the data that should be encrypted
+/// * `output` - a buffer of the same size as input, used for storing the encrypted value
+pub fn chacha20_xor(
+    key: &[u32; 8],
+    counter: u32,
+    nonce: &[u32; 3],
+    input: &[u128],
+    output: &mut [u128],
+) {
+    assert_eq!(input.len(), output.len());
+
+    // One block of the keystream covers 4 u128s
+    for (index, (input, output)) in input.chunks(4).zip(output.chunks_mut(4)).enumerate() {
+        let keystream = chacha20_block(key, counter.wrapping_add(index as u32), nonce);
+        for (i, (input, output)) in input.iter().zip(output.iter_mut()).enumerate() {
+            *output = input ^ words_to_u128(&keystream[i * 4..i * 4 + 4]);
+        }
+    }
+}
+
+/// Poly1305 authenticator using 26 bit limbs, so it only requires 32x32 bit multiplications
+pub struct Poly1305 {
+    r: [u32; 5],
+    s: u128,
+    accumulator: [u32; 5],
+}
+
+impl Poly1305 {
+    /// Creates a new authenticator for the one-time key
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - the one-time key as 8 little endian u32s, r followed by s
+    pub fn new(key: &[u32; 8]) -> Poly1305 {
+        Poly1305 {
+            r: [
+                key[0] & 0x3ff_ffff,
+                ((key[0] >> 26) | (key[1] << 6)) & 0x3ff_ff03,
+                ((key[1] >> 20) | (key[2] << 12)) & 0x3ff_c0ff,
+                ((key[2] >> 14) | (key[3] << 18)) & 0x3f0_3fff,
+                (key[3] >> 8) & 0x00f_ffff,
+            ],
+            s: words_to_u128(&key[4..]),
+            accumulator: [0; 5],
+        }
+    }
+
+    /// Creates the authenticator used by ChaCha20-Poly1305,
+    /// its key is taken from the ChaCha20 block with counter 0
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - the ChaCha20 key as 8 little endian u32s
+    /// * `nonce` - the nonce as 3 little endian u32s
+    pub fn for_chacha20(key: &[u32; 8], nonce: &[u32; 3]) -> Poly1305 {
+        let block = chacha20_block(key, 0, nonce);
+        let mut one_time_key = [0u32; 8];
+        one_time_key.copy_from_slice(&block[..8]);
+
+        Poly1305::new(&one_time_key)
+    }
+
+    /// Adds full 16 byte blocks to the authenticated message
+    ///
+    /// # Arguments
+    ///
+    /// * `blocks` - the blocks of the message
+    pub fn update(&mut self, blocks: &[u128]) {
+        let [r0, r1, r2, r3, r4] = self.r.map(|limb| limb as u64);
+        let [s1, s2, s3, s4] = [r1 * 5, r2 * 5, r3 * 5, r4 * 5];
+
+        for block in blocks {
+            let t = [
+                *block as u32,
+                (block >> 32) as u32,
+                (block >> 64) as u32,
+                (block >> 96) as u32,
+            ];
+            let h = &mut self.accumulator;
+            let h0 = (h[0] + (t[0] & POLY1305_LIMB_MASK)) as u64;
+            let h1 = (h[1] + (((t[0] >> 26) | (t[1] << 6)) & POLY1305_LIMB_MASK)) as u64;
+            let h2 = (h[2] + (((t[1] >> 20) | (t[2] << 12)) & POLY1305_LIMB_MASK)) as u64;
+            let h3 = (h[3] + (((t[2] >> 14) | (t[3] << 18)) & POLY1305_LIMB_MASK)) as u64;
+            let h4 = (h[4] + ((t[3] >> 8) | (1 << 24))) as u64;
+
+            let d0 = h0 * r0 + h1 * s4 + h2 * s3 + h3 * s2 + h4 * s1;
+            let mut d1 = h0 * r1 + h1 * r0 + h2 * s4 + h3 * s3 + h4 * s2;
+            let mut d2 = h0 * r2 + h1 * r1 + h2 * r0 + h3 * s4 + h4 * s3;
+            let mut d3 = h0 * r3 + h1 * r2 + h2 * r1 + h3 * r0 + h4 * s4;
+            let mut d4 = h0 * r4 + h1 * r3 + h2 * r2 + h3 * r1 + h4 * r0;
+
+            d1 += d0 >> 26;
+            d2 += d1 >> 26;
+            d3 += d2 >> 26;
+            d4 += d3 >> 26;
+            let carry = (d4 >> 26) as u32;
+            h[0] = (d0 as u32 & POLY1305_LIMB_MASK) + carry * 5;
+            h[1] = (d1 as u32 & POLY1305_LIMB_MASK) + (h[0] >> 26);
+            h[0] &= POLY1305_LIMB_MASK;
+            h[2] = d2 as u32 & POLY1305_LIMB_MASK;
+            h[3] = d3 as u32 & POLY1305_LIMB_MASK;
+            h[4] = d4 as u32 & POLY1305_LIMB_MASK;
+        }
+    }
+
+    /// Returns the tag of the authenticated message
+    pub fn finalize(self) -> u128 {
+        let mut h = self.accumulator;
+
+        // Fully carry the accumulator
+        let mut carry = h[1] >> 26;
+        h[1] &= POLY1305_LIMB_MASK;
+        for limb in h[2..].iter_mut() {
+            *limb += carry;
+            carry = *limb >> 26;
+            *limb &= POLY1305_LIMB_MASK;
+        }
+        h[0] += carry * 5;
+        carry = h[0] >> 26;
+        h[0] &= POLY1305_LIMB_MASK;
+        h[1] += carry;
+
+        // Compute h - p and use it if it is not negative
+        let mut g = [0u32; 5];
+        carry = 5;
+        for (g, h) in g.iter_mut().zip(h) {
+            *g = h + carry;
+            carry = *g >> 26;
+            *g &= POLY1305_LIMB_MASK;
+        }
+        // A carry out of the last limb means that h + 5 is at least 2^130, so h is at least p
+        if carry == 1 {
+            h = g;
+        }
+
+        let value = h.iter().enumerate().fold(0u128, |value, (i, limb)| {
+            value | (*limb as u128) << (i * 26)
+        });
+        value.wrapping_add(self.s)
+    }
+}
+
+/// Combines 4 little endian u32s to a u128
+#[inline]
+fn words_to_u128(words: &[u32]) -> u128 {
+    words
+        .iter()
+        .rev()
+        .fold(0u128, |value, word| (value << 32) | *word as u128)
+}
@@ -1,3 +1,4 @@
+pub mod chacha20poly1305;
 #[cfg(feature = "platform_verilator_earlgrey")]
 pub mod ecdsa;
 #[cfg(feature = "platform_verilator_earlgrey")]
@@ -1,6 +1,11 @@
 //! FFI Code for the opentitan big number library
+//!
+//! Besides the library itself, this declares the symbols of the OTBN apps embedded by the
+//! opentitan build, so they can be loaded using the OTBN module.
 #![allow(dead_code)]
 
+use crate::modules::OTBNApp;
+
 #[repr(C)]
 pub enum otbn_error_t {
     /// No errors.
@@ -20,3 +25,128 @@ extern "C" {}
 
 #[link(name = "sw_lib_crypto_otbn_util")]
 extern "C" {}
+
+#[link(name = "rsa")]
+extern "C" {
+    static _otbn_local_app_rsa__imem_start: u32;
+    static _otbn_local_app_rsa__imem_end: u32;
+    static _otbn_local_app_rsa__dmem_data_start: u32;
+    static _otbn_local_app_rsa__dmem_data_end: u32;
+    static _otbn_remote_app_rsa__dmem_data_start: u32;
+    static _otbn_remote_app_rsa_mode: u32;
+    static _otbn_remote_app_rsa_n_limbs: u32;
+    static _otbn_remote_app_rsa_inout: u32;
+    static _otbn_remote_app_rsa_modulus: u32;
+    static _otbn_remote_app_rsa_exp: u32;
+}
+
+#[link(name = "p256_scalar_mult")]
+extern "C" {
+    static _otbn_local_app_p256_scalar_mult__imem_start: u32;
+    static _otbn_local_app_p256_scalar_mult__imem_end: u32;
+    static _otbn_local_app_p256_scalar_mult__dmem_data_start: u32;
+    static _otbn_local_app_p256_scalar_mult__dmem_data_end: u32;
+    static _otbn_remote_app_p256_scalar_mult__dmem_data_start: u32;
+    static _otbn_remote_app_p256_scalar_mult_k0: u32;
+    static _otbn_remote_app_p256_scalar_mult_k1: u32;
+    static _otbn_remote_app_p256_scalar_mult_x: u32;
+    static _otbn_remote_app_p256_scalar_mult_y: u32;
+}
+
+/// Mode of the RSA app that raises the input to the public exponent 65537
+pub const RSA_MODE_ENCRYPT: u32 = 1;
+/// Mode of the RSA app that raises the input to the exponent stored in its data memory
+pub const RSA_MODE_DECRYPT: u32 = 2;
+
+/// Offsets of the variables of the RSA app in the data memory
+pub struct RSASymbols {
+    /// Selects the operation, see `RSA_MODE_ENCRYPT` and `RSA_MODE_DECRYPT`
+    pub mode: usize,
+    /// Number of 256 bit limbs of the modulus
+    pub n_limbs: usize,
+    /// The input, overwritten by the output
+    pub inout: usize,
+    pub modulus: usize,
+    /// The private exponent, only used when decrypting
+    pub exp: usize,
+}
+
+/// Offsets of the variables of the P-256 scalar multiplication app in the data memory
+pub struct P256ScalarMultSymbols {
+    /// First share of the scalar
+    pub k0: usize,
+    /// Second share of the scalar, the scalar is the sum of both shares
+    pub k1: usize,
+    /// x coordinate of the point, overwritten by the x coordinate of the product
+    pub x: usize,
+    /// y coordinate of the point, overwritten by the y coordinate of the product
+    pub y: usize,
+}
+
+/// Returns the words between two symbols of an embedded app
+///
+/// # Safety:
+///  - both symbols have to mark the start and end of the same section
+unsafe fn section(start: &'static u32, end: &'static u32) -> &'static [u32] {
+    let start = start as *const u32;
+    let end = end as *const u32;
+
+    core::slice::from_raw_parts(start, end.offset_from(start) as usize)
+}
+
+/// Returns the address of a symbol in the data memory of the OTBN
+#[inline]
+fn dmem_address(symbol: &'static u32) -> usize {
+    symbol as *const u32 as usize
+}
+
+/// Returns the RSA app of the opentitan library and the offsets of its variables
+pub fn rsa_app() -> (OTBNApp, RSASymbols) {
+    unsafe {
+        (
+            OTBNApp {
+                imem: section(
+                    &_otbn_local_app_rsa__imem_start,
+                    &_otbn_local_app_rsa__imem_end,
+                ),
+                dmem_data: section(
+                    &_otbn_local_app_rsa__dmem_data_start,
+                    &_otbn_local_app_rsa__dmem_data_end,
+                ),
+                dmem_data_offset: dmem_address(&_otbn_remote_app_rsa__dmem_data_start),
+            },
+            RSASymbols {
+                mode: dmem_address(&_otbn_remote_app_rsa_mode),
+                n_limbs: dmem_address(&_otbn_remote_app_rsa_n_limbs),
+                inout: dmem_address(&_otbn_remote_app_rsa_inout),
+                modulus: dmem_address(&_otbn_remote_app_rsa_modulus),
+                exp: dmem_address(&_otbn_remote_app_rsa_exp),
+            },
+        )
+    }
+}
+
+/// Returns the P-256 scalar multiplication app of the opentitan library and the offsets of its variables
+pub fn p256_scalar_mult_app() -> (OTBNApp, P256ScalarMultSymbols) {
+    unsafe {
+        (
+            OTBNApp {
+                imem: section(
+                    &_otbn_local_app_p256_scalar_mult__imem_start,
+                    &_otbn_local_app_p256_scalar_mult__imem_end,
+                ),
+                dmem_data: section(
+                    &_otbn_local_app_p256_scalar_mult__dmem_data_start,
+                    &_otbn_local_app_p256_scalar_mult__dmem_data_end,
+                ),
+                dmem_data_offset: dmem_address(&_otbn_remote_app_p256_scalar_mult__dmem_data_start),
+            },
+            P256ScalarMultSymbols {
+                k0: dmem_address(&_otbn_remote_app_p256_scalar_mult_k0),
+                k1: dmem_address(&_otbn_remote_app_p256_scalar_mult_k1),
+                x: dmem_address(&_otbn_remote_app_p256_scalar_mult_x),
+                y: dmem_address(&_otbn_remote_app_p256_scalar_mult_y),
+            },
+        )
+    }
+}
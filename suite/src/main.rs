@@ -9,20 +9,23 @@
 mod runtime;
 mod benchmark;
 mod cmd;
+mod datasets;
 mod libs;
 mod modules;
 mod platform;
 
-use benchmark_common::{OutgoingMessage, SuiteStatus};
+use benchmark_common::{OutgoingMessage, SuiteStatus, SuspendCode};
 use platform::Platform;
 use riscv_rt::entry;
 
 extern crate alloc;
 
 fn main() {
+    runtime::send_resync("");
     runtime::send_message(&OutgoingMessage::Status(SuiteStatus::Ready));
 
     loop {
+        cmd::isolate();
         if let Some(reply) = cmd::run_cmd(runtime::read_message()) {
             runtime::send_message(&reply);
         }
@@ -45,5 +48,5 @@ fn entry() -> ! {
     #[cfg(not(test))]
     main();
 
-    platform::current().suspend(0);
+    platform::current().suspend(SuspendCode::Success);
 }
@@ -6,11 +6,87 @@
 //! This file contains traits for all supported modules.
 //! This folder includes module implementations that can be used and potentially reused by platforms.
 use core::{
+    arch::asm,
     ops::{Deref, DerefMut},
     ptr::NonNull,
 };
 
-use alloc::{string::String, vec::Vec};
+use alloc::vec::Vec;
+use benchmark_common::WaitStrategy;
+
+use crate::runtime::Global;
+
+/// Number of iterations modules spent in busy waits on a status register
+static POLL_COUNT: Global<u64> = Global::new(0);
+
+/// Strategy used by `wait_until` to spend the time between two polls
+static WAIT_STRATEGY: Global<WaitStrategy> = Global::new(WaitStrategy::Spin);
+
+/// Number of nops executed after the first poll when using `WaitStrategy::Backoff`
+const BACKOFF_MIN_NOPS: u32 = 1;
+/// Maximum number of nops executed between two polls when using `WaitStrategy::Backoff`
+const BACKOFF_MAX_NOPS: u32 = 64;
+
+/// Sets the strategy used by all modules to wait for the hardware
+///
+/// # Arguments
+///
+/// * `strategy` - the strategy to use, unknown strategies are handled like `WaitStrategy::Spin`
+pub fn set_wait_strategy(strategy: WaitStrategy) {
+    WAIT_STRATEGY.set(strategy);
+}
+
+/// Returns the strategy currently used by all modules to wait for the hardware
+#[inline]
+pub fn wait_strategy() -> WaitStrategy {
+    WAIT_STRATEGY.get()
+}
+
+/// Polls a status register until `ready` returns true,
+/// modules call this whenever they wait for the hardware.
+///
+/// Every poll that did not succeed is counted, the time between two polls is spent
+/// according to the strategy set by `set_wait_strategy`.
+///
+/// # Arguments
+///
+/// * `ready` - reads the status register and returns true once the hardware is ready
+#[inline]
+#[allow(dead_code)]
+pub fn wait_until(mut ready: impl FnMut() -> bool) {
+    let strategy = WAIT_STRATEGY.get();
+    let mut backoff = BACKOFF_MIN_NOPS;
+
+    while !ready() {
+        POLL_COUNT.with(|count| *count += 1);
+
+        match strategy {
+            WaitStrategy::Spin | WaitStrategy::Unknown => core::hint::spin_loop(),
+            WaitStrategy::Backoff => {
+                nops(backoff);
+                backoff = (backoff * 2).min(BACKOFF_MAX_NOPS);
+            }
+            WaitStrategy::Nops(count) => nops(count),
+            // Safety: only selected on platforms that wake up the core once the hardware is ready
+            WaitStrategy::Interrupt => unsafe { riscv::asm::wfi() },
+        }
+    }
+}
+
+/// Executes the given number of nops, which can not be optimized away
+#[inline]
+fn nops(count: u32) {
+    for _ in 0..count {
+        // Safety: a nop has no effect
+        unsafe { asm!("nop") }
+    }
+}
+
+/// Returns the number of iterations all modules spent in busy waits on a status register
+#[inline]
+pub fn poll_count() -> u64 {
+    POLL_COUNT.get()
+}
 
 /// Generic module trait, implemented by all modules.
 pub trait Module {
@@ -24,6 +100,22 @@ pub trait Module {
 
     /// True if the Module has been initialized.
     fn initialized(&self) -> bool;
+
+    /// Returns the module to the state it had directly after initialization,
+    /// discarding any configuration performed since.
+    /// Modules that do not keep any state between uses do not need to implement this.
+    ///
+    /// # Safety:
+    /// - only call on initialized modules
+    unsafe fn reset(&mut self) -> Result<(), &'static str> {
+        Ok(())
+    }
+
+    /// True if the Module raised a hardware alert since its last reset, e.g. because it detected a fault.
+    /// Modules without alerts do not need to implement this.
+    fn alerted(&self) -> bool {
+        false
+    }
 }
 
 // Module for reading bytes
@@ -39,28 +131,51 @@ pub trait ByteRead {
         }
     }
 
-    fn read_line(&self) -> String {
-        let mut line = String::new();
+    /// Reads bytes until the delimiter is received, returns the bytes without the delimiter
+    ///
+    /// # Arguments
+    ///
+    /// * `delimiter` - the byte terminating the frame
+    fn read_frame(&self, delimiter: u8) -> Vec<u8> {
+        let mut frame = Vec::new();
         loop {
-            let c = self.read_byte_blocking() as char;
-            if c == '\n' || c == '\r' {
-                return line;
+            let byte = self.read_byte_blocking();
+            if byte == delimiter {
+                return frame;
             }
-            line.push(c);
+            frame.push(byte);
         }
     }
 }
 
+// Module for writing bytes
+pub trait ByteWrite {
+    /// Writes all bytes, fails if the module is not ready to send data
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - the bytes that should be written
+    fn write_bytes(&mut self, data: &[u8]) -> core::fmt::Result;
+}
+
 /// Module for communicating with the Benchmarking-CLI
-pub trait CommunicationModule: core::fmt::Write + Module + ByteRead {}
+pub trait CommunicationModule: core::fmt::Write + Module + ByteRead + ByteWrite {}
 
-impl<T> CommunicationModule for T where T: core::fmt::Write + Module + ByteRead {}
+impl<T> CommunicationModule for T where T: core::fmt::Write + Module + ByteRead + ByteWrite {}
 
 /// Module for performing SHA265 hash computation
 pub trait SHA256Module: Module {
     /// Setup the Module for SHA256 computation
     fn init_sha256(&self);
 
+    /// Setup the Module for HMAC-SHA256 computation using the provided key,
+    /// the input and output are handled like for SHA256 computation
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - the 256 bit key, most significant word first
+    fn init_hmac(&self, key: &[u32; 8]);
+
     /// Input data into the module, over which the sha hash should be computed
     /// This function accepts &[u32] for performance reasons.
     /// If the data is present as &[u8] try transmuting it to &[u32].
@@ -70,6 +185,23 @@ pub trait SHA256Module: Module {
     /// * `data` - the data to compute the hash of
     fn write_input(&self, data: &[u32]);
 
+    /// Starts a new message, whose words are then written using `write_word`
+    fn start_message(&self);
+
+    /// Writes the next word of the message into the message FIFO, without checking whether
+    /// the FIFO is full
+    ///
+    /// # Arguments
+    ///
+    /// * `word` - the next word of the message
+    fn write_word(&self, word: u32);
+
+    /// Returns the number of words in the message FIFO that were not absorbed yet
+    fn get_fifo_elements(&self) -> u32;
+
+    /// Returns the number of words the message FIFO can hold
+    fn fifo_capacity(&self) -> u32;
+
     /// Blocks until the SHA256 module completed computation
     fn wait_for_completion(&self);
 
@@ -83,14 +215,27 @@ pub trait SHA256Module: Module {
 
 /// Configuration of the key length used by the aes module
 #[allow(dead_code)]
+#[derive(Clone, Copy)]
 pub enum AESKeyLength {
     Aes128,
     Aes192,
     Aes256,
 }
 
+impl AESKeyLength {
+    /// Returns the length of the key in bits
+    pub fn bits(&self) -> u32 {
+        match self {
+            AESKeyLength::Aes128 => 128,
+            AESKeyLength::Aes192 => 192,
+            AESKeyLength::Aes256 => 256,
+        }
+    }
+}
+
 /// Configuration of the aes mode used by the aes module
 #[allow(dead_code)]
+#[derive(Clone, Copy)]
 pub enum AESMode {
     ECB,
     /// The iv corresponds to 4 consecutive little endian u32s
@@ -105,6 +250,19 @@ pub enum AESMode {
     },
 }
 
+impl AESMode {
+    /// Returns the abbreviation of the mode, ex. `CTR`
+    pub fn name(&self) -> &'static str {
+        match self {
+            AESMode::ECB => "ECB",
+            AESMode::CBC { .. } => "CBC",
+            AESMode::CFB => "CFB",
+            AESMode::OFB => "OFB",
+            AESMode::CTR { .. } => "CTR",
+        }
+    }
+}
+
 /// Configuration of the operation performed by the aes module
 #[allow(dead_code)]
 pub enum AESOperation {
@@ -164,8 +322,38 @@ pub trait AESModule: Module {
     /// * `input` - the data that should be encrypted, will be overwritten with the encrypted message
     fn execute_inplace(&self, data: &mut [u128]);
 
+    /// Encrypts/Decrypts the input data like `execute`,
+    /// while accumulating the cycles spent in the different parts of the loop.
+    ///
+    /// # Panics
+    ///
+    /// If the input and output slice do not have the same size.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - the data that should be encrypted
+    /// * `output` - a buffer of the same size as input, used for storing the encrypted value
+    fn execute_profiled(&self, input: &[u128], output: &mut [u128]) -> AESLoopProfile;
+
     /// Blocks until the SHA256 module completed computation
     fn deinitialize(&self);
+
+    /// Overwrites the key, iv and input data registers with random data
+    /// and blocks until the module is idle again
+    fn clear_input(&self);
+
+    /// Overwrites the output data registers with random data and blocks until the module is idle again
+    fn clear_output(&self);
+}
+
+/// Cycles spent in the parts of the loop of `AESModule::execute_profiled`
+pub struct AESLoopProfile {
+    /// Cycles spent writing blocks into the input registers
+    pub input_writes: u64,
+    /// Cycles spent polling the status register
+    pub status_polls: u64,
+    /// Cycles spent reading blocks from the output registers
+    pub output_reads: u64,
 }
 
 /// Module for random number generation
@@ -173,8 +361,241 @@ pub trait RNGModule: Module {
     /// Initialize the module, optionally provide a seed
     fn init_rng(&self, seed: Option<Vec<u32>>);
 
+    /// Reseed the instantiated module and wait until the reseed is completed
+    ///
+    /// # Arguments
+    ///
+    /// * `additional_data` - seed material that is used instead of the entropy source,
+    ///   at most 12 words are used
+    fn reseed(&self, additional_data: Option<&[u32]>);
+
     /// Generate a random number
     fn generate(&self) -> u128;
+
+    /// Generate multiple random numbers using a single generate command, so the cost of the
+    /// command is shared by all of them
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - the buffer that is filled with random numbers, at most 4095 numbers
+    fn generate_blocks(&self, output: &mut [u128]);
+
+    /// Uninstantiate the module, wiping its internal state, and wait until this is completed.
+    /// Does nothing if the module is not instantiated
+    fn uninstantiate(&self);
+}
+
+/// Module collecting entropy from the noise source of the chip, which seeds the RNG module
+pub trait EntropySourceModule: Module {
+    /// Enables the module, the seeds are passed to the RNG module once the startup health tests
+    /// passed
+    fn enable(&self);
+
+    /// Disables the module, the RNG module can not be seeded from it until it is enabled again
+    fn disable(&self);
+}
+
+/// Module distributing random numbers of its own instance of the RNG module to other modules,
+/// like the masking of the kmac module
+pub trait EDNModule: Module {
+    /// Enables the module and blocks until its instance of the RNG module was instantiated from
+    /// the entropy source, afterwards it generates and reseeds automatically
+    fn enable(&self);
+
+    /// Uninstantiates the instance of the RNG module and disables the module
+    fn disable(&self);
+}
+
+/// Security strength of the extendable output functions computed by the kmac module
+#[derive(Clone, Copy)]
+pub enum XOFStrength {
+    L128,
+    L256,
+}
+
+impl XOFStrength {
+    /// Returns the number of words that can be read per squeeze
+    pub fn rate_words(&self) -> usize {
+        match self {
+            XOFStrength::L128 => 168 / 4,
+            XOFStrength::L256 => 136 / 4,
+        }
+    }
+}
+
+/// Length of the digests of the SHA3 hash functions computed by the kmac module
+#[derive(Clone, Copy)]
+pub enum SHA3Length {
+    L224,
+    L256,
+    L384,
+    L512,
+}
+
+impl SHA3Length {
+    /// Returns the length of a digest of the given bits, None if no SHA3 function produces it
+    ///
+    /// # Arguments
+    ///
+    /// * `bits` - the length of the digest in bits
+    pub fn from_bits(bits: u32) -> Option<SHA3Length> {
+        match bits {
+            224 => Some(SHA3Length::L224),
+            256 => Some(SHA3Length::L256),
+            384 => Some(SHA3Length::L384),
+            512 => Some(SHA3Length::L512),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of words of the digest
+    pub fn digest_words(&self) -> usize {
+        match self {
+            SHA3Length::L224 => 7,
+            SHA3Length::L256 => 8,
+            SHA3Length::L384 => 12,
+            SHA3Length::L512 => 16,
+        }
+    }
+}
+
+/// Source of the entropy the kmac module uses to mask its computation
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum KMACEntropy {
+    /// The computation is not masked, the default
+    Unmasked,
+    /// Masked using entropy expanded from the seed written by software
+    Software,
+    /// Masked using entropy fetched from the EDN
+    EDN,
+}
+
+/// Module for computing SHA3 and the SHAKE, cSHAKE and KMAC extendable output functions
+pub trait KMACModule: Module {
+    /// Selects how the computations set up afterwards are masked, resetting the module restores
+    /// the default
+    ///
+    /// # Arguments
+    ///
+    /// * `entropy` - the source of the entropy used for masking
+    fn set_entropy(&mut self, entropy: KMACEntropy);
+
+    /// Writes the seed the entropy of `KMACEntropy::Software` is expanded from
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - the seed, None restores the seed built into the driver
+    fn set_entropy_seed(&self, seed: Option<[u32; 5]>);
+
+    /// Setup the module for SHAKE or cSHAKE computation.
+    ///
+    /// # Arguments
+    ///
+    /// * `strength` - specifies whether to use SHAKE128/256 or cSHAKE128/256
+    /// * `customization` - function name and customization string if cSHAKE should be used,
+    ///   at most 40 bytes in total
+    fn init_xof(&self, strength: XOFStrength, customization: Option<(&[u8], &[u8])>);
+
+    /// Setup the module for SHA3 computation. The digest is read using `read_output` after
+    /// `process`.
+    ///
+    /// # Arguments
+    ///
+    /// * `length` - specifies whether to use SHA3-224, SHA3-256, SHA3-384 or SHA3-512
+    fn init_sha3(&self, length: SHA3Length);
+
+    /// Setup the module for KMAC computation using the provided key. The input is written like
+    /// for SHAKE, followed by the length of the output using `write_output_length`.
+    ///
+    /// # Arguments
+    ///
+    /// * `strength` - specifies whether to use KMAC128 or KMAC256
+    /// * `key` - the 256 bit key, most significant word first
+    /// * `customization` - the customization string, at most 36 bytes
+    fn init_kmac(&self, strength: XOFStrength, key: &[u32; 8], customization: &[u8]);
+
+    /// Input the length of the output of KMAC, which has to follow the data
+    ///
+    /// # Arguments
+    ///
+    /// * `bits` - the length of the output in bits
+    fn write_output_length(&self, bits: usize);
+
+    /// Input data into the module, the words are interpreted in big endian
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - the data to compute the output of
+    fn write_input(&self, data: &[u32]);
+
+    /// Blocks until all input was absorbed and the first output block can be read
+    fn process(&self);
+
+    /// Reads words of the current output block, at most `XOFStrength::rate_words` words
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - the buffer into which the output should be read
+    fn read_output(&self, buffer: &mut [u32]);
+
+    /// Blocks until the next output block was squeezed and can be read
+    fn squeeze(&self);
+
+    /// Finishes the computation, so the module can be setup again
+    fn finish(&self);
+
+    /// Blocks until the module wiped its state after `finish` and is idle
+    fn wait_for_idle(&self);
+}
+
+/// A program of the OTBN, consisting of its instructions and the initial content of its data memory
+// Programs are only linked on platforms providing the OTBN
+#[allow(dead_code)]
+pub struct OTBNApp {
+    /// The instructions of the program
+    pub imem: &'static [u32],
+    /// The initialized data of the program
+    pub dmem_data: &'static [u32],
+    /// Byte offset in the data memory the initialized data is loaded to
+    pub dmem_data_offset: usize,
+}
+
+/// Module for the big number accelerator OTBN, which executes programs loaded into its own memories
+#[allow(dead_code)]
+pub trait OTBNModule: Module {
+    /// Loads the instructions and the initialized data of a program,
+    /// replacing the program that was loaded before
+    ///
+    /// # Arguments
+    ///
+    /// * `app` - the program that should be loaded
+    fn load_app(&self, app: &OTBNApp);
+
+    /// Writes words into the data memory
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - the byte offset in the data memory, has to be word aligned
+    /// * `data` - the words that should be written
+    fn write_dmem(&self, offset: usize, data: &[u32]);
+
+    /// Reads words from the data memory
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - the byte offset in the data memory, has to be word aligned
+    /// * `buffer` - the buffer the words are read into, its length determines how many words are read
+    fn read_dmem(&self, offset: usize, buffer: &mut [u32]);
+
+    /// Executes the loaded program and waits until it is finished,
+    /// returns the error bits if the execution failed
+    fn execute(&self) -> Result<(), u32>;
+}
+
+/// Module providing a platform timer, independent of the cycle counter of the core
+pub trait TimerModule: Module {
+    /// Returns the current value of the timer
+    fn mtime(&self) -> u64;
 }
 
 /// Wrapper for a pointer to a Module
@@ -213,7 +634,7 @@ impl<T: Module + ?Sized> DerefMut for ModuleRef<T> {
 mod tests {
     use crate::{mark_test_as_skipped, platform, platform::Platform};
 
-    use super::{AESKeyLength, AESMode, AESOperation};
+    use super::{AESKeyLength, AESMode, AESOperation, SHA3Length, XOFStrength};
 
     #[test_case]
     fn sha256_digest_is_correct1() {
@@ -239,6 +660,32 @@ mod tests {
         }
     }
 
+    #[test_case]
+    fn sha256_streamed_digest_is_correct() {
+        if let Some(hmac_module) = platform::current().get_sha256_module() {
+            let capacity = hmac_module.fifo_capacity();
+            let mut output = [0u32; 8];
+
+            hmac_module.init_sha256();
+            hmac_module.start_message();
+            hmac_module.write_word(0);
+            assert!(hmac_module.get_fifo_elements() <= capacity);
+            hmac_module.wait_for_completion();
+            hmac_module.read_digest(&mut output);
+
+            // Same input as sha256_digest_is_correct1
+            assert_eq!(
+                output,
+                [
+                    0xdf3f6198, 0x04a92fdb, 0x4057192d, 0xc43dd748, 0xea778adc, 0x52bc498c,
+                    0xe80524c0, 0x14b81119,
+                ]
+            )
+        } else {
+            mark_test_as_skipped!()
+        }
+    }
+
     #[test_case]
     fn sha256_digest_is_correct2() {
         if let Some(hmac_module) = platform::current().get_sha256_module() {
@@ -273,6 +720,139 @@ mod tests {
         }
     }
 
+    #[test_case]
+    fn hmac_sha256_mac_is_correct() {
+        if let Some(hmac_module) = platform::current().get_sha256_module() {
+            let key = [
+                0x00010203, 0x04050607, 0x08090a0b, 0x0c0d0e0f, 0x10111213, 0x14151617, 0x18191a1b,
+                0x1c1d1e1f,
+            ];
+            let input = [0xdf3f6198];
+            let mut output = [0u32; 8];
+
+            hmac_module.init_hmac(&key);
+            hmac_module.write_input(&input);
+            hmac_module.wait_for_completion();
+            hmac_module.read_digest(&mut output);
+
+            assert_eq!(
+                output,
+                [
+                    // Precomputed value by the python hmac module
+                    0x8503e640, 0xe90183eb, 0xc2978936, 0x2f0963a6, 0x05d3cfe7, 0xb19576e9,
+                    0xff71f5dc, 0xbe735f14,
+                ]
+            )
+        } else {
+            mark_test_as_skipped!()
+        }
+    }
+
+    #[test_case]
+    fn shake_output_is_correct() {
+        if let Some(kmac_module) = platform::current().get_kmac_module() {
+            let input = [0u32; 1];
+            let mut output128 = [0u32; 8];
+            let mut output256 = [0u32; 8];
+
+            kmac_module.init_xof(XOFStrength::L128, None);
+            kmac_module.write_input(&input);
+            kmac_module.process();
+            kmac_module.read_output(&mut output128);
+            kmac_module.finish();
+
+            kmac_module.init_xof(XOFStrength::L256, None);
+            kmac_module.write_input(&input);
+            kmac_module.process();
+            kmac_module.read_output(&mut output256);
+            kmac_module.finish();
+
+            // Precomputed values by the python hashlib module
+            assert_eq!(
+                output128,
+                [
+                    0x8f970ca4, 0x2c849ca0, 0x0c48f88d, 0x88380c57, 0x48f42dcf, 0x52908800,
+                    0x1dc526b7, 0x5db1bbbe,
+                ]
+            );
+            assert_eq!(
+                output256,
+                [
+                    0xb4a21e79, 0x39beb125, 0xbd3c1023, 0x7ed5ea31, 0xb31aee7e, 0x6468734f,
+                    0x8d158b4d, 0x50e995d6,
+                ]
+            )
+        } else {
+            mark_test_as_skipped!()
+        }
+    }
+
+    #[test_case]
+    fn kmac_output_is_correct() {
+        if let Some(kmac_module) = platform::current().get_kmac_module() {
+            let key = [
+                0x40414243, 0x44454647, 0x48494a4b, 0x4c4d4e4f, 0x50515253, 0x54555657, 0x58595a5b,
+                0x5c5d5e5f,
+            ];
+            let mut output = [0u32; 8];
+
+            kmac_module.init_kmac(XOFStrength::L256, &key, b"My Tagged Application");
+            kmac_module.write_input(&[0x00010203]);
+            kmac_module.write_output_length(512);
+            kmac_module.process();
+            kmac_module.read_output(&mut output);
+            kmac_module.finish();
+
+            // Sample #4 of the KMAC examples of NIST SP 800-185
+            assert_eq!(
+                output,
+                [
+                    0x20c570c3, 0x1346f703, 0xc9ac36c6, 0x1c03cb64, 0xc3970d0c, 0xfc787e9b,
+                    0x79599d27, 0x3a68d2f7,
+                ]
+            )
+        } else {
+            mark_test_as_skipped!()
+        }
+    }
+
+    #[test_case]
+    fn sha3_digest_is_correct() {
+        if let Some(kmac_module) = platform::current().get_kmac_module() {
+            let mut output = [0u32; 8];
+
+            kmac_module.init_sha3(SHA3Length::L256);
+            kmac_module.write_input(&[0x00000000]);
+            kmac_module.process();
+            kmac_module.read_output(&mut output);
+            kmac_module.finish();
+
+            assert_eq!(
+                output,
+                [
+                    0x8b0a2385, 0xd83c8bf7, 0xbe27e599, 0x96f7d881, 0xd3bf1fc6, 0x606f81ce,
+                    0x600b753a, 0xd94192a2,
+                ]
+            )
+        } else {
+            mark_test_as_skipped!()
+        }
+    }
+
+    #[test_case]
+    fn rng_instantiates_from_entropy_source() {
+        if let Some(rng_module) = platform::current().get_rng_module() {
+            // Without a seed, the instantiation waits for the entropy source
+            rng_module.init_rng(None);
+            rng_module.generate();
+            rng_module.reseed(None);
+            rng_module.generate();
+            rng_module.uninstantiate();
+        } else {
+            mark_test_as_skipped!()
+        }
+    }
+
     #[test_case]
     fn aes_enc_test1() {
         if let Some(aes_module) = platform::current().get_aes_module() {
@@ -1,6 +1,9 @@
 #![allow(dead_code)]
 
-use crate::modules::{AESKeyLength, AESMode, AESModule, AESOperation, Module};
+use crate::{
+    benchmark::get_cycle,
+    modules::{wait_until, AESKeyLength, AESLoopProfile, AESMode, AESModule, AESOperation, Module},
+};
 use bitflags::bitflags;
 
 bitflags! {
@@ -154,12 +157,22 @@ impl OpentitanAES {
         self.base_address.add(AES_DATA_OUT_OFFSET) as *mut u128
     }
 
-    /// Busy waits until some status is set
+    /// Waits until some status is set
     #[inline]
     unsafe fn _wait_for(&self, status: AesSTATUS) {
-        while !AesSTATUS::from_bits_unchecked(self._status_reg().read_volatile()).contains(status) {
-            core::hint::spin_loop()
-        }
+        wait_until(|| {
+            AesSTATUS::from_bits_unchecked(self._status_reg().read_volatile()).contains(status)
+        })
+    }
+
+    /// Triggers clearing registers and waits until the unit is idle again
+    ///
+    /// # Arguments
+    ///
+    /// * `registers` - the clear triggers of the registers that should be cleared
+    unsafe fn clear(&self, registers: AesTRIGGER) {
+        self._trigger_reg().write_volatile(registers.bits());
+        self._wait_for(AesSTATUS::IDLE);
     }
 
     /// Writes to the control register
@@ -181,6 +194,22 @@ impl Module for OpentitanAES {
     fn initialized(&self) -> bool {
         self.initialized
     }
+
+    unsafe fn reset(&mut self) -> Result<(), &'static str> {
+        // Clears the key, iv and data registers and waits for the unit to become idle
+        self.deinitialize();
+
+        Ok(())
+    }
+
+    fn alerted(&self) -> bool {
+        let alerts = AesSTATUS::ALERT_RECOV_CTRL_UPDATE_ERR | AesSTATUS::ALERT_FATAL_FAULT;
+
+        // Safety: reading the status register has no side effects
+        unsafe {
+            AesSTATUS::from_bits_unchecked(self._status_reg().read_volatile()).intersects(alerts)
+        }
+    }
 }
 
 impl AESModule for OpentitanAES {
@@ -255,6 +284,52 @@ impl AESModule for OpentitanAES {
         }
     }
 
+    fn execute_profiled(&self, input: &[u128], output: &mut [u128]) -> AESLoopProfile {
+        // The cycle counter is only read once at every boundary between two parts of the loop,
+        // the end of one part is the start of the next one. The sub-totals are kept in locals
+        // and only returned after the loop.
+        let mut input_writes = 0;
+        let mut status_polls = 0;
+        let mut output_reads = 0;
+
+        unsafe {
+            let mut boundary = get_cycle();
+
+            for blk_count in 0..(input.len() + 2) {
+                if blk_count == 1 {
+                    self._wait_for(AesSTATUS::INPUT_READY);
+                    let polled = get_cycle();
+                    status_polls += polled - boundary;
+                    boundary = polled;
+                }
+
+                if blk_count > 1 {
+                    self._wait_for(AesSTATUS::OUTPUT_VALID);
+                    let polled = get_cycle();
+                    output[blk_count - 2] = self._output().read();
+                    let read = get_cycle();
+
+                    status_polls += polled - boundary;
+                    output_reads += read - polled;
+                    boundary = read;
+                }
+
+                if blk_count < input.len() {
+                    self._input().write_volatile(input[blk_count]);
+                    let written = get_cycle();
+                    input_writes += written - boundary;
+                    boundary = written;
+                }
+            }
+        }
+
+        AESLoopProfile {
+            input_writes,
+            status_polls,
+            output_reads,
+        }
+    }
+
     fn deinitialize(&self) {
         unsafe {
             let ctrl_val: u32 = AesCTRL::MANUAL_OPERATION.bits();
@@ -262,13 +337,17 @@ impl AESModule for OpentitanAES {
             ctrl_reg.write_volatile(ctrl_val);
             ctrl_reg.write_volatile(ctrl_val);
 
-            self._trigger_reg().write_volatile(
-                (AesTRIGGER::KEY_IV_DATA_IN_CLEAR | AesTRIGGER::DATA_OUT_CLEAR).bits(),
-            );
-
-            self._wait_for(AesSTATUS::IDLE);
+            self.clear(AesTRIGGER::KEY_IV_DATA_IN_CLEAR | AesTRIGGER::DATA_OUT_CLEAR);
         }
     }
+
+    fn clear_input(&self) {
+        unsafe { self.clear(AesTRIGGER::KEY_IV_DATA_IN_CLEAR) }
+    }
+
+    fn clear_output(&self) {
+        unsafe { self.clear(AesTRIGGER::DATA_OUT_CLEAR) }
+    }
 }
 
 /// Serializes the key length according to to the opentitan docs, so it can be directly written into the control register
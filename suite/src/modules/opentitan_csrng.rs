@@ -1,6 +1,8 @@
 #![allow(dead_code)]
 
-use crate::modules::{Module, RNGModule};
+use core::cell::Cell;
+
+use crate::modules::{wait_until, Module, RNGModule};
 use bitflags::bitflags;
 
 bitflags! {
@@ -125,12 +127,14 @@ enum CsrngCMD {
 /// CSRNG driver implementation as described by:
 /// https://docs.opentitan.org/hw/ip/csrng/doc/
 ///
+/// Instantiating without a seed requires the entropy source to be enabled.
+///
 /// TODO: Check on actual hardware if the following errors persist:
 /// - hwip always generates 0 as random bits
-/// - hwip hangs when requesting seed from entropy source,\
-///   potentially because none is present?
 pub struct OpentitanCSRNG {
     initialized: bool,
+    /// Whether an instance was instantiated by `init_rng` and not uninstantiated since
+    instantiated: Cell<bool>,
     base_address: *mut u8,
 }
 
@@ -147,6 +151,7 @@ impl OpentitanCSRNG {
     pub const unsafe fn new(base_address: *mut u8) -> OpentitanCSRNG {
         OpentitanCSRNG {
             initialized: false,
+            instantiated: Cell::new(false),
             base_address,
         }
     }
@@ -208,11 +213,10 @@ impl OpentitanCSRNG {
     /// Sends request data via the command request register
     #[inline]
     unsafe fn send_req_data(&self, data: u32) {
-        while !CsrngCMDStatus::from_bits_unchecked(self._command_status_reg().read_volatile())
-            .contains(CsrngCMDStatus::CMD_RDY)
-        {
-            core::hint::spin_loop();
-        }
+        wait_until(|| {
+            CsrngCMDStatus::from_bits_unchecked(self._command_status_reg().read_volatile())
+                .contains(CsrngCMDStatus::CMD_RDY)
+        });
         self._command_request_reg().write_volatile(data);
     }
 }
@@ -230,6 +234,15 @@ impl Module for OpentitanCSRNG {
     fn initialized(&self) -> bool {
         self.initialized
     }
+
+    unsafe fn reset(&mut self) -> Result<(), &'static str> {
+        if self.instantiated.replace(false) {
+            let header = generate_header(CsrngCMD::Uninstantiate, 0, 0, 0);
+            self.send_req_data(header);
+        }
+
+        Ok(())
+    }
 }
 
 impl RNGModule for OpentitanCSRNG {
@@ -258,26 +271,91 @@ impl RNGModule for OpentitanCSRNG {
                 self.send_req_data(header);
             }
         }
+        self.instantiated.set(true);
+    }
+
+    fn reseed(&self, additional_data: Option<&[u32]>) {
+        unsafe {
+            self._interrupt_state_reg()
+                .write_volatile(CsrngINTRState::CS_CMD_REQ_DONE.bits());
+
+            if let Some(additional_data) = additional_data {
+                let data_len = additional_data.len().min(12);
+
+                let header = generate_header(
+                    CsrngCMD::Reseed,
+                    data_len as u32,
+                    CsrngCMDHeader::FLAG0.bits(),
+                    0,
+                );
+                self.send_req_data(header);
+
+                for value in &additional_data[0..data_len] {
+                    self.send_req_data(*value);
+                }
+            } else {
+                let header = generate_header(CsrngCMD::Reseed, 0, 0, 0);
+                self.send_req_data(header);
+            }
+
+            wait_until(|| {
+                CsrngINTRState::from_bits_unchecked(self._interrupt_state_reg().read_volatile())
+                    .contains(CsrngINTRState::CS_CMD_REQ_DONE)
+            });
+            self._interrupt_state_reg()
+                .write_volatile(CsrngINTRState::CS_CMD_REQ_DONE.bits());
+        }
     }
 
     fn generate(&self) -> u128 {
+        let mut output = [0u128; 1];
+        self.generate_blocks(&mut output);
+        output[0]
+    }
+
+    fn generate_blocks(&self, output: &mut [u128]) {
         unsafe {
-            let header = generate_header(CsrngCMD::Generate, 0, 0, 1);
+            let header = generate_header(CsrngCMD::Generate, 0, 0, output.len() as u32);
             self.send_req_data(header);
 
-            while !CsrngGENBITSValid::from_bits_unchecked(
-                self._generated_bits_valid_reg().read_volatile(),
-            )
-            .contains(CsrngGENBITSValid::GENBITS_VLD)
-            {
-                core::hint::spin_loop()
+            // Every block has to be read before the next one becomes valid
+            for block in output {
+                wait_until(|| {
+                    CsrngGENBITSValid::from_bits_unchecked(
+                        self._generated_bits_valid_reg().read_volatile(),
+                    )
+                    .contains(CsrngGENBITSValid::GENBITS_VLD)
+                });
+
+                *block = (self._generated_bits_reg().read_volatile() as u128) << (0 * 32)
+                    | (self._generated_bits_reg().read_volatile() as u128) << (1 * 32)
+                    | (self._generated_bits_reg().read_volatile() as u128) << (2 * 32)
+                    | (self._generated_bits_reg().read_volatile() as u128) << (3 * 32);
             }
+        }
+    }
+
+    fn uninstantiate(&self) {
+        // The command would never be acknowledged without an instance
+        if !self.instantiated.get() {
+            return;
+        }
+
+        unsafe {
+            self._interrupt_state_reg()
+                .write_volatile(CsrngINTRState::CS_CMD_REQ_DONE.bits());
+
+            let header = generate_header(CsrngCMD::Uninstantiate, 0, 0, 0);
+            self.send_req_data(header);
 
-            (self._generated_bits_reg().read_volatile() as u128) << (0 * 32)
-                | (self._generated_bits_reg().read_volatile() as u128) << (1 * 32)
-                | (self._generated_bits_reg().read_volatile() as u128) << (2 * 32)
-                | (self._generated_bits_reg().read_volatile() as u128) << (3 * 32)
+            wait_until(|| {
+                CsrngINTRState::from_bits_unchecked(self._interrupt_state_reg().read_volatile())
+                    .contains(CsrngINTRState::CS_CMD_REQ_DONE)
+            });
+            self._interrupt_state_reg()
+                .write_volatile(CsrngINTRState::CS_CMD_REQ_DONE.bits());
         }
+        self.instantiated.set(false);
     }
 }
 
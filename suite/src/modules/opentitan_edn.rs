@@ -0,0 +1,259 @@
+#![allow(dead_code)]
+
+use crate::modules::{wait_until, EDNModule, Module};
+use bitflags::bitflags;
+
+bitflags! {
+    /// Abstract representation of the interrupt state register.
+    struct EdnINTRState: u32 {
+        const EDN_CMD_REQ_DONE = 1 << 0;
+        const EDN_FATAL_ERR = 1 << 1;
+    }
+
+    /// Abstract representation of the command status register flags.
+    struct EdnCMDStatus: u32 {
+        const CMD_RDY = 1 << 0;
+        const CMD_STS = 1 << 1;
+    }
+}
+
+/// Offset of the interrupt state register
+const EDN_INTR_STATE_OFFSET: usize = 0x0;
+/// Offset of the control register
+const EDN_CTRL_OFFSET: usize = 0x14;
+/// Offset of the software command request register
+const EDN_SW_CMD_REQ_OFFSET: usize = 0x20;
+/// Offset of the software command status register
+const EDN_SW_CMD_STS_OFFSET: usize = 0x24;
+/// Offset of the reseed command fifo of the auto request mode
+const EDN_RESEED_CMD_OFFSET: usize = 0x28;
+/// Offset of the generate command fifo of the auto request mode
+const EDN_GENERATE_CMD_OFFSET: usize = 0x2c;
+/// Offset of the register limiting the generate commands between two reseeds
+const EDN_MAX_NUM_REQS_BETWEEN_RESEEDS_OFFSET: usize = 0x30;
+
+/// Offset of the field enabling the module in the control register
+const CTRL_EDN_ENABLE_OFFSET: u32 = 0;
+/// Offset of the field enabling the boot request mode in the control register
+const CTRL_BOOT_REQ_MODE_OFFSET: u32 = 4;
+/// Offset of the field enabling the auto request mode in the control register
+const CTRL_AUTO_REQ_MODE_OFFSET: u32 = 8;
+/// Offset of the field resetting the command fifos in the control register
+const CTRL_CMD_FIFO_RST_OFFSET: u32 = 12;
+
+/// Number of 128 bit blocks requested by a generate command of the auto request mode
+const EDN_GENERATE_LENGTH: u32 = 64;
+/// Number of generate commands of the auto request mode between two reseeds
+const EDN_MAX_REQUESTS_BETWEEN_RESEEDS: u32 = 32;
+
+/// Multi bit value representing true
+/// Used when a true value has to be represented with 4 bits
+const K_MULTI_BIT_BOOL4_TRUE: u32 = 0xA;
+/// Multi bit value representing false
+/// Used when a false value has to be represented with 4 bits
+const K_MULTI_BIT_BOOL4_FALSE: u32 = 0x5;
+
+/// Application commands of the CSRNG, which are forwarded by the EDN
+#[derive(Copy, Clone)]
+enum CsrngCMD {
+    Instantiate = 0x1,
+    Reseed = 0x2,
+    Generate = 0x3,
+    Uninstantiate = 0x5,
+}
+
+/// EDN driver implementation as described by:
+/// https://docs.opentitan.org/hw/ip/edn/doc/
+///
+/// The EDN is operated in auto request mode: its instance of the CSRNG is instantiated from the
+/// entropy source once, afterwards generate commands are issued whenever the modules connected
+/// to the EDN request random numbers, with a reseed after a fixed number of them.
+pub struct OpentitanEDN {
+    initialized: bool,
+    base_address: *mut u8,
+}
+
+impl OpentitanEDN {
+    /// Creates a new OpentitanEDN driver
+    ///
+    /// # Arguments
+    ///
+    /// * `base_address` - A pointer to the MMIO address of the edn device
+    ///
+    /// # Safety:
+    ///  - a valid edn device must be at the base_address
+    ///  - no other edn module must use the same base_address
+    pub const unsafe fn new(base_address: *mut u8) -> OpentitanEDN {
+        OpentitanEDN {
+            initialized: false,
+            base_address,
+        }
+    }
+
+    /// Returns pointer to interrupt state register
+    #[inline]
+    unsafe fn _interrupt_state_reg(&self) -> *mut u32 {
+        self.base_address.add(EDN_INTR_STATE_OFFSET) as *mut u32
+    }
+
+    /// Returns pointer to control register
+    #[inline]
+    unsafe fn _control_reg(&self) -> *mut u32 {
+        self.base_address.add(EDN_CTRL_OFFSET) as *mut u32
+    }
+
+    /// Returns pointer to software command request register
+    #[inline]
+    unsafe fn _command_request_reg(&self) -> *mut u32 {
+        self.base_address.add(EDN_SW_CMD_REQ_OFFSET) as *mut u32
+    }
+
+    /// Returns pointer to software command status register
+    #[inline]
+    unsafe fn _command_status_reg(&self) -> *mut u32 {
+        self.base_address.add(EDN_SW_CMD_STS_OFFSET) as *mut u32
+    }
+
+    /// Returns pointer to reseed command register
+    #[inline]
+    unsafe fn _reseed_command_reg(&self) -> *mut u32 {
+        self.base_address.add(EDN_RESEED_CMD_OFFSET) as *mut u32
+    }
+
+    /// Returns pointer to generate command register
+    #[inline]
+    unsafe fn _generate_command_reg(&self) -> *mut u32 {
+        self.base_address.add(EDN_GENERATE_CMD_OFFSET) as *mut u32
+    }
+
+    /// Returns pointer to maximum number of requests between reseeds register
+    #[inline]
+    unsafe fn _max_requests_reg(&self) -> *mut u32 {
+        self.base_address
+            .add(EDN_MAX_NUM_REQS_BETWEEN_RESEEDS_OFFSET) as *mut u32
+    }
+
+    /// Writes the control register, every field is a multi bit value
+    ///
+    /// # Arguments
+    ///
+    /// * `enable` - whether the module is enabled
+    /// * `auto_mode` - whether the auto request mode is used
+    /// * `fifo_reset` - whether the command fifos are held in reset
+    #[inline]
+    unsafe fn write_control(&self, enable: bool, auto_mode: bool, fifo_reset: bool) {
+        let mubi = |value: bool| {
+            if value {
+                K_MULTI_BIT_BOOL4_TRUE
+            } else {
+                K_MULTI_BIT_BOOL4_FALSE
+            }
+        };
+
+        self._control_reg().write_volatile(
+            mubi(enable) << CTRL_EDN_ENABLE_OFFSET
+                | mubi(false) << CTRL_BOOT_REQ_MODE_OFFSET
+                | mubi(auto_mode) << CTRL_AUTO_REQ_MODE_OFFSET
+                | mubi(fifo_reset) << CTRL_CMD_FIFO_RST_OFFSET,
+        );
+    }
+
+    /// Sends a command via the software command request register
+    /// and waits until the CSRNG completed it
+    ///
+    /// # Arguments
+    ///
+    /// * `header` - the header of the command, it must not have any additional data
+    unsafe fn send_command(&self, header: u32) {
+        self._interrupt_state_reg()
+            .write_volatile(EdnINTRState::EDN_CMD_REQ_DONE.bits());
+
+        wait_until(|| {
+            EdnCMDStatus::from_bits_unchecked(self._command_status_reg().read_volatile())
+                .contains(EdnCMDStatus::CMD_RDY)
+        });
+        self._command_request_reg().write_volatile(header);
+
+        wait_until(|| {
+            EdnINTRState::from_bits_unchecked(self._interrupt_state_reg().read_volatile())
+                .contains(EdnINTRState::EDN_CMD_REQ_DONE)
+        });
+        self._interrupt_state_reg()
+            .write_volatile(EdnINTRState::EDN_CMD_REQ_DONE.bits());
+    }
+
+    /// True if the module is enabled
+    fn enabled(&self) -> bool {
+        unsafe {
+            self._control_reg().read_volatile() >> CTRL_EDN_ENABLE_OFFSET & 0xf
+                == K_MULTI_BIT_BOOL4_TRUE
+        }
+    }
+}
+
+impl Module for OpentitanEDN {
+    unsafe fn init(&mut self) -> Result<(), &'static str> {
+        // Modules using the EDN, like the kmac module when masking with its entropy, block until
+        // it serves random numbers
+        self.enable();
+
+        self.initialized = true;
+        Ok(())
+    }
+
+    fn initialized(&self) -> bool {
+        self.initialized
+    }
+
+    unsafe fn reset(&mut self) -> Result<(), &'static str> {
+        if !self.enabled() {
+            self.enable();
+        }
+
+        Ok(())
+    }
+}
+
+impl EDNModule for OpentitanEDN {
+    fn enable(&self) {
+        unsafe {
+            // The command fifos of the auto request mode are cleared before they are filled
+            self.write_control(true, false, true);
+            self.write_control(true, false, false);
+            self._reseed_command_reg()
+                .write_volatile(command_header(CsrngCMD::Reseed, 0));
+            self._generate_command_reg()
+                .write_volatile(command_header(CsrngCMD::Generate, EDN_GENERATE_LENGTH));
+            self._max_requests_reg()
+                .write_volatile(EDN_MAX_REQUESTS_BETWEEN_RESEEDS);
+
+            // The auto request mode starts once the instantiation was completed
+            self.write_control(true, true, false);
+            self.send_command(command_header(CsrngCMD::Instantiate, 0));
+        }
+    }
+
+    fn disable(&self) {
+        unsafe {
+            if !self.enabled() {
+                return;
+            }
+
+            // Leave the auto request mode, so the uninstantiation is accepted
+            self.write_control(true, false, false);
+            self.send_command(command_header(CsrngCMD::Uninstantiate, 0));
+            self.write_control(false, false, false);
+        }
+    }
+}
+
+/// Generates the header of a CSRNG application command without additional data,
+/// according to the documentation of the CSRNG
+///
+/// # Arguments
+///
+/// * `acmd` - The application command to execute
+/// * `glen` - The generate length, has to be between 0 and 4095
+fn command_header(acmd: CsrngCMD, glen: u32) -> u32 {
+    acmd as u32 | (glen & 0b1111_1111_1111) << 12
+}
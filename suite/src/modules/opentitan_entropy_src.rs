@@ -0,0 +1,134 @@
+#![allow(dead_code)]
+
+use crate::modules::{EntropySourceModule, Module};
+
+/// Offset of the module enable register
+const ENTROPY_SRC_MODULE_ENABLE_OFFSET: usize = 0x20;
+/// Offset of the configuration register
+const ENTROPY_SRC_CONF_OFFSET: usize = 0x24;
+/// Offset of the entropy control register
+const ENTROPY_SRC_ENTROPY_CONTROL_OFFSET: usize = 0x28;
+
+/// Offset of the field enabling the FIPS mode in the configuration register
+const CONF_FIPS_ENABLE_OFFSET: u32 = 0;
+/// Offset of the field routing the entropy to the entropy data register in the configuration register
+const CONF_ENTROPY_DATA_REG_ENABLE_OFFSET: u32 = 4;
+/// Offset of the field limiting the health tests to single bits in the configuration register
+const CONF_THRESHOLD_SCOPE_OFFSET: u32 = 12;
+/// Offset of the field enabling the single bit mode in the configuration register
+const CONF_RNG_BIT_ENABLE_OFFSET: u32 = 16;
+/// Offset of the field routing the entropy to software in the entropy control register
+const ENTROPY_CONTROL_ES_ROUTE_OFFSET: u32 = 0;
+/// Offset of the field bypassing the conditioning in the entropy control register
+const ENTROPY_CONTROL_ES_TYPE_OFFSET: u32 = 4;
+
+/// Multi bit value representing true
+/// Used when a true value has to be represented with 4 bits
+const K_MULTI_BIT_BOOL4_TRUE: u32 = 0xA;
+/// Multi bit value representing false
+/// Used when a false value has to be represented with 4 bits
+const K_MULTI_BIT_BOOL4_FALSE: u32 = 0x5;
+
+/// Entropy source driver implementation as described by:
+/// https://docs.opentitan.org/hw/ip/entropy_src/doc/
+///
+/// The entropy source is operated in FIPS mode, its conditioned seeds are passed to the CSRNG
+/// through the hardware interface.
+pub struct OpentitanEntropySrc {
+    initialized: bool,
+    base_address: *mut u8,
+}
+
+impl OpentitanEntropySrc {
+    /// Creates a new OpentitanEntropySrc driver
+    ///
+    /// # Arguments
+    ///
+    /// * `base_address` - A pointer to the MMIO address of the entropy source device
+    ///
+    /// # Safety:
+    ///  - a valid entropy source device must be at the base_address
+    ///  - no other entropy source module must use the same base_address
+    pub const unsafe fn new(base_address: *mut u8) -> OpentitanEntropySrc {
+        OpentitanEntropySrc {
+            initialized: false,
+            base_address,
+        }
+    }
+
+    /// Returns pointer to module enable register
+    #[inline]
+    unsafe fn _module_enable_reg(&self) -> *mut u32 {
+        self.base_address.add(ENTROPY_SRC_MODULE_ENABLE_OFFSET) as *mut u32
+    }
+
+    /// Returns pointer to configuration register
+    #[inline]
+    unsafe fn _config_reg(&self) -> *mut u32 {
+        self.base_address.add(ENTROPY_SRC_CONF_OFFSET) as *mut u32
+    }
+
+    /// Returns pointer to entropy control register
+    #[inline]
+    unsafe fn _entropy_control_reg(&self) -> *mut u32 {
+        self.base_address.add(ENTROPY_SRC_ENTROPY_CONTROL_OFFSET) as *mut u32
+    }
+
+    /// True if the module is enabled
+    fn enabled(&self) -> bool {
+        unsafe { self._module_enable_reg().read_volatile() & 0xf == K_MULTI_BIT_BOOL4_TRUE }
+    }
+}
+
+impl Module for OpentitanEntropySrc {
+    unsafe fn init(&mut self) -> Result<(), &'static str> {
+        // The CSRNG can only be instantiated without a seed once the entropy source runs
+        self.enable();
+
+        self.initialized = true;
+        Ok(())
+    }
+
+    fn initialized(&self) -> bool {
+        self.initialized
+    }
+
+    unsafe fn reset(&mut self) -> Result<(), &'static str> {
+        if !self.enabled() {
+            self.enable();
+        }
+
+        Ok(())
+    }
+}
+
+impl EntropySourceModule for OpentitanEntropySrc {
+    fn enable(&self) {
+        unsafe {
+            // The configuration can only be changed while the module is disabled
+            self._module_enable_reg()
+                .write_volatile(K_MULTI_BIT_BOOL4_FALSE);
+
+            self._config_reg().write_volatile(
+                K_MULTI_BIT_BOOL4_TRUE << CONF_FIPS_ENABLE_OFFSET
+                    | K_MULTI_BIT_BOOL4_FALSE << CONF_ENTROPY_DATA_REG_ENABLE_OFFSET
+                    | K_MULTI_BIT_BOOL4_FALSE << CONF_THRESHOLD_SCOPE_OFFSET
+                    | K_MULTI_BIT_BOOL4_FALSE << CONF_RNG_BIT_ENABLE_OFFSET,
+            );
+            self._entropy_control_reg().write_volatile(
+                K_MULTI_BIT_BOOL4_FALSE << ENTROPY_CONTROL_ES_ROUTE_OFFSET
+                    | K_MULTI_BIT_BOOL4_FALSE << ENTROPY_CONTROL_ES_TYPE_OFFSET,
+            );
+
+            self._module_enable_reg()
+                .write_volatile(K_MULTI_BIT_BOOL4_TRUE);
+        }
+    }
+
+    fn disable(&self) {
+        unsafe {
+            self._module_enable_reg()
+                .write_volatile(K_MULTI_BIT_BOOL4_FALSE);
+        }
+    }
+}
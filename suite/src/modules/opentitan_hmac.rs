@@ -1,6 +1,6 @@
 #![allow(dead_code)]
 
-use crate::modules::{Module, SHA256Module};
+use crate::modules::{wait_until, Module, SHA256Module};
 use bitflags::bitflags;
 
 bitflags! {
@@ -42,6 +42,10 @@ const HMAC_CFG_OFFSET: usize = 0x10;
 const HMAC_CMD_OFFSET: usize = 0x14;
 /// Offset of the status register
 const HMAC_STATUS_OFFSET: usize = 0x18;
+/// Offset of the key register
+///
+/// Key can be used like an [u32; 8] residing at this offset, the most significant word first
+const HMAC_KEY_OFFSET: usize = 0x24;
 /// Offset of the digest register
 ///
 /// Digest can be used like an [u32; 8] residing at this offset
@@ -49,6 +53,13 @@ const HMAC_DIGEST_OFFSET: usize = 0x44;
 /// Offset of the message register
 const HMAC_MSG_OFFSET: usize = 0x800;
 
+/// Position of the fill level of the message FIFO in the status register
+const HMAC_STATUS_FIFO_DEPTH_SHIFT: u32 = 4;
+/// Mask of the fill level of the message FIFO, after shifting the status register
+const HMAC_STATUS_FIFO_DEPTH_MASK: u32 = 0x1f;
+/// Number of words the message FIFO can hold
+const HMAC_FIFO_DEPTH: u32 = 16;
+
 /// HMAC driver implementation as described by:
 /// https://docs.opentitan.org/hw/ip/hmac/doc/
 pub struct OpentitanHMAC {
@@ -97,6 +108,12 @@ impl OpentitanHMAC {
         self.base_address.add(HMAC_STATUS_OFFSET) as *mut u32
     }
 
+    /// Returns pointer to key register
+    #[inline]
+    unsafe fn _key(&self) -> *mut [u32; 8] {
+        self.base_address.add(HMAC_KEY_OFFSET) as *mut [u32; 8]
+    }
+
     /// Returns pointer to digest register
     #[inline]
     unsafe fn _digest(&self) -> *mut [u32; 8] {
@@ -128,33 +145,61 @@ impl SHA256Module for OpentitanHMAC {
         }
     }
 
+    fn init_hmac(&self, key: &[u32; 8]) {
+        unsafe {
+            self._key().write_volatile(*key);
+            self._config_reg()
+                .write_volatile((HmacCFG::HMAC_ENABLED | HmacCFG::SHA_ENABLED).bits())
+        }
+    }
+
     fn write_input(&self, data: &[u32]) {
         unsafe {
             self._command_reg()
                 .write_volatile(HmacCMD::HASH_START.bits());
 
             for value in data {
-                while HmacSTATUS::from_bits_unchecked(self._status_reg().read_volatile())
-                    .contains(HmacSTATUS::FIFO_FULL)
-                {
-                    core::hint::spin_loop()
-                }
+                wait_until(|| {
+                    !HmacSTATUS::from_bits_unchecked(self._status_reg().read_volatile())
+                        .contains(HmacSTATUS::FIFO_FULL)
+                });
 
                 self._msg_reg().write_volatile(*value);
             }
         }
     }
 
+    fn start_message(&self) {
+        unsafe {
+            self._command_reg()
+                .write_volatile(HmacCMD::HASH_START.bits())
+        }
+    }
+
+    fn write_word(&self, word: u32) {
+        unsafe { self._msg_reg().write_volatile(word) }
+    }
+
+    fn get_fifo_elements(&self) -> u32 {
+        unsafe {
+            (self._status_reg().read_volatile() >> HMAC_STATUS_FIFO_DEPTH_SHIFT)
+                & HMAC_STATUS_FIFO_DEPTH_MASK
+        }
+    }
+
+    fn fifo_capacity(&self) -> u32 {
+        HMAC_FIFO_DEPTH
+    }
+
     fn wait_for_completion(&self) {
         unsafe {
             self._command_reg()
                 .write_volatile(HmacCMD::HASH_PROCESS.bits());
 
-            while !HmacINTRSTATE::from_bits_unchecked(self._interrupt_state_reg().read_volatile())
-                .contains(HmacINTRSTATE::HMAC_DONE)
-            {
-                core::hint::spin_loop()
-            }
+            wait_until(|| {
+                HmacINTRSTATE::from_bits_unchecked(self._interrupt_state_reg().read_volatile())
+                    .contains(HmacINTRSTATE::HMAC_DONE)
+            });
 
             self._interrupt_state_reg()
                 .write_volatile(HmacINTRSTATE::HMAC_DONE.bits());
@@ -0,0 +1,460 @@
+#![allow(dead_code)]
+
+use alloc::vec::Vec;
+
+use crate::modules::{wait_until, KMACEntropy, KMACModule, Module, SHA3Length, XOFStrength};
+use bitflags::bitflags;
+
+bitflags! {
+    /// Abstract representation of the config registers flags.
+    struct KmacCFG: u32 {
+        const KMAC_ENABLED = 1 << 0;
+        /// If set the input is interpreted in big endian, otherwise little endian
+        const MSG_ENDIANNESS = 1 << 8;
+        /// If set the state is read in big endian, otherwise little endian
+        const STATE_ENDIANNESS = 1 << 9;
+        const SIDELOAD = 1 << 12;
+        /// Skips the entropy expansion while processing, as the message is not masked
+        const ENTROPY_FAST_PROCESS = 1 << 19;
+        const MSG_MASK = 1 << 20;
+        /// Signals that the entropy configuration is complete
+        const ENTROPY_READY = 1 << 24;
+    }
+
+    /// Abstract representation of the status registers flags.
+    struct KmacSTATUS: u32 {
+        const SHA3_IDLE = 1 << 0;
+        const SHA3_ABSORB = 1 << 1;
+        /// Set when the digest can be read from the state
+        const SHA3_SQUEEZE = 1 << 2;
+        const FIFO_EMPTY = 1 << 14;
+        const FIFO_FULL = 1 << 15;
+    }
+}
+
+/// Commands that can be written to the command register
+#[repr(u32)]
+enum KmacCMD {
+    Start = 0x1d,
+    Process = 0x2e,
+    Run = 0x31,
+    Done = 0x16,
+}
+
+/// Offsets and masks of the multi-bit fields of the configuration register
+mod cfg_reg {
+    pub const KSTRENGTH_OFFSET: u32 = 0x1;
+    pub const KSTRENGTH_MASK: u32 = 0b111;
+    pub const MODE_OFFSET: u32 = 0x4;
+    pub const MODE_MASK: u32 = 0b11;
+    /// Plain SHA3 without an extendable output
+    pub const MODE_SHA3: u32 = 0x0;
+    pub const ENTROPY_MODE_OFFSET: u32 = 0x10;
+    pub const ENTROPY_MODE_MASK: u32 = 0b11;
+    /// Entropy is fetched from the EDN
+    pub const ENTROPY_MODE_EDN: u32 = 0x0;
+    /// Entropy is provided by software through the seed registers
+    pub const ENTROPY_MODE_SW: u32 = 0x2;
+}
+/// Offset of the configuration register \
+/// **Important: This register is shadowed so it has to be written to twice fo the change to take affect**
+const KMAC_CFG_SHADOWED_OFFSET: usize = 0x14;
+/// Offset of the command register
+const KMAC_CMD_OFFSET: usize = 0x18;
+/// Offset of the status register
+const KMAC_STATUS_OFFSET: usize = 0x1c;
+/// Offset of the entropy seed registers
+///
+/// Can be used like a [u32; 5] residing at this offset
+const KMAC_ENTROPY_SEED_OFFSET: usize = 0x2c;
+/// Offset of the registers of the first share of the key
+///
+/// Can be used like a [u32; 16] residing at this offset
+const KMAC_KEY_SHARE0_OFFSET: usize = 0x40;
+/// Offset of the registers of the second share of the key
+///
+/// Can be used like a [u32; 16] residing at this offset
+const KMAC_KEY_SHARE1_OFFSET: usize = 0x80;
+/// Offset of the key length register
+const KMAC_KEY_LEN_OFFSET: usize = 0xc0;
+/// Offset of the prefix registers used by cSHAKE
+///
+/// Can be used like a [u32; 11] residing at this offset
+const KMAC_PREFIX_OFFSET: usize = 0xc4;
+/// Offset of the first share of the keccak state, the output is read from here
+const KMAC_STATE_OFFSET: usize = 0x400;
+/// Offset of the message fifo
+const KMAC_MSG_FIFO_OFFSET: usize = 0x800;
+
+/// Number of prefix registers
+const KMAC_PREFIX_WORDS: usize = 11;
+
+/// Value of the key length register selecting a 256 bit key
+const KMAC_KEY_LEN_256: u32 = 0x2;
+
+/// Function name of KMAC, used in the prefix of the underlying cSHAKE
+const KMAC_FUNCTION_NAME: &[u8] = b"KMAC";
+
+/// Seed of the masking PRNG, used unless entropy is fetched from the EDN
+const KMAC_ENTROPY_SEED: [u32; 5] = [0x5a5a_5a5a, 0xa5a5_a5a5, 0x0123_4567, 0x89ab_cdef, 0x1];
+
+/// KMAC driver implementation as described by:
+/// https://docs.opentitan.org/hw/ip/kmac/doc/
+///
+/// Input and output are big endian, like the ones of the HMAC driver.
+pub struct OpentitanKMAC {
+    initialized: bool,
+    base_address: *mut u8,
+    entropy: KMACEntropy,
+}
+
+impl OpentitanKMAC {
+    /// Creates a new OpentitanKMAC driver
+    ///
+    /// # Arguments
+    ///
+    /// * `base_address` - A pointer to the MMIO address of the kmac device
+    ///
+    /// # Safety:
+    ///  - a valid kmac device must be at the base_address
+    ///  - no other kmac must use the same base_address
+    pub const unsafe fn new(base_address: *mut u8) -> OpentitanKMAC {
+        OpentitanKMAC {
+            initialized: false,
+            base_address,
+            entropy: KMACEntropy::Unmasked,
+        }
+    }
+
+    /// Returns pointer to configuration register \
+    /// **Important: This register is shadowed so it has to be written to twice fo the change to take affect**
+    #[inline]
+    unsafe fn _config_reg(&self) -> *mut u32 {
+        self.base_address.add(KMAC_CFG_SHADOWED_OFFSET) as *mut u32
+    }
+
+    /// Returns pointer to command register
+    #[inline]
+    unsafe fn _command_reg(&self) -> *mut u32 {
+        self.base_address.add(KMAC_CMD_OFFSET) as *mut u32
+    }
+
+    /// Returns pointer to status register
+    #[inline]
+    unsafe fn _status_reg(&self) -> *mut u32 {
+        self.base_address.add(KMAC_STATUS_OFFSET) as *mut u32
+    }
+
+    /// Returns pointer to the entropy seed register with the given index
+    #[inline]
+    unsafe fn _entropy_seed_reg(&self, index: usize) -> *mut u32 {
+        (self.base_address.add(KMAC_ENTROPY_SEED_OFFSET) as *mut u32).add(index)
+    }
+
+    /// Returns pointer to the prefix register with the given index
+    #[inline]
+    unsafe fn _prefix_reg(&self, index: usize) -> *mut u32 {
+        (self.base_address.add(KMAC_PREFIX_OFFSET) as *mut u32).add(index)
+    }
+
+    /// Returns pointer to the register of the given share of the key with the given index
+    #[inline]
+    unsafe fn _key_share_reg(&self, share: usize, index: usize) -> *mut u32 {
+        let offset = if share == 0 {
+            KMAC_KEY_SHARE0_OFFSET
+        } else {
+            KMAC_KEY_SHARE1_OFFSET
+        };
+        (self.base_address.add(offset) as *mut u32).add(index)
+    }
+
+    /// Returns pointer to the key length register
+    #[inline]
+    unsafe fn _key_len_reg(&self) -> *mut u32 {
+        self.base_address.add(KMAC_KEY_LEN_OFFSET) as *mut u32
+    }
+
+    /// Returns pointer to the word of the state with the given index
+    #[inline]
+    unsafe fn _state(&self, index: usize) -> *mut u32 {
+        (self.base_address.add(KMAC_STATE_OFFSET) as *mut u32).add(index)
+    }
+
+    /// Returns pointer to message fifo
+    #[inline]
+    unsafe fn _msg_fifo(&self) -> *mut u32 {
+        self.base_address.add(KMAC_MSG_FIFO_OFFSET) as *mut u32
+    }
+
+    /// Writes a command to the command register
+    #[inline]
+    unsafe fn write_cmd(&self, cmd: KmacCMD) {
+        self._command_reg().write_volatile(cmd as u32);
+    }
+
+    /// Writes to the configuration register
+    unsafe fn write_config(&self, config: u32) {
+        self._config_reg().write_volatile(config);
+        self._config_reg().write_volatile(config);
+    }
+
+    /// Writes the function name and customization string of cSHAKE into the prefix registers
+    ///
+    /// # Arguments
+    ///
+    /// * `function_name` - the function name
+    /// * `customization` - the customization string
+    unsafe fn write_prefix(&self, function_name: &[u8], customization: &[u8]) {
+        let mut prefix = Vec::new();
+        encode_string(&mut prefix, function_name);
+        encode_string(&mut prefix, customization);
+
+        let mut words = [0u32; KMAC_PREFIX_WORDS];
+        for (i, byte) in prefix.iter().take(KMAC_PREFIX_WORDS * 4).enumerate() {
+            // The prefix registers are little endian
+            words[i / 4] |= (*byte as u32) << ((i % 4) * 8);
+        }
+        for (index, word) in words.iter().enumerate() {
+            self._prefix_reg(index).write_volatile(*word);
+        }
+    }
+
+    /// Writes the configuration of a computation and starts it
+    ///
+    /// # Arguments
+    ///
+    /// * `function` - the serialized security strength and mode of the function
+    /// * `keyed` - whether KMAC is computed on top of cSHAKE
+    unsafe fn start(&self, function: u32, keyed: bool) {
+        let (entropy_mode, masking) = match self.entropy {
+            KMACEntropy::Unmasked => (cfg_reg::ENTROPY_MODE_SW, KmacCFG::ENTROPY_FAST_PROCESS),
+            KMACEntropy::Software => (cfg_reg::ENTROPY_MODE_SW, KmacCFG::MSG_MASK),
+            KMACEntropy::EDN => (cfg_reg::ENTROPY_MODE_EDN, KmacCFG::MSG_MASK),
+        };
+        let mut flags =
+            KmacCFG::MSG_ENDIANNESS | KmacCFG::STATE_ENDIANNESS | KmacCFG::ENTROPY_READY | masking;
+        if keyed {
+            flags |= KmacCFG::KMAC_ENABLED;
+        }
+        self.write_config(
+            function
+                | (entropy_mode & cfg_reg::ENTROPY_MODE_MASK) << cfg_reg::ENTROPY_MODE_OFFSET
+                | flags.bits(),
+        );
+        self.write_cmd(KmacCMD::Start);
+    }
+
+    /// Waits until the state can be read
+    #[inline]
+    unsafe fn wait_for_squeeze(&self) {
+        wait_until(|| {
+            KmacSTATUS::from_bits_unchecked(self._status_reg().read_volatile())
+                .contains(KmacSTATUS::SHA3_SQUEEZE)
+        })
+    }
+}
+
+impl Module for OpentitanKMAC {
+    unsafe fn init(&mut self) -> Result<(), &'static str> {
+        self.set_entropy_seed(None);
+
+        self.initialized = true;
+        Ok(())
+    }
+
+    fn initialized(&self) -> bool {
+        self.initialized
+    }
+
+    unsafe fn reset(&mut self) -> Result<(), &'static str> {
+        if KmacSTATUS::from_bits_unchecked(self._status_reg().read_volatile())
+            .contains(KmacSTATUS::SHA3_SQUEEZE)
+        {
+            self.write_cmd(KmacCMD::Done);
+        }
+        self.entropy = KMACEntropy::Unmasked;
+
+        Ok(())
+    }
+}
+
+impl KMACModule for OpentitanKMAC {
+    fn set_entropy(&mut self, entropy: KMACEntropy) {
+        self.entropy = entropy;
+    }
+
+    fn set_entropy_seed(&self, seed: Option<[u32; 5]>) {
+        for (index, word) in seed.unwrap_or(KMAC_ENTROPY_SEED).iter().enumerate() {
+            unsafe { self._entropy_seed_reg(index).write_volatile(*word) }
+        }
+    }
+
+    fn init_xof(&self, strength: XOFStrength, customization: Option<(&[u8], &[u8])>) {
+        unsafe {
+            if let Some((function_name, customization)) = customization {
+                self.write_prefix(function_name, customization);
+            }
+            self.start(
+                _serialize_strength(strength) | _serialize_mode(customization.is_some()),
+                false,
+            );
+        }
+    }
+
+    fn init_sha3(&self, length: SHA3Length) {
+        unsafe {
+            self.start(
+                _serialize_digest_length(length) | cfg_reg::MODE_SHA3 << cfg_reg::MODE_OFFSET,
+                false,
+            )
+        }
+    }
+
+    fn init_kmac(&self, strength: XOFStrength, key: &[u32; 8], customization: &[u8]) {
+        unsafe {
+            // The key registers are little endian, the key is big endian like the input.
+            // It is not masked, so the second share is zero
+            for (index, word) in key.iter().enumerate() {
+                self._key_share_reg(0, index)
+                    .write_volatile(word.swap_bytes());
+                self._key_share_reg(1, index).write_volatile(0);
+            }
+            self._key_len_reg().write_volatile(KMAC_KEY_LEN_256);
+            self.write_prefix(KMAC_FUNCTION_NAME, customization);
+            self.start(_serialize_strength(strength) | _serialize_mode(true), true);
+        }
+    }
+
+    fn write_input(&self, data: &[u32]) {
+        unsafe {
+            for value in data {
+                wait_until(|| {
+                    !KmacSTATUS::from_bits_unchecked(self._status_reg().read_volatile())
+                        .contains(KmacSTATUS::FIFO_FULL)
+                });
+
+                self._msg_fifo().write_volatile(*value);
+            }
+        }
+    }
+
+    fn write_output_length(&self, bits: usize) {
+        // right_encode of NIST SP 800-185, the length is followed by the number of its bytes
+        let bytes = (bits as u64).to_be_bytes();
+        let skip = bytes
+            .iter()
+            .take_while(|byte| **byte == 0)
+            .count()
+            .min(bytes.len() - 1);
+
+        let count = [(bytes.len() - skip) as u8];
+
+        unsafe {
+            // The message fifo accepts single bytes as well
+            let fifo = self._msg_fifo() as *mut u8;
+            for byte in bytes[skip..].iter().chain(&count) {
+                wait_until(|| {
+                    !KmacSTATUS::from_bits_unchecked(self._status_reg().read_volatile())
+                        .contains(KmacSTATUS::FIFO_FULL)
+                });
+                fifo.write_volatile(*byte);
+            }
+        }
+    }
+
+    fn process(&self) {
+        unsafe {
+            self.write_cmd(KmacCMD::Process);
+            self.wait_for_squeeze();
+        }
+    }
+
+    fn read_output(&self, buffer: &mut [u32]) {
+        unsafe {
+            for (index, value) in buffer.iter_mut().enumerate() {
+                *value = self._state(index).read_volatile();
+            }
+        }
+    }
+
+    fn squeeze(&self) {
+        unsafe {
+            self.write_cmd(KmacCMD::Run);
+            self.wait_for_squeeze();
+        }
+    }
+
+    fn finish(&self) {
+        unsafe { self.write_cmd(KmacCMD::Done) }
+    }
+
+    fn wait_for_idle(&self) {
+        unsafe {
+            wait_until(|| {
+                KmacSTATUS::from_bits_unchecked(self._status_reg().read_volatile())
+                    .contains(KmacSTATUS::SHA3_IDLE)
+            })
+        }
+    }
+}
+
+/// Serializes the security strength according to to the opentitan docs, so it can be directly written into the config register
+#[inline]
+fn _serialize_strength(val: XOFStrength) -> u32 {
+    let val = match val {
+        XOFStrength::L128 => 0x0,
+        XOFStrength::L256 => 0x2,
+    };
+
+    (val & cfg_reg::KSTRENGTH_MASK) << cfg_reg::KSTRENGTH_OFFSET
+}
+
+/// Serializes the digest length of SHA3 according to the opentitan docs, so it can be directly
+/// written into the config register, the security strength determines the length
+///
+/// # Arguments
+///
+/// * `length` - the length of the digest
+#[inline]
+fn _serialize_digest_length(length: SHA3Length) -> u32 {
+    let val = match length {
+        SHA3Length::L224 => 0x1,
+        SHA3Length::L256 => 0x2,
+        SHA3Length::L384 => 0x3,
+        SHA3Length::L512 => 0x4,
+    };
+
+    (val & cfg_reg::KSTRENGTH_MASK) << cfg_reg::KSTRENGTH_OFFSET
+}
+
+/// Serializes the mode according to to the opentitan docs, so it can be directly written into the config register
+///
+/// # Arguments
+///
+/// * `customized` - whether cSHAKE is used instead of SHAKE
+#[inline]
+fn _serialize_mode(customized: bool) -> u32 {
+    let val = if customized { 0x3 } else { 0x2 };
+
+    (val & cfg_reg::MODE_MASK) << cfg_reg::MODE_OFFSET
+}
+
+/// Appends a string encoded as described by NIST SP 800-185, the length in bits is left encoded
+/// and followed by the string itself
+///
+/// # Arguments
+///
+/// * `buffer` - the buffer the encoded string is appended to
+/// * `string` - the string that should be encoded
+fn encode_string(buffer: &mut Vec<u8>, string: &[u8]) {
+    let bits = (string.len() as u64 * 8).to_be_bytes();
+    let skip = bits
+        .iter()
+        .take_while(|byte| **byte == 0)
+        .count()
+        .min(bits.len() - 1);
+
+    buffer.push((bits.len() - skip) as u8);
+    buffer.extend_from_slice(&bits[skip..]);
+    buffer.extend_from_slice(string);
+}
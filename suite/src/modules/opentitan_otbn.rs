@@ -0,0 +1,191 @@
+#![allow(dead_code)]
+
+use crate::modules::{wait_until, Module, OTBNApp, OTBNModule};
+use bitflags::bitflags;
+
+bitflags! {
+    /// Abstract representation of the interrupt state register.
+    struct OtbnINTRState: u32 {
+        const DONE = 1 << 0;
+    }
+}
+
+/// Commands that can be written to the command register
+#[repr(u32)]
+enum OtbnCMD {
+    Execute = 0xd8,
+    SecWipeDmem = 0xc3,
+    SecWipeImem = 0x1e,
+}
+
+/// Values of the status register
+mod status {
+    pub const IDLE: u32 = 0x00;
+    pub const BUSY_EXECUTE: u32 = 0x01;
+    pub const BUSY_SEC_WIPE_DMEM: u32 = 0x02;
+    pub const BUSY_SEC_WIPE_IMEM: u32 = 0x03;
+    pub const BUSY_SEC_WIPE_INT: u32 = 0x04;
+    pub const LOCKED: u32 = 0xff;
+}
+
+/// Offset of the interrupt state register
+const OTBN_INTR_STATE_OFFSET: usize = 0x0;
+/// Offset of the command register
+const OTBN_CMD_OFFSET: usize = 0x10;
+/// Offset of the status register
+const OTBN_STATUS_OFFSET: usize = 0x18;
+/// Offset of the error bits register
+const OTBN_ERR_BITS_OFFSET: usize = 0x1c;
+/// Offset of the instruction memory
+const OTBN_IMEM_OFFSET: usize = 0x4000;
+/// Offset of the data memory
+const OTBN_DMEM_OFFSET: usize = 0x8000;
+
+/// OTBN driver implementation as described by:
+/// https://docs.opentitan.org/hw/ip/otbn/doc/
+///
+/// The memories can only be accessed while the OTBN is idle.
+pub struct OpentitanOTBN {
+    initialized: bool,
+    base_address: *mut u8,
+}
+
+impl OpentitanOTBN {
+    /// Creates a new OpentitanOTBN driver
+    ///
+    /// # Arguments
+    ///
+    /// * `base_address` - A pointer to the MMIO address of the otbn device
+    ///
+    /// # Safety:
+    ///  - a valid otbn device must be at the base_address
+    ///  - no other otbn must use the same base_address
+    pub const unsafe fn new(base_address: *mut u8) -> OpentitanOTBN {
+        OpentitanOTBN {
+            initialized: false,
+            base_address,
+        }
+    }
+
+    /// Returns pointer to interrupt state register
+    #[inline]
+    unsafe fn _interrupt_state_reg(&self) -> *mut u32 {
+        self.base_address.add(OTBN_INTR_STATE_OFFSET) as *mut u32
+    }
+
+    /// Returns pointer to command register
+    #[inline]
+    unsafe fn _command_reg(&self) -> *mut u32 {
+        self.base_address.add(OTBN_CMD_OFFSET) as *mut u32
+    }
+
+    /// Returns pointer to status register
+    #[inline]
+    unsafe fn _status_reg(&self) -> *mut u32 {
+        self.base_address.add(OTBN_STATUS_OFFSET) as *mut u32
+    }
+
+    /// Returns pointer to error bits register
+    #[inline]
+    unsafe fn _error_bits_reg(&self) -> *mut u32 {
+        self.base_address.add(OTBN_ERR_BITS_OFFSET) as *mut u32
+    }
+
+    /// Returns pointer to the word of the instruction memory at the given byte offset
+    #[inline]
+    unsafe fn _imem(&self, offset: usize) -> *mut u32 {
+        self.base_address.add(OTBN_IMEM_OFFSET + offset) as *mut u32
+    }
+
+    /// Returns pointer to the word of the data memory at the given byte offset
+    #[inline]
+    unsafe fn _dmem(&self, offset: usize) -> *mut u32 {
+        self.base_address.add(OTBN_DMEM_OFFSET + offset) as *mut u32
+    }
+
+    /// Writes a command to the command register
+    #[inline]
+    unsafe fn write_cmd(&self, cmd: OtbnCMD) {
+        self._command_reg().write_volatile(cmd as u32);
+    }
+
+    /// Waits until the OTBN is idle
+    #[inline]
+    unsafe fn wait_for_idle(&self) {
+        wait_until(|| self._status_reg().read_volatile() == status::IDLE)
+    }
+}
+
+impl Module for OpentitanOTBN {
+    unsafe fn init(&mut self) -> Result<(), &'static str> {
+        if self._status_reg().read_volatile() == status::LOCKED {
+            return Err("OTBN is locked");
+        }
+
+        self.initialized = true;
+        Ok(())
+    }
+
+    fn initialized(&self) -> bool {
+        self.initialized
+    }
+
+    unsafe fn reset(&mut self) -> Result<(), &'static str> {
+        self.wait_for_idle();
+        self.write_cmd(OtbnCMD::SecWipeDmem);
+        self.wait_for_idle();
+        self.write_cmd(OtbnCMD::SecWipeImem);
+        self.wait_for_idle();
+
+        Ok(())
+    }
+}
+
+impl OTBNModule for OpentitanOTBN {
+    fn load_app(&self, app: &OTBNApp) {
+        unsafe {
+            self.wait_for_idle();
+
+            for (index, value) in app.imem.iter().enumerate() {
+                self._imem(index * 4).write_volatile(*value);
+            }
+        }
+        self.write_dmem(app.dmem_data_offset, app.dmem_data);
+    }
+
+    fn write_dmem(&self, offset: usize, data: &[u32]) {
+        unsafe {
+            for (index, value) in data.iter().enumerate() {
+                self._dmem(offset + index * 4).write_volatile(*value);
+            }
+        }
+    }
+
+    fn read_dmem(&self, offset: usize, buffer: &mut [u32]) {
+        unsafe {
+            for (index, value) in buffer.iter_mut().enumerate() {
+                *value = self._dmem(offset + index * 4).read_volatile();
+            }
+        }
+    }
+
+    fn execute(&self) -> Result<(), u32> {
+        unsafe {
+            self._interrupt_state_reg()
+                .write_volatile(OtbnINTRState::DONE.bits());
+            self.write_cmd(OtbnCMD::Execute);
+
+            wait_until(|| {
+                OtbnINTRState::from_bits_unchecked(self._interrupt_state_reg().read_volatile())
+                    .contains(OtbnINTRState::DONE)
+            });
+            self._interrupt_state_reg()
+                .write_volatile(OtbnINTRState::DONE.bits());
+
+            match self._error_bits_reg().read_volatile() {
+                0 => Ok(()),
+                error_bits => Err(error_bits),
+            }
+        }
+    }
+}
@@ -0,0 +1,96 @@
+#![allow(dead_code)]
+
+use crate::modules::{Module, TimerModule};
+
+/// Offset of the control register
+const TIMER_CTRL_OFFSET: usize = 0x4;
+/// Offset of the configuration register of the first hart
+const TIMER_CFG0_OFFSET: usize = 0x10c;
+/// Offset of the lower half of the timer value of the first hart
+const TIMER_V_LOWER0_OFFSET: usize = 0x110;
+/// Offset of the upper half of the timer value of the first hart
+const TIMER_V_UPPER0_OFFSET: usize = 0x114;
+
+/// Timer driver implementation as described by:
+/// https://docs.opentitan.org/hw/ip/rv_timer/doc/
+///
+/// The timer is configured to increment mtime on every clock cycle.
+pub struct OpentitanTimer {
+    initialized: bool,
+    base_address: *mut u8,
+}
+
+impl OpentitanTimer {
+    /// Creates a new OpentitanTimer driver
+    ///
+    /// # Arguments
+    ///
+    /// * `base_address` - A pointer to the MMIO address of the timer device
+    ///
+    /// # Safety:
+    ///  - a valid timer device must be at the base_address
+    ///  - no other timer must use the same base_address
+    pub const unsafe fn new(base_address: *mut u8) -> OpentitanTimer {
+        OpentitanTimer {
+            initialized: false,
+            base_address,
+        }
+    }
+
+    /// Returns pointer to control register
+    #[inline]
+    unsafe fn _control_reg(&self) -> *mut u32 {
+        self.base_address.add(TIMER_CTRL_OFFSET) as *mut u32
+    }
+
+    /// Returns pointer to configuration register
+    #[inline]
+    unsafe fn _config_reg(&self) -> *mut u32 {
+        self.base_address.add(TIMER_CFG0_OFFSET) as *mut u32
+    }
+
+    /// Returns pointer to the lower half of the timer value
+    #[inline]
+    unsafe fn _value_lower_reg(&self) -> *mut u32 {
+        self.base_address.add(TIMER_V_LOWER0_OFFSET) as *mut u32
+    }
+
+    /// Returns pointer to the upper half of the timer value
+    #[inline]
+    unsafe fn _value_upper_reg(&self) -> *mut u32 {
+        self.base_address.add(TIMER_V_UPPER0_OFFSET) as *mut u32
+    }
+}
+
+impl Module for OpentitanTimer {
+    unsafe fn init(&mut self) -> Result<(), &'static str> {
+        self._control_reg().write_volatile(0);
+        // prescale of 0 and step of 1, so mtime is incremented on every clock cycle
+        self._config_reg().write_volatile(1 << 16);
+        self._value_lower_reg().write_volatile(0);
+        self._value_upper_reg().write_volatile(0);
+        self._control_reg().write_volatile(1);
+
+        self.initialized = true;
+        Ok(())
+    }
+
+    fn initialized(&self) -> bool {
+        self.initialized
+    }
+}
+
+impl TimerModule for OpentitanTimer {
+    fn mtime(&self) -> u64 {
+        unsafe {
+            // Retry if the lower half overflowed between reading both halves
+            loop {
+                let upper = self._value_upper_reg().read_volatile();
+                let lower = self._value_lower_reg().read_volatile();
+                if upper == self._value_upper_reg().read_volatile() {
+                    return ((upper as u64) << 32) | lower as u64;
+                }
+            }
+        }
+    }
+}
@@ -2,7 +2,7 @@
 
 use core::fmt::Write;
 
-use crate::modules::{ByteRead, Module};
+use crate::modules::{ByteRead, ByteWrite, Module};
 use bitflags::bitflags;
 
 bitflags! {
@@ -182,11 +182,17 @@ impl Module for OpentitanUart {
 
 impl Write for OpentitanUart {
     fn write_str(&mut self, data: &str) -> core::fmt::Result {
+        self.write_bytes(data.as_bytes())
+    }
+}
+
+impl ByteWrite for OpentitanUart {
+    fn write_bytes(&mut self, data: &[u8]) -> core::fmt::Result {
         if !self.initialized {
             Err(core::fmt::Error)
         } else {
             unsafe {
-                for c in data.as_bytes() {
+                for c in data {
                     while self.put(*c).is_err() {
                         core::hint::spin_loop();
                     }
@@ -1,6 +1,6 @@
 use core::fmt::Write;
 
-use crate::modules::{ByteRead, Module};
+use crate::modules::{ByteRead, ByteWrite, Module};
 use bitflags::bitflags;
 
 bitflags! {
@@ -87,10 +87,16 @@ impl Module for Uart16550 {
 
 impl Write for Uart16550 {
     fn write_str(&mut self, data: &str) -> core::fmt::Result {
+        self.write_bytes(data.as_bytes())
+    }
+}
+
+impl ByteWrite for Uart16550 {
+    fn write_bytes(&mut self, data: &[u8]) -> core::fmt::Result {
         if !self.initialized {
             Err(core::fmt::Error)
         } else {
-            for c in data.as_bytes() {
+            for c in data {
                 self.put(*c);
             }
 
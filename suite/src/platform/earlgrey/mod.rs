@@ -1,6 +1,8 @@
-use core::arch::global_asm;
+use core::arch::{asm, global_asm};
 
-use crate::{modules::ModuleRef, println};
+use benchmark_common::{PlatformDescriptor, SuspendCode};
+
+use crate::{info, modules::ModuleRef};
 
 use super::Platform;
 
@@ -8,8 +10,18 @@ use super::Platform;
 mod opentitan_aes;
 #[path = "../../modules/opentitan_csrng.rs"]
 mod opentitan_csrng;
+#[path = "../../modules/opentitan_edn.rs"]
+mod opentitan_edn;
+#[path = "../../modules/opentitan_entropy_src.rs"]
+mod opentitan_entropy_src;
 #[path = "../../modules/opentitan_hmac.rs"]
 mod opentitan_hmac;
+#[path = "../../modules/opentitan_kmac.rs"]
+mod opentitan_kmac;
+#[path = "../../modules/opentitan_otbn.rs"]
+mod opentitan_otbn;
+#[path = "../../modules/opentitan_timer.rs"]
+mod opentitan_timer;
 #[path = "../../modules/opentitan_uart.rs"]
 mod opentitan_uart;
 
@@ -18,14 +30,26 @@ mod opentitan_uart;
 global_asm!(include_str!("ibex_start.S"));
 
 // Note: clk_hz & baud_rate according to sw/device/lib/arch/device_sim_verilator.c
+const CLK_HZ: u64 = 125_000;
+
 static mut UART0: opentitan_uart::OpentitanUart =
-    unsafe { opentitan_uart::OpentitanUart::new(0x4000_0000 as *mut u8, 7200, 125_000) };
+    unsafe { opentitan_uart::OpentitanUart::new(0x4000_0000 as *mut u8, 7200, CLK_HZ) };
 static mut HMAC: opentitan_hmac::OpentitanHMAC =
     unsafe { opentitan_hmac::OpentitanHMAC::new(0x4111_0000 as *mut u8) };
 static mut AES: opentitan_aes::OpentitanAES =
     unsafe { opentitan_aes::OpentitanAES::new(0x4110_0000 as *mut u8) };
 static mut CSRNG: opentitan_csrng::OpentitanCSRNG =
     unsafe { opentitan_csrng::OpentitanCSRNG::new(0x41150000 as *mut u8) };
+static mut ENTROPY_SRC: opentitan_entropy_src::OpentitanEntropySrc =
+    unsafe { opentitan_entropy_src::OpentitanEntropySrc::new(0x4116_0000 as *mut u8) };
+static mut EDN0: opentitan_edn::OpentitanEDN =
+    unsafe { opentitan_edn::OpentitanEDN::new(0x4117_0000 as *mut u8) };
+static mut KMAC: opentitan_kmac::OpentitanKMAC =
+    unsafe { opentitan_kmac::OpentitanKMAC::new(0x4112_0000 as *mut u8) };
+static mut OTBN: opentitan_otbn::OpentitanOTBN =
+    unsafe { opentitan_otbn::OpentitanOTBN::new(0x4113_0000 as *mut u8) };
+static mut TIMER: opentitan_timer::OpentitanTimer =
+    unsafe { opentitan_timer::OpentitanTimer::new(0x4010_0000 as *mut u8) };
 
 /// EarlGrey platform according to the Opentitan specification:
 ///
@@ -43,10 +67,14 @@ impl Platform for EarlGreyPlatform {
         &mut UART0
     }
 
-    fn suspend(&self, _code: u32) -> ! {
+    fn descriptor(&self) -> PlatformDescriptor {
+        super::build_descriptor("verilator_earlgrey", Some(CLK_HZ), true)
+    }
+
+    fn suspend(&self, code: SuspendCode) -> ! {
         // If this is a successful suspension, try printing it to the user
-        if _code == 0 {
-            println!("Successfully finished executing, going to sleep!")
+        if code == SuspendCode::Success {
+            info!("Successfully finished executing, going to sleep!")
         }
 
         loop {
@@ -56,6 +84,21 @@ impl Platform for EarlGreyPlatform {
         }
     }
 
+    fn set_icache(&self, enabled: bool) -> bool {
+        // The lowest bit of the Ibex cpuctrl CSR (0x7c0) enables the instruction cache
+        unsafe {
+            if enabled {
+                // Invalidate the cache, so no stale instructions are executed once it is enabled
+                asm!("fence.i");
+                asm!("csrs 0x7c0, {0}", in(reg) 1);
+            } else {
+                asm!("csrc 0x7c0, {0}", in(reg) 1);
+            }
+        }
+
+        true
+    }
+
     fn get_sha256_module(&self) -> Option<ModuleRef<dyn crate::modules::SHA256Module>> {
         unsafe { Some(ModuleRef::new(&mut HMAC)) }
     }
@@ -67,4 +110,24 @@ impl Platform for EarlGreyPlatform {
     fn get_rng_module(&self) -> Option<ModuleRef<dyn crate::modules::RNGModule>> {
         unsafe { Some(ModuleRef::new(&mut CSRNG)) }
     }
+
+    fn get_entropy_src_module(&self) -> Option<ModuleRef<dyn crate::modules::EntropySourceModule>> {
+        unsafe { Some(ModuleRef::new(&mut ENTROPY_SRC)) }
+    }
+
+    fn get_edn_module(&self) -> Option<ModuleRef<dyn crate::modules::EDNModule>> {
+        unsafe { Some(ModuleRef::new(&mut EDN0)) }
+    }
+
+    fn get_kmac_module(&self) -> Option<ModuleRef<dyn crate::modules::KMACModule>> {
+        unsafe { Some(ModuleRef::new(&mut KMAC)) }
+    }
+
+    fn get_otbn_module(&self) -> Option<ModuleRef<dyn crate::modules::OTBNModule>> {
+        unsafe { Some(ModuleRef::new(&mut OTBN)) }
+    }
+
+    fn get_timer_module(&self) -> Option<ModuleRef<dyn crate::modules::TimerModule>> {
+        unsafe { Some(ModuleRef::new(&mut TIMER)) }
+    }
 }
@@ -1,4 +1,10 @@
-use crate::modules::{AESModule, CommunicationModule, ModuleRef, RNGModule, SHA256Module};
+use alloc::{string::String, vec::Vec};
+use benchmark_common::{PlatformDescriptor, SuspendCode};
+
+use crate::modules::{
+    AESModule, CommunicationModule, EDNModule, EntropySourceModule, KMACModule, ModuleRef,
+    OTBNModule, RNGModule, SHA256Module, TimerModule,
+};
 
 #[cfg(feature = "platform_verilator_earlgrey")]
 mod earlgrey;
@@ -45,11 +51,97 @@ pub trait Platform {
         None
     }
 
+    /// Returns the platforms entropy source if one is present.
+    fn get_entropy_src_module(&self) -> Option<ModuleRef<dyn EntropySourceModule>> {
+        None
+    }
+
+    /// Returns the platforms edn module serving the kmac module if one is present.
+    fn get_edn_module(&self) -> Option<ModuleRef<dyn EDNModule>> {
+        None
+    }
+
+    /// Returns the platforms kmac module if one is present.
+    fn get_kmac_module(&self) -> Option<ModuleRef<dyn KMACModule>> {
+        None
+    }
+
+    /// Returns the platforms otbn module if one is present.
+    fn get_otbn_module(&self) -> Option<ModuleRef<dyn OTBNModule>> {
+        None
+    }
+
+    /// Returns the platforms timer module if one is present.
+    fn get_timer_module(&self) -> Option<ModuleRef<dyn TimerModule>> {
+        None
+    }
+
+    /// True if the platform routes the interrupts of its modules to the core,
+    /// so a core waiting for an interrupt is woken up once the hardware is ready.
+    /// Only then `WaitStrategy::Interrupt` can be used.
+    fn supports_wait_for_interrupt(&self) -> bool {
+        false
+    }
+
+    /// Enables or disables the instruction cache of the core,
+    /// returns false if the platform has no instruction cache that can be controlled
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - whether the instruction cache should be enabled
+    fn set_icache(&self, _enabled: bool) -> bool {
+        false
+    }
+
+    /// Returns a description of the platform and the build of the suite,
+    /// which is attached to all results produced on this platform
+    fn descriptor(&self) -> PlatformDescriptor;
+
     /// Signals the platform that the suite finished executing.
     /// What should happen when this function is called is defined by the platform.
     ///
     /// # Arguments
     ///
-    /// * `code` - An exit code, where 0 represents success and any other value is interpreted as an error code
-    fn suspend(&self, code: u32) -> !;
+    /// * `code` - Why the suite suspends
+    fn suspend(&self, code: SuspendCode) -> !;
+}
+
+/// Creates the descriptor of a platform, filling in the information about the build of the suite
+///
+/// # Arguments
+///
+/// * `name` - the name of the platform
+/// * `clock_frequency` - frequency of the clock driving the cycle counter in Hz, if known
+/// * `simulated` - whether the platform is a cycle accurate simulation
+fn build_descriptor(
+    name: &str,
+    clock_frequency: Option<u64>,
+    simulated: bool,
+) -> PlatformDescriptor {
+    let mut features = Vec::new();
+
+    if cfg!(feature = "platform_qemu_virt") {
+        features.push(String::from("platform_qemu_virt"));
+    }
+    if cfg!(feature = "platform_verilator_earlgrey") {
+        features.push(String::from("platform_verilator_earlgrey"));
+    }
+    // The codec and the framing determine the wire format, so a mismatch shows in the descriptor
+    if cfg!(feature = "codec_postcard") {
+        features.push(String::from("codec_postcard"));
+    }
+    if cfg!(feature = "codec_cbor") {
+        features.push(String::from("codec_cbor"));
+    }
+    if cfg!(feature = "frame_armor") {
+        features.push(String::from("frame_armor"));
+    }
+
+    PlatformDescriptor {
+        name: String::from(name),
+        features,
+        clock_frequency,
+        git_hash: option_env!("SUITE_GIT_HASH").map(String::from),
+        simulated,
+    }
 }
@@ -1,3 +1,5 @@
+use benchmark_common::{PlatformDescriptor, SuspendCode};
+
 use super::Platform;
 
 #[path = "../modules/uart16550.rs"]
@@ -22,14 +24,19 @@ impl Platform for VirtPlatform {
         &mut UART0
     }
 
-    fn suspend(&self, code: u32) -> ! {
-        // use the sifive_test device to stop the simulation
+    fn descriptor(&self) -> PlatformDescriptor {
+        // Qemu does not model the timing of the hardware, cycle counts do not map to a clock
+        super::build_descriptor("qemu_virt", None, false)
+    }
+
+    fn suspend(&self, code: SuspendCode) -> ! {
+        // use the sifive_test device to stop the simulation,
+        // qemu exits with the lower 16 bits of the code as status
         let addr = 0x100000 as *mut u32;
 
-        let value = if code == 0 {
-            0x5555
-        } else {
-            (code << 16) | 0x3333
+        let value = match code {
+            SuspendCode::Success => 0x5555,
+            code => (code.code() << 16) | 0x3333,
         };
 
         unsafe { addr.write_volatile(value) };
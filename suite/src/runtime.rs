@@ -1,12 +1,17 @@
 //! Contains functions and macros for providing a runtime environment to the benchmarking suite
 use core::{
     alloc::GlobalAlloc,
-    cell::RefCell,
+    cell::{Cell, RefCell, UnsafeCell},
     panic::PanicInfo,
     ptr::{self, NonNull},
 };
 
-use benchmark_common::{deserialize, serialize, IncomingMessage, OutgoingMessage};
+use alloc::{string::String, vec::Vec};
+use benchmark_common::{
+    codec::{Codec, MessageCodec},
+    deserialize, parse_resync, serialize, IncomingMessage, OutgoingMessage, PlatformDescriptor,
+    ResultEnvelope, SuiteConfig, SuiteError, SuspendCode, RESYNC_TOKEN,
+};
 use linked_list_allocator::Heap;
 
 use crate::platform::{self, Platform};
@@ -50,27 +55,381 @@ pub unsafe fn init() -> Result<(), &'static str> {
         }
     }
 
+    // The entropy source seeds the CSRNG, which is used by the EDN
+    if let Some(mut module) = platform::current().get_entropy_src_module() {
+        if !module.initialized() {
+            module.init()?;
+        }
+    }
+
     if let Some(mut module) = platform::current().get_rng_module() {
         if !module.initialized() {
             module.init()?;
         }
     }
 
+    if let Some(mut module) = platform::current().get_edn_module() {
+        if !module.initialized() {
+            module.init()?;
+        }
+    }
+
+    if let Some(mut module) = platform::current().get_kmac_module() {
+        if !module.initialized() {
+            module.init()?;
+        }
+    }
+
+    if let Some(mut module) = platform::current().get_otbn_module() {
+        if !module.initialized() {
+            module.init()?;
+        }
+    }
+
+    if let Some(mut module) = platform::current().get_timer_module() {
+        if !module.initialized() {
+            module.init()?;
+        }
+    }
+
     Ok(())
 }
 
+/// Returns all modules except the communication module to the state they had after `init`,
+/// so benchmarks performed afterwards are not influenced by earlier ones
+///
+/// # Safety
+///  - only call after `init`, while no references to the modules are held
+pub unsafe fn reset() -> Result<(), &'static str> {
+    if let Some(mut module) = platform::current().get_sha256_module() {
+        module.reset()?;
+    }
+
+    if let Some(mut module) = platform::current().get_aes_module() {
+        module.reset()?;
+    }
+
+    if let Some(mut module) = platform::current().get_entropy_src_module() {
+        module.reset()?;
+    }
+
+    if let Some(mut module) = platform::current().get_rng_module() {
+        module.reset()?;
+    }
+
+    if let Some(mut module) = platform::current().get_edn_module() {
+        module.reset()?;
+    }
+
+    if let Some(mut module) = platform::current().get_kmac_module() {
+        module.reset()?;
+    }
+
+    if let Some(mut module) = platform::current().get_otbn_module() {
+        module.reset()?;
+    }
+
+    Ok(())
+}
+
+/// Initializes the heap again, so allocations start from the same state as after `init`.
+/// Returns false without changing the heap if any allocation is still in use
+///
+/// # Safety
+///  - only call after `init`
+pub unsafe fn reinit_heap() -> bool {
+    let mut heap = ALLOCATOR.0.borrow_mut();
+    if heap.used() != 0 {
+        return false;
+    }
+
+    let (heap_bottom, heap_size) = (heap.bottom(), heap.size());
+    heap.init(heap_bottom, heap_size);
+    true
+}
+
+/// Whether the current request started from reset modules and a freshly initialized heap
+static ISOLATED: Global<bool> = Global::new(false);
+
+/// Returns whether the current request started from reset modules and a freshly initialized heap
+pub fn isolated() -> bool {
+    ISOLATED.get()
+}
+
+/// Records whether the current request started from reset modules and a freshly initialized heap
+///
+/// # Arguments
+///
+/// * `isolated` - whether the modules were reset and the heap was initialized again
+pub fn set_isolated(isolated: bool) {
+    ISOLATED.set(isolated);
+}
+
+/// True if any module except the communication module raised a hardware alert
+pub fn alerted() -> bool {
+    let platform = platform::current();
+
+    platform
+        .get_sha256_module()
+        .map_or(false, |module| module.alerted())
+        || platform
+            .get_aes_module()
+            .map_or(false, |module| module.alerted())
+        || platform
+            .get_rng_module()
+            .map_or(false, |module| module.alerted())
+        || platform
+            .get_entropy_src_module()
+            .map_or(false, |module| module.alerted())
+        || platform
+            .get_edn_module()
+            .map_or(false, |module| module.alerted())
+        || platform
+            .get_kmac_module()
+            .map_or(false, |module| module.alerted())
+        || platform
+            .get_otbn_module()
+            .map_or(false, |module| module.alerted())
+}
+
+/// Config consulted by all benchmarks, changed by SetConfig messages
+static CONFIG: Global<SuiteConfig> = Global::new(SuiteConfig::DEFAULT);
+
+/// Returns the config that is currently active
+pub fn config() -> SuiteConfig {
+    CONFIG.get()
+}
+
+/// Applies a config to all following benchmarks,
+/// the active config is left unchanged if it can not be applied on the platform
+///
+/// # Arguments
+///
+/// * `config` - the config that should be applied
+pub fn set_config(config: SuiteConfig) -> Result<(), SuiteError> {
+    if config.icache != self::config().icache && !platform::current().set_icache(config.icache) {
+        return Err(SuiteError::UnsupportedConfig);
+    }
+
+    CONFIG.set(config);
+    Ok(())
+}
+
+/// Returns words derived from the seed of the active config, None if no seed is set
+///
+/// The words are generated by SplitMix64 from the seed and the stream, so the same seed always
+/// produces the same words, while every use of the seed gets its own stream of words.
+///
+/// # Arguments
+///
+/// * `stream` - identifies the use of the words
+pub fn seeded_words<const N: usize>(stream: u64) -> Option<[u32; N]> {
+    let seed = config().seed?;
+    let mut words = [0u32; N];
+
+    for (index, word) in words.iter_mut().enumerate() {
+        *word = seeded_word(seed, stream, index);
+    }
+
+    Some(words)
+}
+
+/// Returns the word at the given index of the words derived from a seed and a stream,
+/// check `seeded_words`
+///
+/// SplitMix64 advances its state by a constant, so every word can be derived on its own, without
+/// storing the words before it.
+///
+/// # Arguments
+///
+/// * `seed` - the seed the words are derived from
+/// * `stream` - identifies the use of the words
+/// * `index` - the index of the word
+pub fn seeded_word(seed: u64, stream: u64, index: usize) -> u32 {
+    let state = (seed ^ stream.wrapping_mul(0xbf58_476d_1ce4_e5b9))
+        .wrapping_add(0x9e37_79b9_7f4a_7c15u64.wrapping_mul(index as u64 + 1));
+    let mut z = state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    (z ^ (z >> 31)) as u32
+}
+
+/// Maximum size of an encoded BenchmarkResults message in bytes,
+/// larger messages are split into multiple ResultChunk messages
+const RESULT_CHUNK_BYTES: usize = 1024;
+
 /// Sends a message using the communication module by first serializing it
 ///
+/// BenchmarkResults messages larger than `RESULT_CHUNK_BYTES` once encoded are split up and sent
+/// as multiple ResultChunk messages, to keep the length of a single frame bounded. Only the first
+/// chunk carries the platform descriptor, the descriptor of later chunks is left empty.
+/// If compression is enabled by the Suite config, the results are packed before sending them.
+///
 /// # Arguments
 ///
 /// * `msg` - the message to send
 pub fn send_message(msg: &OutgoingMessage) {
-    crate::println!("{}", serialize(&msg));
+    match msg {
+        OutgoingMessage::BenchmarkResults(envelope) => {
+            let mut whole = envelope.clone();
+            if config().compression {
+                whole.pack();
+            }
+            let frame = serialize(&OutgoingMessage::BenchmarkResults(whole));
+            if frame.len() <= RESULT_CHUNK_BYTES || envelope.results.len() <= 1 {
+                write_frame(&frame);
+                return;
+            }
+
+            // The bounds of the chunks are determined first, since every chunk carries the total
+            let mut bounds = Vec::new();
+            let mut start = 0;
+            while start < envelope.results.len() {
+                let mut end = start + 1;
+                while end < envelope.results.len()
+                    && serialize(&result_chunk(envelope, start, end + 1, u32::MAX, u32::MAX)).len()
+                        <= RESULT_CHUNK_BYTES
+                {
+                    end += 1;
+                }
+                bounds.push((start, end));
+                start = end;
+            }
+
+            let total = bounds.len() as u32;
+            for (index, (start, end)) in bounds.into_iter().enumerate() {
+                write_frame(&serialize(&result_chunk(
+                    envelope,
+                    start,
+                    end,
+                    index as u32,
+                    total,
+                )));
+            }
+        }
+        _ => write_frame(&serialize(msg)),
+    }
+}
+
+/// Returns the ResultChunk message containing the results in `start..end` of an envelope,
+/// packed if compression is enabled by the Suite config
+///
+/// # Arguments
+///
+/// * `envelope` - the envelope that is split up
+/// * `start` - index of the first result of the chunk
+/// * `end` - index after the last result of the chunk
+/// * `index` - index of the chunk, only the chunk at index 0 carries the platform descriptor
+/// * `total` - total number of chunks
+fn result_chunk(
+    envelope: &ResultEnvelope,
+    start: usize,
+    end: usize,
+    index: u32,
+    total: u32,
+) -> OutgoingMessage {
+    let platform = if index == 0 {
+        envelope.platform.clone()
+    } else {
+        PlatformDescriptor {
+            name: String::new(),
+            features: Vec::new(),
+            clock_frequency: None,
+            git_hash: None,
+            simulated: false,
+        }
+    };
+
+    let mut payload = ResultEnvelope {
+        schema_version: envelope.schema_version,
+        platform,
+        request_id: envelope.request_id,
+        label: envelope.label.clone(),
+        benchmark: envelope.benchmark.clone(),
+        config: envelope.config.clone(),
+        suite_config: envelope.suite_config,
+        aggregate: envelope.aggregate,
+        wait_strategy: envelope.wait_strategy,
+        results: envelope.results[start..end].to_vec(),
+        poll_counts: chunk_of(&envelope.poll_counts, start, end),
+        virtual_time: chunk_of(&envelope.virtual_time, start, end),
+        unaccounted_cycles: chunk_of(&envelope.unaccounted_cycles, start, end),
+        verification_failure: envelope.verification_failure.clone(),
+        retries: envelope.retries,
+        isolated: envelope.isolated,
+        packed: None,
+    };
+    if config().compression {
+        payload.pack();
+    }
+
+    OutgoingMessage::ResultChunk {
+        index,
+        total,
+        payload,
+    }
+}
+
+/// Returns the entries of a per result list belonging to the results in `start..end`,
+/// lists that are shorter than the results yield fewer or no entries
+///
+/// # Arguments
+///
+/// * `values` - one entry per result, or fewer
+/// * `start` - index of the first result of the chunk
+/// * `end` - index after the last result of the chunk
+fn chunk_of<T: Clone>(values: &[T], start: usize, end: usize) -> Vec<T> {
+    values
+        .get(start..end.min(values.len()))
+        .unwrap_or_default()
+        .to_vec()
 }
 
 /// Reads a message using the communication module and deserializes it
+///
+/// Frames containing the resync token are acknowledged and skipped, as are empty frames.
 pub fn read_message() -> IncomingMessage {
-    deserialize(crate::readln!())
+    loop {
+        let frame = read_frame();
+
+        match parse_resync(&String::from_utf8_lossy(&frame)) {
+            // All requests received before the token were already handled at this point
+            Some(id) => send_resync(id),
+            None if frame.is_empty() => continue,
+            None => return deserialize(&frame),
+        }
+    }
+}
+
+/// Sends the resync token, signaling the CLI that any following output belongs to new requests
+///
+/// # Arguments
+///
+/// * `id` - identifier of the resync token that is acknowledged, empty if none
+pub fn send_resync(id: &str) {
+    let mut frame = Vec::from(RESYNC_TOKEN.as_bytes());
+    frame.extend_from_slice(id.as_bytes());
+    frame.push(Codec::DELIMITER);
+
+    write_frame(&frame);
+}
+
+/// Writes a frame, including its delimiter, using the communication module
+///
+/// # Arguments
+///
+/// * `frame` - the frame that should be written
+fn write_frame(frame: &[u8]) {
+    // Safety: invalidating previous references is ok, the module is only referenced in this scope
+    unsafe { platform::current().get_communication_module() }
+        .write_bytes(frame)
+        .unwrap();
+}
+
+/// Reads a frame using the communication module, returns it without its delimiter
+fn read_frame() -> Vec<u8> {
+    // Safety: invalidating previous references is ok, the module is only referenced in this scope
+    unsafe { platform::current().get_communication_module() }.read_frame(Codec::DELIMITER)
 }
 
 /// Since the architecture is assumed to be on a single core and without atomic instructions
@@ -106,6 +465,65 @@ unsafe impl GlobalAlloc for CustomHeap {
     }
 }
 
+/// Global state of the suite, used instead of `static mut`
+///
+/// The suite runs on a single core and does not enable interrupts, so a global is never accessed
+/// concurrently. Accesses of the same global from within `with` are detected and cause a panic,
+/// so the reference passed to it is the only one to the value.
+pub struct Global<T> {
+    value: UnsafeCell<T>,
+    /// True while the value is borrowed by `with`
+    borrowed: Cell<bool>,
+}
+
+// Safety: the suite runs on a single core without interrupts, check the documentation of Global
+unsafe impl<T> Sync for Global<T> {}
+
+impl<T> Global<T> {
+    /// Creates a new global holding the provided value
+    pub const fn new(value: T) -> Global<T> {
+        Global {
+            value: UnsafeCell::new(value),
+            borrowed: Cell::new(false),
+        }
+    }
+
+    /// Calls `f` with a mutable reference to the value and returns its result
+    ///
+    /// # Panics
+    ///
+    /// If `f` accesses the same global.
+    #[inline]
+    pub fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        assert!(
+            !self.borrowed.replace(true),
+            "Global accessed while borrowed"
+        );
+        // Safety: the value is not borrowed and the suite never accesses it concurrently
+        let result = f(unsafe { &mut *self.value.get() });
+        self.borrowed.set(false);
+        result
+    }
+
+    /// Replaces the value
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - the new value
+    #[inline]
+    pub fn set(&self, value: T) {
+        self.with(|current| *current = value);
+    }
+}
+
+impl<T: Copy> Global<T> {
+    /// Returns a copy of the value
+    #[inline]
+    pub fn get(&self) -> T {
+        self.with(|value| *value)
+    }
+}
+
 // Safety of calling get_communication_module() inside the macros:
 // invalidating previous references is ok,
 // because all macros reference the module only in a closed scope
@@ -137,20 +555,48 @@ macro_rules! println {
     });
 }
 
-/// Reads one line using the Communication module and returns it as a String.
+/// Sends a log message with the given level to the CLI.
+/// Apart from the level, can be used like the format macro from rust's alloc library.
+///
+/// Diagnostic output should always use this macro family instead of println,
+/// so it can not be confused with messages of the protocol.
+/// Messages below the log level of the Suite config are discarded.
 #[macro_export]
-macro_rules! readln {
-    () => {
-        unsafe {
-            #[allow(unused_imports)]
-            use crate::platform::Platform;
-            $crate::platform::current()
-                .get_communication_module()
-                .read_line()
+macro_rules! log {
+    ($level:expr, $($arg:tt)*) => {
+        if $level >= $crate::runtime::config().log_level {
+            $crate::runtime::send_message(&benchmark_common::OutgoingMessage::Log {
+                level: $level,
+                text: alloc::format!($($arg)*),
+            })
         }
     };
 }
 
+/// Sends a log message with the debug level to the CLI.
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => ($crate::log!(benchmark_common::LogLevel::Debug, $($arg)*));
+}
+
+/// Sends a log message with the info level to the CLI.
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)*) => ($crate::log!(benchmark_common::LogLevel::Info, $($arg)*));
+}
+
+/// Sends a log message with the warn level to the CLI.
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)*) => ($crate::log!(benchmark_common::LogLevel::Warn, $($arg)*));
+}
+
+/// Sends a log message with the error level to the CLI.
+#[macro_export]
+macro_rules! error {
+    ($($arg:tt)*) => ($crate::log!(benchmark_common::LogLevel::Error, $($arg)*));
+}
+
 /// Automatically called when the suite panics.
 #[cfg(not(test))]
 #[panic_handler]
@@ -159,11 +605,12 @@ fn panic(info: &PanicInfo) -> ! {
         // Safety: invalidating previous references is ok, since we are in a unrecoverable state
         let comm = platform::current().get_communication_module();
         if comm.init().is_ok() {
-            let _ = writeln!(comm, "! {}", info);
+            let _ = write!(comm, "! {}", info);
+            let _ = comm.write_bytes(&[Codec::DELIMITER]);
         }
     }
 
-    platform::current().suspend(101)
+    platform::current().suspend(SuspendCode::PanicOnSuite)
 }
 
 /// Signal to the testing suite that the current test is skipped
@@ -226,7 +673,7 @@ fn panic(info: &PanicInfo) -> ! {
         }
     }
 
-    platform::current().suspend(101)
+    platform::current().suspend(SuspendCode::TestFailure)
 }
 
 #[cfg(test)]